@@ -1,25 +1,27 @@
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use console::style;
 
 use crate::audio::wav;
-use crate::config::AppConfig;
+use crate::config::{AppConfig, ProfileConfig};
+use crate::norms::NormMetric;
 use crate::paths;
 use crate::storage::session_data::*;
 use crate::storage::store;
-use crate::util;
 
-/// Analyze all recordings for a given date and save the results.
+/// Analyze all recordings of a session and save the results.
 ///
-/// This is the main entry point for `voicevo analyze --date YYYY-MM-DD`.
+/// This is the main entry point for `voicevo analyze --date YYYY-MM-DD`
+/// (or `--session YYYY-MM-DD.N`); `date` is a session id, see `SessionId`.
 /// It loads each WAV file, runs the appropriate analysis pipeline, collects
 /// the results into a SessionData, and saves it as JSON.
 pub fn analyze_session(date: &str, app_config: &AppConfig) -> Result<SessionData> {
     analyze_session_with_conditions(date, app_config, None)
 }
 
-/// Analyze all recordings for a given date with optional recording conditions.
+/// Analyze all recordings of a session with optional recording conditions.
 pub fn analyze_session_with_conditions(
     date: &str,
     app_config: &AppConfig,
@@ -35,35 +37,77 @@ pub fn analyze_session_with_conditions(
     );
     println!();
 
-    let date_obj = util::resolve_date(Some(date))?;
+    let session_id = SessionId::parse(date)?;
 
-    // Find latest attempt for each exercise
-    let sustained_path = paths::latest_attempt_path(&date_obj, "sustained");
-    let scale_path = paths::latest_attempt_path(&date_obj, "scale");
-    let reading_path = paths::latest_attempt_path(&date_obj, "reading");
+    // Every take of the day is analyzed and stored per attempt. The session
+    // result comes from the canonical take if one was marked, otherwise the
+    // newest take. Sustained vowel recorded as a multi-trial run is the
+    // exception: the takes of that run are combined (best-of-N MPT).
+    let canonical = store::load_canonical_attempts(date)?;
+    let mut attempt_results = Vec::new();
+
+    let sustained_takes = paths::list_attempts(&session_id, "sustained");
+    let scale_takes = paths::list_attempts(&session_id, "scale");
+    let reading_takes = paths::list_attempts(&session_id, "reading");
 
     // Analyze each exercise that has a recording.
     // We print results as we go so the user gets immediate feedback.
 
-    let thresholds = &app_config.analysis.thresholds;
+    let profile = &app_config.profile;
 
-    let sustained = if let Some(ref p) = sustained_path {
-        Some(analyze_exercise(
-            "Sustained vowel",
-            p,
-            |samples, sr| {
-                let result = super::sustained::analyze(samples, sr, &sustained_pitch)?;
-                print_sustained_results(&result, thresholds);
-                Ok(result)
-            },
-        )?)
-    } else {
-        println!("  {} sustained recording not found, skipping", style("SKIP").yellow());
-        None
+    let trial_run = store::load_trial_run(date, "sustained")?;
+    let sustained_choice = sustained_takes_to_use(
+        &sustained_takes,
+        &canonical,
+        app_config.session.sustained_trials,
+        trial_run.as_deref(),
+    );
+    let (sustained, sustained_path) = match sustained_choice {
+        None => {
+            println!("  {} sustained recording not found, skipping", style("SKIP").yellow());
+            (None, None)
+        }
+        Some(SustainedTakes::Trials(run)) => {
+            let (result, path) = analyze_sustained_trials(
+                &run,
+                &sustained_pitch,
+                profile,
+                &mut attempt_results,
+            )?;
+            attempt_results.extend(analyze_other_takes(
+                "sustained",
+                &sustained_takes,
+                &run,
+                |samples, sr| super::sustained::analyze(samples, sr, &sustained_pitch),
+                ExerciseResult::Sustained,
+            ));
+            (Some(result), Some(path))
+        }
+        Some(SustainedTakes::Single(p)) => {
+            let result = analyze_exercise(
+                "Sustained vowel",
+                &p,
+                |samples, sr| {
+                    let result = super::sustained::analyze(samples, sr, &sustained_pitch)?;
+                    print_sustained_results(&result, profile);
+                    Ok(result)
+                },
+            )?;
+            push_attempt(&mut attempt_results, &p, ExerciseResult::Sustained(result.clone()));
+            attempt_results.extend(analyze_other_takes(
+                "sustained",
+                &sustained_takes,
+                std::slice::from_ref(&p),
+                |samples, sr| super::sustained::analyze(samples, sr, &sustained_pitch),
+                ExerciseResult::Sustained,
+            ));
+            (Some(result), Some(p))
+        }
     };
 
+    let scale_path = chosen_take(&scale_takes, "scale", &canonical);
     let scale = if let Some(ref p) = scale_path {
-        Some(analyze_exercise(
+        let result = analyze_exercise(
             "Chromatic scale",
             p,
             |samples, sr| {
@@ -71,29 +115,49 @@ pub fn analyze_session_with_conditions(
                 print_scale_results(&result);
                 Ok(result)
             },
-        )?)
+        )?;
+        push_attempt(&mut attempt_results, p, ExerciseResult::Scale(result.clone()));
+        attempt_results.extend(analyze_other_takes(
+            "scale",
+            &scale_takes,
+            std::slice::from_ref(p),
+            |samples, sr| super::scale::analyze(samples, sr, &scale_pitch),
+            ExerciseResult::Scale,
+        ));
+        Some(result)
     } else {
         println!("  {} scale recording not found, skipping", style("SKIP").yellow());
         None
     };
 
+    let reading_path = chosen_take(&reading_takes, "reading", &canonical);
     let reading = if let Some(ref p) = reading_path {
-        Some(analyze_exercise(
+        let result = analyze_exercise(
             "Reading passage",
             p,
             |samples, sr| {
                 let result = super::reading::analyze(samples, sr, &reading_pitch)?;
-                print_reading_results(&result);
+                print_reading_results(&result, profile);
                 Ok(result)
             },
-        )?)
+        )?;
+        push_attempt(&mut attempt_results, p, ExerciseResult::Reading(result.clone()));
+        attempt_results.extend(analyze_other_takes(
+            "reading",
+            &reading_takes,
+            std::slice::from_ref(p),
+            |samples, sr| super::reading::analyze(samples, sr, &reading_pitch),
+            ExerciseResult::Reading,
+        ));
+        Some(result)
     } else {
         println!("  {} reading recording not found, skipping", style("SKIP").yellow());
         None
     };
 
     let session = SessionData {
-        date: date.to_string(),
+        date: session_id.date.to_string(),
+        slot: session_id.slot,
         recordings: SessionRecordings {
             sustained: sustained_path.map(|p| p.to_string_lossy().into()),
             scale: scale_path.map(|p| p.to_string_lossy().into()),
@@ -107,10 +171,14 @@ pub fn analyze_session_with_conditions(
             fatigue: None,
         },
         conditions,
+        source: None,
     };
 
     // Save results
     store::save_session(&session)?;
+    if !attempt_results.is_empty() {
+        store::save_attempts(date, &attempt_results)?;
+    }
     println!();
     println!(
         "Results saved to {}",
@@ -131,6 +199,7 @@ pub fn analyze_session_with_conditions(
 ///   (monomorphization) — there's no runtime overhead from this abstraction.
 fn analyze_exercise<T, F>(name: &str, path: &Path, analyze_fn: F) -> Result<T>
 where
+    T: RecordedAnalysis,
     F: FnOnce(&[f32], u32) -> Result<T>,
 {
     println!("  {} {name}", style(">>").cyan());
@@ -139,32 +208,246 @@ where
         .with_context(|| format!("Failed to load {}", path.display()))?;
 
     let duration = samples.len() as f32 / spec.sample_rate as f32;
-    println!("     Loaded: {:.1}s, {} Hz", duration, spec.sample_rate);
+    println!("     Loaded: {:.1}s, {}", duration, RecordingFormat::from(spec).describe());
 
-    let result = analyze_fn(&samples, spec.sample_rate)?;
+    let mut result = analyze_fn(&samples, spec.sample_rate)?;
+    result.set_format(spec);
 
     println!();
     Ok(result)
 }
 
-fn print_sustained_results(r: &SustainedAnalysis, t: &crate::config::ThresholdConfig) {
-    println!("     MPT:      {:.1}s", r.mpt_seconds);
+/// Analyses that carry a RecordingQuality, so the sample rate and format of
+/// the file they came from are stored with them.
+trait RecordedAnalysis {
+    fn recording_quality_mut(&mut self) -> Option<&mut RecordingQuality>;
+
+    fn set_format(&mut self, spec: hound::WavSpec) {
+        if let Some(q) = self.recording_quality_mut() {
+            q.format = Some(spec.into());
+        }
+    }
+}
+
+impl RecordedAnalysis for SustainedAnalysis {
+    fn recording_quality_mut(&mut self) -> Option<&mut RecordingQuality> {
+        self.recording_quality.as_mut()
+    }
+}
+
+impl RecordedAnalysis for ScaleAnalysis {
+    fn recording_quality_mut(&mut self) -> Option<&mut RecordingQuality> {
+        self.recording_quality.as_mut()
+    }
+}
+
+impl RecordedAnalysis for ReadingAnalysis {
+    fn recording_quality_mut(&mut self) -> Option<&mut RecordingQuality> {
+        self.recording_quality.as_mut()
+    }
+}
+
+/// The take whose analysis becomes the session result: the canonical take
+/// if one was marked and still exists, otherwise the newest take.
+fn chosen_take(
+    takes: &[PathBuf],
+    exercise: &str,
+    canonical: &BTreeMap<String, u32>,
+) -> Option<PathBuf> {
+    canonical
+        .get(exercise)
+        .and_then(|&n| takes.iter().find(|p| paths::attempt_number(p, exercise) == Some(n)))
+        .or_else(|| takes.last())
+        .cloned()
+}
+
+/// Sustained takes that make up the session result.
+#[derive(Debug, PartialEq)]
+enum SustainedTakes {
+    /// One take, reported as is.
+    Single(PathBuf),
+    /// The takes of one multi-trial run, combined into a best-of-N result.
+    Trials(Vec<PathBuf>),
+}
+
+/// Choose the sustained takes for the session result.
+///
+/// `trial_run` lists the attempt numbers recorded by the last multi-trial
+/// guided run. Its takes are combined when `sustained_trials` > 1 and no
+/// canonical take was marked. Only that run's takes are combined, not every
+/// attempt on disk: ad-hoc retakes recorded with `voicevo record`, and
+/// sessions recorded before runs were stored, have no run and fall back to
+/// the canonical or newest take, as for the other exercises.
+fn sustained_takes_to_use(
+    takes: &[PathBuf],
+    canonical: &BTreeMap<String, u32>,
+    sustained_trials: u32,
+    trial_run: Option<&[u32]>,
+) -> Option<SustainedTakes> {
+    if sustained_trials > 1 && !canonical.contains_key("sustained") {
+        if let Some(run) = trial_run {
+            let run_takes: Vec<PathBuf> = takes
+                .iter()
+                .filter(|p| paths::attempt_number(p, "sustained").is_some_and(|n| run.contains(&n)))
+                .cloned()
+                .collect();
+            if run_takes.len() > 1 {
+                return Some(SustainedTakes::Trials(run_takes));
+            }
+        }
+    }
+    chosen_take(takes, "sustained", canonical).map(SustainedTakes::Single)
+}
+
+/// Record a take's result for per-attempt storage. Old-format recordings
+/// without an attempt number are only stored as the session result.
+fn push_attempt(results: &mut Vec<AttemptAnalysis>, path: &Path, result: ExerciseResult) {
+    if let Some(attempt) = paths::attempt_number(path, result.exercise()) {
+        results.push(AttemptAnalysis { attempt, result });
+    }
+}
+
+/// Analyze the takes not already analyzed for the session result, for
+/// comparison only. A take that fails to load or analyze is skipped with a note.
+fn analyze_other_takes<T, F>(
+    exercise: &str,
+    takes: &[PathBuf],
+    analyzed: &[PathBuf],
+    analyze_fn: F,
+    wrap: fn(T) -> ExerciseResult,
+) -> Vec<AttemptAnalysis>
+where
+    T: RecordedAnalysis,
+    F: Fn(&[f32], u32) -> Result<T>,
+{
+    let mut results = Vec::new();
+
+    for path in takes.iter().filter(|p| !analyzed.contains(p)) {
+        let Some(attempt) = paths::attempt_number(path, exercise) else {
+            continue;
+        };
+
+        let analyzed = wav::load_samples(path)
+            .with_context(|| format!("Failed to load {}", path.display()))
+            .and_then(|(samples, spec)| {
+                let mut result = analyze_fn(&samples, spec.sample_rate)?;
+                result.set_format(spec);
+                Ok(result)
+            });
+
+        match analyzed {
+            Ok(result) => results.push(AttemptAnalysis { attempt, result: wrap(result) }),
+            Err(e) => println!(
+                "     {}: {} ({e:#})",
+                attempt_label(path),
+                style("skipped").yellow()
+            ),
+        }
+    }
+
+    if !results.is_empty() {
+        println!(
+            "     Also analyzed {} other {} take(s); see `voicevo attempts`",
+            results.len(),
+            exercise
+        );
+        println!();
+    }
+
+    results
+}
+
+/// Analyze the sustained vowel attempts of a multi-trial run and combine them
+/// into a best-of-N result. Returns the combined analysis and the path of the
+/// attempt it was taken from.
+///
+/// An attempt that fails to load or analyze is skipped rather than failing
+/// the whole session — the remaining trials are still valid.
+fn analyze_sustained_trials(
+    attempts: &[PathBuf],
+    pitch_config: &crate::dsp::pitch::PitchConfig,
+    profile: &ProfileConfig,
+    attempt_results: &mut Vec<AttemptAnalysis>,
+) -> Result<(SustainedAnalysis, PathBuf)> {
+    println!(
+        "  {} Sustained vowel ({} trials)",
+        style(">>").cyan(),
+        attempts.len()
+    );
+
+    let mut results = Vec::new();
+    for path in attempts {
+        let name = attempt_label(path);
+        let analyzed = wav::load_samples(path)
+            .with_context(|| format!("Failed to load {}", path.display()))
+            .and_then(|(samples, spec)| {
+                let mut result = super::sustained::analyze(&samples, spec.sample_rate, pitch_config)?;
+                result.set_format(spec);
+                Ok(result)
+            });
+
+        match analyzed {
+            Ok(result) => {
+                println!("     {name}: MPT {:.1}s", result.mpt_seconds);
+                push_attempt(attempt_results, path, ExerciseResult::Sustained(result.clone()));
+                results.push((name, result));
+            }
+            Err(e) => {
+                println!("     {name}: {} ({e:#})", style("skipped").yellow());
+            }
+        }
+    }
+
+    let combined = super::sustained::combine_trials(results)
+        .context("No sustained attempt could be analyzed")?;
+
+    let quality_path = combined
+        .trials
+        .as_ref()
+        .and_then(|t| attempts.iter().find(|p| attempt_label(p) == t.source))
+        .unwrap_or(&attempts[attempts.len() - 1])
+        .clone();
+
+    println!();
+    print_sustained_results(&combined, profile);
+    println!();
+
+    Ok((combined, quality_path))
+}
+
+/// File name of an attempt, used to attribute trial results.
+fn attempt_label(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+fn print_sustained_results(r: &SustainedAnalysis, profile: &ProfileConfig) {
+    println!("     MPT:      {:.1}s {}", r.mpt_seconds, norm_label(NormMetric::Mpt, r.mpt_seconds, profile));
+    if let Some(ref trials) = r.trials {
+        println!(
+            "     Trials:   best of {} (mean {:.1}s, all metrics from {})",
+            trials.attempts.len(),
+            trials.mpt_mean_seconds,
+            trials.source,
+        );
+    }
     println!("     Mean F0:  {:.1} Hz", r.mean_f0_hz);
     println!("     F0 std:   {:.1} Hz", r.f0_std_hz);
     println!(
         "     Jitter:   {:.2}% {}",
         r.jitter_local_percent,
-        threshold_label(r.jitter_local_percent, t.jitter_pathological)
+        norm_label(NormMetric::Jitter, r.jitter_local_percent, profile)
     );
     println!(
         "     Shimmer:  {:.2}% {}",
         r.shimmer_local_percent,
-        threshold_label(r.shimmer_local_percent, t.shimmer_pathological)
+        norm_label(NormMetric::Shimmer, r.shimmer_local_percent, profile)
     );
     println!(
         "     HNR:      {:.1} dB {}",
         r.hnr_db,
-        hnr_label(r.hnr_db, t)
+        norm_label(NormMetric::Hnr, r.hnr_db, profile)
     );
     if let Some(cpps) = r.cpps_db {
         println!(
@@ -178,23 +461,76 @@ fn print_sustained_results(r: &SustainedAnalysis, t: &crate::config::ThresholdCo
     }
     if let Some(ref rel) = r.reliability {
         println!(
-            "     Quality:  {} (active {:.0}%, pitched {:.0}%, tier {})",
+            "     Quality:  {} (active {:.0}%, pitched {:.0}%, tier {}){}",
             style(&rel.analysis_quality).cyan(),
             rel.active_fraction * 100.0,
             rel.pitched_fraction * 100.0,
             rel.dominant_tier,
+            snr_note(rel),
         );
     }
+    print_segment(r.segment.as_ref());
+    print_recording_quality(r.recording_quality.as_ref());
 }
 
 fn print_scale_results(r: &ScaleAnalysis) {
     println!("     Floor:      {:.1} Hz", r.pitch_floor_hz);
     println!("     Ceiling:    {:.1} Hz", r.pitch_ceiling_hz);
-    println!("     Range:      {:.1} Hz ({:.1} semitones)", r.range_hz, r.range_semitones);
+    println!(
+        "     Range:      {:.1} Hz ({:.1} semitones)",
+        r.range_hz, r.range_semitones
+    );
+    print_segment(r.segment.as_ref());
+    print_recording_quality(r.recording_quality.as_ref());
 }
 
-fn print_reading_results(r: &ReadingAnalysis) {
-    println!("     Mean F0:    {:.1} Hz", r.mean_f0_hz);
+/// Print the analyzed region of the recording.
+fn print_segment(segment: Option<&AnalyzedSegment>) {
+    if let Some(seg) = segment {
+        println!(
+            "     Segment:  {:.2}s - {:.2}s ({:.1}s analyzed)",
+            seg.start_secs,
+            seg.end_secs,
+            seg.duration_secs()
+        );
+    }
+}
+
+/// Print SNR and any recording problems (clipping, noise, DC offset, hum).
+fn print_recording_quality(q: Option<&RecordingQuality>) {
+    let Some(q) = q else {
+        return;
+    };
+    println!(
+        "     Recording: SNR {:.0} dB, noise floor {:.0} dB{}",
+        q.snr_db,
+        q.noise_floor_db,
+        if q.warnings.is_empty() {
+            format!(" {}", style("(clean)").green())
+        } else {
+            String::new()
+        }
+    );
+    for w in &q.warnings {
+        println!("       {} {w}", style("!").yellow().bold());
+    }
+}
+
+/// Marker appended to the quality line when low SNR downgraded it.
+fn snr_note(rel: &ReliabilityInfo) -> String {
+    if rel.snr_limited {
+        format!(" {}", style("[limited by SNR]").yellow())
+    } else {
+        String::new()
+    }
+}
+
+fn print_reading_results(r: &ReadingAnalysis, profile: &ProfileConfig) {
+    println!(
+        "     Mean F0:    {:.1} Hz {}",
+        r.mean_f0_hz,
+        norm_label(NormMetric::SpeakingF0, r.mean_f0_hz, profile)
+    );
     println!("     F0 std:     {:.1} Hz", r.f0_std_hz);
     println!(
         "     F0 range:   {:.1} - {:.1} Hz",
@@ -211,22 +547,23 @@ fn print_reading_results(r: &ReadingAnalysis) {
     }
     if let Some(ref rel) = r.reliability {
         println!(
-            "     Quality:    {} (active {:.0}%, pitched {:.0}%, tier {})",
+            "     Quality:    {} (active {:.0}%, pitched {:.0}%, tier {}){}",
             style(&rel.analysis_quality).cyan(),
             rel.active_fraction * 100.0,
             rel.pitched_fraction * 100.0,
             rel.dominant_tier,
+            snr_note(rel),
         );
     }
+    print_segment(r.segment.as_ref());
+    print_recording_quality(r.recording_quality.as_ref());
 }
 
-/// Format a label for metrics where lower is better (jitter, shimmer).
-fn threshold_label(value: f32, threshold: f32) -> String {
-    if value <= threshold {
-        format!("{}", style("(normal)").green())
-    } else {
-        format!("{}", style("(elevated)").yellow())
-    }
+/// Parenthesized norm rating, or nothing when the profile has no norms.
+fn norm_label(metric: NormMetric, value: f32, profile: &ProfileConfig) -> String {
+    crate::audio::exercise::rate(metric, value, profile)
+        .map(|r| format!("({r})"))
+        .unwrap_or_default()
 }
 
 /// Format a label for CPPS. Normal ~5-10 dB, < 3 dB = significant dysphonia.
@@ -240,13 +577,75 @@ fn cpps_label(cpps: f32) -> String {
     }
 }
 
-/// Format a label for HNR where higher is better.
-fn hnr_label(hnr: f32, t: &crate::config::ThresholdConfig) -> String {
-    if hnr >= t.hnr_normal {
-        format!("{}", style("(healthy)").green())
-    } else if hnr >= t.hnr_low {
-        format!("{}", style("(improving)").yellow())
-    } else {
-        format!("{}", style("(low)").red())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn takes(numbers: &[u32]) -> Vec<PathBuf> {
+        numbers
+            .iter()
+            .map(|n| PathBuf::from(format!("/data/2026-01-15/sustained_{n:03}.wav")))
+            .collect()
+    }
+
+    #[test]
+    fn single_trial_config_uses_latest_take() {
+        let takes = takes(&[1, 2]);
+        let none = BTreeMap::new();
+
+        // A re-recording replaces the earlier take rather than joining it,
+        // even if an old multi-trial run listed both
+        for run in [None, Some([1, 2].as_slice())] {
+            assert_eq!(
+                sustained_takes_to_use(&takes, &none, 1, run),
+                Some(SustainedTakes::Single(takes[1].clone()))
+            );
+        }
+    }
+
+    #[test]
+    fn multi_trial_run_combines_only_its_takes() {
+        let takes = takes(&[1, 2, 3, 4]);
+        let none = BTreeMap::new();
+
+        // Take 1 was recorded outside the run, take 4 is a later re-recording
+        assert_eq!(
+            sustained_takes_to_use(&takes, &none, 3, Some(&[2, 3])),
+            Some(SustainedTakes::Trials(takes[1..3].to_vec()))
+        );
+        // No run recorded: newest take
+        assert_eq!(
+            sustained_takes_to_use(&takes, &none, 3, None),
+            Some(SustainedTakes::Single(takes[3].clone()))
+        );
+
+        let canonical = BTreeMap::from([("sustained".to_string(), 1)]);
+        assert_eq!(
+            sustained_takes_to_use(&takes, &canonical, 3, Some(&[2, 3])),
+            Some(SustainedTakes::Single(takes[0].clone()))
+        );
+        assert_eq!(sustained_takes_to_use(&[], &none, 3, Some(&[2, 3])), None);
+    }
+
+    #[test]
+    fn takes_outside_a_run_are_not_combined() {
+        let takes = takes(&[1, 2, 3]);
+        let none = BTreeMap::new();
+
+        // Several ad-hoc takes with no recorded run are never combined
+        assert_eq!(
+            sustained_takes_to_use(&takes, &none, 3, None),
+            Some(SustainedTakes::Single(takes[2].clone()))
+        );
+        // A run with one take left is not combined either: newest take
+        assert_eq!(
+            sustained_takes_to_use(&takes, &none, 3, Some(&[2])),
+            Some(SustainedTakes::Single(takes[2].clone()))
+        );
+        // A run whose takes were all deleted falls back to the newest take
+        assert_eq!(
+            sustained_takes_to_use(&takes, &none, 3, Some(&[7, 8])),
+            Some(SustainedTakes::Single(takes[2].clone()))
+        );
     }
 }
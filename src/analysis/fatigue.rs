@@ -45,6 +45,7 @@ pub fn compute_fatigue(
         effort_per_trial,
         mpt_slope,
         cpps_slope,
+        format: None,
     })
 }
 
@@ -1,5 +1,6 @@
 pub mod analyzer;
 pub mod fatigue;
+pub mod quality;
 pub mod reading;
 pub mod scale;
 pub mod sustained;
@@ -0,0 +1,368 @@
+use rustfft::{num_complex::Complex, FftPlanner};
+
+use crate::dsp::activity::{self, ActivityConfig};
+use crate::dsp::windowing;
+use crate::storage::session_data::{RecordingQuality, SNR_OK_DB, SNR_TREND_ONLY_DB};
+
+/// A sample at or above this magnitude counts as clipped. Integer capture
+/// saturates at full scale, which maps to just under 1.0; float capture can
+/// exceed it.
+const CLIP_LEVEL: f32 = 0.999;
+
+/// More than 0.1% clipped samples audibly distorts the waveform and
+/// inflates shimmer.
+const MAX_CLIPPED_FRACTION: f32 = 0.001;
+
+/// DC offset above 2% of full scale points at a faulty interface or mic.
+const MAX_DC_OFFSET: f32 = 0.02;
+
+/// A 50/60 Hz peak this far above its neighbourhood is audible hum.
+const HUM_PROMINENCE_DB: f32 = 15.0;
+
+/// Minimum leading + trailing silence (frames of 10 ms) for the noise floor
+/// to be measured from silence rather than estimated from quiet frames.
+const MIN_SILENCE_FRAMES: usize = 10;
+
+/// Lowest level reported for digital silence (dBFS).
+const FLOOR_DB: f32 = -120.0;
+
+/// Assess the technical quality of a recording.
+///
+/// - Clipping: fraction of samples at full scale.
+/// - Noise floor: level of the leading and trailing silence around the
+///   phonation (falls back to the quietest 10% of frames when the take
+///   starts or ends mid-phonation).
+/// - SNR: level of the active frames minus the noise floor.
+/// - DC offset: mean sample value.
+/// - Hum: prominence of a 50 or 60 Hz peak in the noise spectrum. Measured
+///   on the silence when there is enough of it, otherwise on the whole take.
+pub fn assess(samples: &[f32], sample_rate: u32) -> RecordingQuality {
+    let clipped_fraction = if samples.is_empty() {
+        0.0
+    } else {
+        samples.iter().filter(|s| s.abs() >= CLIP_LEVEL).count() as f32 / samples.len() as f32
+    };
+
+    let dc_offset = if samples.is_empty() {
+        0.0
+    } else {
+        samples.iter().sum::<f32>() / samples.len() as f32
+    };
+
+    let config = ActivityConfig::default();
+    let frame_size = (config.frame_size_ms / 1000.0 * sample_rate as f32) as usize;
+    let active = activity::detect_activity(samples, sample_rate, &config).active_frames;
+    let powers: Vec<f32> = if frame_size == 0 {
+        Vec::new()
+    } else {
+        samples.chunks_exact(frame_size).map(frame_power).collect()
+    };
+
+    // Leading and trailing silence: frames before the first and after the
+    // last active frame.
+    let first_active = active.iter().position(|&a| a);
+    let last_active = active.iter().rposition(|&a| a);
+    let silence: Vec<usize> = match (first_active, last_active) {
+        (Some(first), Some(last)) => (0..first).chain(last + 1..active.len()).collect(),
+        _ => (0..active.len()).collect(),
+    };
+    let noise_from_silence = silence.len() >= MIN_SILENCE_FRAMES && first_active.is_some();
+
+    let noise_power = if noise_from_silence {
+        mean(silence.iter().map(|&i| powers[i]))
+    } else {
+        quietest_power(&powers, 0.1)
+    };
+
+    let signal_power = if first_active.is_some() {
+        mean(active.iter().zip(&powers).filter(|(&a, _)| a).map(|(_, &p)| p))
+    } else {
+        mean(powers.iter().copied())
+    };
+
+    let noise_floor_db = power_db(noise_power);
+    let snr_db = power_db(signal_power) - noise_floor_db;
+
+    // Hum is easiest to see in silence, where there's no voice energy.
+    let silence_samples: Vec<f32> = if noise_from_silence {
+        silence
+            .iter()
+            .flat_map(|&i| samples[i * frame_size..(i + 1) * frame_size].iter().copied())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let fft_size = hum_fft_size(sample_rate);
+    let hum_source = if silence_samples.len() >= fft_size / 2 {
+        &silence_samples[..]
+    } else {
+        samples
+    };
+    let (hum_freq, hum_prominence_db) = hum_peak(hum_source, sample_rate, fft_size);
+    let hum_hz = (hum_prominence_db >= HUM_PROMINENCE_DB).then_some(hum_freq);
+
+    let mut warnings = Vec::new();
+    if clipped_fraction > MAX_CLIPPED_FRACTION {
+        warnings.push(format!(
+            "Clipping: {:.2}% of samples at full scale. Move back from the mic or lower the input gain.",
+            clipped_fraction * 100.0
+        ));
+    }
+    if snr_db < SNR_TREND_ONLY_DB {
+        warnings.push(format!(
+            "Noisy: SNR {snr_db:.0} dB (noise floor {noise_floor_db:.0} dB). Jitter, shimmer and HNR will not be reliable."
+        ));
+    } else if snr_db < SNR_OK_DB {
+        warnings.push(format!(
+            "Background noise: SNR {snr_db:.0} dB (noise floor {noise_floor_db:.0} dB). A quieter room would help."
+        ));
+    }
+    if dc_offset.abs() > MAX_DC_OFFSET {
+        warnings.push(format!(
+            "DC offset of {dc_offset:+.3}. Check the microphone or audio interface."
+        ));
+    }
+    if let Some(hz) = hum_hz {
+        warnings.push(format!(
+            "Mains hum at {hz:.0} Hz ({hum_prominence_db:.0} dB above the background). Check cables and nearby power supplies."
+        ));
+    }
+
+    RecordingQuality {
+        clipped_fraction,
+        noise_floor_db,
+        noise_from_silence,
+        snr_db,
+        dc_offset,
+        hum_hz,
+        hum_prominence_db,
+        warnings,
+        format: None,
+    }
+}
+
+/// Mean square of a frame, with DC removed so an offset doesn't read as noise.
+fn frame_power(frame: &[f32]) -> f32 {
+    let m = frame.iter().sum::<f32>() / frame.len() as f32;
+    frame.iter().map(|s| (s - m) * (s - m)).sum::<f32>() / frame.len() as f32
+}
+
+fn mean(values: impl Iterator<Item = f32>) -> f32 {
+    let (sum, n) = values.fold((0.0, 0usize), |(s, n), v| (s + v, n + 1));
+    if n == 0 {
+        0.0
+    } else {
+        sum / n as f32
+    }
+}
+
+/// Mean power of the quietest `fraction` of frames.
+fn quietest_power(powers: &[f32], fraction: f32) -> f32 {
+    let mut sorted = powers.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let n = ((sorted.len() as f32 * fraction).ceil() as usize).max(1).min(sorted.len());
+    mean(sorted[..n].iter().copied())
+}
+
+fn power_db(power: f32) -> f32 {
+    if power > 0.0 {
+        (10.0 * power.log10()).max(FLOOR_DB)
+    } else {
+        FLOOR_DB
+    }
+}
+
+/// FFT size giving roughly 2-3 Hz resolution, enough to tell 50 from 60 Hz.
+fn hum_fft_size(sample_rate: u32) -> usize {
+    (sample_rate as usize / 4).next_power_of_two()
+}
+
+/// Find the stronger of the 50 and 60 Hz spectral peaks.
+///
+/// Returns the frequency and its prominence in dB over the median of the
+/// 30-90 Hz band (excluding the hum bins themselves). Uses a Welch average
+/// of Hann-windowed frames with 50% overlap.
+fn hum_peak(samples: &[f32], sample_rate: u32, fft_size: usize) -> (f32, f32) {
+    if samples.is_empty() || sample_rate == 0 {
+        return (50.0, 0.0);
+    }
+
+    let mut planner = FftPlanner::<f32>::new();
+    let fft = planner.plan_fft_forward(fft_size);
+    let half = fft_size / 2;
+    let mut spectrum = vec![0.0f32; half];
+    let mut frames = 0;
+
+    let hop = half.max(1);
+    let mut pos = 0;
+    loop {
+        let end = (pos + fft_size).min(samples.len());
+        let mut frame = samples[pos..end].to_vec();
+        frame.resize(fft_size, 0.0);
+        let mut buf: Vec<Complex<f32>> = windowing::hanning(&frame)
+            .into_iter()
+            .map(|s| Complex::new(s, 0.0))
+            .collect();
+        fft.process(&mut buf);
+        for (acc, c) in spectrum.iter_mut().zip(&buf) {
+            *acc += c.norm_sqr();
+        }
+        frames += 1;
+
+        pos += hop;
+        if pos + fft_size > samples.len() {
+            break;
+        }
+    }
+    for p in &mut spectrum {
+        *p /= frames as f32;
+    }
+
+    let bin_hz = sample_rate as f32 / fft_size as f32;
+    let bin = |hz: f32| (hz / bin_hz).round() as usize;
+    let near = |i: usize, hz: f32| (i as f32 * bin_hz - hz).abs() <= 4.0;
+
+    let mut reference: Vec<f32> = (bin(30.0)..=bin(90.0).min(half - 1))
+        .filter(|&i| !near(i, 50.0) && !near(i, 60.0))
+        .map(|i| spectrum[i])
+        .collect();
+    if reference.is_empty() {
+        return (50.0, 0.0);
+    }
+    reference.sort_by(|a, b| a.total_cmp(b));
+    let median = reference[reference.len() / 2];
+
+    let peak = |hz: f32| {
+        let center = bin(hz);
+        (center.saturating_sub(1)..=(center + 1).min(half - 1))
+            .map(|i| spectrum[i])
+            .fold(0.0f32, f32::max)
+    };
+
+    let (freq, power) = [(50.0, peak(50.0)), (60.0, peak(60.0))]
+        .into_iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((50.0, 0.0));
+
+    if median <= 0.0 {
+        return (freq, if power > 0.0 { -FLOOR_DB } else { 0.0 });
+    }
+    (freq, 10.0 * (power / median).log10())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    const SR: u32 = 44100;
+
+    fn sine(freq: f32, amp: f32, secs: f32) -> Vec<f32> {
+        (0..(SR as f32 * secs) as usize)
+            .map(|i| amp * (2.0 * PI * freq * i as f32 / SR as f32).sin())
+            .collect()
+    }
+
+    /// Deterministic pseudo-random noise in [-amp, amp].
+    fn noise(amp: f32, secs: f32, seed: u32) -> Vec<f32> {
+        let mut state = seed;
+        (0..(SR as f32 * secs) as usize)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                amp * ((state >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0)
+            })
+            .collect()
+    }
+
+    /// Tone with half a second of quiet noise before and after.
+    fn take(tone: Vec<f32>, noise_amp: f32) -> Vec<f32> {
+        let mut out = noise(noise_amp, 0.5, 1);
+        let body = noise(noise_amp, tone.len() as f32 / SR as f32, 2);
+        out.extend(tone.iter().zip(&body).map(|(t, n)| t + n));
+        out.extend(noise(noise_amp, 0.5, 3));
+        out
+    }
+
+    #[test]
+    fn clean_take_has_no_warnings() {
+        let q = assess(&take(sine(150.0, 0.3, 2.0), 0.0005), SR);
+        assert!(q.noise_from_silence);
+        assert!(q.snr_db > 40.0, "snr {}", q.snr_db);
+        assert!(q.clipped_fraction < 0.0001);
+        assert!(q.hum_hz.is_none(), "hum {}", q.hum_prominence_db);
+        assert!(q.warnings.is_empty(), "{:?}", q.warnings);
+    }
+
+    #[test]
+    fn clipping_detected() {
+        let clipped: Vec<f32> = sine(150.0, 2.0, 2.0).into_iter().map(|s| s.clamp(-1.0, 1.0)).collect();
+        let q = assess(&take(clipped, 0.0005), SR);
+        assert!(q.clipped_fraction > 0.1);
+        assert!(q.warnings.iter().any(|w| w.starts_with("Clipping")));
+    }
+
+    #[test]
+    fn noisy_take_has_low_snr() {
+        let q = assess(&take(sine(150.0, 0.1, 2.0), 0.05), SR);
+        assert!(q.snr_db < SNR_TREND_ONLY_DB, "snr {}", q.snr_db);
+        assert!(q.warnings.iter().any(|w| w.starts_with("Noisy")));
+    }
+
+    #[test]
+    fn dc_offset_detected() {
+        let shifted: Vec<f32> = take(sine(150.0, 0.3, 2.0), 0.0005).into_iter().map(|s| s + 0.05).collect();
+        let q = assess(&shifted, SR);
+        assert!((q.dc_offset - 0.05).abs() < 0.01);
+        assert!(q.warnings.iter().any(|w| w.starts_with("DC offset")));
+        // DC is removed before measuring levels, so SNR is unaffected
+        assert!(q.snr_db > 40.0);
+    }
+
+    #[test]
+    fn mains_hum_detected() {
+        let mut samples = take(sine(150.0, 0.3, 2.0), 0.0005);
+        let hum = sine(60.0, 0.003, samples.len() as f32 / SR as f32);
+        for (s, h) in samples.iter_mut().zip(&hum) {
+            *s += h;
+        }
+        let q = assess(&samples, SR);
+        assert_eq!(q.hum_hz, Some(60.0));
+        assert!(q.warnings.iter().any(|w| w.starts_with("Mains hum")));
+    }
+
+    #[test]
+    fn assessment_is_sample_rate_independent() {
+        let assess_at = |sr: u32| {
+            let mut state = 5u32;
+            let samples: Vec<f32> = (0..(3 * sr) as usize)
+                .map(|i| {
+                    let t = i as f32 / sr as f32;
+                    state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                    let noise = 0.0005 * ((state >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0);
+                    let tone = if (0.5..2.5).contains(&t) { 0.3 * (2.0 * PI * 150.0 * t).sin() } else { 0.0 };
+                    tone + noise + 0.003 * (2.0 * PI * 50.0 * t).sin()
+                })
+                .collect();
+            assess(&samples, sr)
+        };
+
+        let reference = assess_at(44_100);
+        for sr in [16_000, 44_100, 48_000] {
+            let q = assess_at(sr);
+            assert!(q.noise_from_silence, "{sr} Hz");
+            assert_eq!(q.hum_hz, Some(50.0), "{sr} Hz");
+            assert!((q.snr_db - reference.snr_db).abs() < 1.0, "{sr} Hz: SNR {}", q.snr_db);
+            assert!((q.noise_floor_db - reference.noise_floor_db).abs() < 1.0, "{sr} Hz");
+        }
+    }
+
+    #[test]
+    fn silence_and_empty_input() {
+        let q = assess(&vec![0.0; SR as usize], SR);
+        assert_eq!(q.noise_floor_db, FLOOR_DB);
+        assert!(q.hum_hz.is_none());
+
+        let q = assess(&[], SR);
+        assert_eq!(q.clipped_fraction, 0.0);
+    }
+}
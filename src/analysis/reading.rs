@@ -1,17 +1,26 @@
 use anyhow::Result;
 
-use crate::dsp::{activity, contour, cpps, pitch, voice_breaks};
-use crate::storage::session_data::{ReliabilityInfo, ReadingAnalysis};
+use crate::dsp::{activity, contour, cpps, pitch, segment, voice_breaks};
+use crate::storage::session_data::{AnalyzedSegment, ReliabilityInfo, ReadingAnalysis};
 
 /// Analyze a reading passage recording.
 ///
 /// Uses three-tier pitch detection fallback for breathy voices.
 /// Energy-based activity detection runs alongside pitch detection.
+///
+/// Analysis covers the speech region only (first to last sound), so the
+/// keypress and silence around it don't count as voice breaks or unvoiced time.
 pub fn analyze(
     samples: &[f32],
     sample_rate: u32,
     pitch_config: &pitch::PitchConfig,
 ) -> Result<ReadingAnalysis> {
+    let recording_quality = super::quality::assess(samples, sample_rate);
+
+    // Segmentation — analyze the speech region only
+    let (samples, range) = segment::trim(samples, sample_rate, segment::SegmentMode::Span);
+    let segment = range.map(|r| AnalyzedSegment::from_range(r, sample_rate));
+
     // Activity detection — ground truth for sound production
     let activity_result = activity::detect_activity(samples, sample_rate, &activity::ActivityConfig::default());
 
@@ -52,13 +61,16 @@ pub fn analyze(
 
     // Compute reliability info
     let pitched_fraction = activity::voiced_quality(pitch_contour, &activity_result.active_frames);
-    let reliability = ReliabilityInfo::compute(
+    let mut reliability = ReliabilityInfo::compute(
         result.tier_counts,
         activity_result.active_fraction,
         pitched_fraction,
         cpps_db.is_some(),
     );
 
+    // A noisy take can't support perturbation measures
+    reliability.apply_snr(recording_quality.snr_db);
+
     let detection_quality = if result.detection_quality == "pitch" {
         None
     } else {
@@ -74,5 +86,7 @@ pub fn analyze(
         cpps_db,
         detection_quality,
         reliability: Some(reliability),
+        recording_quality: Some(recording_quality),
+        segment,
     })
 }
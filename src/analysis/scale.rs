@@ -1,7 +1,7 @@
 use anyhow::Result;
 
-use crate::dsp::{contour, pitch};
-use crate::storage::session_data::ScaleAnalysis;
+use crate::dsp::{contour, pitch, segment};
+use crate::storage::session_data::{AnalyzedSegment, ScaleAnalysis};
 
 /// Analyze a chromatic scale recording.
 ///
@@ -13,11 +13,18 @@ use crate::storage::session_data::ScaleAnalysis;
 ///
 /// We use percentiles instead of min/max to exclude outlier detections
 /// (a stray frame at 30 Hz from a mic bump shouldn't set the floor).
+/// Only the singing region (first to last sound) is analyzed.
 pub fn analyze(
     samples: &[f32],
     sample_rate: u32,
     pitch_config: &pitch::PitchConfig,
 ) -> Result<ScaleAnalysis> {
+    let recording_quality = super::quality::assess(samples, sample_rate);
+
+    // Segmentation — analyze the singing region only
+    let (samples, range) = segment::trim(samples, sample_rate, segment::SegmentMode::Span);
+    let segment = range.map(|r| AnalyzedSegment::from_range(r, sample_rate));
+
     let pitch_contour = pitch::extract_pitch_contour(samples, sample_rate, pitch_config);
     let mut frequencies = pitch::voiced_frequencies(&pitch_contour);
 
@@ -49,5 +56,7 @@ pub fn analyze(
         pitch_ceiling_hz: ceiling,
         range_hz,
         range_semitones,
+        recording_quality: Some(recording_quality),
+        segment,
     })
 }
@@ -1,18 +1,30 @@
 use anyhow::Result;
 
-use crate::dsp::{activity, cpps, hnr, jitter, mpt, periodicity, pitch, shimmer};
-use crate::storage::session_data::{ReliabilityInfo, SustainedAnalysis};
+use crate::dsp::{activity, cpps, hnr, jitter, mpt, periodicity, pitch, segment, shimmer};
+use crate::storage::session_data::{
+    AnalyzedSegment, ReliabilityInfo, SustainedAnalysis, SustainedTrials, TrialMpt,
+};
 
 /// Analyze a sustained vowel recording.
 ///
 /// Uses three-tier pitch detection fallback for breathy voices.
 /// Energy-based activity detection runs alongside pitch detection to
 /// establish ground truth for "is the patient making sound?"
+///
+/// Only the dominant phonation is analyzed; the keypress, breaths and
+/// trailing silence around it are trimmed first. Recording quality is
+/// measured on the whole take, since the noise floor comes from that silence.
 pub fn analyze(
     samples: &[f32],
     sample_rate: u32,
     pitch_config: &pitch::PitchConfig,
 ) -> Result<SustainedAnalysis> {
+    let recording_quality = super::quality::assess(samples, sample_rate);
+
+    // Segmentation — analyze the dominant phonation only
+    let (samples, range) = segment::trim(samples, sample_rate, segment::SegmentMode::Dominant);
+    let segment = range.map(|r| AnalyzedSegment::from_range(r, sample_rate));
+
     // Activity detection — ground truth for sound production
     let activity_result = activity::detect_activity(samples, sample_rate, &activity::ActivityConfig::default());
 
@@ -70,13 +82,16 @@ pub fn analyze(
 
     // Compute reliability info
     let pitched_fraction = activity::voiced_quality(contour, &activity_result.active_frames);
-    let reliability = ReliabilityInfo::compute(
+    let mut reliability = ReliabilityInfo::compute(
         result.tier_counts,
         activity_result.active_fraction,
         pitched_fraction,
         cpps_db.is_some(),
     );
 
+    // A noisy take can't support perturbation measures
+    reliability.apply_snr(recording_quality.snr_db);
+
     let detection_quality = if result.detection_quality == "pitch" {
         None
     } else {
@@ -94,5 +109,156 @@ pub fn analyze(
         periodicity_mean,
         detection_quality,
         reliability: Some(reliability),
+        trials: None,
+        recording_quality: Some(recording_quality),
+        segment,
     })
 }
+
+/// Combine the attempts of one multi-trial run into a best-of-N result.
+///
+/// The clinical MPT protocol takes the longest of several trials. The
+/// longest attempt is reported whole — MPT, F0, jitter, shimmer, HNR and
+/// CPPS all come from it, so every number traces back to one recording.
+/// The other attempts only contribute to the mean MPT.
+///
+/// A single attempt is returned unchanged. Returns None for an empty list.
+pub fn combine_trials(mut results: Vec<(String, SustainedAnalysis)>) -> Option<SustainedAnalysis> {
+    if results.len() <= 1 {
+        return results.pop().map(|(_, r)| r);
+    }
+
+    let best_idx = results
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.1.mpt_seconds.total_cmp(&b.1.mpt_seconds))
+        .map(|(i, _)| i)?;
+
+    let attempts: Vec<TrialMpt> = results
+        .iter()
+        .map(|(name, r)| TrialMpt {
+            attempt: name.clone(),
+            mpt_seconds: r.mpt_seconds,
+        })
+        .collect();
+
+    let (source, mut combined) = results.swap_remove(best_idx);
+    let mpt_mean = attempts.iter().map(|a| a.mpt_seconds).sum::<f32>() / attempts.len() as f32;
+
+    combined.trials = Some(SustainedTrials {
+        attempts,
+        mpt_max_seconds: combined.mpt_seconds,
+        mpt_mean_seconds: mpt_mean,
+        source,
+    });
+    Some(combined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(name: &str, mpt: f32, pitched: f32, hnr: f32) -> (String, SustainedAnalysis) {
+        (
+            name.to_string(),
+            SustainedAnalysis {
+                mpt_seconds: mpt,
+                mean_f0_hz: 120.0,
+                f0_std_hz: 3.0,
+                jitter_local_percent: 1.0,
+                shimmer_local_percent: 4.0,
+                hnr_db: hnr,
+                cpps_db: None,
+                periodicity_mean: None,
+                detection_quality: None,
+                reliability: Some(ReliabilityInfo::compute([80, 10, 10], 0.9, pitched, false)),
+                trials: None,
+                recording_quality: None,
+                segment: None,
+            },
+        )
+    }
+
+    #[test]
+    fn combine_takes_max_and_mean_mpt() {
+        let combined = combine_trials(vec![
+            attempt("sustained_001.wav", 6.0, 0.6, 8.0),
+            attempt("sustained_002.wav", 9.0, 0.6, 8.0),
+            attempt("sustained_003.wav", 7.5, 0.6, 8.0),
+        ])
+        .unwrap();
+
+        let trials = combined.trials.unwrap();
+        assert!((combined.mpt_seconds - 9.0).abs() < 0.01);
+        assert!((trials.mpt_max_seconds - 9.0).abs() < 0.01);
+        assert!((trials.mpt_mean_seconds - 7.5).abs() < 0.01);
+        assert_eq!(trials.source, "sustained_002.wav");
+        assert_eq!(trials.attempts.len(), 3);
+    }
+
+    #[test]
+    fn combine_reports_every_metric_from_one_attempt() {
+        let combined = combine_trials(vec![
+            attempt("sustained_001.wav", 9.0, 0.3, 5.0),
+            attempt("sustained_002.wav", 7.0, 0.8, 12.0),
+        ])
+        .unwrap();
+
+        let trials = combined.trials.unwrap();
+        assert_eq!(trials.source, "sustained_001.wav");
+        // HNR comes from the same take as MPT, not the better-pitched one
+        assert!((combined.mpt_seconds - 9.0).abs() < 0.01);
+        assert!((combined.hnr_db - 5.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn combine_single_attempt_unchanged() {
+        let combined = combine_trials(vec![attempt("sustained_001.wav", 6.0, 0.6, 8.0)]).unwrap();
+        assert!(combined.trials.is_none());
+        assert!((combined.mpt_seconds - 6.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn combine_empty() {
+        assert!(combine_trials(Vec::new()).is_none());
+    }
+
+    /// One second of a 120 Hz vowel-like tone (harmonics falling off at
+    /// 6 dB/octave) between short gaps, with a little noise throughout.
+    fn synthetic_vowel(sample_rate: u32) -> Vec<f32> {
+        let sr = sample_rate as f32;
+        let mut state = 7u32;
+        (0..(1.6 * sr) as usize)
+            .map(|i| {
+                let t = i as f32 / sr;
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                let noise = 0.001 * ((state >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0);
+                let voiced = (0.3..1.3).contains(&t);
+                let tone: f32 = (1..=8)
+                    .map(|h| (2.0 * std::f32::consts::PI * 120.0 * h as f32 * t).sin() / h as f32)
+                    .sum();
+                if voiced { 0.2 * tone + noise } else { noise }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn analysis_is_sample_rate_independent() {
+        let pitch = pitch::PitchConfig::default();
+        let results: Vec<(u32, SustainedAnalysis)> = [16_000, 44_100, 48_000]
+            .into_iter()
+            .map(|sr| (sr, analyze(&synthetic_vowel(sr), sr, &pitch).unwrap()))
+            .collect();
+
+        let (_, reference) = &results[1];
+        for (sr, r) in &results {
+            assert!((r.mean_f0_hz - 120.0).abs() < 1.0, "{sr} Hz: F0 {}", r.mean_f0_hz);
+            assert!((r.mpt_seconds - reference.mpt_seconds).abs() < 0.1, "{sr} Hz: MPT {}", r.mpt_seconds);
+            assert!((r.hnr_db - reference.hnr_db).abs() < 2.0, "{sr} Hz: HNR {}", r.hnr_db);
+            let (cpps, ref_cpps) = (r.cpps_db.unwrap(), reference.cpps_db.unwrap());
+            assert!((cpps - ref_cpps).abs() < 1.5, "{sr} Hz: CPPS {cpps}");
+            let segment = r.segment.as_ref().unwrap();
+            assert!((segment.start_secs - 0.3).abs() < 0.1, "{sr} Hz: segment {segment:?}");
+        }
+    }
+}
@@ -30,6 +30,7 @@ pub fn compute_sz(s_durations: Vec<f32>, z_durations: Vec<f32>) -> Option<SzAnal
         mean_s,
         mean_z,
         sz_ratio,
+        format: None,
     })
 }
 
@@ -5,7 +5,12 @@ use std::thread::JoinHandle;
 
 use anyhow::{Context, Result};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use cpal::SampleFormat;
+use cpal::{
+    FromSample, Sample, SampleFormat, SizedSample, StreamConfig, SupportedStreamConfig, SupportedStreamConfigRange,
+};
+
+use crate::config::RecordingConfig;
+use crate::storage::session_data::RecordingFormat;
 
 /// Silence threshold in dB. RMS below this is considered silence.
 pub const SILENCE_THRESHOLD_DB: f32 = -50.0;
@@ -31,6 +36,8 @@ pub struct AudioState {
     pub live_pitch: Arc<AtomicU32>,
     /// The sample rate of the audio stream.
     pub sample_rate: u32,
+    /// Sample rate and sample format the device delivers.
+    pub format: RecordingFormat,
 }
 
 impl AudioState {
@@ -78,19 +85,16 @@ impl AudioState {
 /// detection and updates `AudioState::live_pitch`.
 pub fn start_capture(
     enable_pitch: bool,
+    recording: &RecordingConfig,
 ) -> Result<(AudioState, cpal::Stream, JoinHandle<Vec<f32>>)> {
     let host = cpal::default_host();
     let device = host
         .default_input_device()
         .context("No default input device found")?;
 
-    let config = device
-        .default_input_config()
-        .context("Failed to get default input config")?;
-
+    let config = input_config(&device, recording.sample_rate)?;
     let sample_rate = config.sample_rate().0;
-    let channels = config.channels() as usize;
-    let format = config.sample_format();
+    let format = stream_format(&config);
 
     let (tx, rx) = mpsc::channel::<Vec<f32>>();
 
@@ -103,68 +107,21 @@ pub fn start_capture(
     let rms_stream = Arc::clone(&live_rms);
     let waveform_stream = Arc::clone(&waveform_buffer);
 
-    let stream = match format {
-        SampleFormat::F32 => device.build_input_stream(
-            &config.into(),
-            move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                if stop_stream.load(Ordering::Relaxed) {
-                    return;
-                }
-                let mono: Vec<f32> = if channels > 1 {
-                    data.iter().step_by(channels).copied().collect()
-                } else {
-                    data.to_vec()
-                };
-                let rms = compute_rms(&mono);
-                rms_stream.store(rms.to_bits(), Ordering::Relaxed);
-                // Push RMS to waveform buffer (non-blocking)
-                if let Ok(mut buf) = waveform_stream.try_lock() {
-                    while buf.len() >= WAVEFORM_BUFFER_SIZE {
-                        buf.pop_front();
-                    }
-                    buf.push_back(rms);
-                }
-                let _ = tx.send(mono);
-            },
-            |err| eprintln!("Stream error: {err}"),
-            None,
-        )?,
-        SampleFormat::I16 => {
-            let stop_stream = Arc::clone(&stop);
-            let rms_stream = Arc::clone(&live_rms);
-            let waveform_stream = Arc::clone(&waveform_buffer);
-            device.build_input_stream(
-                &config.into(),
-                move |data: &[i16], _: &cpal::InputCallbackInfo| {
-                    if stop_stream.load(Ordering::Relaxed) {
-                        return;
-                    }
-                    let mono: Vec<f32> = if channels > 1 {
-                        data.iter()
-                            .step_by(channels)
-                            .map(|&s| s as f32 / i16::MAX as f32)
-                            .collect()
-                    } else {
-                        data.iter()
-                            .map(|&s| s as f32 / i16::MAX as f32)
-                            .collect()
-                    };
-                    let rms = compute_rms(&mono);
-                    rms_stream.store(rms.to_bits(), Ordering::Relaxed);
-                    if let Ok(mut buf) = waveform_stream.try_lock() {
-                        while buf.len() >= WAVEFORM_BUFFER_SIZE {
-                            buf.pop_front();
-                        }
-                        buf.push_back(rms);
-                    }
-                    let _ = tx.send(mono);
-                },
-                |err| eprintln!("Stream error: {err}"),
-                None,
-            )?
+    let stream = build_input_stream(&device, &config, move |mono| {
+        if stop_stream.load(Ordering::Relaxed) {
+            return;
         }
-        other => anyhow::bail!("Unsupported sample format: {other:?}"),
-    };
+        let rms = compute_rms(&mono);
+        rms_stream.store(rms.to_bits(), Ordering::Relaxed);
+        // Push RMS to waveform buffer (non-blocking)
+        if let Ok(mut buf) = waveform_stream.try_lock() {
+            while buf.len() >= WAVEFORM_BUFFER_SIZE {
+                buf.pop_front();
+            }
+            buf.push_back(rms);
+        }
+        let _ = tx.send(mono);
+    })?;
 
     stream.play().context("Failed to start audio stream")?;
 
@@ -198,8 +155,9 @@ pub fn start_capture(
                 }
                 pitch_buffer.extend_from_slice(&chunk);
 
-                // Process when we have enough samples (~1024 for 80Hz floor at 44.1kHz)
-                let window_size = 2048;
+                // ~46 ms windows (2048 samples at 44.1 kHz): two periods
+                // of the 50 Hz floor at any sample rate
+                let window_size = (sample_rate as usize * 2048).div_ceil(44100);
                 while pitch_buffer.len() >= window_size {
                     if let Some(hz) = pitch::detect_pitch_frame(
                         &pitch_buffer[..window_size],
@@ -231,11 +189,95 @@ pub fn start_capture(
         waveform_buffer,
         live_pitch,
         sample_rate,
+        format,
     };
 
     Ok((state, stream, collector_handle))
 }
 
+/// Stream config for capturing at `sample_rate` in the device's native sample
+/// format, so nothing is converted before it reaches us.
+///
+/// Prefers the default config's format and channel count at the requested
+/// rate; when the device can't record at that rate, returns its default
+/// config (callers read the actual rate from the result).
+pub fn input_config(device: &cpal::Device, sample_rate: u32) -> Result<SupportedStreamConfig> {
+    let default = device
+        .default_input_config()
+        .context("Failed to get default input config")?;
+    if default.sample_rate().0 == sample_rate {
+        return Ok(default);
+    }
+
+    let rate = cpal::SampleRate(sample_rate);
+    let candidates: Vec<SupportedStreamConfigRange> = device
+        .supported_input_configs()
+        .map(|configs| configs.filter(|c| c.min_sample_rate() <= rate && rate <= c.max_sample_rate()).collect())
+        .unwrap_or_default();
+    let preference = |c: &SupportedStreamConfigRange| {
+        (c.sample_format() != default.sample_format(), c.channels() != default.channels())
+    };
+    Ok(candidates
+        .into_iter()
+        .min_by_key(preference)
+        .map(|c| c.with_sample_rate(rate))
+        .unwrap_or(default))
+}
+
+/// The stream's sample rate and native sample format. Live exercises keep
+/// no file, so this is the format they were measured from.
+pub fn stream_format(config: &SupportedStreamConfig) -> RecordingFormat {
+    RecordingFormat {
+        sample_rate: config.sample_rate().0,
+        bits_per_sample: (config.sample_format().sample_size() * 8) as u16,
+        float: config.sample_format().is_float(),
+    }
+}
+
+/// Build an input stream that hands `on_data` mono f32 chunks, whatever the
+/// device's sample format. Multi-channel input is reduced to its first
+/// channel.
+pub fn build_input_stream<F>(device: &cpal::Device, config: &SupportedStreamConfig, on_data: F) -> Result<cpal::Stream>
+where
+    F: FnMut(Vec<f32>) + Send + 'static,
+{
+    let channels = config.channels() as usize;
+    let stream_config = config.config();
+    match config.sample_format() {
+        SampleFormat::F32 => build_stream::<f32, F>(device, &stream_config, channels, on_data),
+        SampleFormat::F64 => build_stream::<f64, F>(device, &stream_config, channels, on_data),
+        SampleFormat::I16 => build_stream::<i16, F>(device, &stream_config, channels, on_data),
+        SampleFormat::I32 => build_stream::<i32, F>(device, &stream_config, channels, on_data),
+        SampleFormat::U16 => build_stream::<u16, F>(device, &stream_config, channels, on_data),
+        SampleFormat::I8 => build_stream::<i8, F>(device, &stream_config, channels, on_data),
+        SampleFormat::U8 => build_stream::<u8, F>(device, &stream_config, channels, on_data),
+        other => anyhow::bail!("Unsupported sample format: {other:?}"),
+    }
+}
+
+fn build_stream<T, F>(device: &cpal::Device, config: &StreamConfig, channels: usize, mut on_data: F) -> Result<cpal::Stream>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+    F: FnMut(Vec<f32>) + Send + 'static,
+{
+    let stream = device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| on_data(to_mono_f32(data, channels)),
+        |err| eprintln!("Stream error: {err}"),
+        None,
+    )?;
+    Ok(stream)
+}
+
+/// First channel of interleaved samples, converted to f32 in [-1.0, 1.0].
+fn to_mono_f32<T: Sample>(data: &[T], channels: usize) -> Vec<f32>
+where
+    f32: FromSample<T>,
+{
+    data.iter().step_by(channels.max(1)).map(|&s| f32::from_sample(s)).collect()
+}
+
 /// Compute RMS of a sample buffer (linear, not dB).
 pub fn compute_rms(samples: &[f32]) -> f32 {
     if samples.is_empty() {
@@ -271,6 +313,24 @@ mod tests {
         assert!((rms - 1.0).abs() < 0.001);
     }
 
+    #[test]
+    fn native_formats_convert_to_full_scale_f32() {
+        assert_eq!(to_mono_f32(&[i16::MIN, 0], 1), vec![-1.0, 0.0]);
+        assert_eq!(to_mono_f32(&[i32::MIN, 1 << 30], 1), vec![-1.0, 0.5]);
+        assert_eq!(to_mono_f32(&[0u16, 32768, 49152], 1), vec![-1.0, 0.0, 0.5]);
+        // Stereo keeps the first channel
+        assert_eq!(to_mono_f32(&[0.25f32, 0.9, -0.5, 0.9], 2), vec![0.25, -0.5]);
+    }
+
+    #[test]
+    fn stream_format_reports_native_depth() {
+        let config = |format| SupportedStreamConfig::new(1, cpal::SampleRate(48000), cpal::SupportedBufferSize::Unknown, format);
+        let i32_format = stream_format(&config(SampleFormat::I32));
+        assert_eq!(i32_format, RecordingFormat { sample_rate: 48000, bits_per_sample: 32, float: false });
+        assert_eq!(stream_format(&config(SampleFormat::F32)).describe(), "48 kHz / 32-bit float");
+        assert_eq!(stream_format(&config(SampleFormat::I16)).bits_per_sample, 16);
+    }
+
     #[test]
     fn audio_state_rms_db_silence() {
         let state = AudioState {
@@ -279,6 +339,7 @@ mod tests {
             waveform_buffer: Arc::new(Mutex::new(VecDeque::new())),
             live_pitch: Arc::new(AtomicU32::new(0)),
             sample_rate: 44100,
+            format: RecordingFormat { sample_rate: 44100, bits_per_sample: 16, float: false },
         };
         assert!(state.rms_db().is_infinite());
         assert!(state.is_silent());
@@ -293,6 +354,7 @@ mod tests {
             waveform_buffer: Arc::new(Mutex::new(VecDeque::new())),
             live_pitch: Arc::new(AtomicU32::new(0)),
             sample_rate: 44100,
+            format: RecordingFormat { sample_rate: 44100, bits_per_sample: 16, float: false },
         };
         let db = state.rms_db();
         assert!((db - (-20.0)).abs() < 0.1);
@@ -307,6 +369,7 @@ mod tests {
             waveform_buffer: Arc::new(Mutex::new(VecDeque::new())),
             live_pitch: Arc::new(AtomicU32::new(0)),
             sample_rate: 44100,
+            format: RecordingFormat { sample_rate: 44100, bits_per_sample: 16, float: false },
         };
         assert!(state.pitch_hz().is_none());
     }
@@ -320,6 +383,7 @@ mod tests {
             waveform_buffer: Arc::new(Mutex::new(VecDeque::new())),
             live_pitch: Arc::new(AtomicU32::new(hz.to_bits())),
             sample_rate: 44100,
+            format: RecordingFormat { sample_rate: 44100, bits_per_sample: 16, float: false },
         };
         assert_eq!(state.pitch_hz(), Some(440.0));
     }
@@ -332,6 +396,7 @@ mod tests {
             waveform_buffer: Arc::new(Mutex::new(VecDeque::new())),
             live_pitch: Arc::new(AtomicU32::new(0)),
             sample_rate: 44100,
+            format: RecordingFormat { sample_rate: 44100, bits_per_sample: 16, float: false },
         };
         assert!(state.waveform_snapshot().is_empty());
     }
@@ -347,6 +412,7 @@ mod tests {
             waveform_buffer: Arc::new(Mutex::new(buf)),
             live_pitch: Arc::new(AtomicU32::new(0)),
             sample_rate: 44100,
+            format: RecordingFormat { sample_rate: 44100, bits_per_sample: 16, float: false },
         };
         let snap = state.waveform_snapshot();
         assert_eq!(snap.len(), 2);
@@ -5,8 +5,9 @@ use console::style;
 
 use crate::analysis::sustained;
 use crate::audio::capture;
-use crate::config::AppConfig;
+use crate::config::{AppConfig, ProfileConfig};
 use crate::dsp::pitch::PitchConfig;
+use crate::norms::{self, NormMetric, Standing};
 use crate::storage;
 use crate::util;
 
@@ -49,7 +50,7 @@ pub fn run_sustain_exercise(config: &AppConfig) -> Result<()> {
 
     // --- Phase 2: TUI recording ---
     let mut terminal = crate::tui::init()?;
-    let (audio_state, stream, collector) = capture::start_capture(false)?;
+    let (audio_state, stream, collector) = capture::start_capture(false, &config.recording)?;
     let sample_rate = audio_state.sample_rate;
 
     let outcome = crate::tui::screens::recording::run(
@@ -103,9 +104,17 @@ pub fn run_sustain_exercise(config: &AppConfig) -> Result<()> {
     println!();
 
     // MPT from the exercise timer (clinical stopwatch method)
-    print_metric("MPT", &format!("{:.1}s", phonation_secs), reference_mpt.map(|r| {
-        format_comparison(phonation_secs, r, "s", true)
-    }));
+    let profile = &config.profile;
+    let mpt_notes: Vec<String> = reference_mpt
+        .map(|r| format_comparison(phonation_secs, r, "s", true))
+        .into_iter()
+        .chain(rate(NormMetric::Mpt, phonation_secs, profile))
+        .collect();
+    print_metric(
+        "MPT",
+        &format!("{:.1}s", phonation_secs),
+        (!mpt_notes.is_empty()).then(|| mpt_notes.join("  ")),
+    );
 
     // Voice quality metrics from DSP analysis
     let pitch_config: PitchConfig = (&config.analysis).into();
@@ -113,11 +122,11 @@ pub fn run_sustain_exercise(config: &AppConfig) -> Result<()> {
         Ok(result) => {
             print_metric("Mean F0", &format!("{:.1} Hz", result.mean_f0_hz), None);
             print_metric("Jitter", &format!("{:.2}%", result.jitter_local_percent),
-                Some(rate_jitter(result.jitter_local_percent, &config.analysis.thresholds)));
+                rate(NormMetric::Jitter, result.jitter_local_percent, profile));
             print_metric("Shimmer", &format!("{:.2}%", result.shimmer_local_percent),
-                Some(rate_shimmer(result.shimmer_local_percent, &config.analysis.thresholds)));
+                rate(NormMetric::Shimmer, result.shimmer_local_percent, profile));
             print_metric("HNR", &format!("{:.1} dB", result.hnr_db),
-                Some(rate_hnr(result.hnr_db, &config.analysis.thresholds)));
+                rate(NormMetric::Hnr, result.hnr_db, profile));
             if let Some(cpps) = result.cpps_db {
                 print_metric("CPPS", &format!("{:.1} dB", cpps),
                     Some(rate_cpps(cpps)));
@@ -173,24 +182,6 @@ fn print_metric(label: &str, value: &str, annotation: Option<String>) {
     }
 }
 
-/// Rate jitter quality.
-fn rate_jitter(jitter: f32, thresholds: &crate::config::ThresholdConfig) -> String {
-    if jitter < thresholds.jitter_pathological {
-        style("normal").green().to_string()
-    } else {
-        style("elevated").yellow().to_string()
-    }
-}
-
-/// Rate shimmer quality.
-fn rate_shimmer(shimmer: f32, thresholds: &crate::config::ThresholdConfig) -> String {
-    if shimmer < thresholds.shimmer_pathological {
-        style("normal").green().to_string()
-    } else {
-        style("elevated").yellow().to_string()
-    }
-}
-
 /// Rate CPPS quality. Normal ~5-10 dB, < 3 dB = significant dysphonia.
 fn rate_cpps(cpps: f32) -> String {
     if cpps >= 5.0 {
@@ -202,15 +193,17 @@ fn rate_cpps(cpps: f32) -> String {
     }
 }
 
-/// Rate HNR quality.
-fn rate_hnr(hnr: f32, thresholds: &crate::config::ThresholdConfig) -> String {
-    if hnr >= thresholds.hnr_normal {
-        style("healthy").green().to_string()
-    } else if hnr >= thresholds.hnr_low {
-        style("fair").yellow().to_string()
-    } else {
-        style("low").red().to_string()
-    }
+/// Rate a measurement against the profile's norms: position, z-score and
+/// percentile, colored by whether it falls on the worse side. `None` when
+/// the profile has no bundled norms (e.g. under 18).
+pub(crate) fn rate(metric: NormMetric, value: f32, profile: &ProfileConfig) -> Option<String> {
+    let norm = norms::lookup(metric, profile)?;
+    let text = format!("{} ({})", norm.position(value), norm.describe(value));
+    Some(match norm.standing(value) {
+        Standing::Typical => style(text).green().to_string(),
+        Standing::Borderline => style(text).yellow().to_string(),
+        Standing::Atypical => style(text).red().to_string(),
+    })
 }
 
 #[cfg(test)]
@@ -236,52 +229,54 @@ mod tests {
     }
 
     #[test]
-    fn rate_jitter_normal() {
-        let thresholds = crate::config::ThresholdConfig::default();
-        let result = rate_jitter(0.5, &thresholds);
-        assert!(result.contains("normal"));
+    fn rate_jitter_typical() {
+        let result = rate(NormMetric::Jitter, 0.5, &ProfileConfig::default()).unwrap();
+        assert!(result.contains("typical (z ≈+0.5"));
     }
 
     #[test]
     fn rate_jitter_elevated() {
-        let thresholds = crate::config::ThresholdConfig::default();
-        let result = rate_jitter(2.0, &thresholds);
-        assert!(result.contains("elevated"));
+        let result = rate(NormMetric::Jitter, 2.0, &ProfileConfig::default()).unwrap();
+        assert!(result.contains("well above typical"));
     }
 
     #[test]
-    fn rate_shimmer_normal() {
-        let thresholds = crate::config::ThresholdConfig::default();
-        let result = rate_shimmer(2.0, &thresholds);
-        assert!(result.contains("normal"));
+    fn rate_shimmer_typical() {
+        let result = rate(NormMetric::Shimmer, 2.0, &ProfileConfig::default()).unwrap();
+        assert!(result.contains("typical"));
+        assert!(!result.contains("above"));
     }
 
     #[test]
     fn rate_shimmer_elevated() {
-        let thresholds = crate::config::ThresholdConfig::default();
-        let result = rate_shimmer(5.0, &thresholds);
-        assert!(result.contains("elevated"));
+        let result = rate(NormMetric::Shimmer, 5.0, &ProfileConfig::default()).unwrap();
+        assert!(result.contains("well above typical"));
     }
 
     #[test]
-    fn rate_hnr_healthy() {
-        let thresholds = crate::config::ThresholdConfig::default();
-        let result = rate_hnr(25.0, &thresholds);
-        assert!(result.contains("healthy"));
+    fn rate_hnr_above_typical() {
+        let result = rate(NormMetric::Hnr, 25.0, &ProfileConfig::default()).unwrap();
+        assert!(result.contains("above typical"));
     }
 
     #[test]
-    fn rate_hnr_fair() {
-        let thresholds = crate::config::ThresholdConfig::default();
-        let result = rate_hnr(12.0, &thresholds);
-        assert!(result.contains("fair"));
+    fn rate_hnr_low() {
+        let result = rate(NormMetric::Hnr, 12.0, &ProfileConfig::default()).unwrap();
+        assert!(result.contains("well below typical"));
     }
 
     #[test]
-    fn rate_hnr_low() {
-        let thresholds = crate::config::ThresholdConfig::default();
-        let result = rate_hnr(3.0, &thresholds);
-        assert!(result.contains("low"));
+    fn rate_uses_profile() {
+        let profile = ProfileConfig {
+            sex: Some(crate::config::Sex::Female),
+            age: Some(30),
+            voice_type: None,
+        };
+        let result = rate(NormMetric::SpeakingF0, 205.0, &profile).unwrap();
+        assert!(result.contains("typical (z +0.0, 50th pct)"));
+
+        let child = ProfileConfig { age: Some(10), ..ProfileConfig::default() };
+        assert!(rate(NormMetric::Hnr, 20.0, &child).is_none());
     }
 
     #[test]
@@ -14,7 +14,7 @@ use crate::storage;
 /// The patient performs 5 sustained vowel attempts with 45s rest between each.
 /// We measure MPT, CPPS, and effort for each trial, then compute the slope
 /// to detect vocal fatigue.
-pub fn run_fatigue_exercise(_config: &AppConfig) -> Result<()> {
+pub fn run_fatigue_exercise(config: &AppConfig) -> Result<()> {
     println!();
     println!("{}", style("=== Vocal Fatigue Test ===").bold());
     println!();
@@ -27,7 +27,7 @@ pub fn run_fatigue_exercise(_config: &AppConfig) -> Result<()> {
 
     // TUI phase
     let mut terminal = crate::tui::init()?;
-    let (audio_state, stream, collector) = capture::start_capture(false)?;
+    let (audio_state, stream, collector) = capture::start_capture(false, &config.recording)?;
     let sample_rate = audio_state.sample_rate;
 
     let outcome = crate::tui::screens::fatigue::run(
@@ -67,7 +67,8 @@ pub fn run_fatigue_exercise(_config: &AppConfig) -> Result<()> {
     let effort_per_trial = outcome.effort_per_trial;
 
     match fatigue::compute_fatigue(mpt_per_trial, cpps_per_trial, effort_per_trial) {
-        Some(result) => {
+        Some(mut result) => {
+            result.format = Some(audio_state.format);
             println!();
             println!("{}", style("Results").bold());
             println!();
@@ -112,6 +113,7 @@ pub fn run_fatigue_exercise(_config: &AppConfig) -> Result<()> {
                 Ok(s) => s,
                 Err(_) => crate::storage::session_data::SessionData {
                     date: date.clone(),
+                    slot: 1,
                     recordings: crate::storage::session_data::SessionRecordings {
                         sustained: None,
                         scale: None,
@@ -125,6 +127,7 @@ pub fn run_fatigue_exercise(_config: &AppConfig) -> Result<()> {
                         fatigue: None,
                     },
                     conditions: None,
+                    source: None,
                 },
             };
             session.analysis.fatigue = Some(result);
@@ -0,0 +1,651 @@
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use hound::{SampleFormat, WavSpec};
+
+/// Samples per channel in each frame (the reference encoder's default).
+const BLOCK_SIZE: usize = 4096;
+
+/// Highest fixed predictor order FLAC defines.
+const MAX_FIXED_ORDER: usize = 4;
+
+/// Highest Rice partition order tried; 4096 / 2^6 = 64 residuals each.
+const MAX_PARTITION_ORDER: u32 = 6;
+
+/// Highest Rice parameter in the 4-bit coding method (15 is the escape code).
+const MAX_RICE_PARAM: usize = 14;
+
+/// Byte offset of STREAMINFO's body: "fLaC" and the metadata block header.
+const STREAMINFO_OFFSET: u64 = 8;
+
+/// Whether a path names a FLAC file (by extension).
+pub fn is_flac(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("flac"))
+}
+
+/// Lossless FLAC encoder for integer PCM.
+///
+/// Hand-written rather than a dependency: the maintained pure-Rust encoder
+/// (`flacenc`) pulls samples from a source and assembles the encoded stream
+/// in memory, while recordings here are pushed to disk sample by sample from
+/// the capture callback. Decoding stays on claxon; the tests also check the
+/// output with symphonia's decoder, which shares no code with claxon.
+///
+/// Frames are written as blocks fill, so a recording streams to disk the way
+/// a WAV does; `finalize` patches the sample count into STREAMINFO. Each
+/// channel is coded independently with the best of the fixed predictors
+/// (orders 0-4) and partitioned Rice coding, which gets most of the way to
+/// the reference encoder on voice without LPC. The MD5 signature is left
+/// zeroed ("not computed"), which the format allows.
+pub struct FlacWriter<W: Write + Seek> {
+    out: W,
+    channels: u16,
+    sample_rate: u32,
+    bits_per_sample: u16,
+    /// Samples per channel in every frame but the last.
+    block_size: usize,
+    /// Interleaved samples of the frame being filled.
+    block: Vec<i32>,
+    frame_number: u32,
+    total_samples: u64,
+    min_frame_size: u32,
+    max_frame_size: u32,
+}
+
+impl FlacWriter<BufWriter<File>> {
+    /// Create a FLAC file, creating parent directories as needed.
+    pub fn create(path: &Path, spec: WavSpec) -> Result<Self> {
+        if spec.sample_format != SampleFormat::Int {
+            bail!("FLAC stores integer samples only");
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create FLAC file: {}", path.display()))?;
+        Self::new(BufWriter::new(file), spec.channels, spec.sample_rate, spec.bits_per_sample)
+    }
+}
+
+impl<W: Write + Seek> FlacWriter<W> {
+    pub fn new(out: W, channels: u16, sample_rate: u32, bits_per_sample: u16) -> Result<Self> {
+        Self::with_block_size(out, channels, sample_rate, bits_per_sample, BLOCK_SIZE)
+    }
+
+    /// Like `new`, with `block_size` samples per channel in each frame.
+    pub fn with_block_size(
+        out: W,
+        channels: u16,
+        sample_rate: u32,
+        bits_per_sample: u16,
+        block_size: usize,
+    ) -> Result<Self> {
+        if !(16..=65535).contains(&block_size) {
+            bail!("FLAC block size must be 16 to 65535 samples, not {block_size}");
+        }
+        if !(1..=8).contains(&channels) {
+            bail!("FLAC supports 1 to 8 channels, not {channels}");
+        }
+        if !(4..=24).contains(&bits_per_sample) {
+            bail!("Cannot encode {bits_per_sample}-bit samples as FLAC");
+        }
+        if sample_rate == 0 || sample_rate >= 1 << 20 {
+            bail!("Invalid sample rate for FLAC: {sample_rate}");
+        }
+
+        let mut writer = Self {
+            out,
+            channels,
+            sample_rate,
+            bits_per_sample,
+            block_size,
+            block: Vec::with_capacity(block_size * channels as usize),
+            frame_number: 0,
+            total_samples: 0,
+            min_frame_size: 0,
+            max_frame_size: 0,
+        };
+        let streaminfo = writer.streaminfo();
+        writer.out.write_all(b"fLaC")?;
+        // Last metadata block, type 0 (STREAMINFO), 34 bytes
+        writer.out.write_all(&[0x80, 0, 0, 34])?;
+        writer.out.write_all(&streaminfo)?;
+        Ok(writer)
+    }
+
+    pub fn bits_per_sample(&self) -> u16 {
+        self.bits_per_sample
+    }
+
+    /// Append one sample; channels are interleaved as in a WAV.
+    pub fn write_sample(&mut self, sample: i32) -> Result<()> {
+        let limit = 1i32 << (self.bits_per_sample - 1);
+        if sample < -limit || sample >= limit {
+            bail!("Sample {sample} does not fit in {} bits", self.bits_per_sample);
+        }
+        self.block.push(sample);
+        if self.block.len() == self.block_size * self.channels as usize {
+            self.write_frame()?;
+        }
+        Ok(())
+    }
+
+    /// Flush the last partial frame and write the final STREAMINFO.
+    pub fn finalize(mut self) -> Result<()> {
+        let channels = self.channels as usize;
+        if !self.block.len().is_multiple_of(channels) {
+            bail!("Recording ended part-way through a multi-channel sample");
+        }
+        if !self.block.is_empty() {
+            self.write_frame()?;
+        }
+        let streaminfo = self.streaminfo();
+        self.out.seek(SeekFrom::Start(STREAMINFO_OFFSET))?;
+        self.out.write_all(&streaminfo)?;
+        self.out.seek(SeekFrom::End(0))?;
+        self.out.flush().context("Failed to flush FLAC file")?;
+        Ok(())
+    }
+
+    fn streaminfo(&self) -> [u8; 34] {
+        let mut bits = BitWriter::default();
+        bits.write(self.block_size as u64, 16);
+        bits.write(self.block_size as u64, 16);
+        bits.write(self.min_frame_size as u64, 24);
+        bits.write(self.max_frame_size as u64, 24);
+        bits.write(self.sample_rate as u64, 20);
+        bits.write(self.channels as u64 - 1, 3);
+        bits.write(self.bits_per_sample as u64 - 1, 5);
+        bits.write(self.total_samples, 36);
+        // MD5 of the audio; all zeroes means "not computed"
+        bits.write(0, 64);
+        bits.write(0, 64);
+
+        let mut out = [0u8; 34];
+        out.copy_from_slice(&bits.bytes);
+        out
+    }
+
+    fn write_frame(&mut self) -> Result<()> {
+        let channels = self.channels as usize;
+        let len = self.block.len() / channels;
+        let bps = self.bits_per_sample as u32;
+
+        let mut bits = BitWriter::default();
+        bits.write(0xFFF8, 16); // sync code, fixed block size
+        bits.write(0b0111, 4); // block size as 16-bit (n - 1) after the header
+        bits.write(0b0000, 4); // sample rate from STREAMINFO
+        bits.write(channels as u64 - 1, 4); // independent channels
+        bits.write(bits_code(self.bits_per_sample), 3);
+        bits.write(0, 1);
+        write_utf8(&mut bits, self.frame_number);
+        bits.write(len as u64 - 1, 16);
+        let crc = crc8(&bits.bytes);
+        bits.write(crc as u64, 8);
+
+        let mut channel = Vec::with_capacity(len);
+        for c in 0..channels {
+            channel.clear();
+            channel.extend(self.block.iter().skip(c).step_by(channels));
+            write_subframe(&mut bits, &channel, bps);
+        }
+        bits.align();
+        let crc = crc16(&bits.bytes);
+        bits.write(crc as u64, 16);
+
+        self.out.write_all(&bits.bytes).context("Failed to write FLAC frame")?;
+
+        let size = bits.bytes.len() as u32;
+        self.min_frame_size = if self.frame_number == 0 { size } else { self.min_frame_size.min(size) };
+        self.max_frame_size = self.max_frame_size.max(size);
+        self.frame_number += 1;
+        self.total_samples += len as u64;
+        self.block.clear();
+        Ok(())
+    }
+}
+
+/// Frame header sample-size code; 0 defers to STREAMINFO.
+fn bits_code(bits_per_sample: u16) -> u64 {
+    match bits_per_sample {
+        8 => 0b001,
+        12 => 0b010,
+        16 => 0b100,
+        20 => 0b101,
+        24 => 0b110,
+        _ => 0b000,
+    }
+}
+
+/// Frame numbers use the extended UTF-8 scheme from the FLAC spec.
+fn write_utf8(bits: &mut BitWriter, value: u32) {
+    if value < 0x80 {
+        bits.write(value as u64, 8);
+        return;
+    }
+    let continuation = match value {
+        0..=0x7FF => 1,
+        0x800..=0xFFFF => 2,
+        0x1_0000..=0x1F_FFFF => 3,
+        0x20_0000..=0x3FF_FFFF => 4,
+        _ => 5,
+    };
+    let lead_marker = (0xFF00u32 >> (continuation + 1)) & 0xFF;
+    bits.write((lead_marker | (value >> (6 * continuation))) as u64, 8);
+    for i in (0..continuation).rev() {
+        bits.write((0x80 | ((value >> (6 * i)) & 0x3F)) as u64, 8);
+    }
+}
+
+/// Encode one channel of a frame as whichever subframe type is smallest.
+fn write_subframe(bits: &mut BitWriter, samples: &[i32], bps: u32) {
+    if samples.iter().all(|&s| s == samples[0]) {
+        bits.write(0b0000_0000, 8);
+        bits.write_signed(samples[0] as i64, bps);
+        return;
+    }
+
+    let verbatim_bits = 8 + samples.len() as u64 * bps as u64;
+    let mut best: Option<(u64, usize, Vec<u32>, RicePlan)> = None;
+    for order in 0..=MAX_FIXED_ORDER.min(samples.len() - 1) {
+        let Some(residual) = fixed_residual(samples, order) else {
+            continue;
+        };
+        let plan = plan_rice(&residual, samples.len(), order);
+        let cost = 8 + (order as u64) * bps as u64 + plan.bits;
+        if best.as_ref().is_none_or(|(best_cost, ..)| cost < *best_cost) {
+            best = Some((cost, order, residual, plan));
+        }
+    }
+
+    match best {
+        Some((cost, order, residual, plan)) if cost < verbatim_bits => {
+            bits.write(0b0001_0000 | (order as u64) << 1, 8);
+            for &s in &samples[..order] {
+                bits.write_signed(s as i64, bps);
+            }
+            write_residual(bits, &residual, order, &plan);
+        }
+        _ => {
+            bits.write(0b0000_0010, 8);
+            for &s in samples {
+                bits.write_signed(s as i64, bps);
+            }
+        }
+    }
+}
+
+/// Zigzag-folded residual of the fixed predictor, or None if any value
+/// overflows the 32-bit range decoders work in.
+fn fixed_residual(samples: &[i32], order: usize) -> Option<Vec<u32>> {
+    const COEFFS: [&[i64]; MAX_FIXED_ORDER + 1] =
+        [&[], &[1], &[2, -1], &[3, -3, 1], &[4, -6, 4, -1]];
+    let coeffs = COEFFS[order];
+
+    samples[order..]
+        .iter()
+        .enumerate()
+        .map(|(i, &s)| {
+            let n = i + order;
+            let prediction: i64 = coeffs.iter().enumerate().map(|(j, &c)| c * samples[n - 1 - j] as i64).sum();
+            let r = i32::try_from(s as i64 - prediction).ok()?;
+            Some(((r << 1) ^ (r >> 31)) as u32)
+        })
+        .collect()
+}
+
+/// Partition order and per-partition Rice parameters for a residual.
+struct RicePlan {
+    bits: u64,
+    partition_order: u32,
+    params: Vec<u32>,
+}
+
+/// Pick the partition order and Rice parameters that minimise coded size.
+///
+/// Costs are computed exactly per parameter at the finest partition order
+/// and summed pairwise for coarser ones, so every order is priced without
+/// another pass over the residual.
+fn plan_rice(residual: &[u32], block_len: usize, order: usize) -> RicePlan {
+    let mut finest = 0;
+    while finest < MAX_PARTITION_ORDER
+        && block_len.is_multiple_of(1 << (finest + 1))
+        && block_len >> (finest + 1) > order
+    {
+        finest += 1;
+    }
+
+    let partition_len = block_len >> finest;
+    let mut costs: Vec<[u64; MAX_RICE_PARAM + 1]> = Vec::with_capacity(1 << finest);
+    let mut start = 0;
+    for p in 0..1usize << finest {
+        let end = (p + 1) * partition_len - order;
+        let mut cost = [0u64; MAX_RICE_PARAM + 1];
+        for &u in &residual[start..end] {
+            for (k, c) in cost.iter_mut().enumerate() {
+                *c += (u >> k) as u64 + 1 + k as u64;
+            }
+        }
+        costs.push(cost);
+        start = end;
+    }
+
+    let mut best: Option<RicePlan> = None;
+    let mut partition_order = finest;
+    loop {
+        let mut bits = 2 + 4;
+        let mut params = Vec::with_capacity(costs.len());
+        for cost in &costs {
+            let (k, c) = cost.iter().enumerate().min_by_key(|&(_, c)| *c).unwrap();
+            bits += 4 + c;
+            params.push(k as u32);
+        }
+        if best.as_ref().is_none_or(|b| bits < b.bits) {
+            best = Some(RicePlan { bits, partition_order, params });
+        }
+        if partition_order == 0 {
+            break;
+        }
+        costs = costs
+            .chunks(2)
+            .map(|pair| std::array::from_fn(|k| pair[0][k] + pair[1][k]))
+            .collect();
+        partition_order -= 1;
+    }
+    best.unwrap()
+}
+
+fn write_residual(bits: &mut BitWriter, residual: &[u32], order: usize, plan: &RicePlan) {
+    bits.write(0b00, 2); // Rice coding with 4-bit parameters
+    bits.write(plan.partition_order as u64, 4);
+    let partition_len = (residual.len() + order) >> plan.partition_order;
+
+    let mut start = 0;
+    for (p, &k) in plan.params.iter().enumerate() {
+        let end = (p + 1) * partition_len - order;
+        bits.write(k as u64, 4);
+        for &u in &residual[start..end] {
+            bits.write_rice(u, k);
+        }
+        start = end;
+    }
+}
+
+/// MSB-first bit accumulator.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    acc: u64,
+    pending: u32,
+}
+
+impl BitWriter {
+    /// Write the low `n` bits of `value` (n <= 32).
+    fn write(&mut self, value: u64, n: u32) {
+        if n > 32 {
+            self.write(value >> 32, n - 32);
+            self.write(value & 0xFFFF_FFFF, 32);
+            return;
+        }
+        let mask = if n == 0 { 0 } else { u64::MAX >> (64 - n) };
+        self.acc = (self.acc << n) | (value & mask);
+        self.pending += n;
+        while self.pending >= 8 {
+            self.pending -= 8;
+            self.bytes.push((self.acc >> self.pending) as u8);
+        }
+    }
+
+    /// Write a two's-complement value in `n` bits.
+    fn write_signed(&mut self, value: i64, n: u32) {
+        self.write(value as u64, n);
+    }
+
+    /// Unary quotient (zeros then a one) followed by the low `k` bits.
+    fn write_rice(&mut self, value: u32, k: u32) {
+        let mut quotient = value >> k;
+        while quotient >= 32 {
+            self.write(0, 32);
+            quotient -= 32;
+        }
+        self.write(1, quotient + 1);
+        self.write(value as u64, k);
+    }
+
+    /// Zero-pad to a byte boundary.
+    fn align(&mut self) {
+        if self.pending > 0 {
+            self.write(0, 8 - self.pending);
+        }
+    }
+}
+
+/// CRC-8 over the frame header (polynomial x^8 + x^2 + x + 1).
+fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |mut crc, &byte| {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 };
+        }
+        crc
+    })
+}
+
+/// CRC-16 over the whole frame (polynomial x^16 + x^15 + x^2 + 1).
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |mut crc, &byte| {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x8005 } else { crc << 1 };
+        }
+        crc
+    })
+}
+
+/// Read every sample of a FLAC file as integers, with a WAV-style spec.
+pub fn read_samples(path: &Path) -> Result<(Vec<i32>, WavSpec)> {
+    let mut reader = claxon::FlacReader::open(path)
+        .with_context(|| format!("Failed to open FLAC file: {}", path.display()))?;
+    let info = reader.streaminfo();
+    let spec = WavSpec {
+        channels: info.channels as u16,
+        sample_rate: info.sample_rate,
+        bits_per_sample: info.bits_per_sample as u16,
+        sample_format: SampleFormat::Int,
+    };
+    let samples = reader
+        .samples()
+        .collect::<claxon::Result<Vec<i32>>>()
+        .with_context(|| format!("Failed to decode FLAC file: {}", path.display()))?;
+    Ok((samples, spec))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn test_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("voicevo-tests");
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join(name)
+    }
+
+    fn roundtrip(name: &str, samples: &[i32], channels: u16, bits_per_sample: u16) -> u64 {
+        let path = test_path(name);
+        let spec = WavSpec {
+            channels,
+            sample_rate: 44100,
+            bits_per_sample,
+            sample_format: SampleFormat::Int,
+        };
+        let mut writer = FlacWriter::create(&path, spec).unwrap();
+        for &s in samples {
+            writer.write_sample(s).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let (decoded, decoded_spec) = read_samples(&path).unwrap();
+        assert_eq!(decoded_spec, spec);
+        assert_eq!(decoded.len(), samples.len());
+        assert!(decoded == samples, "decoded samples differ from the input");
+
+        let reader = claxon::FlacReader::open(&path).unwrap();
+        assert_eq!(reader.streaminfo().samples, Some((samples.len() / channels as usize) as u64));
+
+        let size = std::fs::metadata(&path).unwrap().len();
+        let _ = std::fs::remove_file(&path);
+        size
+    }
+
+    fn voice_like(len: usize, amplitude: f64) -> Vec<i32> {
+        // A harmonic-rich tone with a little deterministic noise
+        let mut noise = 12345u32;
+        (0..len)
+            .map(|i| {
+                noise = noise.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                let t = i as f64 / 44100.0;
+                let tone = (2.0 * std::f64::consts::PI * 180.0 * t).sin()
+                    + 0.5 * (2.0 * std::f64::consts::PI * 360.0 * t).sin()
+                    + 0.25 * (2.0 * std::f64::consts::PI * 540.0 * t).sin();
+                let dither = ((noise >> 16) % 64) as f64 - 32.0;
+                (tone / 1.75 * amplitude + dither).round() as i32
+            })
+            .collect()
+    }
+
+    #[test]
+    fn roundtrip_16bit_is_lossless_and_smaller() {
+        let samples = voice_like(44100 + 123, 12000.0);
+        let size = roundtrip("flac-16.flac", &samples, 1, 16);
+        // The WAV would be 2 bytes per sample plus a header
+        assert!(size < samples.len() as u64 * 2 * 3 / 4, "FLAC was {size} bytes");
+    }
+
+    #[test]
+    fn roundtrip_edge_cases() {
+        // Silence (CONSTANT subframes), a single sample, and full-scale noise
+        // that forces VERBATIM subframes
+        roundtrip("flac-silence.flac", &vec![0; 10_000], 1, 16);
+        roundtrip("flac-single.flac", &[-32768], 1, 16);
+        let mut state = 1u32;
+        let noise: Vec<i32> = (0..5000)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                (state >> 16) as i32 - 32768
+            })
+            .collect();
+        roundtrip("flac-noise.flac", &noise, 1, 16);
+    }
+
+    #[test]
+    fn roundtrip_24bit_and_stereo() {
+        let samples = voice_like(9000, 4_000_000.0);
+        roundtrip("flac-24.flac", &samples, 1, 24);
+
+        let stereo: Vec<i32> = voice_like(6000, 9000.0)
+            .chunks(2)
+            .flat_map(|pair| [pair[0], -pair[1]])
+            .collect();
+        roundtrip("flac-stereo.flac", &stereo, 2, 16);
+    }
+
+    /// Decode with symphonia rather than claxon, so encoder bugs that claxon
+    /// happens to tolerate still fail. Verifies frame CRCs while decoding.
+    fn decode_independently(bytes: Vec<u8>) -> (Vec<i32>, u32) {
+        use symphonia::core::audio::SampleBuffer;
+        use symphonia::core::codecs::DecoderOptions;
+        use symphonia::core::errors::Error;
+        use symphonia::core::formats::FormatReader;
+        use symphonia::core::io::MediaSourceStream;
+
+        let source = MediaSourceStream::new(Box::new(std::io::Cursor::new(bytes)), Default::default());
+        let mut reader = symphonia::default::formats::FlacReader::try_new(source, &Default::default()).unwrap();
+        let track = reader.default_track().unwrap();
+        let bits_per_sample = track.codec_params.bits_per_sample.unwrap();
+        let mut decoder = symphonia::default::get_codecs()
+            .make(&track.codec_params, &DecoderOptions { verify: false })
+            .unwrap();
+
+        let mut samples = Vec::new();
+        loop {
+            let packet = match reader.next_packet() {
+                Ok(packet) => packet,
+                Err(Error::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+                Err(e) => panic!("symphonia failed to read a frame: {e}"),
+            };
+            let decoded = decoder.decode(&packet).unwrap();
+            let mut buf = SampleBuffer::<i32>::new(decoded.capacity() as u64, *decoded.spec());
+            buf.copy_interleaved_ref(decoded);
+            // Samples come back scaled to the full i32 range
+            samples.extend(buf.samples().iter().map(|&s| s >> (32 - bits_per_sample)));
+        }
+        (samples, bits_per_sample)
+    }
+
+    #[test]
+    fn independent_decoder_across_block_sizes() {
+        let cases: [(usize, usize, u16, u16); 7] = [
+            // (block size, samples per channel, channels, bits per sample)
+            (16, 16 * 9 + 5, 1, 16),
+            (192, 192 * 4, 1, 16),
+            (1000, 1000 * 3 + 1, 1, 16),
+            (1152, 1152 * 2 + 17, 2, 16),
+            (4096, 4096 + 4095, 1, 24),
+            (4608, 4608 * 2 + 300, 2, 24),
+            (65535, 65535 + 2, 1, 16),
+        ];
+        for (block_size, len, channels, bits_per_sample) in cases {
+            let amplitude = if bits_per_sample == 24 { 4_000_000.0 } else { 12000.0 };
+            let samples = voice_like(len * channels as usize, amplitude);
+
+            let mut out = std::io::Cursor::new(Vec::new());
+            let mut writer =
+                FlacWriter::with_block_size(&mut out, channels, 48000, bits_per_sample, block_size).unwrap();
+            for &s in &samples {
+                writer.write_sample(s).unwrap();
+            }
+            writer.finalize().unwrap();
+
+            let (decoded, decoded_bits) = decode_independently(out.into_inner());
+            assert_eq!(decoded_bits, bits_per_sample as u32);
+            assert_eq!(decoded.len(), samples.len(), "block size {block_size}");
+            assert!(decoded == samples, "block size {block_size}: decoded samples differ from the input");
+        }
+    }
+
+    #[test]
+    fn rejects_bad_block_sizes() {
+        let out = std::io::Cursor::new(Vec::new());
+        assert!(FlacWriter::with_block_size(out.clone(), 1, 44100, 16, 15).is_err());
+        assert!(FlacWriter::with_block_size(out, 1, 44100, 16, 65536).is_err());
+    }
+
+    #[test]
+    fn rejects_out_of_range_samples() {
+        let spec = WavSpec {
+            channels: 1,
+            sample_rate: 44100,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+        let path = test_path("flac-range.flac");
+        let mut writer = FlacWriter::create(&path, spec).unwrap();
+        assert!(writer.write_sample(40_000).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn frame_numbers_use_extended_utf8() {
+        let mut bits = BitWriter::default();
+        write_utf8(&mut bits, 0x7F);
+        write_utf8(&mut bits, 0x80);
+        write_utf8(&mut bits, 0x1234);
+        assert_eq!(bits.bytes, [0x7F, 0xC2, 0x80, 0xE1, 0x88, 0xB4]);
+    }
+}
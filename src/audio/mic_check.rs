@@ -4,12 +4,13 @@ use std::time::Duration;
 use anyhow::{Context, Result};
 use console::style;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use cpal::SampleFormat;
 use indicatif::{ProgressBar, ProgressStyle};
 
+use crate::config::RecordingConfig;
+use crate::storage::session_data::RecordingFormat;
 use crate::util;
 
-use super::recorder;
+use super::{capture, recorder, wav};
 
 const CAPTURE_SECONDS: u64 = 2;
 
@@ -18,16 +19,15 @@ const CAPTURE_SECONDS: u64 = 2;
 /// Waits for the user to press Enter before capturing, so they know exactly
 /// when the mic is live. Shows the device name prominently so there's no
 /// ambiguity about which input is being used.
-pub fn run() -> Result<()> {
+pub fn run(recording: &RecordingConfig) -> Result<()> {
     let host = cpal::default_host();
     let device = host
         .default_input_device()
         .context("No default input device found")?;
 
     let device_name = device.name().unwrap_or_else(|_| "<unknown>".into());
-    let config = device
-        .default_input_config()
-        .context("Failed to get default input config")?;
+    // Check the same stream recordings will use
+    let config = capture::input_config(&device, recording.sample_rate)?;
 
     let sample_rate = config.sample_rate().0;
     let channels = config.channels() as usize;
@@ -38,6 +38,8 @@ pub fn run() -> Result<()> {
         style(&device_name).cyan().bold()
     );
     println!("  Config:  {channels}ch, {sample_rate} Hz, {format:?}");
+    let stored = RecordingFormat::from(wav::recording_spec(sample_rate, recording.bit_depth));
+    println!("  Saved:   {}", stored.describe());
     println!();
     println!(
         "  Press {} to capture a 2-second sample.",
@@ -51,35 +53,13 @@ pub fn run() -> Result<()> {
     // Channel to send captured samples from audio thread to main thread
     let (tx, rx) = mpsc::channel::<Vec<f32>>();
 
-    // Build input stream based on sample format.
-    // PipeWire typically uses F32, ALSA often uses I16.
-    let stream = match format {
-        SampleFormat::F32 => {
-            let tx = tx.clone();
-            device.build_input_stream(
-                &config.into(),
-                move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                    let _ = tx.send(data.to_vec());
-                },
-                |err| eprintln!("Stream error: {err}"),
-                None,
-            )?
-        }
-        SampleFormat::I16 => {
-            let tx = tx.clone();
-            device.build_input_stream(
-                &config.into(),
-                move |data: &[i16], _: &cpal::InputCallbackInfo| {
-                    // Convert i16 samples to f32 [-1.0, 1.0]
-                    let floats: Vec<f32> =
-                        data.iter().map(|&s| s as f32 / i16::MAX as f32).collect();
-                    let _ = tx.send(floats);
-                },
-                |err| eprintln!("Stream error: {err}"),
-                None,
-            )?
-        }
-        other => anyhow::bail!("Unsupported sample format: {other:?}"),
+    // Build input stream in the device's native format (F32 on PipeWire,
+    // often I16 or I32 on ALSA), delivered as mono f32
+    let stream = {
+        let tx = tx.clone();
+        capture::build_input_stream(&device, &config, move |mono| {
+            let _ = tx.send(mono);
+        })?
     };
 
     // Drop our copy of tx so rx will close when the stream's copy is dropped
@@ -117,12 +97,7 @@ pub fn run() -> Result<()> {
 
     pb.finish_and_clear();
 
-    // Downmix stereo (or multi-channel) to mono by taking every Nth sample
-    let mono: Vec<f32> = if channels > 1 {
-        all_samples.iter().step_by(channels).copied().collect()
-    } else {
-        all_samples
-    };
+    let mono = all_samples;
 
     if mono.is_empty() {
         eprintln!(
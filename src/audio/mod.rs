@@ -2,6 +2,7 @@ pub mod capture;
 pub mod devices;
 pub mod exercise;
 pub mod fatigue_exercise;
+pub mod flac;
 pub mod mic_check;
 pub mod playback;
 pub mod recorder;
@@ -1,23 +1,38 @@
 use std::fs::File;
 use std::io::BufReader;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use console::style;
-use rodio::{Decoder, OutputStream, Sink};
+use rodio::{Decoder, OutputStream, Sink, Source};
 
 use crate::paths;
-use crate::util;
+use crate::storage::session_data::{AnalyzedSegment, SessionId};
+use crate::storage::store;
 
 /// Play back a recording. `target` is either a YYYY-MM-DD date or a direct file path.
 /// If it's a date, `exercise` must be provided (e.g., "sustained").
-pub fn play(target: &str, exercise: Option<&str>) -> Result<()> {
-    let path = resolve_play_path(target, exercise)?;
+///
+/// With `analyzed`, plays the session's analyzed recording trimmed to the
+/// segment the metrics were computed on.
+pub fn play(target: &str, exercise: Option<&str>, analyzed: bool) -> Result<()> {
+    let (path, segment) = if analyzed {
+        resolve_analyzed_segment(target, exercise)?
+    } else {
+        (resolve_play_path(target, exercise)?, None)
+    };
 
     println!(
         "Playing {}",
         style(path.display()).green()
     );
+    if let Some(seg) = segment {
+        println!(
+            "  Analyzed segment: {:.2}s - {:.2}s",
+            seg.start_secs, seg.end_secs
+        );
+    }
 
     // OutputStream::try_default() opens the default output device.
     // We must keep `_stream` alive — it's an RAII guard. If we used `_` instead
@@ -34,13 +49,20 @@ pub fn play(target: &str, exercise: Option<&str>) -> Result<()> {
         .with_context(|| format!("Failed to open: {}", path.display()))?;
     let reader = BufReader::new(file);
 
-    // Decoder figures out the audio format (WAV in our case) and produces samples.
+    // Decoder figures out the audio format (WAV or FLAC) and produces samples.
     let source = Decoder::new(reader)
         .with_context(|| format!("Failed to decode: {}", path.display()))?;
 
     // append() queues audio into the sink. It starts playing immediately since
     // the sink isn't paused.
-    sink.append(source);
+    match segment {
+        Some(seg) => sink.append(
+            source
+                .skip_duration(Duration::from_secs_f32(seg.start_secs))
+                .take_duration(Duration::from_secs_f32(seg.duration_secs())),
+        ),
+        None => sink.append(source),
+    }
 
     // Block until playback finishes. Without this, the function would return,
     // dropping `_stream` and cutting off audio mid-play.
@@ -52,20 +74,63 @@ pub fn play(target: &str, exercise: Option<&str>) -> Result<()> {
 
 /// Figure out which file to play based on user input.
 fn resolve_play_path(target: &str, exercise: Option<&str>) -> Result<PathBuf> {
-    // If target looks like a file path (contains a dot or slash), use it directly
-    if target.contains('/') || target.contains('.') {
+    // Anything that isn't a session id (date or date.slot) is a file path
+    let Ok(session) = SessionId::parse(target) else {
         return Ok(PathBuf::from(target));
-    }
+    };
 
-    // Otherwise treat it as a date — exercise name is required
+    // A session needs an exercise name
     let exercise = exercise.context(
         "When playing by date, you must specify an exercise name.\n\
          Usage: voicevo play 2026-02-08 sustained",
     )?;
 
-    let date = util::resolve_date(Some(target))?;
-    paths::latest_attempt_path(&date, exercise)
-        .context(format!("No recordings found for {exercise} on {date}"))
+    paths::latest_attempt_path(&session, exercise)
+        .context(format!("No recordings found for {exercise} in session {session}"))
+}
+
+/// Find the analyzed recording and its segment for a date and exercise.
+fn resolve_analyzed_segment(
+    target: &str,
+    exercise: Option<&str>,
+) -> Result<(PathBuf, Option<AnalyzedSegment>)> {
+    let Ok(session) = SessionId::parse(target) else {
+        anyhow::bail!("--analyzed needs a date or session id, not a file path");
+    };
+    let exercise = exercise.context(
+        "When playing by date, you must specify an exercise name.\n\
+         Usage: voicevo play 2026-02-08 sustained --analyzed",
+    )?;
+
+    let date = session.to_string();
+    let session = store::load_session(&date).with_context(|| {
+        format!("No analyzed session for {date}. Run `voicevo analyze --session {date}` first.")
+    })?;
+
+    let (path, segment) = match exercise {
+        "sustained" => (
+            session.recordings.sustained,
+            session.analysis.sustained.and_then(|a| a.segment),
+        ),
+        "scale" => (
+            session.recordings.scale,
+            session.analysis.scale.and_then(|a| a.segment),
+        ),
+        "reading" => (
+            session.recordings.reading,
+            session.analysis.reading.and_then(|a| a.segment),
+        ),
+        other => anyhow::bail!("Unknown exercise '{other}'. Use sustained, scale, or reading."),
+    };
+
+    let path = path.with_context(|| format!("No {exercise} recording in the session for {date}"))?;
+    if segment.is_none() {
+        println!(
+            "  {} no segment stored for this analysis; playing the whole recording. Re-run `voicevo analyze --session {date}`.",
+            style("Note:").yellow()
+        );
+    }
+    Ok((PathBuf::from(path), segment))
 }
 
 #[cfg(test)]
@@ -96,4 +161,9 @@ mod tests {
         let result = resolve_play_path("2026-02-08", None);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn analyzed_rejects_file_path() {
+        assert!(resolve_analyzed_segment("./test.wav", Some("sustained")).is_err());
+    }
 }
@@ -3,14 +3,15 @@ use std::sync::{mpsc, Arc};
 use std::thread;
 
 use anyhow::{Context, Result};
-use chrono::NaiveDate;
 use console::style;
-use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use cpal::SampleFormat;
+use cpal::traits::{HostTrait, StreamTrait};
 use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 
-use crate::audio::wav;
+use crate::analysis::quality;
+use crate::audio::{capture, wav};
+use crate::config::RecordingConfig;
 use crate::paths;
+use crate::storage::session_data::{RecordingQuality, SessionId};
 use crate::util;
 
 /// Stats returned after a recording completes.
@@ -19,12 +20,15 @@ pub struct RecordingStats {
     pub peak_db: f32,
     pub rms_db: f32,
     pub sample_count: usize,
+    /// Clipping, noise floor, SNR, DC offset and hum checks.
+    pub quality: RecordingQuality,
 }
 
-/// Record a named exercise for a given date.
+/// Record a named exercise for a session.
 /// Creates a new numbered attempt: {exercise}_001.wav, _002.wav, etc.
-pub fn record_exercise(exercise: &str, date: &NaiveDate, config: &crate::config::AppConfig) -> Result<()> {
-    let path = paths::next_attempt_path(date, exercise);
+/// (`.flac` when the config's storage is FLAC).
+pub fn record_exercise(exercise: &str, session: &SessionId, config: &crate::config::AppConfig) -> Result<()> {
+    let path = paths::next_attempt_path(session, exercise, config.recording.effective_storage());
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)?;
     }
@@ -36,8 +40,8 @@ pub fn record_exercise(exercise: &str, date: &NaiveDate, config: &crate::config:
     );
     println!(
         "{} {}",
-        style("Date:").bold(),
-        date
+        style("Session:").bold(),
+        session
     );
     println!(
         "{} {}",
@@ -84,7 +88,7 @@ pub fn record_exercise(exercise: &str, date: &NaiveDate, config: &crate::config:
         style("Enter").red().bold()
     );
 
-    let stats = record_to_file(&path)?;
+    let stats = record_to_file(&path, &config.recording)?;
 
     println!();
     println!(
@@ -92,15 +96,22 @@ pub fn record_exercise(exercise: &str, date: &NaiveDate, config: &crate::config:
         stats.duration_secs
     );
     println!("  Samples:   {}", stats.sample_count);
+    if let Some(format) = stats.quality.format {
+        println!("  Format:    {}", format.describe());
+    }
     println!("  Peak:      {:.1} dB", stats.peak_db);
     println!("  RMS:       {:.1} dB", stats.rms_db);
 
+    println!("  SNR:       {:.0} dB", stats.quality.snr_db);
+
     if stats.peak_db < -60.0 {
         eprintln!();
         eprintln!(
             "  {} Recording appears silent. Check your microphone.",
             style("WARNING").red().bold()
         );
+    } else {
+        print_quality_warnings(&stats.quality);
     }
 
     println!();
@@ -112,26 +123,31 @@ pub fn record_exercise(exercise: &str, date: &NaiveDate, config: &crate::config:
     Ok(())
 }
 
-/// Core recording function: captures from default input device and writes WAV.
+/// Core recording function: captures from default input device and writes a
+/// WAV, or FLAC if the path ends in `.flac`.
 ///
 /// Architecture:
-///   cpal audio callback (runs on audio thread)
-///     → sends f32 sample chunks via mpsc channel
-///       → writer thread receives chunks and writes to WAV file via hound
+///   cpal audio callback (runs on audio thread, device's native sample format)
+///     → sends mono f32 sample chunks via mpsc channel
+///       → writer thread receives chunks and writes them via `wav::RecordingWriter`
+///         at the configured bit depth
 ///   AtomicBool stop signal ← main thread (crossterm Enter keypress)
-pub fn record_to_file(path: &std::path::Path) -> Result<RecordingStats> {
+pub fn record_to_file(path: &std::path::Path, recording: &RecordingConfig) -> Result<RecordingStats> {
     let host = cpal::default_host();
     let device = host
         .default_input_device()
         .context("No default input device found")?;
 
-    let config = device
-        .default_input_config()
-        .context("Failed to get default input config")?;
-
+    let config = capture::input_config(&device, recording.sample_rate)?;
     let sample_rate = config.sample_rate().0;
-    let channels = config.channels() as usize;
-    let format = config.sample_format();
+    if sample_rate != recording.sample_rate {
+        println!(
+            "  {} Device can't record at {} Hz; using {} Hz.",
+            style("NOTE").yellow().bold(),
+            recording.sample_rate,
+            sample_rate
+        );
+    }
 
     // Channel for sending audio data from cpal callback to writer thread
     let (tx, rx) = mpsc::channel::<Vec<f32>>();
@@ -141,67 +157,30 @@ pub fn record_to_file(path: &std::path::Path) -> Result<RecordingStats> {
     let stop_for_stream = Arc::clone(&stop);
 
     // Build the input stream. The closure captures tx by move.
-    let stream = match format {
-        SampleFormat::F32 => device.build_input_stream(
-            &config.into(),
-            move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                if !stop_for_stream.load(Ordering::Relaxed) {
-                    // Downmix to mono if multi-channel
-                    let mono: Vec<f32> = if channels > 1 {
-                        data.iter().step_by(channels).copied().collect()
-                    } else {
-                        data.to_vec()
-                    };
-                    let _ = tx.send(mono);
-                }
-            },
-            |err| eprintln!("Stream error: {err}"),
-            None,
-        )?,
-        SampleFormat::I16 => {
-            device.build_input_stream(
-                &config.into(),
-                move |data: &[i16], _: &cpal::InputCallbackInfo| {
-                    if !stop_for_stream.load(Ordering::Relaxed) {
-                        let floats: Vec<f32> = if channels > 1 {
-                            data.iter()
-                                .step_by(channels)
-                                .map(|&s| s as f32 / i16::MAX as f32)
-                                .collect()
-                        } else {
-                            data.iter()
-                                .map(|&s| s as f32 / i16::MAX as f32)
-                                .collect()
-                        };
-                        let _ = tx.send(floats);
-                    }
-                },
-                |err| eprintln!("Stream error: {err}"),
-                None,
-            )?
+    let stream = capture::build_input_stream(&device, &config, move |mono| {
+        if !stop_for_stream.load(Ordering::Relaxed) {
+            let _ = tx.send(mono);
         }
-        other => anyhow::bail!("Unsupported sample format: {other:?}"),
-    };
+    })?;
 
     stream.play().context("Failed to start audio stream")?;
 
-    // Writer thread: receives mono f32 chunks and writes 16-bit WAV
+    // Writer thread: receives mono f32 chunks and writes them at the configured depth
+    let spec = wav::recording_spec(sample_rate, recording.bit_depth);
     let wav_path = path.to_path_buf();
     let writer_handle = thread::spawn(move || -> Result<Vec<f32>> {
-        let spec = wav::recording_spec(sample_rate);
-        let mut writer = wav::create_writer(&wav_path, spec)?;
+        let mut writer = wav::RecordingWriter::create(&wav_path, spec)?;
         let mut all_samples = Vec::new();
 
         // rx.iter() blocks until the channel is closed (tx is dropped)
         for chunk in rx.iter() {
             for &sample in &chunk {
-                let s16 = (sample * i16::MAX as f32).clamp(i16::MIN as f32, i16::MAX as f32) as i16;
-                writer.write_sample(s16)?;
+                writer.write_sample(sample)?;
             }
             all_samples.extend(chunk);
         }
 
-        writer.finalize().context("Failed to finalize WAV file")?;
+        writer.finalize()?;
         Ok(all_samples)
     });
 
@@ -226,12 +205,15 @@ pub fn record_to_file(path: &std::path::Path) -> Result<RecordingStats> {
     let duration_secs = sample_count as f32 / sample_rate as f32;
     let peak_db = util::peak_db(&all_samples);
     let rms_db = util::rms_db(&all_samples);
+    let mut quality = quality::assess(&all_samples, sample_rate);
+    quality.format = Some(spec.into());
 
     Ok(RecordingStats {
         duration_secs,
         peak_db,
         rms_db,
         sample_count,
+        quality,
     })
 }
 
@@ -259,8 +241,41 @@ pub enum PostRecordChoice {
     Rerecord,
 }
 
-/// Block until the user presses Enter (keep) or 'r' (re-record).
-pub fn wait_for_keep_or_rerecord() -> Result<PostRecordChoice> {
+/// Print recording-quality problems, if any, as warnings.
+fn print_quality_warnings(quality: &RecordingQuality) {
+    if quality.warnings.is_empty() {
+        return;
+    }
+    eprintln!();
+    for w in &quality.warnings {
+        eprintln!("  {} {w}", style("WARNING").yellow().bold());
+    }
+}
+
+/// Show the recording-quality verdict, then block until the user presses
+/// Enter (keep) or 'r' (re-record).
+///
+/// A take with clipping, low SNR, DC offset or hum gets its warnings listed
+/// and a prompt that recommends re-recording — these problems distort the
+/// measurements and would otherwise flow into the trend data.
+pub fn wait_for_keep_or_rerecord(quality: &RecordingQuality) -> Result<PostRecordChoice> {
+    if quality.warnings.is_empty() {
+        println!(
+            "  Press {} to keep, {} to re-record.",
+            style("Enter").green().bold(),
+            style("r").yellow().bold(),
+        );
+    } else {
+        print_quality_warnings(quality);
+        println!();
+        println!(
+            "  Press {} to re-record {}, {} to keep anyway.",
+            style("r").yellow().bold(),
+            style("(recommended)").yellow(),
+            style("Enter").bold(),
+        );
+    }
+
     crossterm::terminal::enable_raw_mode()?;
 
     let choice = loop {
@@ -26,7 +26,7 @@ pub fn run_scale_exercise(config: &AppConfig) -> Result<()> {
 
     // TUI phase: recording with live pitch feedback
     let mut terminal = crate::tui::init()?;
-    let (audio_state, stream, collector) = capture::start_capture(true)?;
+    let (audio_state, stream, collector) = capture::start_capture(true, &config.recording)?;
     let sample_rate = audio_state.sample_rate;
 
     let outcome = crate::tui::screens::scale::run(&mut terminal, &audio_state)?;
@@ -71,7 +71,10 @@ pub fn run_scale_exercise(config: &AppConfig) -> Result<()> {
 
     let pitch_config: PitchConfig = (&config.analysis).into();
     match scale::analyze(&all_samples, sample_rate, &pitch_config) {
-        Ok(result) => {
+        Ok(mut result) => {
+            if let Some(q) = result.recording_quality.as_mut() {
+                q.format = Some(audio_state.format);
+            }
             println!("  {:12} {:>12}", style("Pitch floor").bold(), format!("{:.1} Hz", result.pitch_floor_hz));
             println!("  {:12} {:>12}", style("Pitch ceil").bold(), format!("{:.1} Hz", result.pitch_ceiling_hz));
             println!("  {:12} {:>12}", style("Range").bold(), format!("{:.1} semitones", result.range_semitones));
@@ -82,6 +85,7 @@ pub fn run_scale_exercise(config: &AppConfig) -> Result<()> {
             let mut session = storage::store::load_session(&date).unwrap_or_else(|_| {
                 crate::storage::session_data::SessionData {
                     date: date.clone(),
+                    slot: 1,
                     recordings: crate::storage::session_data::SessionRecordings {
                         sustained: None,
                         scale: None,
@@ -95,6 +99,7 @@ pub fn run_scale_exercise(config: &AppConfig) -> Result<()> {
                         fatigue: None,
                     },
                     conditions: None,
+                    source: None,
                 }
             });
             session.analysis.scale = Some(result);
@@ -1,33 +1,65 @@
+use std::time::Duration;
+
 use anyhow::Result;
 use chrono::NaiveDate;
 use console::style;
+use indicatif::{ProgressBar, ProgressStyle};
 
 use crate::analysis;
-use crate::config::AppConfig;
+use crate::config::{AppConfig, RecordingConfig};
 use crate::paths;
 use crate::report;
+use crate::storage::session_data::SessionId;
+use crate::storage::store;
 
 use super::mic_check;
 use super::recorder;
 use super::recorder::PostRecordChoice;
 
+/// A new session on `date`: the slot after any session already recorded or
+/// analyzed that day (`voicevo record session --new`).
+pub fn next_session(date: NaiveDate) -> Result<SessionId> {
+    let recorded = std::fs::read_dir(paths::recordings_dir())
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter_map(|e| e.file_name().to_str().and_then(|n| SessionId::parse(n).ok()))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let analyzed = store::list_sessions()?
+        .iter()
+        .filter_map(|id| SessionId::parse(id).ok())
+        .collect::<Vec<_>>();
+
+    let last = recorded
+        .iter()
+        .chain(&analyzed)
+        .filter(|id| id.date == date)
+        .map(|id| id.slot)
+        .max()
+        .unwrap_or(0);
+    Ok(SessionId::new(date, last + 1))
+}
+
 /// Run a full guided recording session.
 ///
 /// This walks the user through:
 ///   1. Mic check
-///   2. Sustained vowel recording (with re-record option)
+///   2. Sustained vowel recording (with re-record option; best of N trials
+///      when `session.sustained_trials` > 1)
 ///   3. Chromatic scale recording (with re-record option)
 ///   4. Reading passage recording (with re-record option)
 ///   5. Analysis + full trend report
-pub fn run_guided_session(date: &NaiveDate, config: &AppConfig) -> Result<()> {
-    let date_str = date.to_string();
+pub fn run_guided_session(session: &SessionId, config: &AppConfig) -> Result<()> {
+    let session_str = session.to_string();
 
     println!();
     println!(
         "{}",
         style("=== Voice Recovery Tracker — Recording Session ===").bold()
     );
-    println!("  Date: {}", style(date).cyan());
+    println!("  Session: {}", style(session).cyan());
     println!();
 
     // --- Step 1: Mic check ---
@@ -38,7 +70,7 @@ pub fn run_guided_session(date: &NaiveDate, config: &AppConfig) -> Result<()> {
     );
     println!();
 
-    mic_check::run()?;
+    mic_check::run(&config.recording)?;
     println!();
 
     println!(
@@ -61,7 +93,30 @@ pub fn run_guided_session(date: &NaiveDate, config: &AppConfig) -> Result<()> {
     );
     println!();
 
-    let sustained_stats = record_with_retry(date, "sustained")?;
+    let trials = config.session.sustained_trials.max(1);
+    let mut sustained_stats = Vec::new();
+    let mut trial_attempts = Vec::new();
+    for trial in 1..=trials {
+        if trials > 1 {
+            println!(
+                "  {}",
+                style(format!("Trial {trial}/{trials}")).bold()
+            );
+            println!();
+        }
+
+        let (stats, path) = record_with_retry(session, "sustained", &config.recording)?;
+        sustained_stats.push(stats);
+        trial_attempts.extend(paths::attempt_number(&path, "sustained"));
+
+        if trial < trials {
+            rest_between_trials(config.session.trial_rest_secs);
+        }
+    }
+    // Analysis combines only the takes of this run into the best-of-N result
+    if trials > 1 {
+        store::save_trial_run(&session_str, "sustained", &trial_attempts)?;
+    }
 
     // --- Step 3: Chromatic scale ---
     println!(
@@ -78,7 +133,7 @@ pub fn run_guided_session(date: &NaiveDate, config: &AppConfig) -> Result<()> {
     println!("  then back down.");
     println!();
 
-    let scale_stats = record_with_retry(date, "scale")?;
+    let (scale_stats, _) = record_with_retry(session, "scale", &config.recording)?;
 
     // --- Step 4: Reading passage ---
     println!(
@@ -94,7 +149,7 @@ pub fn run_guided_session(date: &NaiveDate, config: &AppConfig) -> Result<()> {
     }
     println!();
 
-    let reading_stats = record_with_retry(date, "reading")?;
+    let (reading_stats, _) = record_with_retry(session, "reading", &config.recording)?;
 
     // --- Summary ---
     println!(
@@ -111,12 +166,18 @@ pub fn run_guided_session(date: &NaiveDate, config: &AppConfig) -> Result<()> {
     );
     println!("  {:-<12} {:->10} {:->10} {:->10}", "", "", "", "");
 
-    print_summary_row("Sustained", &sustained_stats);
+    if let [stats] = sustained_stats.as_slice() {
+        print_summary_row("Sustained", stats);
+    } else {
+        for (i, stats) in sustained_stats.iter().enumerate() {
+            print_summary_row(&format!("Sustained {}", i + 1), stats);
+        }
+    }
     print_summary_row("Scale", &scale_stats);
     print_summary_row("Reading", &reading_stats);
 
     println!();
-    let rec_dir = paths::recordings_dir().join(date.to_string());
+    let rec_dir = paths::session_recordings_dir(session);
     println!(
         "  Recordings saved to {}",
         style(rec_dir.display()).green()
@@ -134,7 +195,7 @@ pub fn run_guided_session(date: &NaiveDate, config: &AppConfig) -> Result<()> {
         style("=== Analyzing ===").bold()
     );
     println!();
-    analysis::analyzer::analyze_session_with_conditions(&date_str, config, Some(conditions))?;
+    analysis::analyzer::analyze_session_with_conditions(&session_str, config, Some(conditions))?;
     println!();
 
     // --- Report ---
@@ -143,40 +204,40 @@ pub fn run_guided_session(date: &NaiveDate, config: &AppConfig) -> Result<()> {
         style("=== Generating Report ===").bold()
     );
     println!();
-    report::generate_full_report(config)?;
+    let sessions = report::generate_full_report(config, false, false)?;
+    println!();
+
+    report::goals::print_status(&config.goals, &sessions)?;
 
     Ok(())
 }
 
 /// Record an exercise, letting the user re-record until satisfied.
+/// Returns the kept take's stats and path.
 ///
-/// Loop: record → show stats → Enter to keep / 'r' to re-record.
-/// On re-record, the previous file is deleted and a new attempt is created.
+/// Loop: record → show stats and quality warnings → Enter to keep / 'r' to re-record.
+/// On re-record, the previous take is kept but marked discarded (see
+/// `store::discard_take`) and a new attempt is created.
 fn record_with_retry(
-    date: &NaiveDate,
+    session: &SessionId,
     exercise: &str,
-) -> Result<recorder::RecordingStats> {
+    recording: &RecordingConfig,
+) -> Result<(recorder::RecordingStats, std::path::PathBuf)> {
     loop {
-        let path = paths::next_attempt_path(date, exercise);
+        let path = paths::next_attempt_path(session, exercise, recording.effective_storage());
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent)?;
         }
 
-        let stats = record_exercise_with_path(&path)?;
-
-        println!(
-            "  Press {} to keep, {} to re-record.",
-            style("Enter").green().bold(),
-            style("r").yellow().bold(),
-        );
+        let stats = record_exercise_with_path(&path, recording)?;
 
-        match recorder::wait_for_keep_or_rerecord()? {
+        match recorder::wait_for_keep_or_rerecord(&stats.quality)? {
             PostRecordChoice::Keep => {
                 println!();
-                return Ok(stats);
+                return Ok((stats, path));
             }
             PostRecordChoice::Rerecord => {
-                std::fs::remove_file(&path)?;
+                store::discard_take(session, exercise, &path)?;
                 println!(
                     "  {} Re-recording {}...",
                     style("DISCARDED").yellow(),
@@ -191,6 +252,7 @@ fn record_with_retry(
 /// Record an exercise with clear start/stop prompts.
 fn record_exercise_with_path(
     path: &std::path::Path,
+    recording: &RecordingConfig,
 ) -> Result<recorder::RecordingStats> {
     println!(
         "  Press {} when ready to record.",
@@ -205,7 +267,7 @@ fn record_exercise_with_path(
         style("Enter").bold()
     );
 
-    let stats = recorder::record_to_file(path)?;
+    let stats = recorder::record_to_file(path, recording)?;
 
     println!(
         "  {}",
@@ -213,8 +275,8 @@ fn record_exercise_with_path(
     );
     println!();
     println!(
-        "  Duration: {:.1}s  |  Peak: {:.1} dB  |  RMS: {:.1} dB",
-        stats.duration_secs, stats.peak_db, stats.rms_db
+        "  Duration: {:.1}s  |  Peak: {:.1} dB  |  RMS: {:.1} dB  |  SNR: {:.0} dB",
+        stats.duration_secs, stats.peak_db, stats.rms_db, stats.quality.snr_db
     );
 
     if stats.peak_db < -60.0 {
@@ -228,6 +290,31 @@ fn record_exercise_with_path(
     Ok(stats)
 }
 
+/// Count down the rest period between sustained trials.
+///
+/// MPT trials need recovery time in between, otherwise later trials are
+/// shortened by fatigue rather than by anything the voice is doing.
+fn rest_between_trials(secs: u32) {
+    if secs == 0 {
+        return;
+    }
+
+    println!("  Rest for {secs} seconds before the next trial.");
+
+    let pb = ProgressBar::new(secs as u64);
+    pb.set_style(
+        ProgressStyle::with_template("  Resting   {bar:30.cyan/dim} {pos}/{len}s")
+            .unwrap(),
+    );
+    for _ in 0..secs {
+        std::thread::sleep(Duration::from_secs(1));
+        pb.inc(1);
+    }
+    pb.finish_and_clear();
+
+    println!();
+}
+
 fn print_summary_row(name: &str, stats: &recorder::RecordingStats) {
     println!(
         "  {:12} {:>9.1}s {:>9.1} {:>9.1}",
@@ -6,13 +6,14 @@ use console::style;
 use crate::analysis::sz;
 use crate::audio::capture;
 use crate::config::AppConfig;
+use crate::norms::NormMetric;
 use crate::storage;
 
 /// Run the S/Z ratio exercise with TUI.
 ///
 /// The patient sustains /s/ (voiceless) and /z/ (voiced) multiple times.
 /// We measure durations and compute the ratio.
-pub fn run_sz_exercise(_config: &AppConfig) -> Result<()> {
+pub fn run_sz_exercise(config: &AppConfig) -> Result<()> {
     println!();
     println!("{}", style("=== S/Z Ratio Test ===").bold());
     println!();
@@ -26,7 +27,7 @@ pub fn run_sz_exercise(_config: &AppConfig) -> Result<()> {
 
     // TUI phase
     let mut terminal = crate::tui::init()?;
-    let (audio_state, stream, collector) = capture::start_capture(false)?;
+    let (audio_state, stream, collector) = capture::start_capture(false, &config.recording)?;
 
     let outcome = crate::tui::screens::sz::run(&mut terminal, &audio_state)?;
 
@@ -39,20 +40,18 @@ pub fn run_sz_exercise(_config: &AppConfig) -> Result<()> {
 
     // Compute results (normal stdout)
     match sz::compute_sz(outcome.s_durations, outcome.z_durations) {
-        Some(result) => {
+        Some(mut result) => {
+            result.format = Some(audio_state.format);
             println!();
             println!("{}", style("Results").bold());
             println!();
             println!("  Mean /s/: {:.1}s", result.mean_s);
             println!("  Mean /z/: {:.1}s", result.mean_z);
             println!(
-                "  S/Z ratio: {:.2} {}",
+                "  S/Z ratio: {:.2}  {}",
                 result.sz_ratio,
-                if result.sz_ratio > 1.4 {
-                    style("(elevated — may indicate glottal air leak)").red().to_string()
-                } else {
-                    style("(normal range)").green().to_string()
-                }
+                crate::audio::exercise::rate(NormMetric::SzRatio, result.sz_ratio, &config.profile)
+                    .unwrap_or_default()
             );
 
             // Save to today's session
@@ -61,6 +60,7 @@ pub fn run_sz_exercise(_config: &AppConfig) -> Result<()> {
                 Ok(s) => s,
                 Err(_) => crate::storage::session_data::SessionData {
                     date: date.clone(),
+                    slot: 1,
                     recordings: crate::storage::session_data::SessionRecordings {
                         sustained: None,
                         scale: None,
@@ -74,6 +74,7 @@ pub fn run_sz_exercise(_config: &AppConfig) -> Result<()> {
                         fatigue: None,
                     },
                     conditions: None,
+                    source: None,
                 },
             };
             session.analysis.sz = Some(result);
@@ -1,20 +1,40 @@
+use std::fs::File;
+use std::io::BufWriter;
 use std::path::Path;
 
 use anyhow::{Context, Result};
 use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
 
-/// Standard WAV spec for our recordings: mono 16-bit PCM.
-pub fn recording_spec(sample_rate: u32) -> WavSpec {
+use super::flac::{self, FlacWriter};
+use crate::config::BitDepth;
+
+/// WAV spec for our recordings: mono PCM at the configured bit depth.
+pub fn recording_spec(sample_rate: u32, bit_depth: BitDepth) -> WavSpec {
+    let (bits_per_sample, sample_format) = match bit_depth {
+        BitDepth::I16 => (16, SampleFormat::Int),
+        BitDepth::I24 => (24, SampleFormat::Int),
+        BitDepth::F32 => (32, SampleFormat::Float),
+    };
     WavSpec {
         channels: 1,
         sample_rate,
-        bits_per_sample: 16,
-        sample_format: SampleFormat::Int,
+        bits_per_sample,
+        sample_format,
     }
 }
 
+/// Scale a [-1.0, 1.0] sample to a signed integer of the given width,
+/// clamping anything beyond full scale.
+///
+/// Uses 2^(bits-1) like cpal and `load_samples`, so integer samples survive
+/// a load and re-quantize unchanged; +1.0 clamps to the largest value.
+pub fn quantize(sample: f32, bits_per_sample: u16) -> i32 {
+    let scale = (1i64 << (bits_per_sample - 1)) as f64;
+    (sample as f64 * scale).round().clamp(-scale, scale - 1.0) as i32
+}
+
 /// Create a WavWriter at the given path, creating parent directories as needed.
-pub fn create_writer(path: &Path, spec: WavSpec) -> Result<WavWriter<std::io::BufWriter<std::fs::File>>> {
+pub fn create_writer(path: &Path, spec: WavSpec) -> Result<WavWriter<BufWriter<File>>> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)
             .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
@@ -24,9 +44,57 @@ pub fn create_writer(path: &Path, spec: WavSpec) -> Result<WavWriter<std::io::Bu
         .with_context(|| format!("Failed to create WAV file: {}", path.display()))
 }
 
-/// Load all samples from a WAV file as f32 in [-1.0, 1.0].
+/// Writer for a new recording in the configured storage format and bit depth.
+pub enum RecordingWriter {
+    Wav(WavWriter<BufWriter<File>>),
+    Flac(FlacWriter<BufWriter<File>>),
+}
+
+impl RecordingWriter {
+    /// Create a writer, choosing the format from the path's extension.
+    pub fn create(path: &Path, spec: WavSpec) -> Result<Self> {
+        if flac::is_flac(path) {
+            Ok(Self::Flac(FlacWriter::create(path, spec)?))
+        } else {
+            Ok(Self::Wav(create_writer(path, spec)?))
+        }
+    }
+
+    /// Write one sample in [-1.0, 1.0], quantized to the file's bit depth.
+    pub fn write_sample(&mut self, sample: f32) -> Result<()> {
+        match self {
+            Self::Wav(w) => {
+                let spec = w.spec();
+                match spec.sample_format {
+                    SampleFormat::Float => w.write_sample(sample),
+                    SampleFormat::Int => w.write_sample(quantize(sample, spec.bits_per_sample)),
+                }
+                .context("Failed to write WAV sample")
+            }
+            Self::Flac(w) => {
+                let bits = w.bits_per_sample();
+                w.write_sample(quantize(sample, bits))
+            }
+        }
+    }
+
+    pub fn finalize(self) -> Result<()> {
+        match self {
+            Self::Wav(w) => w.finalize().context("Failed to finalize WAV file"),
+            Self::Flac(w) => w.finalize(),
+        }
+    }
+}
+
+/// Load all samples from a WAV or FLAC file as f32 in [-1.0, 1.0].
 /// Returns (samples, spec) so callers can read the sample rate.
 pub fn load_samples(path: &Path) -> Result<(Vec<f32>, WavSpec)> {
+    if flac::is_flac(path) {
+        let (samples, spec) = flac::read_samples(path)?;
+        let max_val = (1 << (spec.bits_per_sample - 1)) as f32;
+        return Ok((samples.into_iter().map(|v| v as f32 / max_val).collect(), spec));
+    }
+
     let mut reader = WavReader::open(path)
         .with_context(|| format!("Failed to open WAV file: {}", path.display()))?;
 
@@ -65,7 +133,7 @@ mod tests {
     #[test]
     fn wav_roundtrip() {
         let path = test_wav_path();
-        let spec = recording_spec(44100);
+        let spec = recording_spec(44100, BitDepth::I16);
 
         // Write a known signal: a short ramp
         let original: Vec<f32> = (0..1000).map(|i| (i as f32 / 1000.0) * 2.0 - 1.0).collect();
@@ -98,13 +166,105 @@ mod tests {
         let _ = std::fs::remove_file(&path);
     }
 
+    #[test]
+    fn flac_recording_loads_like_wav() {
+        let dir = std::env::temp_dir().join("voicevo-tests");
+        let wav_path = dir.join("same-take.wav");
+        let flac_path = dir.join("same-take.flac");
+        let spec = recording_spec(44100, BitDepth::I16);
+
+        for path in [&wav_path, &flac_path] {
+            let mut writer = RecordingWriter::create(path, spec).unwrap();
+            for i in 0..5000i32 {
+                writer.write_sample(((i * 37) % 20000 - 10000) as f32 / 32768.0).unwrap();
+            }
+            writer.finalize().unwrap();
+        }
+
+        let (wav, wav_spec) = load_samples(&wav_path).unwrap();
+        let (flac, flac_spec) = load_samples(&flac_path).unwrap();
+        assert_eq!(wav_spec, flac_spec);
+        assert_eq!(wav, flac);
+
+        let _ = std::fs::remove_file(&wav_path);
+        let _ = std::fs::remove_file(&flac_path);
+    }
+
     #[test]
     fn recording_spec_values() {
-        let spec = recording_spec(48000);
+        let spec = recording_spec(48000, BitDepth::I16);
         assert_eq!(spec.channels, 1);
         assert_eq!(spec.sample_rate, 48000);
         assert_eq!(spec.bits_per_sample, 16);
         assert_eq!(spec.sample_format, SampleFormat::Int);
+
+        let spec = recording_spec(48000, BitDepth::F32);
+        assert_eq!(spec.bits_per_sample, 32);
+        assert_eq!(spec.sample_format, SampleFormat::Float);
+    }
+
+    #[test]
+    fn high_resolution_recordings_keep_quiet_detail() {
+        // Phonation around -40 dBFS with detail below one 16-bit step
+        let original: Vec<f32> = (0..4800)
+            .map(|i| 0.01 * (i as f32 * 0.05).sin() + 0.00001 * (i as f32 * 0.7).sin())
+            .collect();
+        let dir = std::env::temp_dir().join("voicevo-tests");
+
+        for (bit_depth, ext, tolerance) in [
+            (BitDepth::I24, "wav", 1.0 / 8_388_607.0),
+            (BitDepth::I24, "flac", 1.0 / 8_388_607.0),
+            (BitDepth::F32, "wav", 0.0),
+        ] {
+            let path = dir.join(format!("hires-{bit_depth:?}.{ext}"));
+            let spec = recording_spec(48000, bit_depth);
+            let mut writer = RecordingWriter::create(&path, spec).unwrap();
+            for &s in &original {
+                writer.write_sample(s).unwrap();
+            }
+            writer.finalize().unwrap();
+
+            let (loaded, loaded_spec) = load_samples(&path).unwrap();
+            assert_eq!(loaded_spec, spec);
+            for (a, b) in original.iter().zip(&loaded) {
+                assert!((a - b).abs() <= tolerance, "{bit_depth:?} {ext}: {a} vs {b}");
+            }
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+
+    #[test]
+    fn quantize_clamps_to_full_scale() {
+        assert_eq!(quantize(0.5, 16), 16384);
+        assert_eq!(quantize(1.5, 16), 32767);
+        assert_eq!(quantize(-1.5, 16), -32768);
+        assert_eq!(quantize(1.0, 24), 8_388_607);
+    }
+
+    #[test]
+    fn integer_extremes_survive_a_round_trip() {
+        let dir = std::env::temp_dir().join("voicevo-tests");
+        let cases: [(BitDepth, &[i32]); 2] = [
+            (BitDepth::I16, &[i16::MIN as i32, -1, 0, 1, i16::MAX as i32]),
+            (BitDepth::I24, &[-8_388_608, -8_388_607, 0, 8_388_606, 8_388_607]),
+        ];
+        for (bit_depth, values) in cases {
+            let spec = recording_spec(48000, bit_depth);
+            let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            for ext in ["wav", "flac"] {
+                let path = dir.join(format!("extremes-{bit_depth:?}.{ext}"));
+                let mut writer = RecordingWriter::create(&path, spec).unwrap();
+                for &v in values {
+                    writer.write_sample(v as f32 / scale).unwrap();
+                }
+                writer.finalize().unwrap();
+
+                let (loaded, _) = load_samples(&path).unwrap();
+                let requantized: Vec<i32> = loaded.iter().map(|&s| quantize(s, spec.bits_per_sample)).collect();
+                assert_eq!(requantized, values, "{bit_depth:?} {ext}");
+                let _ = std::fs::remove_file(&path);
+            }
+        }
     }
 
     #[test]
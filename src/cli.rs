@@ -21,11 +21,15 @@ pub enum Command {
 
     /// Play back a recording
     Play {
-        /// Date (YYYY-MM-DD) or path to a WAV file
+        /// Session (YYYY-MM-DD or YYYY-MM-DD.N) or path to a WAV file
         target: String,
 
-        /// Exercise name (required when target is a date)
+        /// Exercise name (required when target is a session)
         exercise: Option<String>,
+
+        /// Play only the segment that was analyzed (session targets only)
+        #[arg(long)]
+        analyzed: bool,
     },
 
     /// Analyze recorded sessions
@@ -34,6 +38,10 @@ pub enum Command {
         #[arg(long)]
         date: Option<String>,
 
+        /// Session id, e.g. 2026-03-01.2 for the day's second session
+        #[arg(long, conflicts_with = "date")]
+        session: Option<String>,
+
         /// Re-analyze all sessions
         #[arg(long)]
         all: bool,
@@ -52,28 +60,171 @@ pub enum Command {
         /// Include all sessions
         #[arg(long)]
         all: bool,
+
+        /// Embed the recordings in the HTML report (larger, fully portable file)
+        #[arg(long)]
+        embed_audio: bool,
+
+        /// Also draw each metric adjusted for recorded conditions on the trend chart
+        #[arg(long)]
+        adjust_conditions: bool,
     },
 
-    /// Compare two sessions side by side
+    /// Compare sessions side by side, or the sessions around an event
     Compare {
         /// Baseline session date
-        #[arg(long)]
-        baseline: String,
+        #[arg(long, requires = "current", conflicts_with_all = ["dates", "from", "to", "around_event"])]
+        baseline: Option<String>,
 
         /// Current session date
+        #[arg(long, requires = "baseline")]
+        current: Option<String>,
+
+        /// Sessions to compare, comma-separated; "first" and "latest" pick
+        /// the first and newest session (e.g. first,2026-03-01,latest)
+        #[arg(long, value_delimiter = ',', conflicts_with_all = ["from", "to", "around_event"])]
+        dates: Vec<String>,
+
+        /// Compare every session from this date (inclusive)
+        #[arg(long, conflicts_with = "around_event")]
+        from: Option<String>,
+
+        /// Compare every session up to this date (inclusive)
+        #[arg(long, conflicts_with = "around_event")]
+        to: Option<String>,
+
+        /// Output format: table, markdown, or csv
+        #[arg(long, default_value = "table")]
+        format: String,
+
+        /// Write markdown or CSV output to this file instead of stdout
+        #[arg(long)]
+        output: Option<std::path::PathBuf>,
+
+        /// Compare all sessions before and after a clinical event (id from `event list`)
         #[arg(long)]
-        current: String,
+        around_event: Option<i64>,
+
+        /// Window on each side of the event, e.g. 4w, 10d, 2m
+        #[arg(long, default_value = "4w", requires = "around_event")]
+        window: String,
     },
 
     /// List all recorded sessions
     Sessions,
 
+    /// Show progress toward the recovery goals in the config file
+    Goals,
+
+    /// Find sessions by metric value, e.g. `voicevo query "hnr > 12" --where "fatigue<3"`
+    Query {
+        /// Metric comparison, e.g. "hnr > 12" (mpt, hnr, jitter, shimmer, cpps,
+        /// range, speaking_f0, voice_breaks, sz_ratio, ...)
+        expr: String,
+
+        /// Also require this of another metric or a recording condition
+        /// (fatigue, time_of_day, mucus, hydration, throat_cleared); repeatable
+        #[arg(long = "where")]
+        filters: Vec<String>,
+
+        /// Leave out values the analysis flagged as unreliable
+        #[arg(long)]
+        valid_only: bool,
+    },
+
+    /// Summarize sessions per week or month (medians, best MPT, conditions)
+    Summary {
+        /// Grouping period: week (ISO week) or month
+        #[arg(long, default_value = "week")]
+        period: String,
+    },
+
+    /// Record clinical events (therapy, injections, illness...) on the timeline
+    Event {
+        #[command(subcommand)]
+        action: EventCommand,
+    },
+
+    /// List every take of a day with its metrics, or choose the canonical take
+    Attempts {
+        /// Session date (defaults to today)
+        #[arg(long)]
+        date: Option<String>,
+
+        /// Session id, e.g. 2026-03-01.2 for the day's second session
+        #[arg(long, conflicts_with = "date")]
+        session: Option<String>,
+
+        /// Exercise to choose a canonical take for (sustained, scale, reading)
+        #[arg(long, requires = "canonical")]
+        exercise: Option<String>,
+
+        /// Attempt number to use in reports instead of the newest take.
+        /// For sustained, this replaces the best-of-N trial result.
+        #[arg(long, requires = "exercise")]
+        canonical: Option<u32>,
+    },
+
+    /// Export the frame-level contour of a recording (CSV or Praat files)
+    ExportFrames {
+        /// Session date (defaults to today)
+        #[arg(long)]
+        date: Option<String>,
+
+        /// Session id, e.g. 2026-03-01.2 for the day's second session
+        #[arg(long, conflicts_with = "date")]
+        session: Option<String>,
+
+        /// Exercise to export (sustained, scale, reading)
+        #[arg(long)]
+        exercise: String,
+
+        /// Output format: csv, pitchtier, or textgrid
+        #[arg(long, default_value = "csv")]
+        format: String,
+
+        /// Export a specific take instead of the one used for the session
+        #[arg(long)]
+        attempt: Option<u32>,
+
+        /// Output file (defaults to the reports directory)
+        #[arg(long)]
+        output: Option<std::path::PathBuf>,
+    },
+
+    /// Draw a spectrogram of a recording with the pitch contour overlaid
+    Plot {
+        /// Session date (defaults to today)
+        #[arg(long)]
+        date: Option<String>,
+
+        /// Session id, e.g. 2026-03-01.2 for the day's second session
+        #[arg(long, conflicts_with = "date")]
+        session: Option<String>,
+
+        /// Exercise to plot (sustained, scale, reading)
+        #[arg(long)]
+        exercise: String,
+
+        /// Plot a specific take instead of the one used for the session
+        #[arg(long)]
+        attempt: Option<u32>,
+
+        /// Output PNG (defaults to the reports directory)
+        #[arg(long)]
+        output: Option<std::path::PathBuf>,
+    },
+
     /// Get an LLM interpretation of a session's analysis
     Explain {
         /// Session date (defaults to today)
         #[arg(long)]
         date: Option<String>,
 
+        /// Session id, e.g. 2026-03-01.2 for the day's second session
+        #[arg(long, conflicts_with = "date")]
+        session: Option<String>,
+
         /// LLM provider: "claude" or "gpt"
         #[arg(long, default_value = "claude")]
         provider: String,
@@ -96,7 +247,7 @@ pub enum Command {
         deep: bool,
     },
 
-    /// Discard the latest recording attempt for an exercise
+    /// Discard the latest recording attempt for an exercise (the file is kept, marked discarded)
     Discard {
         /// Exercise name (sustained, scale, reading). If omitted, discards the most recently modified recording.
         exercise: Option<String>,
@@ -104,9 +255,13 @@ pub enum Command {
         /// Date of the recording (defaults to today)
         #[arg(long)]
         date: Option<String>,
+
+        /// Session id, e.g. 2026-03-01.2 for the day's second session
+        #[arg(long, conflicts_with = "date")]
+        session: Option<String>,
     },
 
-    /// Open the latest report in your default viewer
+    /// Open the latest HTML report (or chart) in your default viewer
     Browse,
 
     /// Interactive voice exercises with real-time feedback
@@ -118,13 +273,136 @@ pub enum Command {
     /// Export all data as LLM-friendly markdown and copy to clipboard
     Dump,
 
+    /// Write a bundle for a clinician (CSVs per exercise, a summary with
+    /// charts, and the first and latest recording of each exercise) or a
+    /// FHIR R4 Bundle of Observations for a medical record
+    #[command(group(clap::ArgGroup::new("kind").required(true).args(["for_clinician", "fhir"])))]
+    Export {
+        /// Clinician bundle (spreadsheets, summary and audio)
+        #[arg(long)]
+        for_clinician: bool,
+
+        /// FHIR R4 Bundle JSON with an Observation per metric and session
+        #[arg(long)]
+        fhir: bool,
+
+        /// Sessions to include, e.g. 2026-01-01..2026-06-01 (either end may be left open)
+        #[arg(long)]
+        range: Option<String>,
+
+        /// Output directory (clinician bundle) or JSON file (FHIR)
+        #[arg(long)]
+        out: std::path::PathBuf,
+
+        /// Shift dates so the first session falls on 2000-01-01 and leave out
+        /// free-text notes
+        #[arg(long)]
+        deidentify: bool,
+
+        /// FHIR subject of the Observations, e.g. 123 or Patient/123
+        #[arg(long, conflicts_with_all = ["for_clinician", "deidentify"])]
+        patient: Option<String>,
+    },
+
+    /// Import measurements made elsewhere as sessions, marked with their source
+    Import {
+        /// File format: csv or praat-voice-report
+        #[arg(long, default_value = "csv")]
+        format: String,
+
+        /// CSV with a date column and one session per row, or saved Praat
+        /// voice report output
+        file: std::path::PathBuf,
+
+        /// Exercise a Praat voice report measured: sustained, scale or reading
+        #[arg(long, default_value = "sustained")]
+        exercise: String,
+
+        /// Session date (YYYY-MM-DD) for Praat reports, overriding their Date line
+        #[arg(long)]
+        date: Option<String>,
+    },
+
     /// Migrate JSON session files to SQLite database
     Migrate,
 
+    /// Inspect the SQLite database
+    Db {
+        #[command(subcommand)]
+        action: DbCommand,
+    },
+
+    /// Check config, database and recordings for missing, stale or broken data
+    Doctor {
+        /// Re-link moved recordings, re-analyze stale sessions and remove
+        /// empty records where that loses nothing
+        #[arg(long)]
+        fix: bool,
+    },
+
+    /// Back up recordings, database, reports and config to one archive
+    Backup {
+        /// Archive to write, e.g. voicevo-2026-03-01.tar.zst
+        output: std::path::PathBuf,
+    },
+
+    /// Restore a backup archive after verifying its checksums
+    Restore {
+        /// Archive written by `voicevo backup`
+        archive: std::path::PathBuf,
+
+        /// Verify the archive and list what would be restored, without writing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Replace files that already exist
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Convert WAV recordings to lossless FLAC to save space
+    Compact {
+        /// List the recordings that would be converted, without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Show where data and config files are stored
     Paths,
 }
 
+#[derive(Subcommand)]
+pub enum EventCommand {
+    /// Add an event
+    Add {
+        /// Event date (defaults to today)
+        #[arg(long)]
+        date: Option<String>,
+
+        /// injection, surgery, therapy, illness, medication, or other
+        #[arg(long)]
+        kind: String,
+
+        /// Free-text description
+        #[arg(long, default_value = "")]
+        note: String,
+    },
+
+    /// List all events
+    List,
+
+    /// Remove an event by id (see `event list`)
+    Remove {
+        id: i64,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DbCommand {
+    /// Show the schema version and any pending migrations
+    Status,
+}
+
 #[derive(Subcommand)]
 pub enum ExerciseCommand {
     /// Sustained phonation: hold "AAAH" with live timer and volume meter
@@ -150,6 +428,10 @@ pub enum RecordCommand {
         /// Recording date (defaults to today)
         #[arg(long)]
         date: Option<String>,
+
+        /// Session id, e.g. 2026-03-01.2 for the day's second session
+        #[arg(long, conflicts_with = "date")]
+        session: Option<String>,
     },
 
     /// Record a chromatic scale (low to high and back)
@@ -157,6 +439,10 @@ pub enum RecordCommand {
         /// Recording date (defaults to today)
         #[arg(long)]
         date: Option<String>,
+
+        /// Session id, e.g. 2026-03-01.2 for the day's second session
+        #[arg(long, conflicts_with = "date")]
+        session: Option<String>,
     },
 
     /// Record a reading passage
@@ -164,6 +450,10 @@ pub enum RecordCommand {
         /// Recording date (defaults to today)
         #[arg(long)]
         date: Option<String>,
+
+        /// Session id, e.g. 2026-03-01.2 for the day's second session
+        #[arg(long, conflicts_with = "date")]
+        session: Option<String>,
     },
 
     /// Run a full guided session (all exercises)
@@ -171,5 +461,13 @@ pub enum RecordCommand {
         /// Recording date (defaults to today)
         #[arg(long)]
         date: Option<String>,
+
+        /// Session id, e.g. 2026-03-01.2 for the day's second session
+        #[arg(long, conflicts_with = "date")]
+        session: Option<String>,
+
+        /// Start another session today instead of adding to the first one
+        #[arg(long, conflicts_with_all = ["date", "session"])]
+        new: bool,
     },
 }
@@ -15,14 +15,65 @@ pub struct AppConfig {
     pub recording: RecordingConfig,
     pub analysis: AnalysisConfig,
     pub session: SessionConfig,
+    pub profile: ProfileConfig,
+    /// Recovery targets, one `[[goals]]` table each (see `report::goals`).
+    pub goals: Vec<GoalConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct RecordingConfig {
+    /// Capture rate in Hz. Falls back to the device's default rate when the
+    /// device can't record at it.
     pub sample_rate: u32,
     pub channels: u16,
     pub device: String,
+    /// Sample format of new recordings: "16", "24" or "32f".
+    pub bit_depth: BitDepth,
+    /// File format for new recordings; `voicevo compact` converts old WAVs.
+    pub storage: StorageFormat,
+}
+
+impl RecordingConfig {
+    /// Storage format actually used for new recordings: FLAC has no float
+    /// samples, so 32-bit float recordings are always WAV.
+    pub fn effective_storage(&self) -> StorageFormat {
+        match self.bit_depth {
+            BitDepth::F32 => StorageFormat::Wav,
+            _ => self.storage,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BitDepth {
+    #[default]
+    #[serde(rename = "16")]
+    I16,
+    /// Keeps detail in quiet, breathy phonation that 16-bit loses
+    #[serde(rename = "24")]
+    I24,
+    /// 32-bit float, stored exactly as the device delivers it
+    #[serde(rename = "32f")]
+    F32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageFormat {
+    #[default]
+    Wav,
+    /// Lossless, roughly half the size of WAV for voice
+    Flac,
+}
+
+impl StorageFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            StorageFormat::Wav => "wav",
+            StorageFormat::Flac => "flac",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,6 +109,72 @@ pub struct ThresholdConfig {
 #[serde(default)]
 pub struct SessionConfig {
     pub reading_passage: String,
+    /// Number of sustained vowel trials per guided session. Clinical MPT
+    /// protocol is best of three; 1 keeps the single-take behaviour. Only the
+    /// takes of one guided run are combined; other retakes are not.
+    pub sustained_trials: u32,
+    /// Rest between sustained trials, in seconds.
+    pub trial_rest_secs: u32,
+}
+
+/// Who is speaking, so metrics can be compared against matching norms
+/// (see `norms`). Every field is optional; missing ones widen the norms.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileConfig {
+    pub sex: Option<Sex>,
+    /// Age in years
+    pub age: Option<u32>,
+    /// Singing voice type, e.g. "bass", "tenor", "alto", "soprano"
+    pub voice_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Sex {
+    Male,
+    Female,
+}
+
+impl ProfileConfig {
+    /// Short description for report headers, e.g. "male, 58" or "sex unspecified".
+    pub fn describe(&self) -> String {
+        let sex = match self.sex {
+            Some(Sex::Male) => "male",
+            Some(Sex::Female) => "female",
+            None => "sex unspecified",
+        };
+        let mut parts = vec![sex.to_string()];
+        if let Some(age) = self.age {
+            parts.push(age.to_string());
+        }
+        if let Some(ref v) = self.voice_type {
+            parts.push(v.clone());
+        }
+        parts.join(", ")
+    }
+}
+
+/// A recovery target agreed with the therapist, e.g. MPT ≥ 12 s by a date.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalConfig {
+    /// Metric key from `report::stats::metric_defs`, e.g. "mpt", "hnr", "sz_ratio"
+    pub metric: String,
+    pub target: f32,
+    /// Which side of the target counts as reached. Defaults to the metric's
+    /// better direction (above for MPT, below for jitter).
+    #[serde(default)]
+    pub direction: Option<GoalDirection>,
+    /// Target date, YYYY-MM-DD
+    #[serde(default)]
+    pub deadline: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GoalDirection {
+    Above,
+    Below,
 }
 
 // --- Default implementations ---
@@ -69,6 +186,8 @@ impl Default for AppConfig {
             recording: RecordingConfig::default(),
             analysis: AnalysisConfig::default(),
             session: SessionConfig::default(),
+            profile: ProfileConfig::default(),
+            goals: Vec::new(),
         }
     }
 }
@@ -79,6 +198,8 @@ impl Default for RecordingConfig {
             sample_rate: 44100,
             channels: 1,
             device: "default".into(),
+            bit_depth: BitDepth::I16,
+            storage: StorageFormat::Wav,
         }
     }
 }
@@ -136,6 +257,8 @@ and form a rainbow. The rainbow is a division of white light into \
 many beautiful colors. These take the shape of a long round arch, \
 with its path high above, and its two ends apparently beyond the horizon."
                 .into(),
+            sustained_trials: 1,
+            trial_rest_secs: 30,
         }
     }
 }
@@ -181,6 +304,20 @@ mod tests {
         assert_eq!(cfg.analysis.pitch_floor_hz, 30.0);
         assert_eq!(cfg.analysis.thresholds.jitter_pathological, 1.04);
         assert!(!cfg.session.reading_passage.is_empty());
+        assert_eq!(cfg.session.sustained_trials, 1);
+        assert_eq!(cfg.session.trial_rest_secs, 30);
+    }
+
+    #[test]
+    fn parse_sustained_trials() {
+        let toml_str = r#"
+[session]
+sustained_trials = 3
+"#;
+        let cfg: AppConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(cfg.session.sustained_trials, 3);
+        assert_eq!(cfg.session.trial_rest_secs, 30);
+        assert!(!cfg.session.reading_passage.is_empty());
     }
 
     #[test]
@@ -195,6 +332,27 @@ pitch_floor_hz = 40.0
         // Unspecified fields should be defaults
         assert_eq!(cfg.analysis.pitch_ceiling_hz, 1000.0);
         assert_eq!(cfg.recording.sample_rate, 44100);
+        assert_eq!(cfg.recording.storage, StorageFormat::Wav);
+    }
+
+    #[test]
+    fn parse_flac_storage() {
+        let cfg: AppConfig = toml::from_str("[recording]\nstorage = \"flac\"\n").unwrap();
+        assert_eq!(cfg.recording.storage, StorageFormat::Flac);
+        assert_eq!(cfg.recording.storage.extension(), "flac");
+    }
+
+    #[test]
+    fn parse_bit_depth() {
+        let cfg: AppConfig = toml::from_str("[recording]\nbit_depth = \"24\"\nsample_rate = 48000\n").unwrap();
+        assert_eq!(cfg.recording.bit_depth, BitDepth::I24);
+        assert_eq!(cfg.recording.sample_rate, 48000);
+
+        let cfg: AppConfig = toml::from_str("[recording]\nbit_depth = \"32f\"\nstorage = \"flac\"\n").unwrap();
+        assert_eq!(cfg.recording.bit_depth, BitDepth::F32);
+        assert_eq!(cfg.recording.effective_storage(), StorageFormat::Wav);
+
+        assert!(toml::from_str::<AppConfig>("[recording]\nbit_depth = \"20\"\n").is_err());
     }
 
     #[test]
@@ -226,6 +384,41 @@ pitch_floor_hz = 40.0
         assert_eq!(pitch.pitch_ceiling_hz, 1000.0);
     }
 
+    #[test]
+    fn parse_profile() {
+        let toml_str = r#"
+[profile]
+sex = "female"
+age = 47
+"#;
+        let cfg: AppConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(cfg.profile.sex, Some(Sex::Female));
+        assert_eq!(cfg.profile.age, Some(47));
+        assert_eq!(cfg.profile.describe(), "female, 47");
+        assert_eq!(AppConfig::default().profile.describe(), "sex unspecified");
+    }
+
+    #[test]
+    fn parse_goals() {
+        let toml_str = r#"
+[[goals]]
+metric = "mpt"
+target = 12.0
+deadline = "2026-12-01"
+
+[[goals]]
+metric = "sz_ratio"
+target = 1.2
+direction = "below"
+"#;
+        let cfg: AppConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(cfg.goals.len(), 2);
+        assert_eq!(cfg.goals[0].metric, "mpt");
+        assert_eq!(cfg.goals[0].direction, None);
+        assert_eq!(cfg.goals[0].deadline.as_deref(), Some("2026-12-01"));
+        assert_eq!(cfg.goals[1].direction, Some(GoalDirection::Below));
+    }
+
     #[test]
     fn roundtrip_toml() {
         let cfg = AppConfig::default();
@@ -49,12 +49,31 @@ impl Default for CppsConfig {
 ///
 /// Clinical reference: normal ~5-10 dB, < 3 dB = significant dysphonia.
 pub fn compute_cpps(samples: &[f32], sample_rate: u32, config: &CppsConfig) -> Option<f32> {
+    let cpp_values: Vec<f32> = cpps_per_frame(samples, sample_rate, config)
+        .into_iter()
+        .flatten()
+        .collect();
+
+    if cpp_values.is_empty() {
+        return None;
+    }
+
+    // Mean CPPS across frames
+    let mean = cpp_values.iter().sum::<f32>() / cpp_values.len() as f32;
+    Some(mean)
+}
+
+/// CPPS for every analysis frame, one entry per hop starting at sample 0.
+///
+/// Frames below the energy gate (or without a usable cepstral peak) are None.
+/// Returns an empty list when the recording is shorter than one frame.
+pub fn cpps_per_frame(samples: &[f32], sample_rate: u32, config: &CppsConfig) -> Vec<Option<f32>> {
     let sr = sample_rate as f32;
     let frame_size = (config.frame_size_ms / 1000.0 * sr) as usize;
     let hop_size = (config.hop_size_ms / 1000.0 * sr) as usize;
 
     if frame_size == 0 || samples.len() < frame_size {
-        return None;
+        return Vec::new();
     }
 
     // FFT size: next power of 2 for efficiency
@@ -70,7 +89,7 @@ pub fn compute_cpps(samples: &[f32], sample_rate: u32, config: &CppsConfig) -> O
     let q_max = q_max.min(fft_size / 2);
 
     if q_min >= q_max || q_max >= fft_size / 2 {
-        return None;
+        return Vec::new();
     }
 
     let mut cpp_values = Vec::new();
@@ -88,6 +107,7 @@ pub fn compute_cpps(samples: &[f32], sample_rate: u32, config: &CppsConfig) -> O
         };
 
         if rms_db < config.energy_gate_db {
+            cpp_values.push(None);
             pos += hop_size;
             continue;
         }
@@ -127,20 +147,12 @@ pub fn compute_cpps(samples: &[f32], sample_rate: u32, config: &CppsConfig) -> O
         let cepstrum: Vec<f32> = log_power.iter().map(|c| c.re * norm).collect();
 
         // Step 4: Find peak in quefrency range
-        if let Some(cpp) = cepstral_peak_prominence(&cepstrum, q_min, q_max) {
-            cpp_values.push(cpp);
-        }
+        cpp_values.push(cepstral_peak_prominence(&cepstrum, q_min, q_max));
 
         pos += hop_size;
     }
 
-    if cpp_values.is_empty() {
-        return None;
-    }
-
-    // Mean CPPS across frames
-    let mean = cpp_values.iter().sum::<f32>() / cpp_values.len() as f32;
-    Some(mean)
+    cpp_values
 }
 
 /// Compute the cepstral peak prominence for one frame's cepstrum.
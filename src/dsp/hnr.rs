@@ -27,55 +27,11 @@ pub fn compute_hnr_db(
     contour: &[PitchFrame],
     hop_size_ms: f32,
 ) -> Option<f32> {
-    let sr = sample_rate as f32;
-    let hop_samples = (hop_size_ms / 1000.0 * sr) as usize;
-
-    let mut hnr_values: Vec<f32> = Vec::new();
-
-    for (i, frame) in contour.iter().enumerate() {
-        let Some(f0) = frame.frequency else {
-            continue;
-        };
-
-        // Pitch period in samples — the lag at which we compute autocorrelation
-        let period_samples = (sr / f0).round() as usize;
-        if period_samples == 0 {
-            continue;
-        }
-
-        // We need at least 2 periods of audio to compute autocorrelation at this lag.
-        // Center the window around the frame position.
-        let center = i * hop_samples;
-        let window_size = period_samples * 3; // 3 periods gives a stable estimate
-        let start = center.saturating_sub(window_size / 2);
-        let end = (start + window_size).min(samples.len());
-
-        if end - start < period_samples * 2 {
-            continue; // not enough audio
-        }
-
-        let chunk = &samples[start..end];
-
-        // Compute normalized autocorrelation at the pitch period lag.
-        //
-        // Autocorrelation at lag L:
-        //   r(L) = Σ x(n) * x(n + L)  /  sqrt(Σ x(n)^2 * Σ x(n+L)^2)
-        //
-        // This is like a dot product between the signal and a shifted copy,
-        // normalized so the result is between -1 and 1.
-        let r = normalized_autocorrelation(chunk, period_samples);
-
-        // Clamp r to a valid range — numerical imprecision can push it slightly
-        // above 1.0 or below 0.0
-        let r = r.clamp(0.001, 0.999);
-
-        // Convert to dB: HNR = 10 * log10(r / (1 - r))
-        // When r = 0.5 → HNR = 0 dB (equal harmonic and noise energy)
-        // When r = 0.99 → HNR = 20 dB (harmonics 100x stronger than noise)
-        // When r = 0.01 → HNR = -20 dB (almost pure noise)
-        let hnr = 10.0 * (r / (1.0 - r)).log10();
-        hnr_values.push(hnr);
-    }
+    let hnr_values: Vec<f32> = frame_correlations(samples, sample_rate, contour, hop_size_ms)
+        .into_iter()
+        .flatten()
+        .map(hnr_db_from_correlation)
+        .collect();
 
     if hnr_values.is_empty() {
         return None;
@@ -86,6 +42,65 @@ pub fn compute_hnr_db(
     Some(mean_hnr)
 }
 
+/// Normalized autocorrelation at the pitch period for every contour frame.
+///
+/// Unvoiced frames, and frames too close to the end of the audio to hold
+/// two pitch periods, are None. Values are raw (not clamped).
+pub fn frame_correlations(
+    samples: &[f32],
+    sample_rate: u32,
+    contour: &[PitchFrame],
+    hop_size_ms: f32,
+) -> Vec<Option<f32>> {
+    let sr = sample_rate as f32;
+    let hop_samples = (hop_size_ms / 1000.0 * sr) as usize;
+
+    contour
+        .iter()
+        .enumerate()
+        .map(|(i, frame)| {
+            let f0 = frame.frequency?;
+
+            // Pitch period in samples — the lag at which we compute autocorrelation
+            let period_samples = (sr / f0).round() as usize;
+            if period_samples == 0 {
+                return None;
+            }
+
+            // We need at least 2 periods of audio to compute autocorrelation at this lag.
+            // Center the window around the frame position.
+            let center = i * hop_samples;
+            let window_size = period_samples * 3; // 3 periods gives a stable estimate
+            let start = center.saturating_sub(window_size / 2);
+            let end = (start + window_size).min(samples.len());
+
+            if end.saturating_sub(start) < period_samples * 2 {
+                return None; // not enough audio
+            }
+
+            // Autocorrelation at lag L:
+            //   r(L) = Σ x(n) * x(n + L)  /  sqrt(Σ x(n)^2 * Σ x(n+L)^2)
+            //
+            // This is like a dot product between the signal and a shifted copy,
+            // normalized so the result is between -1 and 1.
+            Some(normalized_autocorrelation(&samples[start..end], period_samples))
+        })
+        .collect()
+}
+
+/// Convert a pitch-period autocorrelation to HNR in dB.
+pub fn hnr_db_from_correlation(r: f32) -> f32 {
+    // Clamp r to a valid range — numerical imprecision can push it slightly
+    // above 1.0 or below 0.0
+    let r = r.clamp(0.001, 0.999);
+
+    // Convert to dB: HNR = 10 * log10(r / (1 - r))
+    // When r = 0.5 → HNR = 0 dB (equal harmonic and noise energy)
+    // When r = 0.99 → HNR = 20 dB (harmonics 100x stronger than noise)
+    // When r = 0.01 → HNR = -20 dB (almost pure noise)
+    10.0 * (r / (1.0 - r)).log10()
+}
+
 /// Compute the normalized autocorrelation of a signal at a given lag.
 ///
 /// This compares the signal with a shifted copy of itself.
@@ -6,6 +6,7 @@ pub mod jitter;
 pub mod mpt;
 pub mod periodicity;
 pub mod pitch;
+pub mod segment;
 pub mod shimmer;
 pub mod voice_breaks;
 pub mod windowing;
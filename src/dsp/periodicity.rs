@@ -1,4 +1,4 @@
-use super::hnr::normalized_autocorrelation;
+use super::hnr::frame_correlations;
 use super::pitch::PitchFrame;
 
 /// Compute mean periodicity score across voiced, active frames.
@@ -22,41 +22,14 @@ pub fn compute_periodicity(
     active_frames: &[bool],
     hop_size_ms: f32,
 ) -> Option<f32> {
-    let sr = sample_rate as f32;
-    let hop_samples = (hop_size_ms / 1000.0 * sr) as usize;
-
-    let mut values: Vec<f32> = Vec::new();
-
-    for (i, frame) in contour.iter().enumerate() {
-        let Some(f0) = frame.frequency else {
-            continue;
-        };
-
+    let values: Vec<f32> = frame_correlations(samples, sample_rate, contour, hop_size_ms)
+        .into_iter()
+        .enumerate()
         // Only use frames that are also active (have energy)
-        if i < active_frames.len() && !active_frames[i] {
-            continue;
-        }
-
-        let period_samples = (sr / f0).round() as usize;
-        if period_samples == 0 {
-            continue;
-        }
-
-        // Need at least 2 periods of audio for autocorrelation
-        let center = i * hop_samples;
-        let window_size = period_samples * 3;
-        let start = center.saturating_sub(window_size / 2);
-        let end = (start + window_size).min(samples.len());
-
-        if end - start < period_samples * 2 {
-            continue;
-        }
-
-        let chunk = &samples[start..end];
-        let r = normalized_autocorrelation(chunk, period_samples);
-        let r = r.clamp(0.0, 1.0);
-        values.push(r);
-    }
+        .filter(|&(i, _)| i >= active_frames.len() || active_frames[i])
+        .filter_map(|(_, r)| r)
+        .map(|r| r.clamp(0.0, 1.0))
+        .collect();
 
     if values.is_empty() {
         return None;
@@ -78,9 +78,7 @@ impl Default for PitchConfig {
 /// `None` means the frame was unvoiced (no detectable pitch).
 #[derive(Debug, Clone)]
 pub struct PitchFrame {
-    /// Time in seconds from the start of the audio.
-    /// Used in tests and by callers inspecting the contour.
-    #[allow(dead_code)]
+    /// Time in seconds from the start of the audio (start of the window).
     pub time: f32,
 
     /// Detected fundamental frequency, or None if unvoiced.
@@ -110,8 +108,7 @@ pub fn energy_based_contour(
 
     // Match the same stepping as extract_pitch_contour so frame indices
     // map to the same audio positions for shimmer/HNR.
-    let min_buffer = (2.0 * sr / config.pitch_floor_hz).ceil() as usize;
-    let detector_size = min_buffer.next_power_of_two().max(frame_size);
+    let detector_size = detector_size(sample_rate, config);
 
     let mut contour = Vec::new();
     let mut pos = 0;
@@ -259,16 +256,11 @@ pub fn extract_pitch_contour(
     let sr = sample_rate as f32;
 
     // Convert milliseconds to samples.
-    // e.g., 30ms at 44100 Hz = 1323 samples
-    let frame_size = (config.frame_size_ms / 1000.0 * sr) as usize;
+    // e.g., 10ms at 44100 Hz = 441 samples
     let hop_size = (config.hop_size_ms / 1000.0 * sr) as usize;
 
-    // The McLeod detector needs a buffer large enough to capture at least
-    // 2 full cycles of the lowest frequency we want to detect.
-    // At 30 Hz and 44100 Hz sample rate: period = 44100/30 = 1470 samples.
-    // We need 2x that = 2940. Round up to next power of 2 for FFT efficiency.
-    let min_buffer = (2.0 * sr / config.pitch_floor_hz).ceil() as usize;
-    let detector_size = min_buffer.next_power_of_two().max(frame_size);
+    // Window length: at least 2 cycles of the pitch floor (and one frame)
+    let detector_size = detector_size(sample_rate, config);
 
     // Padding helps with edge effects in the autocorrelation.
     // Half the detector size is standard.
@@ -312,6 +304,19 @@ pub fn extract_pitch_contour(
     contour
 }
 
+/// Length in samples of the window each contour frame is detected on.
+///
+/// The McLeod detector needs a buffer large enough to capture at least
+/// 2 full cycles of the lowest frequency we want to detect.
+/// At 30 Hz and 44100 Hz sample rate: period = 44100/30 = 1470 samples.
+/// We need 2x that = 2940. Round up to next power of 2 for FFT efficiency.
+pub fn detector_size(sample_rate: u32, config: &PitchConfig) -> usize {
+    let sr = sample_rate as f32;
+    let frame_size = (config.frame_size_ms / 1000.0 * sr) as usize;
+    let min_buffer = (2.0 * sr / config.pitch_floor_hz).ceil() as usize;
+    min_buffer.next_power_of_two().max(frame_size)
+}
+
 /// Detect pitch from a single audio frame.
 ///
 /// Runs McLeod on the provided samples using default config tuned for
@@ -0,0 +1,164 @@
+use std::ops::Range;
+
+use super::activity::{self, ActivityConfig};
+use super::contour;
+
+/// Which region of a recording to keep for analysis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentMode {
+    /// The longest phonation, bridging short gaps (sustained vowel).
+    Dominant,
+    /// Everything from the first to the last sound (scale, reading), so
+    /// pauses between phrases or notes stay in.
+    Span,
+}
+
+/// Gaps up to this long are bridged when finding the dominant phonation.
+/// Matches the MPT bridging threshold — anything longer is a breath.
+const MAX_BRIDGE_MS: f32 = 250.0;
+
+/// Padding kept on each side of the segment so pitch onsets aren't clipped.
+const PAD_MS: f32 = 50.0;
+
+/// Find the sample range to analyze.
+///
+/// Recordings include the Enter keypress, breaths and trailing silence.
+/// Activity detection already drops bursts shorter than 80 ms (key clicks);
+/// this picks the region of interest out of what remains.
+///
+/// Returns None when no activity was detected.
+pub fn find_segment(samples: &[f32], sample_rate: u32, mode: SegmentMode) -> Option<Range<usize>> {
+    let config = ActivityConfig::default();
+    let active = activity::detect_activity(samples, sample_rate, &config).active_frames;
+    let frame_size = (config.frame_size_ms / 1000.0 * sample_rate as f32) as usize;
+
+    let runs = active_runs(&active);
+    let (first, last) = match mode {
+        SegmentMode::Dominant => {
+            let max_gap = (MAX_BRIDGE_MS / config.frame_size_ms) as usize;
+            contour::merge_close_runs(&runs, max_gap)
+                .into_iter()
+                .max_by_key(|&(start, end)| end - start)?
+        }
+        SegmentMode::Span => (runs.first()?.0, runs.last()?.1),
+    };
+
+    let pad = (PAD_MS / 1000.0 * sample_rate as f32) as usize;
+    let start = (first * frame_size).saturating_sub(pad);
+    let end = ((last + 1) * frame_size + pad).min(samples.len());
+    (start < end).then_some(start..end)
+}
+
+/// Trim a recording to its analysis segment. Falls back to the whole
+/// recording (and no range) when nothing is active.
+pub fn trim(samples: &[f32], sample_rate: u32, mode: SegmentMode) -> (&[f32], Option<Range<usize>>) {
+    match find_segment(samples, sample_rate, mode) {
+        Some(range) => (&samples[range.clone()], Some(range)),
+        None => (samples, None),
+    }
+}
+
+/// Runs of consecutive active frames as inclusive (start, end) indices.
+fn active_runs(active: &[bool]) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut start = None;
+
+    for (i, &a) in active.iter().enumerate() {
+        match (a, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                runs.push((s, i - 1));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        runs.push((s, active.len() - 1));
+    }
+
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    const SR: u32 = 44100;
+
+    fn tone(secs: f32) -> Vec<f32> {
+        (0..(SR as f32 * secs) as usize)
+            .map(|i| 0.3 * (2.0 * PI * 150.0 * i as f32 / SR as f32).sin())
+            .collect()
+    }
+
+    fn silence(secs: f32) -> Vec<f32> {
+        vec![0.0; (SR as f32 * secs) as usize]
+    }
+
+    fn secs(range: &Range<usize>) -> (f32, f32) {
+        (range.start as f32 / SR as f32, range.end as f32 / SR as f32)
+    }
+
+    #[test]
+    fn dominant_picks_longest_phonation() {
+        // Short onset attempt, a breath pause, then the real phonation
+        let mut samples = silence(0.5);
+        samples.extend(tone(0.4));
+        samples.extend(silence(0.8));
+        samples.extend(tone(2.0));
+        samples.extend(silence(1.0));
+
+        let (start, end) = secs(&find_segment(&samples, SR, SegmentMode::Dominant).unwrap());
+        assert!((start - 1.65).abs() < 0.05, "start {start}");
+        assert!((end - 3.75).abs() < 0.05, "end {end}");
+    }
+
+    #[test]
+    fn dominant_bridges_short_gaps() {
+        let mut samples = silence(0.5);
+        samples.extend(tone(1.0));
+        samples.extend(silence(0.2));
+        samples.extend(tone(1.0));
+        samples.extend(silence(0.5));
+
+        let (start, end) = secs(&find_segment(&samples, SR, SegmentMode::Dominant).unwrap());
+        assert!((start - 0.45).abs() < 0.05, "start {start}");
+        assert!((end - 2.75).abs() < 0.05, "end {end}");
+    }
+
+    #[test]
+    fn span_keeps_pauses_between_phrases() {
+        let mut samples = silence(0.5);
+        samples.extend(tone(1.0));
+        samples.extend(silence(0.8));
+        samples.extend(tone(1.0));
+        samples.extend(silence(0.5));
+
+        let (start, end) = secs(&find_segment(&samples, SR, SegmentMode::Span).unwrap());
+        assert!((start - 0.45).abs() < 0.05, "start {start}");
+        assert!((end - 3.35).abs() < 0.05, "end {end}");
+    }
+
+    #[test]
+    fn key_click_is_ignored() {
+        let mut samples = silence(0.5);
+        samples.extend(tone(1.0));
+        samples.extend(silence(0.5));
+        samples.extend(tone(0.03)); // Enter keypress
+        samples.extend(silence(0.1));
+
+        let (_, end) = secs(&find_segment(&samples, SR, SegmentMode::Span).unwrap());
+        assert!((end - 1.55).abs() < 0.05, "end {end}");
+    }
+
+    #[test]
+    fn silence_falls_back_to_whole_recording() {
+        let samples = silence(1.0);
+        assert!(find_segment(&samples, SR, SegmentMode::Dominant).is_none());
+        let (trimmed, range) = trim(&samples, SR, SegmentMode::Span);
+        assert_eq!(trimmed.len(), samples.len());
+        assert!(range.is_none());
+    }
+}
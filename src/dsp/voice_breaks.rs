@@ -16,34 +16,39 @@ use super::pitch::PitchFrame;
 ///
 /// Returns the number of voice breaks detected.
 pub fn count_voice_breaks(contour: &[PitchFrame], hop_size_ms: f32, max_break_ms: f32) -> usize {
-    let runs = contour::voiced_runs(contour);
+    find_voice_breaks(contour, hop_size_ms, max_break_ms).len()
+}
 
-    if runs.len() < 2 {
-        return 0;
-    }
+/// Locate voice breaks as inclusive (first, last) unvoiced frame indices.
+///
+/// Same rules as `count_voice_breaks`, for callers that need to mark the
+/// breaks on a timeline.
+pub fn find_voice_breaks(
+    contour: &[PitchFrame],
+    hop_size_ms: f32,
+    max_break_ms: f32,
+) -> Vec<(usize, usize)> {
+    let runs = contour::voiced_runs(contour);
 
     let min_gap_ms = 50.0;
     let max_gap_ms = max_break_ms;
 
-    let mut breaks = 0;
-
     // Look at the gaps between consecutive voiced runs.
     // Each gap is the space between one run ending and the next one starting.
-    for pair in runs.windows(2) {
-        let (_, end_of_prev) = pair[0];
-        let (start_of_next, _) = pair[1];
-
-        // Gap in frames: from the frame after the previous run ends
-        // to the frame before the next run starts
-        let gap_frames = start_of_next - end_of_prev - 1;
-        let gap_ms = gap_frames as f32 * hop_size_ms;
-
-        if gap_ms >= min_gap_ms && gap_ms <= max_gap_ms {
-            breaks += 1;
-        }
-    }
-
-    breaks
+    runs.windows(2)
+        .filter_map(|pair| {
+            let (_, end_of_prev) = pair[0];
+            let (start_of_next, _) = pair[1];
+
+            // Gap in frames: from the frame after the previous run ends
+            // to the frame before the next run starts
+            let gap_frames = start_of_next - end_of_prev - 1;
+            let gap_ms = gap_frames as f32 * hop_size_ms;
+
+            (gap_ms >= min_gap_ms && gap_ms <= max_gap_ms)
+                .then_some((end_of_prev + 1, start_of_next - 1))
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -125,4 +130,10 @@ mod tests {
         );
         assert_eq!(count_voice_breaks(&contour, 10.0, 250.0), 1);
     }
+
+    #[test]
+    fn break_positions() {
+        let contour = make_contour(&[(20, true), (8, false), (20, true), (3, false), (5, true)], 10.0);
+        assert_eq!(find_voice_breaks(&contour, 10.0, 250.0), vec![(20, 27)]);
+    }
 }
@@ -6,7 +6,8 @@ pub mod provider;
 use anyhow::{Context, Result};
 use provider::Provider;
 
-use crate::storage::session_data::SessionData;
+use crate::config::ProfileConfig;
+use crate::storage::session_data::{ClinicalEvent, SessionData};
 
 /// Run the full LLM interpretation pipeline:
 ///   1. Build prompts from session data
@@ -18,13 +19,15 @@ pub fn interpret(
     model: Option<&str>,
     current: &SessionData,
     history: &[SessionData],
+    profile: &ProfileConfig,
+    events: &[ClinicalEvent],
     trend_report: Option<&str>,
 ) -> Result<String> {
     let api_key = provider.api_key()?;
     let model = model.unwrap_or_else(|| provider.default_model());
 
     let system = prompt::system_prompt();
-    let user = prompt::user_prompt(current, history, trend_report);
+    let user = prompt::user_prompt(current, history, profile, events, trend_report);
 
     match provider {
         Provider::Anthropic => anthropic::complete(&api_key, model, &system, &user),
@@ -45,6 +48,8 @@ pub struct DeepReport {
 pub fn deep_interpret(
     current: &SessionData,
     history: &[SessionData],
+    profile: &ProfileConfig,
+    events: &[ClinicalEvent],
     tier: provider::ModelTier,
     trend_report: Option<&str>,
 ) -> Result<DeepReport> {
@@ -56,7 +61,7 @@ pub fn deep_interpret(
     let gpt_key = gpt.api_key()?;
 
     let system = prompt::system_prompt();
-    let user = prompt::user_prompt(current, history, trend_report);
+    let user = prompt::user_prompt(current, history, profile, events, trend_report);
 
     let claude_model = claude.model_for_tier(tier);
     let gpt_model = gpt.model_for_tier(tier);
@@ -80,6 +85,8 @@ pub fn deep_interpret(
     let synthesis_user = prompt::synthesis_user_prompt(
         current,
         history,
+        profile,
+        events,
         &claude_response,
         &gpt_response,
     );
@@ -1,4 +1,5 @@
-use crate::storage::session_data::{ReliabilityInfo, SessionData};
+use crate::config::ProfileConfig;
+use crate::storage::session_data::{ClinicalEvent, ReliabilityInfo, SessionData};
 
 /// The system prompt that gives the LLM medical and acoustic context.
 /// This never changes between calls — it defines the role and domain knowledge.
@@ -25,8 +26,8 @@ Recovery is gradual over months. Signs of improvement include:
 ## Metric definitions
 
 ### Sustained vowel ("AAAH")
-- **MPT** (Maximum Phonation Time): how long the patient can sustain a vowel. Healthy adults: 15-25 seconds. Below 10 seconds suggests significant cord dysfunction.
-- **Mean F0**: average fundamental frequency. Healthy adult males: 85-180 Hz, females: 165-255 Hz. Vocal cord paralysis often pushes F0 above the normal range.
+- **MPT** (Maximum Phonation Time): how long the patient can sustain a vowel. Healthy adults typically reach 15-25 seconds, less with age and in women. Below 10 seconds suggests significant cord dysfunction.
+- **Mean F0**: average fundamental frequency. The expected range depends on sex and age — use the normative comparison in the data rather than a fixed range. Vocal cord paralysis often pushes F0 above the patient's norm.
 - **F0 std**: pitch stability. Lower is more stable. Below 5 Hz is very stable.
 - **Jitter**: cycle-to-cycle pitch variation (%). Normal < 1.04%. Higher suggests irregular cord vibration.
 - **Shimmer**: cycle-to-cycle amplitude variation (%). Normal < 3.81%. Higher suggests inconsistent cord closure.
@@ -100,6 +101,20 @@ Sessions may include self-reported recording conditions (time of day, fatigue, h
 
 When conditions are available, factor them into your interpretation. For example, worse metrics on a high-fatigue morning session may not indicate regression — compare against sessions with similar conditions when possible.
 
+## Normative comparison
+
+The data may include a normative comparison: the current session's metrics as z-scores and percentiles against healthy adults of the patient's sex and age (from the patient's profile). Prefer these over the fixed cutoffs above when they are present — a jitter of 0.8% means something different at 30 than at 75. A z-score of -2 on a higher-is-better metric means only about 2% of healthy peers score lower. Values flagged ⚠ are more than 1 SD from the mean on the worse side. If the profile's sex is unspecified, the norms are pooled across sexes and wider, especially for F0.
+
+## Clinical events
+
+The data may list clinical events — injection laryngoplasty, surgery, voice therapy, illness, medication changes — with how many days before or after the current session they happened. Account for them when explaining changes: a jump right after an injection is the intervention, not spontaneous recovery (and injectable fillers partly resorb over the following weeks to months); therapy effects build gradually over weeks; a cold or laryngitis can worsen every metric temporarily. Don't credit or blame an event for a change that started before it.
+
+## Trend statistics
+
+The data may include pre-computed trend statistics: Theil–Sen slopes per week with 95% confidence intervals, change points, and the minimal detectable change (MDC) — the smallest change that exceeds day-to-day measurement noise. Treat changes smaller than the MDC as noise, not progress or regression. When a metric moved beyond its MDC, you can say so with confidence.
+
+Condition-adjusted trends, when present, fit each metric on time plus the recorded conditions. The adjusted slope is the trend with conditions held fixed, and each condition effect (e.g. "morning sessions: -2.1 dB HNR") is how much that condition shifts the metric. Prefer the adjusted slope when it differs from the raw one — it separates recovery from when and how the patient happened to record.
+
 Keep it to 2-3 short paragraphs. Don't repeat the raw numbers back — the patient already sees them in the terminal output."#
         .to_string()
 }
@@ -107,10 +122,16 @@ Keep it to 2-3 short paragraphs. Don't repeat the raw numbers back — the patie
 /// Build the user message from the current session and optional history.
 /// Formats the data as readable text rather than raw JSON so the LLM
 /// can focus on interpretation rather than parsing.
-pub fn user_prompt(current: &SessionData, history: &[SessionData], trend_report: Option<&str>) -> String {
+pub fn user_prompt(
+    current: &SessionData,
+    history: &[SessionData],
+    profile: &ProfileConfig,
+    events: &[ClinicalEvent],
+    trend_report: Option<&str>,
+) -> String {
     let mut parts = Vec::new();
 
-    parts.push(format!("## Current session: {}", current.date));
+    parts.push(format!("## Current session: {}", current.id()));
     parts.push(String::new());
 
     if let Some(c) = &current.conditions {
@@ -130,6 +151,13 @@ pub fn user_prompt(current: &SessionData, history: &[SessionData], trend_report:
         parts.push("### Sustained vowel".into());
         push_reliability_header(&mut parts, s.reliability.as_ref(), s.detection_quality.as_deref());
         parts.push(format!("- MPT: {:.1} seconds", s.mpt_seconds));
+        if let Some(ref t) = s.trials {
+            parts.push(format!(
+                "- MPT trials: best of {} (mean {:.1} seconds)",
+                t.attempts.len(),
+                t.mpt_mean_seconds
+            ));
+        }
         parts.push(format!("- Mean F0: {:.1} Hz", s.mean_f0_hz));
         parts.push(format!("- F0 std: {:.1} Hz", s.f0_std_hz));
         parts.push(format!("- Jitter: {:.2}%{}", s.jitter_local_percent,
@@ -197,6 +225,25 @@ pub fn user_prompt(current: &SessionData, history: &[SessionData], trend_report:
         parts.push(String::new());
     }
 
+    // Current session against sex- and age-matched norms
+    let norms_table = crate::norms::markdown_table(current, profile);
+    if !norms_table.is_empty() {
+        parts.push(format!("## Normative comparison (healthy adults: {})", profile.describe()));
+        parts.push(String::new());
+        parts.push(norms_table);
+    }
+
+    // Interventions and illnesses, relative to this session
+    if !events.is_empty() {
+        parts.push("## Clinical events".into());
+        parts.push(String::new());
+        for e in events {
+            let note = if e.note.is_empty() { String::new() } else { format!(": {}", e.note) };
+            parts.push(format!("- {} ({}){note} — {}", e.date, e.kind, relative_to(&e.date, &current.date)));
+        }
+        parts.push(String::new());
+    }
+
     // Add pre-computed trend report if available
     if let Some(report) = trend_report {
         parts.push("## Trend Report (pre-computed)".into());
@@ -205,13 +252,32 @@ pub fn user_prompt(current: &SessionData, history: &[SessionData], trend_report:
         parts.push(String::new());
     }
 
+    // Add robust trend statistics over history plus the current session
+    if !history.is_empty() {
+        let mut all: Vec<SessionData> = history.to_vec();
+        all.push(current.clone());
+        let trends = crate::report::stats::compute_trends(&all);
+        if trends.iter().any(|t| t.n >= 3) {
+            parts.push("## Trend Statistics (pre-computed)".into());
+            parts.push(String::new());
+            parts.push(crate::report::stats::markdown_section(&trends));
+        }
+
+        let adjusted = crate::report::adjust::compute_adjusted_trends(&all);
+        if !adjusted.is_empty() {
+            parts.push("## Condition-Adjusted Trends (pre-computed)".into());
+            parts.push(String::new());
+            parts.push(crate::report::adjust::markdown_section(&adjusted));
+        }
+    }
+
     // Add historical context if we have prior sessions
     if !history.is_empty() {
         parts.push(format!("## History ({} prior session{})", history.len(), if history.len() == 1 { "" } else { "s" }));
         parts.push(String::new());
 
         for session in history {
-            parts.push(format!("### {}", session.date));
+            parts.push(format!("### {}", session.id()));
 
             if let Some(c) = &session.conditions {
                 let mut cond_parts = vec![
@@ -296,10 +362,12 @@ Be concise — 2-3 short paragraphs. Use plain language. Don't hedge excessively
 pub fn synthesis_user_prompt(
     current: &SessionData,
     history: &[SessionData],
+    profile: &ProfileConfig,
+    events: &[ClinicalEvent],
     claude_response: &str,
     gpt_response: &str,
 ) -> String {
-    let data = user_prompt(current, history, None);
+    let data = user_prompt(current, history, profile, events, None);
 
     format!(
         "## Raw measurement data\n\n{data}\n\n\
@@ -310,6 +378,21 @@ pub fn synthesis_user_prompt(
     )
 }
 
+/// How far `date` is from the session, e.g. "12 days before this session".
+fn relative_to(date: &str, session_date: &str) -> String {
+    let parse = |d: &str| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok();
+    let (Some(d), Some(s)) = (parse(date), parse(session_date)) else {
+        return "date unknown".into();
+    };
+    let days = (s - d).num_days();
+    let plural = |n: i64| if n == 1 { "" } else { "s" };
+    match days {
+        0 => "same day as this session".into(),
+        n if n > 0 => format!("{n} day{} before this session", plural(n)),
+        n => format!("{} day{} after this session", -n, plural(-n)),
+    }
+}
+
 /// Push a reliability or detection_quality header line into the prompt parts.
 fn push_reliability_header(parts: &mut Vec<String>, rel: Option<&ReliabilityInfo>, dq: Option<&str>) {
     if let Some(r) = rel {
@@ -387,6 +470,7 @@ mod tests {
     fn sample_session(date: &str) -> SessionData {
         SessionData {
             date: date.into(),
+            slot: 1,
             recordings: SessionRecordings {
                 sustained: Some("test.wav".into()),
                 scale: None,
@@ -404,6 +488,9 @@ mod tests {
                     periodicity_mean: None,
                     detection_quality: None,
                     reliability: None,
+                    trials: None,
+                    recording_quality: None,
+                    segment: None,
                 }),
                 scale: None,
                 reading: None,
@@ -411,6 +498,7 @@ mod tests {
                 fatigue: None,
             },
             conditions: None,
+            source: None,
         }
     }
 
@@ -426,7 +514,7 @@ mod tests {
     #[test]
     fn user_prompt_includes_current_data() {
         let session = sample_session("2026-02-15");
-        let prompt = user_prompt(&session, &[], None);
+        let prompt = user_prompt(&session, &[], &ProfileConfig::default(), &[], None);
         assert!(prompt.contains("2026-02-15"));
         assert!(prompt.contains("645.0 Hz"));
         assert!(prompt.contains("8.0 seconds"));
@@ -436,7 +524,7 @@ mod tests {
     fn user_prompt_includes_history() {
         let current = sample_session("2026-02-22");
         let history = vec![sample_session("2026-02-15")];
-        let prompt = user_prompt(&current, &history, None);
+        let prompt = user_prompt(&current, &history, &ProfileConfig::default(), &[], None);
         assert!(prompt.contains("History (1 prior session)"));
         assert!(prompt.contains("2026-02-15"));
     }
@@ -444,14 +532,14 @@ mod tests {
     #[test]
     fn user_prompt_no_history_section_when_empty() {
         let current = sample_session("2026-02-15");
-        let prompt = user_prompt(&current, &[], None);
+        let prompt = user_prompt(&current, &[], &ProfileConfig::default(), &[], None);
         assert!(!prompt.contains("History"));
     }
 
     #[test]
     fn user_prompt_includes_trend_report() {
         let session = sample_session("2026-02-15");
-        let prompt = user_prompt(&session, &[], Some("MPT improving +2.1s"));
+        let prompt = user_prompt(&session, &[], &ProfileConfig::default(), &[], Some("MPT improving +2.1s"));
         assert!(prompt.contains("Trend Report (pre-computed)"));
         assert!(prompt.contains("MPT improving +2.1s"));
     }
@@ -459,10 +547,53 @@ mod tests {
     #[test]
     fn user_prompt_no_trend_section_when_none() {
         let session = sample_session("2026-02-15");
-        let prompt = user_prompt(&session, &[], None);
+        let prompt = user_prompt(&session, &[], &ProfileConfig::default(), &[], None);
         assert!(!prompt.contains("Trend Report"));
     }
 
+    #[test]
+    fn user_prompt_includes_trend_statistics() {
+        let current = sample_session("2026-02-22");
+        let history = vec![sample_session("2026-02-08"), sample_session("2026-02-15")];
+        let prompt = user_prompt(&current, &history, &ProfileConfig::default(), &[], None);
+        assert!(prompt.contains("## Trend Statistics (pre-computed)"));
+        assert!(prompt.contains("| MPT | 3 |"));
+
+        let prompt = user_prompt(&current, &history[..1], &ProfileConfig::default(), &[], None);
+        assert!(!prompt.contains("Trend Statistics"));
+    }
+
+    #[test]
+    fn user_prompt_includes_normative_comparison() {
+        let session = sample_session("2026-02-15");
+        let profile = ProfileConfig {
+            sex: Some(crate::config::Sex::Male),
+            age: Some(60),
+            voice_type: None,
+        };
+        let prompt = user_prompt(&session, &[], &profile, &[], None);
+        assert!(prompt.contains("## Normative comparison (healthy adults: male, 60)"));
+        assert!(prompt.contains("| MPT | 8.0 s | 23.0 s ± 7.0 s |"));
+        assert!(!system_prompt().contains("85-180 Hz"));
+    }
+
+    #[test]
+    fn user_prompt_includes_clinical_events() {
+        let session = sample_session("2026-02-15");
+        let events = vec![
+            ClinicalEvent { id: 1, date: "2026-02-03".into(), kind: "injection".into(), note: "Hyaluronic acid".into() },
+            ClinicalEvent { id: 2, date: "2026-02-16".into(), kind: "therapy".into(), note: String::new() },
+        ];
+        let prompt = user_prompt(&session, &[], &ProfileConfig::default(), &events, None);
+        assert!(prompt.contains("## Clinical events"));
+        assert!(prompt.contains("- 2026-02-03 (injection): Hyaluronic acid — 12 days before this session"));
+        assert!(prompt.contains("- 2026-02-16 (therapy) — 1 day after this session"));
+        assert!(system_prompt().contains("## Clinical events"));
+
+        let prompt = user_prompt(&session, &[], &ProfileConfig::default(), &[], None);
+        assert!(!prompt.contains("Clinical events"));
+    }
+
     #[test]
     fn synthesis_prompt_contains_instructions() {
         let prompt = synthesis_system_prompt();
@@ -476,6 +607,8 @@ mod tests {
         let prompt = synthesis_user_prompt(
             &session,
             &[],
+            &ProfileConfig::default(),
+            &[],
             "Claude says something",
             "GPT says something else",
         );
@@ -498,7 +631,7 @@ mod tests {
             hydration: "low".into(),
             notes: Some("bad night".into()),
         });
-        let prompt = user_prompt(&session, &[], None);
+        let prompt = user_prompt(&session, &[], &ProfileConfig::default(), &[], None);
         assert!(prompt.contains("### Recording conditions"));
         assert!(prompt.contains("Time of day: morning"));
         assert!(prompt.contains("Fatigue: 7/10"));
@@ -511,7 +644,7 @@ mod tests {
     #[test]
     fn user_prompt_omits_conditions_when_none() {
         let session = sample_session("2026-02-15");
-        let prompt = user_prompt(&session, &[], None);
+        let prompt = user_prompt(&session, &[], &ProfileConfig::default(), &[], None);
         assert!(!prompt.contains("Recording conditions"));
     }
 
@@ -527,7 +660,7 @@ mod tests {
             hydration: "high".into(),
             notes: None,
         });
-        let prompt = user_prompt(&current, &[past], None);
+        let prompt = user_prompt(&current, &[past], &ProfileConfig::default(), &[], None);
         assert!(prompt.contains("Conditions: evening, fatigue=3, mucus=low, hydration=high"));
         assert!(!prompt.contains("throat_cleared"));
     }
@@ -4,6 +4,7 @@ mod cli;
 mod config;
 mod dsp;
 mod llm;
+mod norms;
 mod paths;
 mod report;
 mod storage;
@@ -12,45 +13,54 @@ mod util;
 
 use anyhow::{Context, Result};
 use clap::Parser;
-use cli::{Cli, Command, ExerciseCommand, RecordCommand};
+use cli::{Cli, Command, DbCommand, EventCommand, ExerciseCommand, RecordCommand};
 use console::style;
+use storage::session_data::SessionId;
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    // The doctor reports config errors itself rather than failing on them
+    if let Command::Doctor { fix } = cli.command {
+        return report::doctor::run(fix);
+    }
     let app_config = config::load_config()?;
 
     match cli.command {
         Command::Devices => audio::devices::list_devices(),
 
         Command::Record { exercise } => match exercise {
-            RecordCommand::MicCheck => audio::mic_check::run(),
+            RecordCommand::MicCheck => audio::mic_check::run(&app_config.recording),
 
-            RecordCommand::Sustained { date } => {
-                let date = util::resolve_date(date.as_deref())?;
-                audio::recorder::record_exercise("sustained", &date, &app_config)
+            RecordCommand::Sustained { date, session } => {
+                let session = SessionId::resolve(session.as_deref(), date.as_deref())?;
+                audio::recorder::record_exercise("sustained", &session, &app_config)
             }
 
-            RecordCommand::Scale { date } => {
-                let date = util::resolve_date(date.as_deref())?;
-                audio::recorder::record_exercise("scale", &date, &app_config)
+            RecordCommand::Scale { date, session } => {
+                let session = SessionId::resolve(session.as_deref(), date.as_deref())?;
+                audio::recorder::record_exercise("scale", &session, &app_config)
             }
 
-            RecordCommand::Reading { date } => {
-                let date = util::resolve_date(date.as_deref())?;
-                audio::recorder::record_exercise("reading", &date, &app_config)
+            RecordCommand::Reading { date, session } => {
+                let session = SessionId::resolve(session.as_deref(), date.as_deref())?;
+                audio::recorder::record_exercise("reading", &session, &app_config)
             }
 
-            RecordCommand::Session { date } => {
-                let date = util::resolve_date(date.as_deref())?;
-                audio::session::run_guided_session(&date, &app_config)
+            RecordCommand::Session { date, session, new } => {
+                let session = if new {
+                    audio::session::next_session(chrono::Local::now().date_naive())?
+                } else {
+                    SessionId::resolve(session.as_deref(), date.as_deref())?
+                };
+                audio::session::run_guided_session(&session, &app_config)
             }
         },
 
-        Command::Play { target, exercise } => {
-            audio::playback::play(&target, exercise.as_deref())
+        Command::Play { target, exercise, analyzed } => {
+            audio::playback::play(&target, exercise.as_deref(), analyzed)
         }
 
-        Command::Analyze { date, all, version: _version } => {
+        Command::Analyze { date, session, all, version: _version } => {
             if all {
                 let dates = find_recording_dates()?;
                 if dates.is_empty() {
@@ -64,10 +74,8 @@ fn main() -> Result<()> {
                 println!("Analyzed {} session(s).", dates.len());
                 Ok(())
             } else {
-                let date = date.unwrap_or_else(|| {
-                    chrono::Local::now().format("%Y-%m-%d").to_string()
-                });
-                analysis::analyzer::analyze_session(&date, &app_config)?;
+                let session = SessionId::resolve(session.as_deref(), date.as_deref())?;
+                analysis::analyzer::analyze_session(&session.to_string(), &app_config)?;
                 Ok(())
             }
         }
@@ -117,6 +125,9 @@ fn main() -> Result<()> {
                         if !recorded.is_empty() {
                             println!("    recorded: {}", style(recorded.join(", ")).yellow());
                         }
+                        if let Some(source) = &session.source {
+                            println!("    imported: {}", style(source).dim());
+                        }
                     }
                     Err(_) => {
                         println!("  {} (corrupt)", style(date).red());
@@ -128,9 +139,41 @@ fn main() -> Result<()> {
             Ok(())
         }
 
-        Command::Report { last, all } => {
+        Command::Attempts { date, session, exercise, canonical } => {
+            let date = SessionId::resolve(session.as_deref(), date.as_deref())?.to_string();
+            if let (Some(ex), Some(n)) = (exercise, canonical) {
+                report::attempts::mark_canonical(&date, &ex, n)?;
+                println!();
+            }
+            report::attempts::list_attempts(&date)
+        }
+
+        Command::ExportFrames { date, session, exercise, format, attempt, output } => {
+            let date = SessionId::resolve(session.as_deref(), date.as_deref())?.to_string();
+            report::frames::export_frames(
+                &date,
+                &exercise,
+                &format,
+                attempt,
+                output.as_deref(),
+                &app_config,
+            )
+        }
+
+        Command::Plot { date, session, exercise, attempt, output } => {
+            let date = SessionId::resolve(session.as_deref(), date.as_deref())?.to_string();
+            report::spectrogram::plot_recording(
+                &date,
+                &exercise,
+                attempt,
+                output.as_deref(),
+                &app_config,
+            )
+        }
+
+        Command::Report { last, all, embed_audio, adjust_conditions } => {
             if all {
-                report::generate_full_report(&app_config)?;
+                report::generate_full_report(&app_config, embed_audio, adjust_conditions)?;
             } else {
                 let dates = storage::store::list_sessions()?;
                 if dates.is_empty() {
@@ -158,6 +201,8 @@ fn main() -> Result<()> {
                     return Ok(());
                 }
 
+                let events = storage::store::load_events()?;
+
                 let reports = paths::reports_dir();
                 std::fs::create_dir_all(&reports)?;
 
@@ -165,47 +210,89 @@ fn main() -> Result<()> {
                     "report_{}.png",
                     chrono::Local::now().format("%Y-%m-%d")
                 ));
-                report::charts::generate_trend_chart(&sessions, &chart_path)?;
+                report::charts::generate_trend_chart(&sessions, &events, &chart_path, &app_config.profile, adjust_conditions)?;
                 println!("Chart saved to {}", style(chart_path.display()).green());
 
-                let md = report::markdown::generate_report(&sessions, &app_config)?;
+                let mut md = report::markdown::generate_report(&sessions, &events, &app_config)?;
+                md.push_str(&report::recording_plots_markdown(&sessions, &app_config, &reports));
                 let md_path = reports.join(format!(
                     "report_{}.md",
                     chrono::Local::now().format("%Y-%m-%d")
                 ));
                 std::fs::write(&md_path, &md)?;
                 println!("Report saved to {}", style(md_path.display()).green());
+
+                let html_path = report::write_html_report(&sessions, &events, &app_config, &reports, embed_audio)?;
+                println!("HTML report saved to {}", style(html_path.display()).green());
             }
 
             Ok(())
         }
 
-        Command::Compare { baseline, current } => {
-            report::compare::compare_sessions(&baseline, &current)
+        Command::Goals => {
+            if app_config.goals.is_empty() {
+                println!("No goals configured.");
+                println!(
+                    "  Add [[goals]] tables (metric, target, optional direction and deadline) to {}",
+                    style(paths::config_file().display()).cyan()
+                );
+                return Ok(());
+            }
+            let sessions: Vec<storage::session_data::SessionData> = storage::store::list_sessions()?
+                .iter()
+                .filter_map(|d| storage::store::load_session(d).ok())
+                .collect();
+            report::goals::print_status(&app_config.goals, &sessions)
         }
 
-        Command::Explain { date, provider, model, fast, think, deep } => {
-            let date = date.unwrap_or_else(|| {
-                chrono::Local::now().format("%Y-%m-%d").to_string()
-            });
+        Command::Query { expr, filters, valid_only } => report::query::run(&expr, &filters, valid_only),
+
+        Command::Summary { period } => report::summary::run(&period),
+
+        Command::Event { action } => match action {
+            EventCommand::Add { date, kind, note } => {
+                report::events::add_event(date.as_deref(), &kind, &note)
+            }
+            EventCommand::List => report::events::list_events(),
+            EventCommand::Remove { id } => report::events::remove_event(id),
+        },
+
+        Command::Compare { baseline, current, dates, from, to, format, output, around_event, window } => {
+            if let Some(id) = around_event {
+                return report::compare::compare_around_event(id, &window);
+            }
+            let dates = match (baseline, current) {
+                (Some(baseline), Some(current)) => vec![baseline, current],
+                _ if dates.is_empty() && from.is_none() && to.is_none() => anyhow::bail!(
+                    "Give --baseline and --current, --dates, --from/--to, or --around-event"
+                ),
+                _ => report::compare::select_dates(&dates, from.as_deref(), to.as_deref())?,
+            };
+            report::compare::compare_sessions(&dates, &format, output.as_deref(), &app_config.profile)
+        }
+
+        Command::Explain { date, session, provider, model, fast, think, deep } => {
+            let date = SessionId::resolve(session.as_deref(), date.as_deref())?.to_string();
 
             let tier = llm::provider::ModelTier::from_flags(fast, think);
 
             let current = storage::store::load_session(&date)
                 .with_context(|| format!(
-                    "No analyzed session for {date}. Run `voicevo analyze --date {date}` first."
+                    "No analyzed session {date}. Run `voicevo analyze --session {date}` first."
                 ))?;
 
             // Load all prior sessions as historical context
+            let current_id = SessionId::parse(&date)?;
             let all_dates = storage::store::list_sessions()?;
             let history: Vec<storage::session_data::SessionData> = all_dates
                 .iter()
-                .filter(|d| d.as_str() < date.as_str())
+                .filter(|d| SessionId::parse(d).is_ok_and(|id| id < current_id))
                 .filter_map(|d| storage::store::load_session(d).ok())
                 .collect();
 
             // Load the latest trend report if available
             let trend_report = load_latest_report();
+            let events = storage::store::load_events()?;
 
             if deep {
                 println!(
@@ -215,7 +302,7 @@ fn main() -> Result<()> {
                 );
                 println!();
 
-                let report = llm::deep_interpret(&current, &history, tier, trend_report.as_deref())?;
+                let report = llm::deep_interpret(&current, &history, &app_config.profile, &events, tier, trend_report.as_deref())?;
 
                 println!("{}", style("--- Claude ---").blue().bold());
                 println!();
@@ -228,6 +315,12 @@ fn main() -> Result<()> {
                 println!("{}", style("--- Synthesis & Fact-Check ---").yellow().bold());
                 println!();
                 println!("{}", report.synthesis);
+
+                let combined = format!(
+                    "## Claude\n\n{}\n\n## GPT\n\n{}\n\n## Synthesis & Fact-Check\n\n{}\n",
+                    report.claude_response, report.gpt_response, report.synthesis
+                );
+                save_interpretation(&date, "claude+gpt", &tier.to_string(), &combined);
             } else {
                 let provider = llm::provider::Provider::from_str_loose(&provider)?;
                 let resolved_model = model.as_deref()
@@ -246,10 +339,13 @@ fn main() -> Result<()> {
                     Some(resolved_model),
                     &current,
                     &history,
+                    &app_config.profile,
+                    &events,
                     trend_report.as_deref(),
                 )?;
 
                 println!("{response}");
+                save_interpretation(&date, &provider.to_string(), resolved_model, &response);
             }
 
             Ok(())
@@ -262,33 +358,31 @@ fn main() -> Result<()> {
             ExerciseCommand::Scale => audio::scale_exercise::run_scale_exercise(&app_config),
         },
 
-        Command::Discard { exercise, date } => {
-            let date_str = date.unwrap_or_else(|| {
-                chrono::Local::now().format("%Y-%m-%d").to_string()
-            });
-            let date_obj = util::resolve_date(Some(&date_str))?;
+        Command::Discard { exercise, date, session } => {
+            let session = SessionId::resolve(session.as_deref(), date.as_deref())?;
 
-            let path = if let Some(ref ex) = exercise {
-                paths::latest_attempt_path(&date_obj, ex)
+            let take = if let Some(ref ex) = exercise {
+                paths::latest_attempt_path(&session, ex).map(|p| (ex.as_str(), p))
             } else {
-                // No exercise specified: find most recently modified WAV across all exercises
+                // No exercise specified: find most recently modified take across all exercises
                 ["sustained", "scale", "reading"]
-                    .iter()
-                    .filter_map(|ex| paths::latest_attempt_path(&date_obj, ex))
-                    .filter_map(|p| {
+                    .into_iter()
+                    .filter_map(|ex| paths::latest_attempt_path(&session, ex).map(|p| (ex, p)))
+                    .filter_map(|(ex, p)| {
                         std::fs::metadata(&p)
                             .and_then(|m| m.modified())
                             .ok()
-                            .map(|t| (p, t))
+                            .map(|t| (ex, p, t))
                     })
-                    .max_by_key(|(_, t)| *t)
-                    .map(|(p, _)| p)
+                    .max_by_key(|(.., t)| *t)
+                    .map(|(ex, p, _)| (ex, p))
             };
 
-            match path {
-                Some(p) => {
-                    std::fs::remove_file(&p)?;
+            match take {
+                Some((ex, p)) => {
+                    let kept = storage::store::discard_take(&session, ex, &p)?;
                     println!("Discarded {}", style(p.display()).red());
+                    println!("  Kept as {} (listed by `voicevo attempts`)", kept.display());
                     println!();
                     println!(
                         "Re-record with {}, then {}.",
@@ -301,7 +395,7 @@ fn main() -> Result<()> {
                     println!(
                         "No recordings found for {} on {}.",
                         style(target).cyan(),
-                        style(&date_str).cyan()
+                        style(&session).cyan()
                     );
                 }
             }
@@ -317,26 +411,14 @@ fn main() -> Result<()> {
                 );
             }
 
-            // Find the most recent PNG report
-            let mut pngs: Vec<_> = std::fs::read_dir(&reports)?
-                .filter_map(|e| e.ok())
-                .filter(|e| {
-                    e.path()
-                        .extension()
-                        .is_some_and(|ext| ext == "png")
-                })
-                .collect();
-
-            if pngs.is_empty() {
-                anyhow::bail!(
-                    "No chart files found in {}. Run `voicevo report --all` first.",
+            // Prefer the newest HTML report; fall back to the PNG chart
+            let latest = ["html", "png"]
+                .iter()
+                .find_map(|ext| latest_report_file(&reports, ext))
+                .with_context(|| format!(
+                    "No reports found in {}. Run `voicevo report --all` first.",
                     reports.display()
-                );
-            }
-
-            // Sort by name (report_YYYY-MM-DD.png) so last = newest
-            pngs.sort_by_key(|e| e.file_name());
-            let latest = pngs.last().unwrap().path();
+                ))?;
 
             println!("Opening {}", style(latest.display()).green());
             std::process::Command::new("xdg-open")
@@ -355,7 +437,8 @@ fn main() -> Result<()> {
             }
 
             let trend_report = load_latest_report();
-            let md = build_dump_markdown(&sessions, &app_config, trend_report.as_deref());
+            let events = storage::store::load_events()?;
+            let md = build_dump_markdown(&sessions, &events, &app_config, trend_report.as_deref());
 
             copy_to_clipboard(&md)?;
 
@@ -387,6 +470,137 @@ fn main() -> Result<()> {
             Ok(())
         }
 
+        Command::Db { action: DbCommand::Status } => {
+            let status = storage::store::schema_status()?;
+            println!("Database: {}", style(paths::db_path().display()).green());
+            println!("Schema version: {} (latest {})", status.current, status.latest);
+            if status.pending.is_empty() {
+                println!("Up to date.");
+            } else {
+                println!("Pending migrations (applied the next time the database is opened):");
+                for (version, description) in &status.pending {
+                    println!("  {:>3}  {description}", style(version).cyan());
+                }
+            }
+            Ok(())
+        }
+
+        Command::Backup { output } => {
+            let manifest = storage::store::backup(&output)?;
+            println!(
+                "Backed up {} files ({:.1} MB) to {}",
+                manifest.files.len(),
+                manifest.total_size() as f64 / 1e6,
+                style(output.display()).green()
+            );
+            match manifest.schema_version {
+                Some(version) => println!("Database snapshot at schema version {version}."),
+                None => println!("No database found; recordings and config only."),
+            }
+            Ok(())
+        }
+
+        Command::Restore { archive, dry_run, force } => {
+            let plan = storage::store::restore(&archive, dry_run, force)?;
+            println!(
+                "Verified {} files from a backup made {} (voicevo {}).",
+                plan.files.len(),
+                plan.manifest.created,
+                plan.manifest.voicevo_version
+            );
+            if dry_run {
+                for file in &plan.files {
+                    let note = if file.overwrites {
+                        style(format!("{:<9}", "overwrite")).yellow()
+                    } else {
+                        style(format!("{:<9}", "new")).green()
+                    };
+                    println!("  {note}  {:>10} B  {}", file.size, file.target.display());
+                }
+                if plan.overwrites() > 0 && !force {
+                    println!();
+                    println!("{} file(s) already exist; restore needs --force to replace them.", plan.overwrites());
+                }
+                println!("Dry run: nothing written.");
+            } else {
+                println!("Restored into {}", style(paths::data_dir().display()).green());
+                if plan.manifest.schema_version.is_some_and(|v| v < storage::schema::latest_version()) {
+                    println!("The database will be migrated to the latest schema when next opened.");
+                }
+            }
+            Ok(())
+        }
+
+        Command::Compact { dry_run } => {
+            let summary = storage::store::compact(dry_run)?;
+            for (path, reason) in &summary.skipped {
+                println!("  {} {}: {reason}", style("SKIP").yellow(), path.display());
+            }
+            if summary.converted.is_empty() {
+                println!("No WAV recordings to convert.");
+            } else if dry_run {
+                for (wav, _) in &summary.converted {
+                    println!("  {}", wav.display());
+                }
+                println!(
+                    "Would convert {} recording(s) ({:.1} MB of WAV). Dry run: nothing written.",
+                    summary.converted.len(),
+                    summary.bytes_before as f64 / 1e6
+                );
+            } else {
+                println!(
+                    "Converted {} recording(s) to FLAC: {:.1} MB -> {} ({:.0}% saved)",
+                    summary.converted.len(),
+                    summary.bytes_before as f64 / 1e6,
+                    style(format!("{:.1} MB", summary.bytes_after as f64 / 1e6)).green(),
+                    100.0 * (1.0 - summary.bytes_after as f64 / summary.bytes_before.max(1) as f64)
+                );
+            }
+            if app_config.recording.storage == config::StorageFormat::Wav {
+                println!("New recordings are still saved as WAV; set storage = \"flac\" under [recording] in the config to change that.");
+            }
+            Ok(())
+        }
+
+        Command::Export { for_clinician: _, fhir, range, out, deidentify, patient } => {
+            if fhir {
+                report::fhir::export(range.as_deref(), &out, deidentify, patient.as_deref())
+            } else {
+                report::clinician::export(range.as_deref(), &out, deidentify, &app_config)
+            }
+        }
+
+        Command::Import { format, file, exercise, date } => {
+            let format = storage::import::Format::parse(&format)?;
+            let date = date
+                .map(|d| chrono::NaiveDate::parse_from_str(&d, "%Y-%m-%d").context("Invalid --date, expected YYYY-MM-DD"))
+                .transpose()?;
+            let text = std::fs::read_to_string(&file).with_context(|| format!("Failed to read {}", file.display()))?;
+            let file_name = file.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+
+            let mut imported = storage::import::parse(format, &text, &file_name, &exercise, date)?;
+            for warning in &imported.warnings {
+                println!("  {} {warning}", style("WARN").yellow());
+            }
+            if imported.sessions.is_empty() {
+                anyhow::bail!("Nothing to import from {}", file.display());
+            }
+            storage::store::import(&mut imported.sessions)?;
+
+            println!("Imported {} session(s) from {}:", imported.sessions.len(), style(file.display()).green());
+            for session in &imported.sessions {
+                let a = &session.analysis;
+                let exercises: Vec<&str> = [("sustained", a.sustained.is_some()), ("scale", a.scale.is_some()), ("reading", a.reading.is_some())]
+                    .into_iter()
+                    .filter_map(|(name, present)| present.then_some(name))
+                    .collect();
+                println!("  {} {}", style(format!("{:<14}", session.id())).cyan(), exercises.join(", "));
+            }
+            Ok(())
+        }
+
+        Command::Doctor { .. } => unreachable!("handled before the config is loaded"),
+
         Command::Paths => {
             println!("{}", style("voicevo paths").bold());
             println!();
@@ -401,6 +615,33 @@ fn main() -> Result<()> {
     }
 }
 
+/// Newest `report_YYYY-MM-DD.<ext>` in the reports directory.
+fn latest_report_file(reports: &std::path::Path, ext: &str) -> Option<std::path::PathBuf> {
+    let mut files: Vec<_> = std::fs::read_dir(reports)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.extension().is_some_and(|e| e == ext)
+                && p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with("report_"))
+        })
+        .collect();
+
+    // Sort by name (report_YYYY-MM-DD) so last = newest
+    files.sort();
+    files.pop()
+}
+
+/// Keep an interpretation for the HTML report. A storage failure only warns,
+/// since the response has already been shown.
+fn save_interpretation(date: &str, provider: &str, model: &str, content: &str) {
+    if let Err(e) = storage::store::save_interpretation(date, provider, model, content) {
+        eprintln!("{} could not save interpretation: {e:#}", style("Warning:").yellow());
+    }
+}
+
 /// Load the latest markdown trend report, if any exist.
 fn load_latest_report() -> Option<String> {
     let reports = paths::reports_dir();
@@ -436,6 +677,7 @@ fn load_all_sessions() -> Result<Vec<storage::session_data::SessionData>> {
 /// formatted for easy consumption by any LLM.
 fn build_dump_markdown(
     sessions: &[storage::session_data::SessionData],
+    events: &[storage::session_data::ClinicalEvent],
     config: &config::AppConfig,
     trend_report: Option<&str>,
 ) -> String {
@@ -464,11 +706,11 @@ fn build_dump_markdown(
     // Metric reference
     md.push_str("## Metric Reference\n\n");
     md.push_str("### Sustained Vowel (\"AAAH\")\n");
-    md.push_str("- **MPT** (Maximum Phonation Time): healthy 15-25s, <10s = significant dysfunction\n");
-    md.push_str("- **Mean F0**: fundamental frequency. Males 85-180 Hz, females 165-255 Hz\n");
-    md.push_str("- **Jitter**: cycle-to-cycle pitch variation. Normal <1.04%\n");
-    md.push_str("- **Shimmer**: cycle-to-cycle amplitude variation. Normal <3.81%\n");
-    md.push_str("- **HNR**: harmonic-to-noise ratio. Normal >20 dB, <7 dB = severely breathy\n\n");
+    md.push_str("- **MPT** (Maximum Phonation Time): seconds a vowel can be sustained\n");
+    md.push_str("- **Mean F0**: fundamental frequency; the expected range depends on sex and age\n");
+    md.push_str("- **Jitter**: cycle-to-cycle pitch variation\n");
+    md.push_str("- **Shimmer**: cycle-to-cycle amplitude variation\n");
+    md.push_str("- **HNR**: harmonic-to-noise ratio; low values mean a breathy voice\n\n");
     md.push_str("### Chromatic Scale\n");
     md.push_str("- **Pitch floor/ceiling**: 5th-95th percentile of detected F0\n");
     md.push_str("- **Range**: healthy adults 24-36 semitones\n\n");
@@ -476,13 +718,37 @@ fn build_dump_markdown(
     md.push_str("- **Voice breaks**: voicing pauses 50-500ms indicating cord failure\n");
     md.push_str("- **Voiced fraction**: healthy speakers 60-80%\n\n");
 
-    // Clinical thresholds
-    let t = &config.analysis.thresholds;
-    md.push_str("## Clinical Thresholds\n\n");
-    md.push_str(&format!("- Jitter pathological: >{:.2}%\n", t.jitter_pathological));
-    md.push_str(&format!("- Shimmer pathological: >{:.2}%\n", t.shimmer_pathological));
-    md.push_str(&format!("- HNR concerning: <{:.1} dB\n", t.hnr_low));
-    md.push_str(&format!("- HNR normal: >{:.1} dB\n\n", t.hnr_normal));
+    // Normative ranges for the patient's profile
+    let profile = &config.profile;
+    let norm = |m: norms::NormMetric| norms::lookup(m, profile);
+    let flag = |m: norms::NormMetric, value: f32| match norm(m).map(|n| n.standing(value)) {
+        Some(norms::Standing::Borderline | norms::Standing::Atypical) => " ⚠",
+        _ => "",
+    };
+    md.push_str(&format!("## Normative Ranges (healthy adults: {})\n\n", profile.describe()));
+    for (label, unit, metric) in [
+        ("MPT", "s", norms::NormMetric::Mpt),
+        ("Speaking F0", "Hz", norms::NormMetric::SpeakingF0),
+        ("Jitter", "%", norms::NormMetric::Jitter),
+        ("Shimmer", "%", norms::NormMetric::Shimmer),
+        ("HNR", "dB", norms::NormMetric::Hnr),
+        ("S/Z ratio", "", norms::NormMetric::SzRatio),
+    ] {
+        if let Some(n) = norm(metric) {
+            md.push_str(&format!(
+                "- {label}: {} ± {} (mean ± SD)\n",
+                report::stats::format_value(n.mean, unit),
+                report::stats::format_value(n.sd, unit),
+            ));
+        }
+    }
+    md.push_str("\n\u{26a0} marks values more than 1 SD from the mean on the worse side.\n\n");
+
+    if !events.is_empty() {
+        md.push_str("## Clinical Events\n\n");
+        md.push_str(&report::events::markdown_section(events));
+        md.push('\n');
+    }
 
     md.push_str("---\n\n");
 
@@ -490,23 +756,23 @@ fn build_dump_markdown(
     md.push_str("## All Session Data\n\n");
 
     for session in sessions {
-        md.push_str(&format!("### {}\n\n", session.date));
+        md.push_str(&format!("### {}\n\n", session.id()));
 
         if let Some(ref s) = session.analysis.sustained {
             md.push_str("**Sustained Vowel**\n");
-            md.push_str(&format!("- MPT: {:.1}s\n", s.mpt_seconds));
+            md.push_str(&format!("- MPT: {:.1}s{}\n", s.mpt_seconds, flag(norms::NormMetric::Mpt, s.mpt_seconds)));
             md.push_str(&format!("- Mean F0: {:.1} Hz (std: {:.1} Hz)\n", s.mean_f0_hz, s.f0_std_hz));
             md.push_str(&format!("- Jitter: {:.2}%{}\n",
                 s.jitter_local_percent,
-                if s.jitter_local_percent > t.jitter_pathological { " ⚠" } else { "" },
+                flag(norms::NormMetric::Jitter, s.jitter_local_percent),
             ));
             md.push_str(&format!("- Shimmer: {:.2}%{}\n",
                 s.shimmer_local_percent,
-                if s.shimmer_local_percent > t.shimmer_pathological { " ⚠" } else { "" },
+                flag(norms::NormMetric::Shimmer, s.shimmer_local_percent),
             ));
             md.push_str(&format!("- HNR: {:.1} dB{}\n",
                 s.hnr_db,
-                if s.hnr_db < t.hnr_low { " ⚠" } else { "" },
+                flag(norms::NormMetric::Hnr, s.hnr_db),
             ));
             md.push_str("\n");
         }
@@ -579,24 +845,24 @@ fn copy_to_clipboard(text: &str) -> Result<()> {
     );
 }
 
-/// Find all dates that have recording directories.
+/// Find all sessions that have recording directories, oldest first.
 fn find_recording_dates() -> Result<Vec<String>> {
     let dir = paths::recordings_dir();
     if !dir.exists() {
         return Ok(Vec::new());
     }
 
-    let mut dates: Vec<String> = std::fs::read_dir(&dir)?
+    let mut sessions: Vec<SessionId> = std::fs::read_dir(&dir)?
         .filter_map(|entry| {
             let entry = entry.ok()?;
             if entry.file_type().ok()?.is_dir() {
-                Some(entry.file_name().to_string_lossy().to_string())
+                SessionId::parse(&entry.file_name().to_string_lossy()).ok()
             } else {
                 None
             }
         })
         .collect();
 
-    dates.sort();
-    Ok(dates)
+    sessions.sort();
+    Ok(sessions.iter().map(SessionId::to_string).collect())
 }
@@ -0,0 +1,435 @@
+//! Bundled normative data for healthy adult voices.
+//!
+//! Each metric has a mean and standard deviation per sex and age band, so a
+//! measurement can be shown as a z-score and percentile for someone like the
+//! patient instead of against a single cutoff. Values are rounded
+//! consolidations of published adult norms, and every entry names its
+//! source. Metrics without a published norm that fits this app's
+//! measurement (CPPS, scale range) have no entry.
+//!
+//! The jitter, shimmer and HNR norms were measured with Praat, while this
+//! app uses its own algorithms for them. Their z-scores are only
+//! approximate and are marked with "≈".
+
+use crate::config::{ProfileConfig, Sex};
+use crate::report::stats::{self, Direction};
+use crate::storage::session_data::SessionData;
+
+/// Metrics with bundled norms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NormMetric {
+    Mpt,
+    SpeakingF0,
+    Jitter,
+    Shimmer,
+    Hnr,
+    SzRatio,
+}
+
+/// Age bands the data is stratified by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AgeBand {
+    Young,  // 18–39
+    Middle, // 40–64
+    Older,  // 65+
+}
+
+/// (mean, sd) for young, middle and older adults.
+type Row = [(f32, f32); 3];
+
+struct Entry {
+    metric: NormMetric,
+    direction: Direction,
+    source: &'static str,
+    /// The published values come from a different algorithm than ours.
+    approximate: bool,
+    male: Row,
+    female: Row,
+    /// Used when sex is not set in the profile.
+    pooled: Row,
+}
+
+const NORMS: &[Entry] = &[
+    Entry {
+        metric: NormMetric::Mpt,
+        source: "Maslan et al. (2011)",
+        approximate: false,
+        direction: Direction::HigherIsBetter,
+        male: [(25.0, 7.0), (23.0, 7.0), (18.0, 6.0)],
+        female: [(19.5, 5.5), (18.5, 5.5), (14.5, 5.0)],
+        pooled: [(22.0, 7.0), (21.0, 7.0), (16.0, 6.0)],
+    },
+    Entry {
+        metric: NormMetric::SpeakingF0,
+        source: "Goy et al. (2013)",
+        approximate: false,
+        direction: Direction::Neutral,
+        male: [(115.0, 18.0), (112.0, 18.0), (128.0, 20.0)],
+        female: [(205.0, 22.0), (192.0, 22.0), (185.0, 24.0)],
+        pooled: [(160.0, 50.0), (152.0, 45.0), (157.0, 40.0)],
+    },
+    Entry {
+        metric: NormMetric::Jitter,
+        source: "Brockmann et al. (2008)",
+        approximate: true,
+        direction: Direction::LowerIsBetter,
+        male: [(0.40, 0.20), (0.50, 0.25), (0.70, 0.35)],
+        female: [(0.40, 0.20), (0.50, 0.25), (0.70, 0.35)],
+        pooled: [(0.40, 0.20), (0.50, 0.25), (0.70, 0.35)],
+    },
+    Entry {
+        metric: NormMetric::Shimmer,
+        source: "Brockmann et al. (2008)",
+        approximate: true,
+        direction: Direction::LowerIsBetter,
+        male: [(2.6, 1.0), (3.1, 1.2), (3.9, 1.5)],
+        female: [(2.4, 1.0), (2.9, 1.2), (3.7, 1.5)],
+        pooled: [(2.5, 1.0), (3.0, 1.2), (3.8, 1.5)],
+    },
+    Entry {
+        metric: NormMetric::Hnr,
+        source: "Goy et al. (2013)",
+        approximate: true,
+        direction: Direction::HigherIsBetter,
+        male: [(20.0, 3.5), (19.0, 3.5), (17.0, 4.0)],
+        female: [(21.5, 3.5), (20.5, 3.5), (18.0, 4.0)],
+        pooled: [(20.5, 3.5), (19.5, 3.5), (17.5, 4.0)],
+    },
+    Entry {
+        metric: NormMetric::SzRatio,
+        source: "Eckel & Boone (1981)",
+        approximate: false,
+        direction: Direction::LowerIsBetter,
+        male: [(1.0, 0.2), (1.0, 0.2), (1.0, 0.2)],
+        female: [(1.0, 0.2), (1.0, 0.2), (1.0, 0.2)],
+        pooled: [(1.0, 0.2), (1.0, 0.2), (1.0, 0.2)],
+    },
+];
+
+/// Reference distribution for one metric.
+#[derive(Debug, Clone, Copy)]
+pub struct Norm {
+    pub mean: f32,
+    pub sd: f32,
+    pub direction: Direction,
+    /// Citation for the published values.
+    pub source: &'static str,
+    /// Measured with a different algorithm than this app's, so the z-score
+    /// is only a rough guide.
+    pub approximate: bool,
+}
+
+/// Where a value falls clinically, taking the better direction into account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Standing {
+    /// Within 1 SD, or beyond it on the good side.
+    Typical,
+    /// 1–2 SD on the worse side.
+    Borderline,
+    /// More than 2 SD on the worse side.
+    Atypical,
+}
+
+impl Norm {
+    pub fn z(&self, value: f32) -> f32 {
+        (value - self.mean) / self.sd
+    }
+
+    /// Percentile (0–100) of `value` under a normal distribution.
+    pub fn percentile(&self, value: f32) -> f32 {
+        100.0 * normal_cdf(self.z(value))
+    }
+
+    pub fn standing(&self, value: f32) -> Standing {
+        let z = self.z(value);
+        let worse = match self.direction {
+            Direction::HigherIsBetter => -z,
+            Direction::LowerIsBetter => z,
+            Direction::Neutral => z.abs(),
+        };
+        if worse > 2.0 {
+            Standing::Atypical
+        } else if worse > 1.0 {
+            Standing::Borderline
+        } else {
+            Standing::Typical
+        }
+    }
+
+    /// Where the value sits, in words: "typical", "below typical", "well above typical".
+    pub fn position(&self, value: f32) -> &'static str {
+        let z = self.z(value);
+        match (z.abs() > 2.0, z.abs() > 1.0, z > 0.0) {
+            (true, _, true) => "well above typical",
+            (true, _, false) => "well below typical",
+            (false, true, true) => "above typical",
+            (false, true, false) => "below typical",
+            _ => "typical",
+        }
+    }
+
+    /// Signed z-score, e.g. "-1.3", prefixed with "≈" when the norm is approximate.
+    pub fn z_text(&self, value: f32) -> String {
+        let approx = if self.approximate { "≈" } else { "" };
+        format!("{approx}{:+.1}", self.z(value))
+    }
+
+    /// e.g. "z -1.3, 10th pct"
+    pub fn describe(&self, value: f32) -> String {
+        format!("z {}, {}", self.z_text(value), format_percentile(self.percentile(value)))
+    }
+
+    /// The ±1 SD range.
+    pub fn typical_range(&self) -> (f32, f32) {
+        (self.mean - self.sd, self.mean + self.sd)
+    }
+}
+
+/// Norm for `metric` matching the profile. `None` under 18: the bundled
+/// data only covers adults.
+pub fn lookup(metric: NormMetric, profile: &ProfileConfig) -> Option<Norm> {
+    let band = match profile.age {
+        Some(age) if age < 18 => return None,
+        Some(age) if age >= 65 => AgeBand::Older,
+        Some(age) if age >= 40 => AgeBand::Middle,
+        _ => AgeBand::Young,
+    };
+    let entry = NORMS.iter().find(|e| e.metric == metric)?;
+    let row = match profile.sex {
+        Some(Sex::Male) => &entry.male,
+        Some(Sex::Female) => &entry.female,
+        None => &entry.pooled,
+    };
+    let (mut mean, sd) = row[band as usize];
+
+    // Voice type shifts habitual pitch within the sex norm
+    if metric == NormMetric::SpeakingF0 {
+        mean += match profile.voice_type.as_deref() {
+            Some("bass") | Some("contralto") | Some("alto") => -12.0,
+            Some("tenor") | Some("soprano") => 12.0,
+            _ => 0.0,
+        };
+    }
+
+    Some(Norm {
+        mean,
+        sd,
+        direction: entry.direction,
+        source: entry.source,
+        approximate: entry.approximate,
+    })
+}
+
+/// One normed measurement from a session.
+pub struct NormedValue {
+    pub label: &'static str,
+    pub value: f32,
+    pub unit: &'static str,
+    pub metric: NormMetric,
+}
+
+/// The session's measurements that have bundled norms.
+pub fn session_values(session: &SessionData) -> Vec<NormedValue> {
+    let mut out = Vec::new();
+    let mut push = |label, value, unit, metric| out.push(NormedValue { label, value, unit, metric });
+    let a = &session.analysis;
+    if let Some(s) = &a.sustained {
+        push("MPT", s.mpt_seconds, "s", NormMetric::Mpt);
+        push("Jitter", s.jitter_local_percent, "%", NormMetric::Jitter);
+        push("Shimmer", s.shimmer_local_percent, "%", NormMetric::Shimmer);
+        push("HNR", s.hnr_db, "dB", NormMetric::Hnr);
+    }
+    if let Some(r) = &a.reading {
+        push("Speaking F0", r.mean_f0_hz, "Hz", NormMetric::SpeakingF0);
+    }
+    if let Some(sz) = &a.sz {
+        push("S/Z ratio", sz.sz_ratio, "", NormMetric::SzRatio);
+    }
+    out
+}
+
+/// Markdown table of a session's metrics against the profile's norms, or
+/// an empty string when nothing could be compared.
+pub fn markdown_table(session: &SessionData, profile: &ProfileConfig) -> String {
+    let rows: Vec<(NormedValue, Norm)> = session_values(session)
+        .into_iter()
+        .filter_map(|v| lookup(v.metric, profile).map(|n| (v, n)))
+        .collect();
+    if rows.is_empty() {
+        return String::new();
+    }
+
+    let mut md = String::from(
+        "| Metric | Value | Norm (mean ± SD) | z | Percentile | Standing |\n\
+         |--------|-------|------------------|---|------------|----------|\n",
+    );
+    for (v, n) in &rows {
+        let standing = match n.standing(v.value) {
+            Standing::Typical => n.position(v.value).to_string(),
+            _ => format!("{} \u{26a0}", n.position(v.value)),
+        };
+        md.push_str(&format!(
+            "| {} | {} | {} ± {} | {} | {} | {} |\n",
+            v.label,
+            stats::format_value(v.value, v.unit),
+            stats::format_value(n.mean, v.unit),
+            stats::format_value(n.sd, v.unit),
+            n.z_text(v.value),
+            format_percentile(n.percentile(v.value)),
+            standing,
+        ));
+    }
+
+    if rows.iter().any(|(_, n)| n.approximate) {
+        md.push_str("\n≈ Norm measured with Praat; this app's algorithm differs, so the z-score is approximate.\n");
+    }
+    let mut sources: Vec<&str> = Vec::new();
+    for (_, n) in &rows {
+        if !sources.contains(&n.source) {
+            sources.push(n.source);
+        }
+    }
+    md.push_str(&format!("\nNorm sources: {}.\n", sources.join("; ")));
+    md
+}
+
+/// "10th pct", with the tails clamped to "<1st" / ">99th".
+pub fn format_percentile(p: f32) -> String {
+    if p < 1.0 {
+        return "<1st pct".into();
+    }
+    if p > 99.0 {
+        return ">99th pct".into();
+    }
+    let n = p.round() as u32;
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{n}{suffix} pct")
+}
+
+/// Standard normal CDF (Abramowitz & Stegun 7.1.26, error < 1.5e-7).
+pub(crate) fn normal_cdf(z: f32) -> f32 {
+    let x = (z as f64) / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * x.abs());
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-x * x).exp();
+    let erf = if x >= 0.0 { erf } else { -erf };
+    (0.5 * (1.0 + erf)) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(sex: Option<Sex>, age: Option<u32>) -> ProfileConfig {
+        ProfileConfig { sex, age, voice_type: None }
+    }
+
+    fn norm(mean: f32, sd: f32, direction: Direction) -> Norm {
+        Norm { mean, sd, direction, source: "test", approximate: false }
+    }
+
+    #[test]
+    fn stratifies_by_sex_and_age() {
+        let young_man = lookup(NormMetric::SpeakingF0, &profile(Some(Sex::Male), Some(30))).unwrap();
+        let older_woman = lookup(NormMetric::SpeakingF0, &profile(Some(Sex::Female), Some(70))).unwrap();
+        assert_eq!(young_man.mean, 115.0);
+        assert_eq!(older_woman.mean, 185.0);
+
+        let mpt = lookup(NormMetric::Mpt, &profile(None, None)).unwrap();
+        assert_eq!(mpt.mean, 22.0);
+
+        assert!(lookup(NormMetric::Hnr, &profile(Some(Sex::Male), Some(12))).is_none());
+    }
+
+    #[test]
+    fn voice_type_shifts_f0() {
+        let mut p = profile(Some(Sex::Male), Some(50));
+        p.voice_type = Some("bass".into());
+        assert_eq!(lookup(NormMetric::SpeakingF0, &p).unwrap().mean, 100.0);
+    }
+
+    #[test]
+    fn z_and_percentile() {
+        let norm = norm(20.0, 4.0, Direction::HigherIsBetter);
+        assert!((norm.z(16.0) + 1.0).abs() < 1e-6);
+        assert!((norm.percentile(16.0) - 15.87).abs() < 0.05);
+        assert!((norm.percentile(20.0) - 50.0).abs() < 1e-3);
+        assert_eq!(norm.describe(16.0), "z -1.0, 16th pct");
+
+        let approx = Norm { approximate: true, ..norm };
+        assert_eq!(approx.describe(16.0), "z ≈-1.0, 16th pct");
+    }
+
+    #[test]
+    fn every_norm_cites_a_source() {
+        for entry in NORMS {
+            assert!(!entry.source.is_empty(), "{:?} has no source", entry.metric);
+        }
+        let p = profile(None, None);
+        for metric in [NormMetric::Jitter, NormMetric::Shimmer, NormMetric::Hnr] {
+            assert!(lookup(metric, &p).unwrap().approximate);
+        }
+        assert!(!lookup(NormMetric::Mpt, &p).unwrap().approximate);
+    }
+
+    #[test]
+    fn standing_respects_direction() {
+        let hnr = norm(20.0, 3.5, Direction::HigherIsBetter);
+        assert_eq!(hnr.standing(30.0), Standing::Typical);
+        assert_eq!(hnr.standing(15.0), Standing::Borderline);
+        assert_eq!(hnr.standing(8.0), Standing::Atypical);
+        assert_eq!(hnr.position(8.0), "well below typical");
+
+        let jitter = norm(0.4, 0.2, Direction::LowerIsBetter);
+        assert_eq!(jitter.standing(0.1), Standing::Typical);
+        assert_eq!(jitter.standing(1.5), Standing::Atypical);
+
+        let f0 = norm(115.0, 18.0, Direction::Neutral);
+        assert_eq!(f0.standing(80.0), Standing::Borderline);
+        assert_eq!(f0.standing(170.0), Standing::Atypical);
+    }
+
+    #[test]
+    fn markdown_table_lists_normed_metrics() {
+        let mut session: SessionData = serde_json::from_str(
+            r#"{"date":"2026-03-01","recordings":{"sustained":null,"scale":null,"reading":null},
+                "analysis":{"sustained":null,"scale":null,"reading":null}}"#,
+        )
+        .unwrap();
+        assert!(markdown_table(&session, &profile(None, None)).is_empty());
+
+        session.analysis.sz = Some(crate::storage::session_data::SzAnalysis {
+            s_durations: vec![10.0],
+            z_durations: vec![5.0],
+            mean_s: 10.0,
+            mean_z: 5.0,
+            sz_ratio: 2.0,
+            format: None,
+        });
+        let md = markdown_table(&session, &profile(Some(Sex::Male), Some(50)));
+        assert!(md.contains("| S/Z ratio | 2.00 | 1.00 ± 0.20 | +5.0 | >99th pct | well above typical ⚠ |"));
+        assert!(md.contains("Norm sources: Eckel & Boone (1981)."));
+        assert!(!md.contains("approximate"));
+
+        session.analysis.sustained = Some(crate::storage::session_data::SustainedAnalysis::test_default());
+        let md = markdown_table(&session, &profile(Some(Sex::Male), Some(50)));
+        assert!(md.contains("| Jitter | 1.00% | 0.50% ± 0.25% | ≈+2.0 |"));
+        assert!(md.contains("the z-score is approximate"));
+        assert!(md.contains("Norm sources: Maslan et al. (2011); Brockmann et al. (2008); Goy et al. (2013); Eckel & Boone (1981)."));
+    }
+
+    #[test]
+    fn percentile_formatting() {
+        assert_eq!(format_percentile(0.3), "<1st pct");
+        assert_eq!(format_percentile(1.2), "1st pct");
+        assert_eq!(format_percentile(12.0), "12th pct");
+        assert_eq!(format_percentile(22.4), "22nd pct");
+        assert_eq!(format_percentile(99.6), ">99th pct");
+    }
+}
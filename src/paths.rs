@@ -1,6 +1,9 @@
 use std::path::PathBuf;
 use std::sync::OnceLock;
 
+use crate::config::StorageFormat;
+use crate::storage::session_data::SessionId;
+
 /// XDG-compliant directory layout for voicevo.
 ///
 /// On Linux this follows the XDG Base Directory Specification:
@@ -60,76 +63,142 @@ pub fn db_path() -> PathBuf {
     data_dir().join("voicevo.db")
 }
 
-/// List all attempt files for an exercise on a date, sorted ascending.
+/// A session's recordings: <data_dir>/recordings/<session id>, e.g.
+/// `recordings/2026-03-01` for the day's first session and
+/// `recordings/2026-03-01.2` for the second.
+pub fn session_recordings_dir(session: &SessionId) -> PathBuf {
+    recordings_dir().join(session.to_string())
+}
+
+/// File extensions recordings may be stored with. When a take exists in
+/// both (a `voicevo compact` cut short), the first listed wins.
+pub const RECORDING_EXTENSIONS: [&str; 2] = ["flac", "wav"];
+
+/// List all attempt files for an exercise in a session, sorted ascending.
 /// Includes old-format `{exercise}.wav` (as lowest priority) then numbered
-/// files `{exercise}_001.wav`, `{exercise}_002.wav`, etc.
-pub fn list_attempts(date: &chrono::NaiveDate, exercise: &str) -> Vec<PathBuf> {
-    let dir = recordings_dir().join(date.to_string());
-    list_attempts_in(&dir, exercise)
+/// files `{exercise}_001.wav`, `{exercise}_002.flac`, etc., in either format.
+pub fn list_attempts(session: &SessionId, exercise: &str) -> Vec<PathBuf> {
+    list_attempts_in(&session_recordings_dir(session), exercise)
 }
 
-/// Path for the next recording attempt.
+/// Path for the next recording attempt in the given storage format.
 /// Returns `{exercise}_001.wav` if no attempts exist, otherwise increments
 /// the highest existing attempt number.
-pub fn next_attempt_path(date: &chrono::NaiveDate, exercise: &str) -> PathBuf {
-    let dir = recordings_dir().join(date.to_string());
-    next_attempt_in(&dir, exercise)
+pub fn next_attempt_path(session: &SessionId, exercise: &str, storage: StorageFormat) -> PathBuf {
+    next_attempt_in(&session_recordings_dir(session), exercise, storage)
+}
+
+/// Discarded takes of an exercise in a session, sorted ascending (see
+/// `discarded_path`).
+pub fn list_discarded(session: &SessionId, exercise: &str) -> Vec<PathBuf> {
+    list_discarded_in(&session_recordings_dir(session), exercise)
+}
+
+/// Where a take goes when it is discarded: `sustained_002.wav` becomes
+/// `sustained_002.discarded.wav`. Discarded takes are kept for reference
+/// but are not attempts — `list_attempts` skips them — and their numbers
+/// are never reused.
+pub fn discarded_path(path: &std::path::Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let ext = path.extension().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{stem}.{DISCARDED_MARKER}.{ext}"))
+}
+
+/// Attempt number of a discarded take (see `discarded_path`).
+pub fn discarded_attempt_number(path: &std::path::Path, exercise: &str) -> Option<u32> {
+    let stem = path.file_stem()?.to_str()?.strip_suffix(&format!(".{DISCARDED_MARKER}"))?;
+    attempt_number(std::path::Path::new(stem), exercise)
 }
 
+const DISCARDED_MARKER: &str = "discarded";
+
 /// Path to the latest (highest-numbered) attempt, or None if no recordings exist.
-pub fn latest_attempt_path(date: &chrono::NaiveDate, exercise: &str) -> Option<PathBuf> {
-    list_attempts(date, exercise).into_iter().last()
+pub fn latest_attempt_path(session: &SessionId, exercise: &str) -> Option<PathBuf> {
+    list_attempts(session, exercise).into_iter().last()
+}
+
+/// Path of a specific numbered attempt: `{exercise}_NNN.flac` if it was
+/// stored compressed, otherwise `{exercise}_NNN.wav`.
+pub fn attempt_path(session: &SessionId, exercise: &str, attempt: u32) -> PathBuf {
+    let dir = session_recordings_dir(session);
+    RECORDING_EXTENSIONS
+        .iter()
+        .map(|ext| dir.join(format!("{exercise}_{attempt:03}.{ext}")))
+        .find(|p| p.exists())
+        .unwrap_or_else(|| dir.join(format!("{exercise}_{attempt:03}.wav")))
 }
 
-/// Internal: list attempts within a given directory.
-fn list_attempts_in(dir: &std::path::Path, exercise: &str) -> Vec<PathBuf> {
+/// Attempt number of a numbered take, or None for the old-format
+/// `{exercise}.wav` (which predates numbering).
+pub fn attempt_number(path: &std::path::Path, exercise: &str) -> Option<u32> {
+    let prefix = format!("{exercise}_");
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .and_then(|s| s.strip_prefix(&prefix))
+        .and_then(|n| n.parse::<u32>().ok())
+}
+
+/// List attempts within a given directory (see `list_attempts`).
+pub fn list_attempts_in(dir: &std::path::Path, exercise: &str) -> Vec<PathBuf> {
     let mut attempts = Vec::new();
 
-    // Check old format: {exercise}.wav
-    let old_path = dir.join(format!("{exercise}.wav"));
-    if old_path.exists() {
+    // Check old format: {exercise}.wav (or its compacted .flac)
+    if let Some(old_path) = RECORDING_EXTENSIONS
+        .iter()
+        .map(|ext| dir.join(format!("{exercise}.{ext}")))
+        .find(|p| p.exists())
+    {
         attempts.push(old_path);
     }
 
-    // Check numbered format: {exercise}_001.wav, {exercise}_002.wav, etc.
-    if let Ok(entries) = std::fs::read_dir(dir) {
-        let prefix = format!("{exercise}_");
-        let mut numbered: Vec<PathBuf> = entries
-            .filter_map(|e| e.ok())
-            .map(|e| e.path())
-            .filter(|p| {
-                p.extension().is_some_and(|ext| ext == "wav")
-                    && p.file_stem()
-                        .and_then(|s| s.to_str())
-                        .is_some_and(|s| {
-                            s.starts_with(&prefix)
-                                && s[prefix.len()..].parse::<u32>().is_ok()
-                        })
-            })
-            .collect();
-        numbered.sort();
-        attempts.extend(numbered);
-    }
-
+    // Check numbered format: {exercise}_001.wav, {exercise}_002.flac, etc.
+    attempts.extend(numbered_in(dir, exercise, attempt_number));
     attempts
 }
 
-/// Internal: compute the next attempt path within a directory.
-fn next_attempt_in(dir: &std::path::Path, exercise: &str) -> PathBuf {
-    let attempts = list_attempts_in(dir, exercise);
-    let prefix = format!("{exercise}_");
-    let max_num = attempts
-        .iter()
+/// List discarded takes within a given directory (see `list_discarded`).
+pub fn list_discarded_in(dir: &std::path::Path, exercise: &str) -> Vec<PathBuf> {
+    numbered_in(dir, exercise, discarded_attempt_number)
+}
+
+/// Recordings in `dir` that `number` gives an attempt number, sorted by
+/// number, one per number (FLAC preferred when a take is in both formats).
+fn numbered_in(
+    dir: &std::path::Path,
+    exercise: &str,
+    number: fn(&std::path::Path, &str) -> Option<u32>,
+) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut numbered: Vec<(u32, usize, PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
         .filter_map(|p| {
-            p.file_stem()
-                .and_then(|s| s.to_str())
-                .and_then(|s| s.strip_prefix(&prefix))
-                .and_then(|n| n.parse::<u32>().ok())
+            let format_rank = RECORDING_EXTENSIONS
+                .iter()
+                .position(|ext| p.extension().is_some_and(|e| e == *ext))?;
+            let n = number(&p, exercise)?;
+            Some((n, format_rank, p))
         })
-        .max()
-        .unwrap_or(0);
+        .collect();
+    numbered.sort();
+    numbered.dedup_by_key(|(n, ..)| *n);
+    numbered.into_iter().map(|(.., p)| p).collect()
+}
 
-    dir.join(format!("{exercise}_{:03}.wav", max_num + 1))
+/// Internal: compute the next attempt path within a directory. Numbers of
+/// discarded takes count as used.
+fn next_attempt_in(dir: &std::path::Path, exercise: &str, storage: StorageFormat) -> PathBuf {
+    let kept = list_attempts_in(dir, exercise)
+        .into_iter()
+        .filter_map(|p| attempt_number(&p, exercise));
+    let discarded = list_discarded_in(dir, exercise)
+        .into_iter()
+        .filter_map(|p| discarded_attempt_number(&p, exercise));
+    let max_num = kept.chain(discarded).max().unwrap_or(0);
+
+    dir.join(format!("{exercise}_{:03}.{}", max_num + 1, storage.extension()))
 }
 
 /// Path to a session JSON file: <data_dir>/sessions/YYYY-MM-DD.json
@@ -161,6 +230,14 @@ mod tests {
         assert!(path.ends_with("sessions/2026-02-15.json"));
     }
 
+    #[test]
+    fn session_recordings_dir_structure() {
+        let first = SessionId::parse("2026-02-15").unwrap();
+        assert!(session_recordings_dir(&first).ends_with("recordings/2026-02-15"));
+        let second = SessionId::parse("2026-02-15.2").unwrap();
+        assert!(session_recordings_dir(&second).ends_with("recordings/2026-02-15.2"));
+    }
+
     #[test]
     fn config_file_structure() {
         let path = config_file();
@@ -220,7 +297,7 @@ mod tests {
     #[test]
     fn next_attempt_empty_dir() {
         let tmp = TempDir::new().unwrap();
-        let path = next_attempt_in(tmp.path(), "sustained");
+        let path = next_attempt_in(tmp.path(), "sustained", StorageFormat::Wav);
         assert!(path.ends_with("sustained_001.wav"));
     }
 
@@ -229,7 +306,7 @@ mod tests {
         let tmp = TempDir::new().unwrap();
         fs::write(tmp.path().join("sustained_001.wav"), b"fake").unwrap();
         fs::write(tmp.path().join("sustained_002.wav"), b"fake").unwrap();
-        let path = next_attempt_in(tmp.path(), "sustained");
+        let path = next_attempt_in(tmp.path(), "sustained", StorageFormat::Wav);
         assert!(path.ends_with("sustained_003.wav"));
     }
 
@@ -237,11 +314,62 @@ mod tests {
     fn next_attempt_with_old_format_only() {
         let tmp = TempDir::new().unwrap();
         fs::write(tmp.path().join("sustained.wav"), b"fake").unwrap();
-        let path = next_attempt_in(tmp.path(), "sustained");
+        let path = next_attempt_in(tmp.path(), "sustained", StorageFormat::Wav);
         // Old format has no number, so max_num=0, next=001
         assert!(path.ends_with("sustained_001.wav"));
     }
 
+    #[test]
+    fn next_attempt_in_flac_continues_numbering() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("sustained_001.wav"), b"fake").unwrap();
+        fs::write(tmp.path().join("sustained_002.flac"), b"fake").unwrap();
+        let path = next_attempt_in(tmp.path(), "sustained", StorageFormat::Flac);
+        assert!(path.ends_with("sustained_003.flac"));
+    }
+
+    #[test]
+    fn discarded_takes_are_listed_apart_and_not_reused() {
+        let tmp = TempDir::new().unwrap();
+        let take = tmp.path().join("sustained_002.flac");
+        assert_eq!(discarded_path(&take), tmp.path().join("sustained_002.discarded.flac"));
+
+        fs::write(tmp.path().join("sustained_001.wav"), b"fake").unwrap();
+        fs::write(discarded_path(&take), b"fake").unwrap();
+        fs::write(tmp.path().join("scale_003.discarded.wav"), b"fake").unwrap();
+
+        let attempts = list_attempts_in(tmp.path(), "sustained");
+        assert_eq!(attempts, [tmp.path().join("sustained_001.wav")]);
+        let discarded = list_discarded_in(tmp.path(), "sustained");
+        assert_eq!(discarded, [discarded_path(&take)]);
+        assert_eq!(discarded_attempt_number(&discarded[0], "sustained"), Some(2));
+        assert_eq!(attempt_number(&discarded[0], "sustained"), None);
+
+        let next = next_attempt_in(tmp.path(), "sustained", StorageFormat::Wav);
+        assert!(next.ends_with("sustained_003.wav"));
+    }
+
+    #[test]
+    fn list_attempts_mixed_formats() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("sustained.flac"), b"fake").unwrap();
+        fs::write(tmp.path().join("sustained_001.wav"), b"fake").unwrap();
+        fs::write(tmp.path().join("sustained_002.flac"), b"fake").unwrap();
+        // A take present in both formats is listed once, as FLAC
+        fs::write(tmp.path().join("sustained_003.wav"), b"fake").unwrap();
+        fs::write(tmp.path().join("sustained_003.flac"), b"fake").unwrap();
+        fs::write(tmp.path().join("sustained_004.txt"), b"fake").unwrap();
+        let attempts = list_attempts_in(tmp.path(), "sustained");
+        let names: Vec<_> = attempts
+            .iter()
+            .map(|p| p.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            ["sustained.flac", "sustained_001.wav", "sustained_002.flac", "sustained_003.flac"]
+        );
+    }
+
     #[test]
     fn latest_attempt_returns_highest() {
         let tmp = TempDir::new().unwrap();
@@ -253,4 +381,13 @@ mod tests {
             .last();
         assert!(latest.unwrap().ends_with("sustained_002.wav"));
     }
+
+    #[test]
+    fn attempt_number_parsing() {
+        let p = std::path::Path::new("/x/2026-01-01/sustained_012.wav");
+        assert_eq!(attempt_number(p, "sustained"), Some(12));
+        assert_eq!(attempt_number(p, "scale"), None);
+        let old = std::path::Path::new("/x/2026-01-01/sustained.wav");
+        assert_eq!(attempt_number(old, "sustained"), None);
+    }
 }
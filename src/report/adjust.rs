@@ -0,0 +1,370 @@
+use chrono::NaiveDate;
+
+use super::stats::{self, MetricDef};
+use crate::storage::session_data::{RecordingConditions, SessionData};
+
+/// Residual degrees of freedom a fit must keep; covariates beyond that are dropped.
+const MIN_RESIDUAL_DOF: usize = 2;
+
+/// Fewest sessions with conditions before a fit is attempted.
+const MIN_SESSIONS: usize = 4;
+
+/// A recorded condition entered into the regression.
+struct Covariate {
+    label: &'static str,
+    value: fn(&RecordingConditions) -> f64,
+}
+
+/// Covariates in priority order: when there are too few sessions for all
+/// of them, the later ones are left out.
+const COVARIATES: &[Covariate] = &[
+    Covariate { label: "morning sessions", value: morning },
+    Covariate { label: "fatigue (per point)", value: fatigue },
+    Covariate { label: "throat cleared", value: throat_cleared },
+    Covariate { label: "low hydration", value: low_hydration },
+    Covariate { label: "high mucus", value: high_mucus },
+    Covariate { label: "evening sessions", value: evening },
+];
+
+fn morning(c: &RecordingConditions) -> f64 {
+    (c.time_of_day == "morning") as u8 as f64
+}
+
+fn evening(c: &RecordingConditions) -> f64 {
+    (c.time_of_day == "evening") as u8 as f64
+}
+
+fn fatigue(c: &RecordingConditions) -> f64 {
+    c.fatigue_level as f64
+}
+
+fn throat_cleared(c: &RecordingConditions) -> f64 {
+    c.throat_cleared as u8 as f64
+}
+
+fn low_hydration(c: &RecordingConditions) -> f64 {
+    (c.hydration == "low") as u8 as f64
+}
+
+fn high_mucus(c: &RecordingConditions) -> f64 {
+    (c.mucus_level == "high") as u8 as f64
+}
+
+/// Estimated effect of one condition on a metric, holding time and the
+/// other conditions fixed.
+#[derive(Debug, Clone)]
+pub struct ConditionEffect {
+    pub label: &'static str,
+    pub effect: f32,
+    /// Standard error of the effect.
+    pub se: f32,
+}
+
+/// Metric trend after regressing out recorded conditions.
+pub struct AdjustedTrend {
+    pub name: &'static str,
+    pub unit: &'static str,
+    /// Sessions with both the metric and recorded conditions.
+    pub n: usize,
+    /// Time-only least-squares slope on the same sessions, per day.
+    pub raw_slope: f32,
+    /// Time slope with conditions in the model, per day, with 95% CI.
+    pub adjusted_slope: stats::Slope,
+    pub effects: Vec<ConditionEffect>,
+    /// (session index, value adjusted to average conditions).
+    pub adjusted: Vec<(usize, f32)>,
+}
+
+impl AdjustedTrend {
+    /// e.g. "morning sessions: -2.1 dB HNR (±1.2 dB); fatigue (per point): ...",
+    /// with the 95% margin in parentheses.
+    pub fn effects_text(&self) -> String {
+        self.effects
+            .iter()
+            .map(|e| {
+                format!(
+                    "{}: {} {} (±{})",
+                    e.label,
+                    stats::format_signed(e.effect, self.unit),
+                    self.name,
+                    stats::format_value(1.96 * e.se, self.unit)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// Condition-adjusted trends for every metric with enough sessions.
+pub fn compute_adjusted_trends(sessions: &[SessionData]) -> Vec<AdjustedTrend> {
+    stats::metric_defs()
+        .iter()
+        .filter_map(|def| adjust_for_conditions(sessions, def))
+        .collect()
+}
+
+/// Fit `metric ~ time + conditions` by least squares.
+///
+/// Covariates are centered, so the adjusted series is each value moved to
+/// the average conditions of the period rather than to an arbitrary
+/// reference session. Covariates that never vary, or that would leave
+/// fewer than `MIN_RESIDUAL_DOF` residual degrees of freedom or make the
+/// fit singular, are skipped, as are non-finite metric values. Returns
+/// `None` when no condition made it in.
+pub fn adjust_for_conditions(sessions: &[SessionData], def: &MetricDef) -> Option<AdjustedTrend> {
+    let mut rows = Vec::new();
+    let mut first: Option<NaiveDate> = None;
+    for (i, s) in sessions.iter().enumerate() {
+        let (Some(c), Some(y)) = (&s.conditions, (def.extract)(s).filter(|y| y.is_finite())) else {
+            continue;
+        };
+        let Ok(date) = NaiveDate::parse_from_str(&s.date, "%Y-%m-%d") else {
+            continue;
+        };
+        let first = *first.get_or_insert(date);
+        rows.push((i, (date - first).num_days() as f64, c, y as f64));
+    }
+    let n = rows.len();
+    if n < MIN_SESSIONS {
+        return None;
+    }
+
+    let y: Vec<f64> = rows.iter().map(|r| r.3).collect();
+    let mut columns: Vec<Vec<f64>> = vec![vec![1.0; n], centered(rows.iter().map(|r| r.1).collect())];
+    let raw = fit(&columns, &y)?;
+
+    let mut included = Vec::new();
+    for cov in COVARIATES {
+        if n < columns.len() + 1 + MIN_RESIDUAL_DOF {
+            break;
+        }
+        let col = centered(rows.iter().map(|r| (cov.value)(r.2)).collect());
+        if col.iter().all(|v| v.abs() < 1e-12) {
+            continue;
+        }
+        columns.push(col);
+        if fit(&columns, &y).is_some() {
+            included.push(cov);
+        } else {
+            columns.pop();
+        }
+    }
+    if included.is_empty() {
+        return None;
+    }
+
+    let model = fit(&columns, &y)?;
+    let slope_se = model.se[1];
+    let adjusted_slope = stats::Slope {
+        per_day: model.beta[1] as f32,
+        ci_low: (model.beta[1] - 1.96 * slope_se) as f32,
+        ci_high: (model.beta[1] + 1.96 * slope_se) as f32,
+    };
+
+    let effects = included
+        .iter()
+        .enumerate()
+        .map(|(k, cov)| ConditionEffect {
+            label: cov.label,
+            effect: model.beta[k + 2] as f32,
+            se: model.se[k + 2] as f32,
+        })
+        .collect();
+
+    let adjusted = rows
+        .iter()
+        .enumerate()
+        .map(|(r, row)| {
+            let shift: f64 = (2..columns.len()).map(|j| model.beta[j] * columns[j][r]).sum();
+            (row.0, (row.3 - shift) as f32)
+        })
+        .collect();
+
+    Some(AdjustedTrend {
+        name: def.name,
+        unit: def.unit,
+        n,
+        raw_slope: raw.beta[1] as f32,
+        adjusted_slope,
+        effects,
+        adjusted,
+    })
+}
+
+fn centered(mut v: Vec<f64>) -> Vec<f64> {
+    let mean = v.iter().sum::<f64>() / v.len() as f64;
+    v.iter_mut().for_each(|x| *x -= mean);
+    v
+}
+
+struct Fit {
+    beta: Vec<f64>,
+    se: Vec<f64>,
+}
+
+/// Ordinary least squares via the normal equations. `None` if the design
+/// is singular or leaves no residual degrees of freedom.
+fn fit(columns: &[Vec<f64>], y: &[f64]) -> Option<Fit> {
+    let p = columns.len();
+    let n = y.len();
+    if n <= p {
+        return None;
+    }
+
+    let dot = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(x, y)| x * y).sum::<f64>();
+    let xtx: Vec<Vec<f64>> = columns
+        .iter()
+        .map(|a| columns.iter().map(|b| dot(a, b)).collect())
+        .collect();
+    let inv = invert(xtx)?;
+    let xty: Vec<f64> = columns.iter().map(|c| dot(c, y)).collect();
+    let beta: Vec<f64> = inv.iter().map(|row| dot(row, &xty)).collect();
+
+    let rss: f64 = (0..n)
+        .map(|i| {
+            let fitted: f64 = (0..p).map(|j| beta[j] * columns[j][i]).sum();
+            (y[i] - fitted).powi(2)
+        })
+        .sum();
+    let sigma2 = rss / (n - p) as f64;
+    let se = (0..p).map(|j| (sigma2 * inv[j][j]).max(0.0).sqrt()).collect();
+
+    Some(Fit { beta, se })
+}
+
+/// Gauss–Jordan inverse with partial pivoting.
+fn invert(mut a: Vec<Vec<f64>>) -> Option<Vec<Vec<f64>>> {
+    let p = a.len();
+    let scale = a.iter().flatten().fold(0.0f64, |m, v| m.max(v.abs())).max(1.0);
+    let mut inv: Vec<Vec<f64>> = (0..p)
+        .map(|i| (0..p).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+
+    for col in 0..p {
+        let pivot = (col..p).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        let magnitude = a[pivot][col].abs();
+        if !magnitude.is_finite() || magnitude < 1e-9 * scale {
+            return None;
+        }
+        a.swap(col, pivot);
+        inv.swap(col, pivot);
+
+        let d = a[col][col];
+        a[col].iter_mut().for_each(|v| *v /= d);
+        inv[col].iter_mut().for_each(|v| *v /= d);
+
+        for row in 0..p {
+            if row == col {
+                continue;
+            }
+            let f = a[row][col];
+            if f == 0.0 {
+                continue;
+            }
+            for k in 0..p {
+                a[row][k] -= f * a[col][k];
+                inv[row][k] -= f * inv[col][k];
+            }
+        }
+    }
+    Some(inv)
+}
+
+/// Markdown table of adjusted trends and condition effects, shared by the
+/// markdown report and the LLM prompt.
+pub fn markdown_section(trends: &[AdjustedTrend]) -> String {
+    let mut md = String::from(
+        "| Metric | Sessions | Raw slope | Adjusted slope (95% CI) | Condition effects |\n\
+         |--------|----------|-----------|-------------------------|-------------------|\n",
+    );
+    for t in trends {
+        let unit = if t.unit.is_empty() { String::new() } else { format!(" {}", t.unit) };
+        md.push_str(&format!(
+            "| {} | {} | {:+.2}{unit}/wk | {} | {} |\n",
+            t.name,
+            t.n,
+            t.raw_slope * 7.0,
+            stats::format_slope(&t.adjusted_slope, t.unit),
+            t.effects_text()
+        ));
+    }
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::session_data::*;
+
+    fn session(day: u32, hnr: f32, time_of_day: &str, fatigue: u8) -> SessionData {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap() + chrono::Days::new(day as u64);
+        let mut s = SessionData::test_default(&date.format("%Y-%m-%d").to_string());
+        s.analysis.sustained = Some(SustainedAnalysis { hnr_db: hnr, ..SustainedAnalysis::test_default() });
+        s.conditions = Some(RecordingConditions { fatigue_level: fatigue, ..RecordingConditions::test_default(time_of_day) });
+        s
+    }
+
+    fn hnr() -> MetricDef {
+        stats::metric_defs().into_iter().find(|d| d.name == "HNR").unwrap()
+    }
+
+    #[test]
+    fn recovers_morning_effect_and_trend() {
+        // HNR = 10 + 0.1/day − 2 dB in the morning, fatigue constant
+        let sessions: Vec<SessionData> = (0..10)
+            .map(|i| {
+                let morning = i % 3 == 0;
+                let hnr = 10.0 + 0.1 * i as f32 - if morning { 2.0 } else { 0.0 };
+                session(i, hnr, if morning { "morning" } else { "afternoon" }, 3)
+            })
+            .collect();
+
+        let t = adjust_for_conditions(&sessions, &hnr()).unwrap();
+        assert_eq!(t.n, 10);
+        assert!((t.adjusted_slope.per_day - 0.1).abs() < 1e-4);
+        assert_eq!(t.effects.len(), 1, "constant fatigue is skipped");
+        assert_eq!(t.effects[0].label, "morning sessions");
+        assert!((t.effects[0].effect + 2.0).abs() < 1e-4);
+        assert!(t.effects_text().contains("morning sessions: -2.0 dB HNR"));
+
+        // The adjusted series lies on the pure time trend
+        let diffs: Vec<f32> = t.adjusted.windows(2).map(|w| w[1].1 - w[0].1).collect();
+        assert!(diffs.iter().all(|d| (d - 0.1).abs() < 1e-3), "{diffs:?}");
+    }
+
+    #[test]
+    fn confounded_trend_is_corrected() {
+        // Early sessions were all mornings: the raw slope overstates recovery
+        let sessions: Vec<SessionData> = (0..8)
+            .map(|i| {
+                let morning = i < 4 || i == 6;
+                let hnr = 10.0 + 0.05 * i as f32 - if morning { 3.0 } else { 0.0 };
+                session(i, hnr, if morning { "morning" } else { "evening" }, 2)
+            })
+            .collect();
+        let t = adjust_for_conditions(&sessions, &hnr()).unwrap();
+        assert!(t.raw_slope > 0.2);
+        assert!((t.adjusted_slope.per_day - 0.05).abs() < 1e-3);
+    }
+
+    #[test]
+    fn no_fit_without_variation_or_sessions() {
+        let flat: Vec<SessionData> = (0..6).map(|i| session(i, 10.0 + i as f32, "morning", 2)).collect();
+        assert!(adjust_for_conditions(&flat, &hnr()).is_none());
+
+        let few: Vec<SessionData> = (0..3)
+            .map(|i| session(i, 10.0, if i == 0 { "morning" } else { "evening" }, i as u8))
+            .collect();
+        assert!(adjust_for_conditions(&few, &hnr()).is_none());
+    }
+
+    #[test]
+    fn drops_covariates_when_sessions_are_scarce() {
+        // 5 sessions: intercept + time + 1 covariate leaves 2 residual dof
+        let sessions: Vec<SessionData> = (0..5)
+            .map(|i| session(i, 10.0 + i as f32, if i % 2 == 0 { "morning" } else { "evening" }, i as u8 * 2))
+            .collect();
+        let t = adjust_for_conditions(&sessions, &hnr()).unwrap();
+        assert_eq!(t.effects.len(), 1);
+    }
+}
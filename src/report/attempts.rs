@@ -0,0 +1,282 @@
+use anyhow::{Context, Result};
+use console::style;
+
+use crate::paths;
+use crate::storage::session_data::{ExerciseResult, SessionData, SessionId};
+use crate::storage::store;
+
+const EXERCISES: [&str; 3] = ["sustained", "scale", "reading"];
+
+/// List every take of a session with its metrics and reliability.
+///
+/// The take used for the session result is marked with `*`; takes marked
+/// canonical with `--canonical` are labelled as such. Discarded takes are
+/// listed too, and a stored analysis whose recording is gone is flagged.
+pub fn list_attempts(date: &str) -> Result<()> {
+    let session_id = SessionId::parse(date)?;
+    let session = store::load_session(date).with_context(|| {
+        format!("No analyzed session for {date}. Run `voicevo analyze --session {date}` first.")
+    })?;
+    let attempts = store::load_attempts(date)?;
+    let canonical = store::load_canonical_attempts(date)?;
+
+    println!(
+        "{}",
+        style("=== Recording Attempts ===").bold()
+    );
+    println!("  Session: {}", style(date).cyan());
+    println!();
+
+    let mut any = false;
+    for exercise in EXERCISES {
+        let takes = paths::list_attempts(&session_id, exercise);
+        let discarded = paths::list_discarded(&session_id, exercise);
+        let analyzed = attempts
+            .iter()
+            .filter(|a| a.result.exercise() == exercise)
+            .map(|a| a.attempt);
+        let files = take_files(exercise, &takes, &discarded, analyzed);
+        if takes.is_empty() && files.is_empty() {
+            continue;
+        }
+        any = true;
+
+        println!("{}", style(format!("  {}", title(exercise))).bold());
+
+        if takes.first().is_some_and(|p| paths::attempt_number(p, exercise).is_none()) {
+            println!("      {:>3}  (unnumbered {exercise}.wav, session result only)", "-");
+        }
+
+        let used = session_take(&session, exercise);
+        for (n, file) in files {
+            let marker = if used == Some(n) { "*" } else { " " };
+            let mut label = String::new();
+            if canonical.get(exercise) == Some(&n) {
+                label.push_str(&format!(" {}", style("canonical").green()));
+            }
+            match file {
+                TakeFile::Kept => {}
+                TakeFile::Discarded => label.push_str(&format!(" {}", style("discarded").yellow())),
+                TakeFile::Missing => label.push_str(&format!(" {}", style("recording missing").red())),
+            }
+
+            let result = attempts
+                .iter()
+                .find(|a| a.attempt == n && a.result.exercise() == exercise);
+            match result {
+                Some(a) => println!(
+                    "    {marker} {n:03}  {}  [{}]{label}",
+                    summary(&a.result),
+                    quality(&a.result),
+                ),
+                None => println!(
+                    "    {marker} {n:03}  {}{label}",
+                    style("not analyzed").dim()
+                ),
+            }
+        }
+
+        if exercise == "sustained" && !canonical.contains_key(exercise) {
+            if let Some(t) = session.analysis.sustained.as_ref().and_then(|s| s.trials.as_ref()) {
+                println!(
+                    "          session uses best of {} trials ({})",
+                    t.attempts.len(),
+                    t.source
+                );
+            }
+        }
+        println!();
+    }
+
+    if !any {
+        println!("  No recordings found.");
+        return Ok(());
+    }
+
+    println!(
+        "  {} = take used in reports. Choose another with {}.",
+        style("*").bold(),
+        style(format!("voicevo attempts --session {date} --exercise <name> --canonical <N>")).cyan()
+    );
+    Ok(())
+}
+
+/// Mark a take as canonical so reports use it instead of the newest take.
+pub fn mark_canonical(date: &str, exercise: &str, attempt: u32) -> Result<()> {
+    if !EXERCISES.contains(&exercise) {
+        anyhow::bail!("Unknown exercise '{exercise}'. Use sustained, scale, or reading.");
+    }
+
+    let session = SessionId::parse(date)?;
+    let path = paths::attempt_path(&session, exercise, attempt);
+    if !path.exists() {
+        anyhow::bail!("Recording not found: {}", path.display());
+    }
+
+    store::set_canonical_attempt(date, exercise, attempt, &path.to_string_lossy())?;
+
+    println!(
+        "Take {} is now canonical for {} on {}.",
+        style(format!("{attempt:03}")).green(),
+        style(exercise).cyan(),
+        style(date).cyan()
+    );
+    Ok(())
+}
+
+/// Where a numbered take's recording is.
+#[derive(Debug, PartialEq)]
+enum TakeFile {
+    Kept,
+    Discarded,
+    /// Only a stored analysis is left, e.g. the file was deleted by hand.
+    Missing,
+}
+
+/// Every numbered take of an exercise — kept, discarded, or known only from
+/// a stored analysis — in attempt order.
+fn take_files(
+    exercise: &str,
+    takes: &[std::path::PathBuf],
+    discarded: &[std::path::PathBuf],
+    analyzed: impl Iterator<Item = u32>,
+) -> Vec<(u32, TakeFile)> {
+    let mut files = std::collections::BTreeMap::new();
+    for n in analyzed {
+        files.insert(n, TakeFile::Missing);
+    }
+    for n in discarded.iter().filter_map(|p| paths::discarded_attempt_number(p, exercise)) {
+        files.insert(n, TakeFile::Discarded);
+    }
+    for n in takes.iter().filter_map(|p| paths::attempt_number(p, exercise)) {
+        files.insert(n, TakeFile::Kept);
+    }
+    files.into_iter().collect()
+}
+
+/// Attempt number of the take behind the session-level result.
+fn session_take(session: &SessionData, exercise: &str) -> Option<u32> {
+    let path = match exercise {
+        "sustained" => session.recordings.sustained.as_deref(),
+        "scale" => session.recordings.scale.as_deref(),
+        "reading" => session.recordings.reading.as_deref(),
+        _ => None,
+    }?;
+    paths::attempt_number(std::path::Path::new(path), exercise)
+}
+
+fn title(exercise: &str) -> &'static str {
+    match exercise {
+        "sustained" => "Sustained Vowel",
+        "scale" => "Pitch Range (Scale)",
+        _ => "Reading Passage",
+    }
+}
+
+/// One-line metric summary of a take.
+fn summary(result: &ExerciseResult) -> String {
+    match result {
+        ExerciseResult::Sustained(s) => {
+            let mut line = format!(
+                "MPT {:.1}s  F0 {:.0} Hz  jitter {:.2}%  shimmer {:.2}%  HNR {:.1} dB",
+                s.mpt_seconds,
+                s.mean_f0_hz,
+                s.jitter_local_percent,
+                s.shimmer_local_percent,
+                s.hnr_db
+            );
+            if let Some(cpps) = s.cpps_db {
+                line.push_str(&format!("  CPPS {cpps:.1} dB"));
+            }
+            line
+        }
+        ExerciseResult::Scale(s) => format!(
+            "{:.0}-{:.0} Hz  range {:.1} st",
+            s.pitch_floor_hz, s.pitch_ceiling_hz, s.range_semitones
+        ),
+        ExerciseResult::Reading(r) => {
+            let mut line = format!(
+                "F0 {:.0} Hz  breaks {}  voiced {:.0}%",
+                r.mean_f0_hz,
+                r.voice_breaks,
+                r.voiced_fraction * 100.0
+            );
+            if let Some(cpps) = r.cpps_db {
+                line.push_str(&format!("  CPPS {cpps:.1} dB"));
+            }
+            line
+        }
+    }
+}
+
+/// Reliability label of a take ("good", "ok", ...) and its SNR, or "n/a".
+fn quality(result: &ExerciseResult) -> String {
+    let mut parts = Vec::new();
+    if let Some(r) = result.reliability() {
+        parts.push(format!(
+            "{}, pitched {:.0}%",
+            r.analysis_quality,
+            r.pitched_fraction * 100.0
+        ));
+    }
+    if let Some(q) = result.recording_quality() {
+        parts.push(format!("SNR {:.0} dB", q.snr_db));
+        if !q.warnings.is_empty() {
+            parts.push(format!("{} warning(s)", q.warnings.len()));
+        }
+    }
+
+    if parts.is_empty() {
+        "n/a".into()
+    } else {
+        parts.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::session_data::*;
+
+    #[test]
+    fn take_files_flags_discarded_and_missing() {
+        let dir = std::path::Path::new("/data/2026-01-15");
+        let takes = [dir.join("sustained.wav"), dir.join("sustained_003.flac")];
+        let discarded = [dir.join("sustained_002.discarded.wav")];
+
+        let files = take_files("sustained", &takes, &discarded, [1, 2, 3].into_iter());
+        assert_eq!(
+            files,
+            [(1, TakeFile::Missing), (2, TakeFile::Discarded), (3, TakeFile::Kept)]
+        );
+    }
+
+    #[test]
+    fn summary_per_exercise() {
+        let scale = ExerciseResult::Scale(ScaleAnalysis {
+            pitch_floor_hz: 90.0,
+            pitch_ceiling_hz: 300.0,
+            range_hz: 210.0,
+            range_semitones: 20.8,
+            recording_quality: None,
+            segment: None,
+        });
+        assert_eq!(summary(&scale), "90-300 Hz  range 20.8 st");
+        assert_eq!(quality(&scale), "n/a");
+
+        let reading = ExerciseResult::Reading(ReadingAnalysis {
+            mean_f0_hz: 120.0,
+            f0_std_hz: 15.0,
+            f0_range_hz: (90.0, 160.0),
+            voice_breaks: 3,
+            voiced_fraction: 0.55,
+            cpps_db: Some(4.31),
+            detection_quality: None,
+            reliability: Some(ReliabilityInfo::compute([80, 10, 10], 0.9, 0.7, true)),
+            recording_quality: None,
+            segment: None,
+        });
+        assert_eq!(summary(&reading), "F0 120 Hz  breaks 3  voiced 55%  CPPS 4.3 dB");
+        assert_eq!(quality(&reading), "good, pitched 70%");
+    }
+}
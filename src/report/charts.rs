@@ -3,7 +3,13 @@ use std::path::Path;
 use anyhow::{Context, Result};
 use plotters::prelude::*;
 
-use crate::storage::session_data::SessionData;
+use super::adjust::{self, AdjustedTrend};
+use super::events;
+use super::stats;
+use super::summary::PeriodSummary;
+use crate::config::ProfileConfig;
+use crate::norms::{self, Norm, NormMetric};
+use crate::storage::session_data::{ClinicalEvent, SessionData};
 
 /// Chart dimensions
 const WIDTH: u32 = 1200;
@@ -16,12 +22,24 @@ const COLOR_PRIMARY: RGBColor = RGBColor(41, 128, 185); // blue
 const COLOR_SECONDARY: RGBColor = RGBColor(231, 76, 60); // red
 const COLOR_TERTIARY: RGBColor = RGBColor(46, 204, 113); // green
 const COLOR_THRESHOLD: RGBColor = RGBColor(200, 200, 200); // light gray
+const COLOR_ADJUSTED: RGBColor = RGBColor(142, 68, 173); // purple
+const COLOR_EVENT: RGBColor = RGBColor(230, 126, 34); // orange
 
 /// Generate a multi-panel trend report PNG from a list of sessions.
 ///
 /// Each panel shows one metric over time, with dates on the x-axis.
-/// Threshold lines are drawn where clinically relevant.
-pub fn generate_trend_chart(sessions: &[SessionData], output_path: &Path) -> Result<()> {
+/// Threshold lines are drawn where clinically relevant, and clinical
+/// events as vertical markers (labelled in the top panel). With
+/// `adjust_conditions`, each metric also gets a dashed condition-adjusted
+/// series (see `adjust::adjust_for_conditions`) where a fit is possible.
+/// Sessions imported from other tools are drawn with hollow points.
+pub fn generate_trend_chart(
+    sessions: &[SessionData],
+    events: &[ClinicalEvent],
+    output_path: &Path,
+    profile: &ProfileConfig,
+    adjust_conditions: bool,
+) -> Result<()> {
     if let Some(parent) = output_path.parent() {
         std::fs::create_dir_all(parent)?;
     }
@@ -37,6 +55,15 @@ pub fn generate_trend_chart(sessions: &[SessionData], output_path: &Path) -> Res
     ))
     .context("Failed to draw title")?;
 
+    if sessions.iter().any(|s| s.source.is_some()) {
+        root.draw(&Text::new(
+            "Hollow points: imported measurements",
+            (WIDTH as i32 - 300, 25),
+            ("sans-serif", 14).into_font().color(&BLACK),
+        ))
+        .context("Failed to draw legend")?;
+    }
+
     // Split into panels
     let panels_area = root.margin(60, 10, 10, 10);
     let panels = panels_area.split_evenly((PANELS as usize, 1));
@@ -45,35 +72,117 @@ pub fn generate_trend_chart(sessions: &[SessionData], output_path: &Path) -> Res
     let dates: Vec<&str> = sessions.iter().map(|s| s.date.as_str()).collect();
     let x_range = 0..dates.len();
 
+    // Clinical events, as vertical markers at the session on or after each
+    let markers: Vec<(usize, String)> = events::session_positions(&dates, events)
+        .into_iter()
+        .map(|(i, list)| (i, events::label(&list)))
+        .collect();
+
+    let adjusted = if adjust_conditions {
+        adjust::compute_adjusted_trends(sessions)
+    } else {
+        Vec::new()
+    };
+
     // Panel 1: Voice Quality Index (composite)
-    draw_voice_quality(&panels[0], sessions, &dates, x_range.clone())?;
+    draw_voice_quality(&panels[0], sessions, &dates, &markers, x_range.clone())?;
 
     // Panel 2: Pitch Range (floor + ceiling)
-    draw_pitch_range(&panels[1], sessions, &dates, x_range.clone())?;
+    draw_pitch_range(&panels[1], sessions, &dates, &markers, x_range.clone())?;
 
     // Panel 3: HNR
-    draw_hnr(&panels[2], sessions, &dates, x_range.clone())?;
+    draw_hnr(&panels[2], sessions, &adjusted, &dates, &markers, x_range.clone())?;
 
     // Panel 4: Jitter + Shimmer
-    draw_jitter_shimmer(&panels[3], sessions, &dates, x_range.clone())?;
+    draw_jitter_shimmer(&panels[3], sessions, &adjusted, &dates, &markers, x_range.clone())?;
 
     // Panel 5: MPT
-    draw_mpt(&panels[4], sessions, &dates, x_range.clone())?;
+    draw_mpt(&panels[4], sessions, &adjusted, &dates, &markers, x_range.clone())?;
 
     // Panel 6: Voice Breaks
-    draw_voice_breaks(&panels[5], sessions, &dates, x_range.clone())?;
+    draw_voice_breaks(&panels[5], sessions, &adjusted, &dates, &markers, x_range.clone())?;
 
     // Panel 7: CPPS
-    draw_cpps(&panels[6], sessions, &dates, x_range.clone())?;
+    draw_cpps(&panels[6], sessions, &adjusted, &dates, &markers, x_range.clone())?;
 
     // Panel 8: Mean Speaking F0
-    draw_mean_f0(&panels[7], sessions, &dates, x_range)?;
+    let f0_norm = norms::lookup(NormMetric::SpeakingF0, profile);
+    draw_mean_f0(&panels[7], sessions, &adjusted, f0_norm, &dates, &markers, x_range)?;
 
     root.present().context("Failed to write chart PNG")?;
 
     Ok(())
 }
 
+/// Chart the per-period summaries: one panel per recorded metric, with the
+/// period median as a point and the period's range (min to max) as an error bar.
+pub fn generate_summary_chart(summaries: &[PeriodSummary], output_path: &Path) -> Result<()> {
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let defs = stats::metric_defs();
+    let shown: Vec<usize> = (0..defs.len())
+        .filter(|&m| summaries.iter().any(|p| p.metrics[m].is_some()))
+        .collect();
+    let height = PANEL_HEIGHT * shown.len().max(1) as u32 + 80;
+
+    let root = BitMapBackend::new(output_path, (WIDTH, height)).into_drawing_area();
+    root.fill(&WHITE).context("Failed to fill background")?;
+    root.draw(&Text::new(
+        "Voice Recovery — Period Summary",
+        (WIDTH as i32 / 2 - 190, 15),
+        ("sans-serif", 28).into_font().color(&BLACK),
+    ))
+    .context("Failed to draw title")?;
+
+    let labels: Vec<&str> = summaries.iter().map(|p| p.label.as_str()).collect();
+    let panels = root.margin(60, 10, 10, 10).split_evenly((shown.len().max(1), 1));
+    for (panel, &m) in panels.iter().zip(&shown) {
+        let def = &defs[m];
+        let bars: Vec<(usize, f32, f32, f32)> = summaries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, p)| p.metrics[m].map(|s| (i, s.min, s.median, s.max)))
+            .collect();
+        let all_vals: Vec<f32> = bars.iter().flat_map(|&(_, lo, _, hi)| [lo, hi]).collect();
+        let (y_min, y_max) = min_max_with_margin(&all_vals, 0.0, 1.0);
+
+        let caption = if def.unit.is_empty() {
+            def.name.to_string()
+        } else {
+            format!("{} ({})", def.name, def.unit)
+        };
+        // One slot of padding either side so the end bars aren't clipped
+        let mut chart = ChartBuilder::on(panel)
+            .caption(caption, ("sans-serif", 18))
+            .margin(5)
+            .x_label_area_size(30)
+            .y_label_area_size(50)
+            .build_cartesian_2d(0..labels.len() + 1, y_min..y_max)?;
+
+        chart
+            .configure_mesh()
+            .x_labels(labels.len().min(12) + 2)
+            .x_label_formatter(&|x| {
+                x.checked_sub(1)
+                    .and_then(|i| date_labels(&labels).into_iter().find(|(j, _)| *j == i))
+                    .map(|(_, l)| l)
+                    .unwrap_or_default()
+            })
+            .draw()?;
+
+        chart.draw_series(bars.iter().map(|&(i, lo, med, hi)| {
+            ErrorBar::new_vertical(i + 1, lo, med, hi, COLOR_PRIMARY.stroke_width(2), 10)
+        }))?;
+        chart.draw_series(LineSeries::new(bars.iter().map(|&(i, _, med, _)| (i + 1, med)), &COLOR_PRIMARY))?;
+        chart.draw_series(bars.iter().map(|&(i, _, med, _)| Circle::new((i + 1, med), 4, COLOR_PRIMARY.filled())))?;
+    }
+
+    root.present().context("Failed to write chart PNG")?;
+    Ok(())
+}
+
 /// Helper: get x-axis labels, showing every Nth date to avoid crowding.
 fn date_labels(dates: &[&str]) -> Vec<(usize, String)> {
     let step = (dates.len() / 8).max(1);
@@ -93,6 +202,7 @@ fn draw_pitch_range(
     area: &DrawingArea<BitMapBackend, plotters::coord::Shift>,
     sessions: &[SessionData],
     dates: &[&str],
+    markers: &[(usize, String)],
     x_range: std::ops::Range<usize>,
 ) -> Result<()> {
     let floors: Vec<Option<f32>> = sessions
@@ -130,6 +240,8 @@ fn draw_pitch_range(
         })
         .draw()?;
 
+    draw_event_markers(&mut chart, markers, y_min, y_max, false)?;
+
     // Floor line
     let floor_points: Vec<(usize, f32)> = floors
         .iter()
@@ -139,6 +251,7 @@ fn draw_pitch_range(
     chart.draw_series(LineSeries::new(floor_points.iter().copied(), &COLOR_PRIMARY))?
         .label("Floor")
         .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &COLOR_PRIMARY));
+    chart.draw_series(imported_markers(sessions, &floor_points, COLOR_PRIMARY))?;
 
     // Ceiling line
     let ceiling_points: Vec<(usize, f32)> = ceilings
@@ -149,6 +262,7 @@ fn draw_pitch_range(
     chart.draw_series(LineSeries::new(ceiling_points.iter().copied(), &COLOR_SECONDARY))?
         .label("Ceiling")
         .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &COLOR_SECONDARY));
+    chart.draw_series(imported_markers(sessions, &ceiling_points, COLOR_SECONDARY))?;
 
     chart.configure_series_labels().draw()?;
 
@@ -158,14 +272,20 @@ fn draw_pitch_range(
 fn draw_hnr(
     area: &DrawingArea<BitMapBackend, plotters::coord::Shift>,
     sessions: &[SessionData],
+    adjusted: &[AdjustedTrend],
     dates: &[&str],
+    markers: &[(usize, String)],
     x_range: std::ops::Range<usize>,
 ) -> Result<()> {
     let values: Vec<Option<f32>> = sessions
         .iter()
         .map(|s| s.analysis.sustained.as_ref().map(|a| a.hnr_db))
         .collect();
-    let all_vals: Vec<f32> = values.iter().filter_map(|v| *v).collect();
+    let all_vals: Vec<f32> = values
+        .iter()
+        .filter_map(|v| *v)
+        .chain(adjusted_values(adjusted, &["HNR"]))
+        .collect();
     let (y_min, y_max) = min_max_with_margin(&all_vals, 0.0, 30.0);
 
     let mut chart = ChartBuilder::on(area)
@@ -187,6 +307,8 @@ fn draw_hnr(
         })
         .draw()?;
 
+    draw_event_markers(&mut chart, markers, y_min, y_max, false)?;
+
     // Threshold lines
     draw_horizontal_line(&mut chart, 7.0, y_min, y_max, "<7 dB = severely breathy")?;
     draw_horizontal_line(&mut chart, 20.0, y_min, y_max, ">20 dB = healthy voice")?;
@@ -197,7 +319,11 @@ fn draw_hnr(
         .filter_map(|(i, v)| v.map(|f| (i, f)))
         .collect();
     chart.draw_series(LineSeries::new(points.iter().copied(), &COLOR_PRIMARY))?;
-    chart.draw_series(points.iter().map(|&(x, y)| Circle::new((x, y), 4, COLOR_PRIMARY.filled())))?;
+    chart.draw_series(points.iter().map(|&p| session_marker(sessions, p, 4, COLOR_PRIMARY)))?;
+
+    if draw_adjusted(&mut chart, adjusted, "HNR")? {
+        chart.configure_series_labels().draw()?;
+    }
 
     Ok(())
 }
@@ -205,7 +331,9 @@ fn draw_hnr(
 fn draw_jitter_shimmer(
     area: &DrawingArea<BitMapBackend, plotters::coord::Shift>,
     sessions: &[SessionData],
+    adjusted: &[AdjustedTrend],
     dates: &[&str],
+    markers: &[(usize, String)],
     x_range: std::ops::Range<usize>,
 ) -> Result<()> {
     let jitter: Vec<Option<f32>> = sessions
@@ -221,6 +349,8 @@ fn draw_jitter_shimmer(
         .iter()
         .chain(shimmer.iter())
         .filter_map(|v| *v)
+        
+        .chain(adjusted_values(adjusted, &["Jitter", "Shimmer"]))
         .collect();
     let (y_min, y_max) = min_max_with_margin(&all_vals, 0.0, 10.0);
 
@@ -243,6 +373,8 @@ fn draw_jitter_shimmer(
         })
         .draw()?;
 
+    draw_event_markers(&mut chart, markers, y_min, y_max, false)?;
+
     // Threshold lines: values below these are normal (Praat norms)
     draw_horizontal_line(&mut chart, 1.04, y_min, y_max, "Jitter normal limit (1.04%)")?;
     draw_horizontal_line(&mut chart, 3.81, y_min, y_max, "Shimmer normal limit (3.81%)")?;
@@ -256,6 +388,7 @@ fn draw_jitter_shimmer(
         .draw_series(LineSeries::new(j_points.iter().copied(), &COLOR_PRIMARY))?
         .label("Jitter")
         .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &COLOR_PRIMARY));
+    chart.draw_series(imported_markers(sessions, &j_points, COLOR_PRIMARY))?;
 
     let s_points: Vec<(usize, f32)> = shimmer
         .iter()
@@ -266,6 +399,10 @@ fn draw_jitter_shimmer(
         .draw_series(LineSeries::new(s_points.iter().copied(), &COLOR_SECONDARY))?
         .label("Shimmer")
         .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &COLOR_SECONDARY));
+    chart.draw_series(imported_markers(sessions, &s_points, COLOR_SECONDARY))?;
+
+    draw_adjusted(&mut chart, adjusted, "Jitter")?;
+    draw_adjusted(&mut chart, adjusted, "Shimmer")?;
 
     chart.configure_series_labels().draw()?;
 
@@ -275,14 +412,20 @@ fn draw_jitter_shimmer(
 fn draw_mpt(
     area: &DrawingArea<BitMapBackend, plotters::coord::Shift>,
     sessions: &[SessionData],
+    adjusted: &[AdjustedTrend],
     dates: &[&str],
+    markers: &[(usize, String)],
     x_range: std::ops::Range<usize>,
 ) -> Result<()> {
     let values: Vec<Option<f32>> = sessions
         .iter()
         .map(|s| s.analysis.sustained.as_ref().map(|a| a.mpt_seconds))
         .collect();
-    let all_vals: Vec<f32> = values.iter().filter_map(|v| *v).collect();
+    let all_vals: Vec<f32> = values
+        .iter()
+        .filter_map(|v| *v)
+        .chain(adjusted_values(adjusted, &["MPT"]))
+        .collect();
     let (y_min, y_max) = min_max_with_margin(&all_vals, 0.0, 25.0);
 
     let mut chart = ChartBuilder::on(area)
@@ -304,6 +447,8 @@ fn draw_mpt(
         })
         .draw()?;
 
+    draw_event_markers(&mut chart, markers, y_min, y_max, false)?;
+
     // Threshold lines
     draw_horizontal_line(&mut chart, 10.0, y_min, y_max, "<10s = significant dysfunction")?;
     draw_horizontal_line(&mut chart, 15.0, y_min, y_max, ">15s = healthy range")?;
@@ -314,7 +459,11 @@ fn draw_mpt(
         .filter_map(|(i, v)| v.map(|f| (i, f)))
         .collect();
     chart.draw_series(LineSeries::new(points.iter().copied(), &COLOR_TERTIARY))?;
-    chart.draw_series(points.iter().map(|&(x, y)| Circle::new((x, y), 4, COLOR_TERTIARY.filled())))?;
+    chart.draw_series(points.iter().map(|&p| session_marker(sessions, p, 4, COLOR_TERTIARY)))?;
+
+    if draw_adjusted(&mut chart, adjusted, "MPT")? {
+        chart.configure_series_labels().draw()?;
+    }
 
     Ok(())
 }
@@ -322,14 +471,20 @@ fn draw_mpt(
 fn draw_voice_breaks(
     area: &DrawingArea<BitMapBackend, plotters::coord::Shift>,
     sessions: &[SessionData],
+    adjusted: &[AdjustedTrend],
     dates: &[&str],
+    markers: &[(usize, String)],
     x_range: std::ops::Range<usize>,
 ) -> Result<()> {
     let values: Vec<Option<f32>> = sessions
         .iter()
         .map(|s| s.analysis.reading.as_ref().map(|a| a.voice_breaks as f32))
         .collect();
-    let all_vals: Vec<f32> = values.iter().filter_map(|v| *v).collect();
+    let all_vals: Vec<f32> = values
+        .iter()
+        .filter_map(|v| *v)
+        .chain(adjusted_values(adjusted, &["Voice breaks"]))
+        .collect();
     let (y_min, y_max) = min_max_with_margin(&all_vals, 0.0, 10.0);
 
     let mut chart = ChartBuilder::on(area)
@@ -351,6 +506,8 @@ fn draw_voice_breaks(
         })
         .draw()?;
 
+    draw_event_markers(&mut chart, markers, y_min, y_max, false)?;
+
     // Threshold lines: fewer is better, 0 is ideal
     draw_horizontal_line(&mut chart, 0.0, y_min, y_max, "0 = ideal (no breaks)")?;
     draw_horizontal_line(&mut chart, 5.0, y_min, y_max, ">5 = concerning")?;
@@ -361,7 +518,11 @@ fn draw_voice_breaks(
         .filter_map(|(i, v)| v.map(|f| (i, f)))
         .collect();
     chart.draw_series(LineSeries::new(points.iter().copied(), &COLOR_SECONDARY))?;
-    chart.draw_series(points.iter().map(|&(x, y)| Circle::new((x, y), 4, COLOR_SECONDARY.filled())))?;
+    chart.draw_series(points.iter().map(|&p| session_marker(sessions, p, 4, COLOR_SECONDARY)))?;
+
+    if draw_adjusted(&mut chart, adjusted, "Voice breaks")? {
+        chart.configure_series_labels().draw()?;
+    }
 
     Ok(())
 }
@@ -369,7 +530,9 @@ fn draw_voice_breaks(
 fn draw_cpps(
     area: &DrawingArea<BitMapBackend, plotters::coord::Shift>,
     sessions: &[SessionData],
+    adjusted: &[AdjustedTrend],
     dates: &[&str],
+    markers: &[(usize, String)],
     x_range: std::ops::Range<usize>,
 ) -> Result<()> {
     // Collect CPPS from sustained and reading exercises
@@ -386,6 +549,8 @@ fn draw_cpps(
         .iter()
         .chain(reading_cpps.iter())
         .filter_map(|v| *v)
+        
+        .chain(adjusted_values(adjusted, &["CPPS (sustained)", "CPPS (reading)"]))
         .collect();
     let (y_min, y_max) = min_max_with_margin(&all_vals, 0.0, 12.0);
 
@@ -408,6 +573,8 @@ fn draw_cpps(
         })
         .draw()?;
 
+    draw_event_markers(&mut chart, markers, y_min, y_max, false)?;
+
     // Threshold lines
     draw_horizontal_line(&mut chart, 3.0, y_min, y_max, "<3 dB = significant dysphonia")?;
     draw_horizontal_line(&mut chart, 5.0, y_min, y_max, ">5 dB = normal voice")?;
@@ -422,7 +589,7 @@ fn draw_cpps(
             .draw_series(LineSeries::new(s_points.iter().copied(), &COLOR_PRIMARY))?
             .label("Sustained")
             .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &COLOR_PRIMARY));
-        chart.draw_series(s_points.iter().map(|&(x, y)| Circle::new((x, y), 4, COLOR_PRIMARY.filled())))?;
+        chart.draw_series(s_points.iter().map(|&p| session_marker(sessions, p, 4, COLOR_PRIMARY)))?;
     }
 
     let r_points: Vec<(usize, f32)> = reading_cpps
@@ -435,9 +602,12 @@ fn draw_cpps(
             .draw_series(LineSeries::new(r_points.iter().copied(), &COLOR_TERTIARY))?
             .label("Reading")
             .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &COLOR_TERTIARY));
-        chart.draw_series(r_points.iter().map(|&(x, y)| Circle::new((x, y), 4, COLOR_TERTIARY.filled())))?;
+        chart.draw_series(r_points.iter().map(|&p| session_marker(sessions, p, 4, COLOR_TERTIARY)))?;
     }
 
+    draw_adjusted(&mut chart, adjusted, "CPPS (sustained)")?;
+    draw_adjusted(&mut chart, adjusted, "CPPS (reading)")?;
+
     chart.configure_series_labels().draw()?;
 
     Ok(())
@@ -446,14 +616,21 @@ fn draw_cpps(
 fn draw_mean_f0(
     area: &DrawingArea<BitMapBackend, plotters::coord::Shift>,
     sessions: &[SessionData],
+    adjusted: &[AdjustedTrend],
+    norm: Option<Norm>,
     dates: &[&str],
+    markers: &[(usize, String)],
     x_range: std::ops::Range<usize>,
 ) -> Result<()> {
     let values: Vec<Option<f32>> = sessions
         .iter()
         .map(|s| s.analysis.reading.as_ref().map(|a| a.mean_f0_hz))
         .collect();
-    let all_vals: Vec<f32> = values.iter().filter_map(|v| *v).collect();
+    let all_vals: Vec<f32> = values
+        .iter()
+        .filter_map(|v| *v)
+        .chain(adjusted_values(adjusted, &["Speaking F0"]))
+        .collect();
     let (y_min, y_max) = min_max_with_margin(&all_vals, 50.0, 200.0);
 
     let mut chart = ChartBuilder::on(area)
@@ -475,9 +652,14 @@ fn draw_mean_f0(
         })
         .draw()?;
 
-    // Male normal range: 85–180 Hz
-    draw_horizontal_line(&mut chart, 85.0, y_min, y_max, "Male low (85 Hz)")?;
-    draw_horizontal_line(&mut chart, 180.0, y_min, y_max, "Male high (180 Hz)")?;
+    draw_event_markers(&mut chart, markers, y_min, y_max, false)?;
+
+    // Typical range (±1 SD) for the profile's sex and age
+    if let Some(n) = norm {
+        let (low, high) = n.typical_range();
+        draw_horizontal_line(&mut chart, low, y_min, y_max, &format!("Typical low ({low:.0} Hz)"))?;
+        draw_horizontal_line(&mut chart, high, y_min, y_max, &format!("Typical high ({high:.0} Hz)"))?;
+    }
 
     let points: Vec<(usize, f32)> = values
         .iter()
@@ -485,7 +667,11 @@ fn draw_mean_f0(
         .filter_map(|(i, v)| v.map(|f| (i, f)))
         .collect();
     chart.draw_series(LineSeries::new(points.iter().copied(), &COLOR_PRIMARY))?;
-    chart.draw_series(points.iter().map(|&(x, y)| Circle::new((x, y), 4, COLOR_PRIMARY.filled())))?;
+    chart.draw_series(points.iter().map(|&p| session_marker(sessions, p, 4, COLOR_PRIMARY)))?;
+
+    if draw_adjusted(&mut chart, adjusted, "Speaking F0")? {
+        chart.configure_series_labels().draw()?;
+    }
 
     Ok(())
 }
@@ -516,7 +702,90 @@ fn draw_horizontal_line(
     Ok(())
 }
 
+/// Draw a dashed vertical line at each clinical event; with `labels`, name
+/// the events next to the line.
+fn draw_event_markers(
+    chart: &mut ChartContext<BitMapBackend, Cartesian2d<plotters::coord::types::RangedCoordusize, plotters::coord::types::RangedCoordf32>>,
+    markers: &[(usize, String)],
+    y_min: f32,
+    y_max: f32,
+    labels: bool,
+) -> Result<()> {
+    for (x, label) in markers {
+        chart.draw_series(DashedLineSeries::new(
+            vec![(*x, y_min), (*x, y_max)],
+            4,
+            3,
+            COLOR_EVENT.stroke_width(2),
+        ))?;
+        if labels {
+            let y = y_max - (y_max - y_min) * 0.04;
+            chart.draw_series(std::iter::once(Text::new(
+                label.clone(),
+                (*x, y),
+                ("sans-serif", 13).into_font().color(&COLOR_EVENT),
+            )))?;
+        }
+    }
+    Ok(())
+}
+
+/// Adjusted values of the named metrics, so the y-range covers them.
+fn adjusted_values<'a>(adjusted: &'a [AdjustedTrend], names: &'a [&str]) -> impl Iterator<Item = f32> + 'a {
+    adjusted
+        .iter()
+        .filter(|a| names.contains(&a.name))
+        .flat_map(|a| a.adjusted.iter().map(|&(_, v)| v))
+}
+
+/// Draw the condition-adjusted series for `name` as a dashed line.
+/// Returns whether anything was drawn, so the caller knows to show a legend.
+fn draw_adjusted(
+    chart: &mut ChartContext<BitMapBackend, Cartesian2d<plotters::coord::types::RangedCoordusize, plotters::coord::types::RangedCoordf32>>,
+    adjusted: &[AdjustedTrend],
+    name: &str,
+) -> Result<bool> {
+    let Some(trend) = adjusted.iter().find(|a| a.name == name) else {
+        return Ok(false);
+    };
+    chart
+        .draw_series(DashedLineSeries::new(
+            trend.adjusted.iter().copied(),
+            6,
+            4,
+            COLOR_ADJUSTED.stroke_width(2),
+        ))?
+        .label(format!("{name} (condition-adjusted)"))
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], COLOR_ADJUSTED));
+    chart.draw_series(
+        trend
+            .adjusted
+            .iter()
+            .map(|&(x, y)| Circle::new((x, y), 3, COLOR_ADJUSTED.stroke_width(1))),
+    )?;
+    Ok(true)
+}
+
 /// Compute y-axis range with margin, falling back to defaults if no data.
+/// A session's point: filled when voicevo recorded it, hollow when it was
+/// imported (see `SessionData::source`).
+fn session_marker(sessions: &[SessionData], (x, y): (usize, f32), size: i32, color: RGBColor) -> Circle<(usize, f32), i32> {
+    let style = if sessions[x].source.is_some() { color.stroke_width(2) } else { color.filled() };
+    Circle::new((x, y), size, style)
+}
+
+/// Hollow markers on the imported sessions of a line without points.
+fn imported_markers<'a>(
+    sessions: &'a [SessionData],
+    points: &'a [(usize, f32)],
+    color: RGBColor,
+) -> impl Iterator<Item = Circle<(usize, f32), i32>> + 'a {
+    points
+        .iter()
+        .filter(|&&(x, _)| sessions[x].source.is_some())
+        .map(move |&p| session_marker(sessions, p, 4, color))
+}
+
 fn min_max_with_margin(values: &[f32], default_min: f32, default_max: f32) -> (f32, f32) {
     if values.is_empty() {
         return (default_min, default_max);
@@ -539,7 +808,7 @@ fn min_max_with_margin(values: &[f32], default_min: f32, default_max: f32) -> (f
 ///   Voiced frac:   0.2  → 0,    0.8   → 100
 ///
 /// All sub-scores are clamped to [0, 100] then averaged.
-fn compute_voice_quality_index(session: &SessionData) -> Option<f32> {
+pub fn compute_voice_quality_index(session: &SessionData) -> Option<f32> {
     let mut scores = Vec::new();
 
     if let Some(ref s) = session.analysis.sustained {
@@ -575,6 +844,7 @@ fn draw_voice_quality(
     area: &DrawingArea<BitMapBackend, plotters::coord::Shift>,
     sessions: &[SessionData],
     dates: &[&str],
+    markers: &[(usize, String)],
     x_range: std::ops::Range<usize>,
 ) -> Result<()> {
     let values: Vec<Option<f32>> = sessions
@@ -628,6 +898,9 @@ fn draw_voice_quality(
         "Healthy", (1usize, 82.0_f32), label_font,
     )))?;
 
+    // Drawn over the zone shading
+    draw_event_markers(&mut chart, markers, 0.0, 105.0, true)?;
+
     let points: Vec<(usize, f32)> = values
         .iter()
         .enumerate()
@@ -641,7 +914,7 @@ fn draw_voice_quality(
     chart.draw_series(
         points
             .iter()
-            .map(|&(x, y)| Circle::new((x, y), 5, COLOR_PRIMARY.filled())),
+            .map(|&p| session_marker(sessions, p, 5, COLOR_PRIMARY)),
     )?;
 
     Ok(())
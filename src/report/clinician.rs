@@ -0,0 +1,569 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use console::style;
+
+use super::{charts, compare, stats};
+use crate::config::AppConfig;
+use crate::norms;
+use crate::storage::session_data::{
+    ClinicalEvent, RecordingConditions, RecordingQuality, ReliabilityInfo, SessionData, SessionRecordings,
+};
+use crate::storage::store;
+
+/// De-identified bundles move the first exported session to this date, so
+/// intervals (and charts) are preserved but the calendar is not.
+const SHIFTED_BASELINE: NaiveDate = match NaiveDate::from_ymd_opt(2000, 1, 1) {
+    Some(d) => d,
+    None => unreachable!(),
+};
+
+/// Exercises with recordings worth sending, in bundle order.
+const AUDIO_EXERCISES: [&str; 3] = ["sustained", "scale", "reading"];
+
+/// Parse `--range`: "2026-01-01..2026-06-01", inclusive; either end may be
+/// left open ("2026-01-01.." or "..2026-06-01").
+pub fn parse_range(range: &str) -> Result<(Option<NaiveDate>, Option<NaiveDate>)> {
+    let Some((from, to)) = range.split_once("..") else {
+        bail!("Invalid range '{range}' (expected FROM..TO, e.g. 2026-01-01..2026-06-01)");
+    };
+    let date = |s: &str| -> Result<Option<NaiveDate>> {
+        if s.is_empty() {
+            return Ok(None);
+        }
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map(Some)
+            .with_context(|| format!("Invalid date '{s}' in range (expected YYYY-MM-DD)"))
+    };
+    let (from, to) = (date(from.trim())?, date(to.trim())?);
+    if let (Some(f), Some(t)) = (from, to) {
+        if f > t {
+            bail!("Range starts after it ends: {range}");
+        }
+    }
+    Ok((from, to))
+}
+
+/// `voicevo export --for-clinician`: write CSVs per exercise, a summary
+/// markdown with charts and the first and latest recording of each exercise
+/// into `out`.
+pub fn export(range: Option<&str>, out: &Path, deidentify: bool, config: &AppConfig) -> Result<()> {
+    let sessions = sessions_in_range(range)?;
+    let events = store::load_events()?;
+
+    // Audio is picked from the real sessions; everything written from here
+    // on uses the (possibly shifted) copies
+    let audio = select_audio(&sessions);
+    let (sessions, events) = if deidentify {
+        deidentified(&sessions, &events)?
+    } else {
+        (sessions, events)
+    };
+
+    std::fs::create_dir_all(out).with_context(|| format!("Failed to create {}", out.display()))?;
+
+    let mut written = Vec::new();
+    for (name, csv) in exercise_csvs(&sessions) {
+        std::fs::write(out.join(name), csv).with_context(|| format!("Failed to write {name}"))?;
+        written.push(name.to_string());
+    }
+
+    let chart = "trends.png";
+    charts::generate_trend_chart(&sessions, &events, &out.join(chart), &config.profile, false)?;
+
+    let audio_dir = out.join("audio");
+    let mut copied = Vec::new();
+    for (exercise, which, index, source) in &audio {
+        let ext = source.extension().and_then(|e| e.to_str()).unwrap_or("wav");
+        let name = format!("{exercise}_{which}.{ext}");
+        std::fs::create_dir_all(&audio_dir)?;
+        std::fs::copy(source, audio_dir.join(&name))
+            .with_context(|| format!("Failed to copy {}", source.display()))?;
+        copied.push((*exercise, *which, sessions[*index].id(), format!("audio/{name}")));
+    }
+
+    let md = summary_markdown(&sessions, &written, chart, &copied, deidentify, config);
+    std::fs::write(out.join("summary.md"), md).context("Failed to write summary.md")?;
+
+    println!(
+        "Clinician bundle for {} session(s) ({} to {}) written to {}",
+        sessions.len(),
+        sessions[0].id(),
+        sessions[sessions.len() - 1].id(),
+        style(out.display()).green()
+    );
+    println!("  summary.md, {chart}, {}", written.join(", "));
+    if !copied.is_empty() {
+        println!("  {} recording(s) in audio/", copied.len());
+    }
+    if deidentify {
+        println!("  De-identified: dates shifted so the first session is {SHIFTED_BASELINE}; notes left out.");
+    }
+    Ok(())
+}
+
+/// Analyzed sessions within `--range` (see `parse_range`), oldest first.
+/// Fails when there are none.
+pub fn sessions_in_range(range: Option<&str>) -> Result<Vec<SessionData>> {
+    let (from, to) = range.map(parse_range).transpose()?.unwrap_or((None, None));
+    let sessions: Vec<SessionData> = store::list_sessions()?
+        .iter()
+        .filter_map(|id| store::load_session(id).ok())
+        .filter(|s| {
+            let Ok(date) = NaiveDate::parse_from_str(&s.date, "%Y-%m-%d") else {
+                return false;
+            };
+            from.is_none_or(|f| date >= f) && to.is_none_or(|t| date <= t)
+        })
+        .collect();
+    if sessions.is_empty() {
+        bail!("No analyzed sessions in the selected range.");
+    }
+    Ok(sessions)
+}
+
+/// First and latest existing recording of each exercise, as (exercise,
+/// "first"/"latest", session index, path). One recording is listed once.
+fn select_audio(sessions: &[SessionData]) -> Vec<(&'static str, &'static str, usize, PathBuf)> {
+    let mut audio = Vec::new();
+    for exercise in AUDIO_EXERCISES {
+        let existing: Vec<(usize, PathBuf)> = sessions
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| {
+                let path = match exercise {
+                    "sustained" => s.recordings.sustained.as_ref(),
+                    "scale" => s.recordings.scale.as_ref(),
+                    _ => s.recordings.reading.as_ref(),
+                }?;
+                let path = PathBuf::from(path);
+                path.exists().then_some((i, path))
+            })
+            .collect();
+        let (Some(first), Some(latest)) = (existing.first(), existing.last()) else {
+            continue;
+        };
+        audio.push((exercise, "first", first.0, first.1.clone()));
+        if latest.0 != first.0 {
+            audio.push((exercise, "latest", latest.0, latest.1.clone()));
+        }
+    }
+    audio
+}
+
+/// Copies of the sessions and events with dates shifted so the first session
+/// falls on `SHIFTED_BASELINE`, and without free-text notes or file paths.
+pub fn deidentified(sessions: &[SessionData], events: &[ClinicalEvent]) -> Result<(Vec<SessionData>, Vec<ClinicalEvent>)> {
+    let baseline = NaiveDate::parse_from_str(&sessions[0].date, "%Y-%m-%d")?;
+    let shift = |date: &str| -> Result<String> {
+        let d = NaiveDate::parse_from_str(date, "%Y-%m-%d")?;
+        Ok((SHIFTED_BASELINE + (d - baseline)).to_string())
+    };
+
+    let sessions = sessions
+        .iter()
+        .map(|s| {
+            let mut s = s.clone();
+            s.date = shift(&s.date)?;
+            s.recordings = SessionRecordings { sustained: None, scale: None, reading: None };
+            if let Some(ref mut c) = s.conditions {
+                c.notes = None;
+            }
+            Ok(s)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let events = events
+        .iter()
+        .map(|e| {
+            Ok(ClinicalEvent {
+                date: shift(&e.date)?,
+                note: String::new(),
+                ..e.clone()
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok((sessions, events))
+}
+
+/// One tidy CSV per exercise that has data: a row per session, the metrics,
+/// reliability flags, recording quality and recording conditions.
+fn exercise_csvs(sessions: &[SessionData]) -> Vec<(&'static str, String)> {
+    let baseline = NaiveDate::parse_from_str(&sessions[0].date, "%Y-%m-%d").ok();
+    let lead = |s: &SessionData| -> Vec<String> {
+        let day = NaiveDate::parse_from_str(&s.date, "%Y-%m-%d")
+            .ok()
+            .zip(baseline)
+            .map(|(d, b)| (d - b).num_days().to_string())
+            .unwrap_or_default();
+        vec![s.id(), s.date.clone(), day]
+    };
+
+    let mut csvs = Vec::new();
+    let mut table = |name: &'static str, columns: &[&str], rows: Vec<Vec<String>>| {
+        if rows.is_empty() {
+            return;
+        }
+        let mut header = vec!["session", "date", "day"];
+        header.extend_from_slice(columns);
+        header.extend_from_slice(&CONDITION_COLUMNS);
+        let mut csv = csv_line(header.iter().map(|h| h.to_string()));
+        for row in rows {
+            csv.push_str(&csv_line(row));
+        }
+        csvs.push((name, csv));
+    };
+
+    table(
+        "sustained.csv",
+        &[
+            "mpt_s", "mean_f0_hz", "f0_std_hz", "jitter_pct", "shimmer_pct", "hnr_db", "cpps_db",
+            "analysis_quality", "jitter_valid", "shimmer_valid", "hnr_valid", "cpps_valid", "snr_db", "quality_warnings",
+        ],
+        sessions
+            .iter()
+            .filter_map(|s| {
+                let a = s.analysis.sustained.as_ref()?;
+                let mut row = lead(s);
+                row.extend([
+                    num(a.mpt_seconds, 2),
+                    num(a.mean_f0_hz, 1),
+                    num(a.f0_std_hz, 2),
+                    num(a.jitter_local_percent, 3),
+                    num(a.shimmer_local_percent, 3),
+                    num(a.hnr_db, 2),
+                    opt(a.cpps_db, 2),
+                ]);
+                row.extend(reliability_cells(a.reliability.as_ref(), &["jitter", "shimmer", "hnr", "cpps"]));
+                row.extend(quality_cells(a.recording_quality.as_ref()));
+                row.extend(condition_cells(s.conditions.as_ref()));
+                Some(row)
+            })
+            .collect(),
+    );
+
+    table(
+        "scale.csv",
+        &["pitch_floor_hz", "pitch_ceiling_hz", "range_hz", "range_semitones", "snr_db", "quality_warnings"],
+        sessions
+            .iter()
+            .filter_map(|s| {
+                let a = s.analysis.scale.as_ref()?;
+                let mut row = lead(s);
+                row.extend([
+                    num(a.pitch_floor_hz, 1),
+                    num(a.pitch_ceiling_hz, 1),
+                    num(a.range_hz, 1),
+                    num(a.range_semitones, 2),
+                ]);
+                row.extend(quality_cells(a.recording_quality.as_ref()));
+                row.extend(condition_cells(s.conditions.as_ref()));
+                Some(row)
+            })
+            .collect(),
+    );
+
+    table(
+        "reading.csv",
+        &[
+            "mean_f0_hz", "f0_std_hz", "f0_p5_hz", "f0_p95_hz", "voice_breaks", "voiced_fraction", "cpps_db",
+            "analysis_quality", "voice_breaks_valid", "cpps_valid", "snr_db", "quality_warnings",
+        ],
+        sessions
+            .iter()
+            .filter_map(|s| {
+                let a = s.analysis.reading.as_ref()?;
+                let mut row = lead(s);
+                row.extend([
+                    num(a.mean_f0_hz, 1),
+                    num(a.f0_std_hz, 2),
+                    num(a.f0_range_hz.0, 1),
+                    num(a.f0_range_hz.1, 1),
+                    a.voice_breaks.to_string(),
+                    num(a.voiced_fraction, 3),
+                    opt(a.cpps_db, 2),
+                ]);
+                row.extend(reliability_cells(a.reliability.as_ref(), &["voice_breaks", "cpps"]));
+                row.extend(quality_cells(a.recording_quality.as_ref()));
+                row.extend(condition_cells(s.conditions.as_ref()));
+                Some(row)
+            })
+            .collect(),
+    );
+
+    table(
+        "sz.csv",
+        &["trials", "mean_s", "mean_z", "sz_ratio"],
+        sessions
+            .iter()
+            .filter_map(|s| {
+                let a = s.analysis.sz.as_ref()?;
+                let mut row = lead(s);
+                row.extend([a.s_durations.len().to_string(), num(a.mean_s, 2), num(a.mean_z, 2), num(a.sz_ratio, 2)]);
+                row.extend(condition_cells(s.conditions.as_ref()));
+                Some(row)
+            })
+            .collect(),
+    );
+
+    table(
+        "fatigue.csv",
+        &["trials", "first_mpt_s", "last_mpt_s", "mpt_slope", "cpps_slope"],
+        sessions
+            .iter()
+            .filter_map(|s| {
+                let a = s.analysis.fatigue.as_ref()?;
+                let mut row = lead(s);
+                row.extend([
+                    a.mpt_per_trial.len().to_string(),
+                    a.mpt_per_trial.first().map(|&v| num(v, 2)).unwrap_or_default(),
+                    a.mpt_per_trial.last().map(|&v| num(v, 2)).unwrap_or_default(),
+                    num(a.mpt_slope, 3),
+                    num(a.cpps_slope, 3),
+                ]);
+                row.extend(condition_cells(s.conditions.as_ref()));
+                Some(row)
+            })
+            .collect(),
+    );
+
+    csvs
+}
+
+const CONDITION_COLUMNS: [&str; 6] = ["time_of_day", "fatigue", "mucus", "hydration", "throat_cleared", "notes"];
+
+fn condition_cells(c: Option<&RecordingConditions>) -> Vec<String> {
+    match c {
+        Some(c) => vec![
+            c.time_of_day.clone(),
+            c.fatigue_level.to_string(),
+            c.mucus_level.clone(),
+            c.hydration.clone(),
+            c.throat_cleared.to_string(),
+            c.notes.clone().unwrap_or_default(),
+        ],
+        None => vec![String::new(); CONDITION_COLUMNS.len()],
+    }
+}
+
+/// Overall quality plus the validity flag of each named metric; blank for
+/// analyses that predate reliability info.
+fn reliability_cells(r: Option<&ReliabilityInfo>, metrics: &[&str]) -> Vec<String> {
+    let Some(r) = r else {
+        return vec![String::new(); metrics.len() + 1];
+    };
+    let v = &r.metrics_validity;
+    let mut cells = vec![r.analysis_quality.clone()];
+    cells.extend(metrics.iter().map(|&m| match m {
+        "jitter" => v.jitter.to_string(),
+        "shimmer" => v.shimmer.to_string(),
+        "hnr" => v.hnr.to_string(),
+        "cpps" => v.cpps.to_string(),
+        // "valid", "trend_only" or "unavailable"
+        _ => v.voice_breaks.clone(),
+    }));
+    cells
+}
+
+fn quality_cells(q: Option<&RecordingQuality>) -> [String; 2] {
+    match q {
+        Some(q) => [num(q.snr_db, 1), q.warnings.join("; ")],
+        None => [String::new(), String::new()],
+    }
+}
+
+fn num(v: f32, decimals: usize) -> String {
+    format!("{v:.decimals$}")
+}
+
+fn opt(v: Option<f32>, decimals: usize) -> String {
+    v.map(|v| num(v, decimals)).unwrap_or_default()
+}
+
+/// One CSV line, quoting fields that contain separators or quotes.
+fn csv_line(cells: impl IntoIterator<Item = String>) -> String {
+    let cells: Vec<String> = cells
+        .into_iter()
+        .map(|c| {
+            if c.contains([',', '"', '\n']) {
+                format!("\"{}\"", c.replace('"', "\"\""))
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("{}\n", cells.join(","))
+}
+
+/// The bundle's cover document: what's in it, the trend chart, first vs
+/// latest session, norms and trend statistics. Plain markdown, so it
+/// converts to PDF with pandoc or any markdown viewer.
+fn summary_markdown(
+    sessions: &[SessionData],
+    csvs: &[String],
+    chart: &str,
+    audio: &[(&str, &str, String, String)],
+    deidentify: bool,
+    config: &AppConfig,
+) -> String {
+    let first = &sessions[0];
+    let latest = &sessions[sessions.len() - 1];
+
+    let mut md = String::from("# Voice Recovery — Clinician Summary\n\n");
+    md.push_str(&format!(
+        "Sessions: {} ({} to {})  \n",
+        sessions.len(),
+        first.id(),
+        latest.id()
+    ));
+    md.push_str(&format!("Norms for: {}  \n", config.profile.describe()));
+    if deidentify {
+        md.push_str(&format!(
+            "De-identified: dates are shifted so the first session falls on {SHIFTED_BASELINE} \
+             (intervals preserved); free-text notes are left out.\n\n"
+        ));
+    } else {
+        md.push_str(&format!("Exported: {}\n\n", chrono::Local::now().format("%Y-%m-%d")));
+    }
+
+    md.push_str("## Contents\n\n");
+    for csv in csvs {
+        md.push_str(&format!(
+            "- `{csv}`: one row per session with metrics, reliability flags and recording conditions\n"
+        ));
+    }
+    if !audio.is_empty() {
+        md.push_str("- `audio/`: first and latest recording of each exercise\n");
+    }
+    md.push_str("\nThe `day` column counts days since the first session in the bundle.\n\n");
+
+    md.push_str(&format!("## Trends\n\n![Trend chart]({chart})\n\n"));
+
+    md.push_str("## First vs Latest Session\n\n");
+    if sessions.len() > 1 {
+        md.push_str(&compare::markdown_table(&[first.clone(), latest.clone()], &config.profile));
+    } else {
+        md.push_str(&compare::markdown_table(std::slice::from_ref(first), &config.profile));
+    }
+    md.push('\n');
+
+    let norms_table = norms::markdown_table(latest, &config.profile);
+    if !norms_table.is_empty() {
+        md.push_str(&format!("## Normative Comparison ({})\n\n", latest.id()));
+        md.push_str(&norms_table);
+        md.push('\n');
+    }
+
+    let trends = stats::compute_trends(sessions);
+    if !trends.is_empty() {
+        md.push_str("## Trend Statistics\n\n");
+        md.push_str(&stats::markdown_section(&trends));
+        md.push('\n');
+    }
+
+    if !audio.is_empty() {
+        md.push_str("## Recordings\n\n| Exercise | Recording | Session | File |\n|----------|-----------|---------|------|\n");
+        for (exercise, which, session, file) in audio {
+            md.push_str(&format!("| {exercise} | {which} | {session} | [{file}]({file}) |\n"));
+        }
+        md.push('\n');
+    }
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::session_data::*;
+
+    fn session(date: &str, mpt: f32) -> SessionData {
+        let mut s = SessionData::test_default(date);
+        s.recordings.sustained = Some(format!("/data/recordings/{date}/sustained_001.wav"));
+        s.analysis.sustained = Some(SustainedAnalysis {
+            mpt_seconds: mpt,
+            cpps_db: Some(4.2),
+            reliability: Some(ReliabilityInfo::compute([10, 80, 10], 0.8, 0.6, true)),
+            ..SustainedAnalysis::test_default()
+        });
+        s.conditions = Some(RecordingConditions {
+            notes: Some("after clinic, felt \"tight\"".into()),
+            ..RecordingConditions::test_default("morning")
+        });
+        s
+    }
+
+    #[test]
+    fn parse_ranges() {
+        let d = |s| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        assert_eq!(
+            parse_range("2026-01-01..2026-06-01").unwrap(),
+            (Some(d("2026-01-01")), Some(d("2026-06-01")))
+        );
+        assert_eq!(parse_range("2026-01-01..").unwrap(), (Some(d("2026-01-01")), None));
+        assert_eq!(parse_range("..2026-06-01").unwrap(), (None, Some(d("2026-06-01"))));
+        assert!(parse_range("2026-01-01").is_err());
+        assert!(parse_range("2026-06-01..2026-01-01").is_err());
+        assert!(parse_range("2026-13-01..").is_err());
+    }
+
+    #[test]
+    fn sustained_csv_has_flags_and_conditions() {
+        let csvs = exercise_csvs(&[session("2026-01-15", 6.5), session("2026-01-25", 8.0)]);
+        assert_eq!(csvs.len(), 1);
+        let (name, csv) = &csvs[0];
+        assert_eq!(*name, "sustained.csv");
+
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("session,date,day,mpt_s,"));
+        assert!(lines[0].ends_with("jitter_valid,shimmer_valid,hnr_valid,cpps_valid,snr_db,quality_warnings,time_of_day,fatigue,mucus,hydration,throat_cleared,notes"));
+        // Tier 2 dominant: relaxed detection, jitter still valid
+        assert_eq!(
+            lines[2],
+            "2026-01-25,2026-01-25,10,8.00,120.0,5.00,1.000,4.000,12.00,4.20,ok,true,true,true,true,,,morning,3,low,normal,false,\"after clinic, felt \"\"tight\"\"\""
+        );
+    }
+
+    #[test]
+    fn deidentified_dates_keep_intervals() {
+        let sessions = [session("2026-01-15", 6.5), session("2026-03-01", 8.0)];
+        let events = [ClinicalEvent {
+            id: 1,
+            date: "2026-02-01".into(),
+            kind: "injection".into(),
+            note: "Dr. Smith, St. Mary's".into(),
+        }];
+        let (sessions, events) = deidentified(&sessions, &events).unwrap();
+
+        assert_eq!(sessions[0].date, "2000-01-01");
+        assert_eq!(sessions[1].date, "2000-02-15");
+        assert!(sessions[0].recordings.sustained.is_none());
+        assert!(sessions[0].conditions.as_ref().unwrap().notes.is_none());
+        assert_eq!(events[0].date, "2000-01-18");
+        assert_eq!(events[0].note, "");
+        assert_eq!(events[0].kind, "injection");
+
+        let (_, csv) = &exercise_csvs(&sessions)[0];
+        assert!(!csv.contains("2026"));
+        assert!(csv.lines().nth(2).unwrap().starts_with("2000-02-15,2000-02-15,45,"));
+    }
+
+    #[test]
+    fn audio_is_first_and_latest_existing_recording() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let wav = |name: &str| {
+            let path = tmp.path().join(name);
+            std::fs::write(&path, b"RIFF").unwrap();
+            path.to_string_lossy().into_owned()
+        };
+
+        let mut sessions = vec![session("2026-01-10", 5.0), session("2026-01-15", 6.0), session("2026-01-20", 7.0)];
+        // The first session's file is gone, so the second is the earliest
+        sessions[1].recordings.sustained = Some(wav("a.wav"));
+        sessions[2].recordings.sustained = Some(wav("b.wav"));
+        sessions[2].recordings.reading = Some(wav("c.wav"));
+
+        let audio: Vec<(&str, &str, usize)> = select_audio(&sessions).into_iter().map(|(e, w, i, _)| (e, w, i)).collect();
+        assert_eq!(audio, vec![("sustained", "first", 1), ("sustained", "latest", 2), ("reading", "first", 2)]);
+    }
+}
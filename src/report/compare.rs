@@ -1,13 +1,173 @@
-use anyhow::Result;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
 use console::style;
 
-use crate::storage::session_data::SessionData;
+use super::stats::{self, Direction, MannWhitney};
+use crate::config::ProfileConfig;
+use crate::norms::{self, NormMetric, Norm, Standing};
+use crate::storage::session_data::{SessionData, SessionId};
 use crate::storage::store;
 
-/// Compare two sessions side by side and print the results.
-pub fn compare_sessions(baseline_date: &str, current_date: &str) -> Result<()> {
-    let baseline = store::load_session(baseline_date)?;
-    let current = store::load_session(current_date)?;
+/// p-value below which a before/after difference is marked.
+const SIGNIFICANCE: f32 = 0.05;
+
+/// Widest a session column gets in the terminal table.
+const COLUMN_WIDTH: usize = 19;
+
+/// One row of the session comparison table.
+struct Row {
+    group: &'static str,
+    label: &'static str,
+    unit: &'static str,
+    /// Controls the delta colour: green for improvement, red for regression.
+    higher_is_better: bool,
+    /// Decimal places for values and deltas.
+    decimals: usize,
+    norm: Option<NormMetric>,
+    extract: fn(&SessionData) -> Option<f32>,
+}
+
+fn rows() -> Vec<Row> {
+    let row = |group, label, unit, higher_is_better, norm, extract| Row {
+        group,
+        label,
+        unit,
+        higher_is_better,
+        decimals: 1,
+        norm,
+        extract,
+    };
+    vec![
+        row("Sustained Vowel", "MPT", "s", true, Some(NormMetric::Mpt), |s| {
+            s.analysis.sustained.as_ref().map(|a| a.mpt_seconds)
+        }),
+        row("Sustained Vowel", "Mean F0", "Hz", true, None, |s| {
+            s.analysis.sustained.as_ref().map(|a| a.mean_f0_hz)
+        }),
+        row("Sustained Vowel", "F0 std", "Hz", false, None, |s| {
+            s.analysis.sustained.as_ref().map(|a| a.f0_std_hz)
+        }),
+        row("Sustained Vowel", "Jitter", "%", false, Some(NormMetric::Jitter), |s| {
+            s.analysis.sustained.as_ref().map(|a| a.jitter_local_percent)
+        }),
+        row("Sustained Vowel", "Shimmer", "%", false, Some(NormMetric::Shimmer), |s| {
+            s.analysis.sustained.as_ref().map(|a| a.shimmer_local_percent)
+        }),
+        row("Sustained Vowel", "HNR", "dB", true, Some(NormMetric::Hnr), |s| {
+            s.analysis.sustained.as_ref().map(|a| a.hnr_db)
+        }),
+        row("Pitch Range (Scale)", "Floor", "Hz", false, None, |s| {
+            s.analysis.scale.as_ref().map(|a| a.pitch_floor_hz)
+        }),
+        row("Pitch Range (Scale)", "Ceiling", "Hz", true, None, |s| {
+            s.analysis.scale.as_ref().map(|a| a.pitch_ceiling_hz)
+        }),
+        row("Pitch Range (Scale)", "Range", "st", true, None, |s| {
+            s.analysis.scale.as_ref().map(|a| a.range_semitones)
+        }),
+        row("Reading Passage", "Mean F0", "Hz", true, Some(NormMetric::SpeakingF0), |s| {
+            s.analysis.reading.as_ref().map(|a| a.mean_f0_hz)
+        }),
+        row("Reading Passage", "F0 std", "Hz", true, None, |s| {
+            s.analysis.reading.as_ref().map(|a| a.f0_std_hz)
+        }),
+        Row {
+            decimals: 0,
+            ..row("Reading Passage", "Breaks", "", false, None, |s| {
+                s.analysis.reading.as_ref().map(|a| a.voice_breaks as f32)
+            })
+        },
+        row("Reading Passage", "Voiced", "%", true, None, |s| {
+            s.analysis.reading.as_ref().map(|a| a.voiced_fraction * 100.0)
+        }),
+    ]
+}
+
+/// Compare sessions side by side: one column per session, with deltas
+/// against the first.
+///
+/// `format` is "table" (coloured terminal output), "markdown" or "csv";
+/// markdown and CSV go to `output` when given, otherwise to stdout.
+/// Metrics with bundled norms also show where the last session sits
+/// against the profile's sex- and age-matched norms.
+pub fn compare_sessions(
+    dates: &[String],
+    format: &str,
+    output: Option<&Path>,
+    profile: &ProfileConfig,
+) -> Result<()> {
+    if dates.len() < 2 {
+        bail!("Need at least two sessions to compare (got {}).", dates.len());
+    }
+    let sessions: Vec<SessionData> = dates
+        .iter()
+        .map(|d| {
+            store::load_session(d).with_context(|| {
+                format!("No analyzed session for {d}. Run `voicevo analyze --date {d}` first.")
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    let text = match format {
+        "table" => {
+            print_table(&sessions, profile);
+            return Ok(());
+        }
+        "markdown" => markdown_table(&sessions, profile),
+        "csv" => csv_table(&sessions),
+        other => bail!("Unknown format '{other}'. Use table, markdown, or csv."),
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, &text)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!("Comparison saved to {}", style(path.display()).green());
+        }
+        None => print!("{text}"),
+    }
+    Ok(())
+}
+
+/// Resolve which sessions to compare: explicit dates (the keywords "first"
+/// and "latest" pick the first and newest session), or every session in
+/// an inclusive date range.
+pub fn select_dates(dates: &[String], from: Option<&str>, to: Option<&str>) -> Result<Vec<String>> {
+    let all = store::list_sessions()?;
+    if all.is_empty() {
+        bail!("No analyzed sessions found.");
+    }
+
+    if !dates.is_empty() {
+        return dates
+            .iter()
+            .map(|d| match d.as_str() {
+                "first" => Ok(all[0].clone()),
+                "latest" => Ok(all[all.len() - 1].clone()),
+                _ => {
+                    SessionId::parse(d)
+                        .with_context(|| format!("Invalid session '{d}' (expected YYYY-MM-DD[.N], first or latest)"))?;
+                    Ok(d.clone())
+                }
+            })
+            .collect();
+    }
+
+    let selected: Vec<String> = all
+        .into_iter()
+        .filter(|id| {
+            // A range covers every session of its end days, so compare dates only
+            let d = id.split('.').next().unwrap_or(id);
+            from.is_none_or(|f| d >= f) && to.is_none_or(|t| d <= t)
+        })
+        .collect();
+    Ok(selected)
+}
+
+fn print_table(sessions: &[SessionData], profile: &ProfileConfig) {
+    let norm = |m: Option<NormMetric>| m.and_then(|m| norms::lookup(m, profile));
 
     println!(
         "{}",
@@ -15,137 +175,479 @@ pub fn compare_sessions(baseline_date: &str, current_date: &str) -> Result<()> {
     );
     println!();
     println!(
-        "  Baseline: {}    Current: {}",
-        style(baseline_date).cyan(),
-        style(current_date).cyan()
+        "  {} sessions, deltas against {}    Norms: {}",
+        sessions.len(),
+        style(sessions[0].id()).cyan(),
+        style(profile.describe()).dim()
     );
     println!();
 
-    // Sustained vowel comparison
-    if let (Some(ref b), Some(ref c)) =
-        (&baseline.analysis.sustained, &current.analysis.sustained)
-    {
-        println!("{}", style("  Sustained Vowel").bold());
-        print_comparison("    MPT", b.mpt_seconds, c.mpt_seconds, "s", true);
-        print_comparison("    Mean F0", b.mean_f0_hz, c.mean_f0_hz, "Hz", true);
-        print_comparison("    F0 std", b.f0_std_hz, c.f0_std_hz, "Hz", false);
-        print_comparison("    Jitter", b.jitter_local_percent, c.jitter_local_percent, "%", false);
-        print_comparison("    Shimmer", b.shimmer_local_percent, c.shimmer_local_percent, "%", false);
-        print_comparison("    HNR", b.hnr_db, c.hnr_db, "dB", true);
+    let header: String = sessions
+        .iter()
+        .map(|s| format!("{:>width$}", s.id(), width = COLUMN_WIDTH))
+        .collect();
+    println!("  {:14}{}", "", style(header).cyan());
+
+    let rows = rows();
+    let mut groups: Vec<&str> = rows.iter().map(|r| r.group).collect();
+    groups.dedup();
+    for group in groups {
+        let group_rows: Vec<&Row> = rows.iter().filter(|r| r.group == group).collect();
+        let recorded = sessions
+            .iter()
+            .filter(|s| group_rows.iter().any(|r| (r.extract)(s).is_some()))
+            .count();
+        if recorded == 0 {
+            println!(
+                "  {} — not recorded in any of these sessions",
+                style(group).dim()
+            );
+            continue;
+        }
+        let missing = if recorded < sessions.len() {
+            format!(" (recorded in {recorded} of {} sessions)", sessions.len())
+        } else {
+            String::new()
+        };
+        println!("{}{}", style(format!("  {group}")).bold(), style(missing).dim());
+        for row in group_rows {
+            print_row(row, sessions, norm(row.norm));
+        }
         println!();
-    } else {
-        print_missing("Sustained", &baseline, &current);
     }
+}
 
-    // Scale comparison
-    if let (Some(ref b), Some(ref c)) = (&baseline.analysis.scale, &current.analysis.scale) {
-        println!("{}", style("  Pitch Range (Scale)").bold());
-        print_comparison("    Floor", b.pitch_floor_hz, c.pitch_floor_hz, "Hz", false);
-        print_comparison("    Ceiling", b.pitch_ceiling_hz, c.pitch_ceiling_hz, "Hz", true);
-        print_comparison("    Range", b.range_semitones, c.range_semitones, "st", true);
-        println!();
-    } else {
-        print_missing("Scale", &baseline, &current);
-    }
-
-    // Reading comparison
-    if let (Some(ref b), Some(ref c)) = (&baseline.analysis.reading, &current.analysis.reading) {
-        println!("{}", style("  Reading Passage").bold());
-        print_comparison("    Mean F0", b.mean_f0_hz, c.mean_f0_hz, "Hz", true);
-        print_comparison("    F0 std", b.f0_std_hz, c.f0_std_hz, "Hz", true);
-        print_comparison_int("    Breaks", b.voice_breaks, c.voice_breaks, false);
-        print_comparison(
-            "    Voiced",
-            b.voiced_fraction * 100.0,
-            c.voiced_fraction * 100.0,
-            "%",
-            true,
-        );
-        println!();
-    } else {
-        print_missing("Reading", &baseline, &current);
+/// Print a table row: the first session's value, then each later value
+/// with its delta against the first.
+/// `higher_is_better` controls the delta color: green for improvement, red for regression.
+/// With a norm, the last value's z-score and percentile follow.
+fn print_row(row: &Row, sessions: &[SessionData], norm: Option<Norm>) {
+    let values: Vec<Option<f32>> = sessions.iter().map(|s| (row.extract)(s)).collect();
+    let first = values[0];
+    let unit = if row.unit.is_empty() { String::new() } else { format!(" {}", row.unit) };
+
+    let mut line = format!("    {:12}", row.label);
+    for (i, value) in values.iter().enumerate() {
+        let Some(v) = value else {
+            line.push_str(&format!("{:>width$}", "—", width = COLUMN_WIDTH));
+            continue;
+        };
+        let text = format!("{:.*}{unit}", row.decimals, v);
+        match (i, first) {
+            (0, _) | (_, None) => line.push_str(&format!("{:>width$}", text, width = COLUMN_WIDTH)),
+            (_, Some(f)) => {
+                let delta = delta_text(v - f, row.decimals, row.higher_is_better);
+                line.push_str(&format!("{:>width$} {delta}", text, width = COLUMN_WIDTH - 8));
+            }
+        }
     }
 
-    Ok(())
+    let last = values.last().copied().flatten();
+    if let (Some(n), Some(v)) = (norm, last) {
+        let text = n.describe(v);
+        let text = match n.standing(v) {
+            Standing::Typical => style(text).dim().to_string(),
+            Standing::Borderline => style(text).yellow().to_string(),
+            Standing::Atypical => style(text).red().to_string(),
+        };
+        line.push_str(&format!("  {text}"));
+    }
+    println!("{line}");
 }
 
-/// Print a comparison line for f32 values.
-/// `higher_is_better` controls the arrow color: green for improvement, red for regression.
-fn print_comparison(label: &str, baseline: f32, current: f32, unit: &str, higher_is_better: bool) {
-    let delta = current - baseline;
-    let arrow = if delta.abs() < 0.01 {
-        style("=").dim().to_string()
+/// Coloured, fixed-width delta, e.g. "(+1.3)" in green.
+fn delta_text(delta: f32, decimals: usize, higher_is_better: bool) -> String {
+    let threshold = 0.5 * 10f32.powi(-(decimals as i32));
+    if delta.abs() < threshold.max(0.01) {
+        return style(format!("{:7}", "(=)")).dim().to_string();
+    }
+    let text = format!("{:7}", format!("({:+.*})", decimals, delta));
+    let improving = if higher_is_better { delta > 0.0 } else { delta < 0.0 };
+    if improving {
+        style(text).green().to_string()
     } else {
-        let improving = if higher_is_better {
-            delta > 0.0
-        } else {
-            delta < 0.0
+        style(text).red().to_string()
+    }
+}
+
+/// Markdown table: one column per session, later values with their delta
+/// against the first, plus the last session's norm position.
+pub fn markdown_table(sessions: &[SessionData], profile: &ProfileConfig) -> String {
+    let mut md = String::from("| Metric |");
+    for s in sessions {
+        md.push_str(&format!(" {} |", s.id()));
+    }
+    md.push_str(" Norm (last) |\n|--------|");
+    md.push_str(&"------|".repeat(sessions.len()));
+    md.push_str("-------------|\n");
+
+    for row in rows() {
+        let values: Vec<Option<f32>> = sessions.iter().map(|s| (row.extract)(s)).collect();
+        if values.iter().all(Option::is_none) {
+            continue;
+        }
+        let unit = if row.unit.is_empty() { String::new() } else { format!(" ({})", row.unit) };
+        md.push_str(&format!("| {} {}{unit} |", short_group(row.group), row.label));
+        for (i, value) in values.iter().enumerate() {
+            let cell = match (value, values[0]) {
+                (None, _) => "—".to_string(),
+                (Some(v), Some(f)) if i > 0 => format!("{:.*} ({:+.*})", row.decimals, v, row.decimals, v - f),
+                (Some(v), _) => format!("{:.*}", row.decimals, v),
+            };
+            md.push_str(&format!(" {cell} |"));
+        }
+        let norm = match (row.norm.and_then(|m| norms::lookup(m, profile)), values.last().copied().flatten()) {
+            (Some(n), Some(v)) => n.describe(v),
+            _ => "—".into(),
         };
-        if improving {
-            style(format!("{:+.1}", delta)).green().to_string()
-        } else {
-            style(format!("{:+.1}", delta)).red().to_string()
+        md.push_str(&format!(" {norm} |\n"));
+    }
+    md
+}
+
+/// CSV with one column per session and one delta column per later session.
+fn csv_table(sessions: &[SessionData]) -> String {
+    let mut csv = String::from("exercise,metric,unit");
+    for s in sessions {
+        csv.push_str(&format!(",{}", s.id()));
+    }
+    for s in &sessions[1..] {
+        csv.push_str(&format!(",delta_{}", s.id()));
+    }
+    csv.push('\n');
+
+    for row in rows() {
+        let values: Vec<Option<f32>> = sessions.iter().map(|s| (row.extract)(s)).collect();
+        if values.iter().all(Option::is_none) {
+            continue;
         }
-    };
+        csv.push_str(&format!("{},{},{}", short_group(row.group), row.label, row.unit));
+        for v in &values {
+            csv.push(',');
+            if let Some(v) = v {
+                csv.push_str(&format!("{v:.3}"));
+            }
+        }
+        for v in &values[1..] {
+            csv.push(',');
+            if let (Some(v), Some(f)) = (v, values[0]) {
+                csv.push_str(&format!("{:.3}", v - f));
+            }
+        }
+        csv.push('\n');
+    }
+    csv
+}
 
-    println!(
-        "{:16} {:>8.1} {} → {:>8.1} {}  {}",
-        label, baseline, unit, current, unit, arrow
-    );
+/// "Sustained Vowel" → "sustained", for compact labels.
+fn short_group(group: &str) -> &'static str {
+    match group {
+        "Sustained Vowel" => "sustained",
+        "Pitch Range (Scale)" => "scale",
+        _ => "reading",
+    }
 }
 
-/// Print a comparison line for integer values.
-fn print_comparison_int(label: &str, baseline: usize, current: usize, higher_is_better: bool) {
-    let delta = current as i64 - baseline as i64;
-    let arrow = if delta == 0 {
-        style("=").dim().to_string()
-    } else {
-        let improving = if higher_is_better {
-            delta > 0
-        } else {
-            delta < 0
+/// One metric's values in the windows before and after an event.
+pub struct WindowComparison {
+    pub name: &'static str,
+    pub unit: &'static str,
+    pub direction: Direction,
+    pub before: Vec<f32>,
+    pub after: Vec<f32>,
+}
+
+impl WindowComparison {
+    pub fn mean_before(&self) -> Option<f32> {
+        mean(&self.before)
+    }
+
+    pub fn mean_after(&self) -> Option<f32> {
+        mean(&self.after)
+    }
+
+    /// Cohen's d of after relative to before.
+    pub fn effect_size(&self) -> Option<f32> {
+        stats::cohens_d(&self.before, &self.after)
+    }
+
+    pub fn test(&self) -> Option<MannWhitney> {
+        stats::mann_whitney(&self.before, &self.after)
+    }
+}
+
+fn mean(values: &[f32]) -> Option<f32> {
+    (!values.is_empty()).then(|| values.iter().sum::<f32>() / values.len() as f32)
+}
+
+/// Parse a window length such as "4w", "10d", "2m" (30-day months) or a
+/// bare number of days.
+pub fn parse_window(window: &str) -> Result<i64> {
+    let w = window.trim().to_lowercase();
+    let (num, unit_days) = match w.char_indices().last() {
+        Some((i, 'd')) => (&w[..i], 1),
+        Some((i, 'w')) => (&w[..i], 7),
+        Some((i, 'm')) => (&w[..i], 30),
+        _ => (w.as_str(), 1),
+    };
+    let n: i64 = num
+        .parse()
+        .ok()
+        .filter(|n| *n > 0)
+        .with_context(|| format!("Invalid window '{window}'. Use e.g. 4w, 10d or 2m."))?;
+    Ok(n * unit_days)
+}
+
+/// Split sessions into those within `days` before and after `event`.
+/// Sessions on the event date itself belong to neither side.
+pub fn split_windows(
+    sessions: &[SessionData],
+    event: NaiveDate,
+    days: i64,
+) -> (Vec<&SessionData>, Vec<&SessionData>) {
+    let mut before = Vec::new();
+    let mut after = Vec::new();
+    for s in sessions {
+        let Ok(date) = NaiveDate::parse_from_str(&s.date, "%Y-%m-%d") else {
+            continue;
         };
-        if improving {
-            style(format!("{:+}", delta)).green().to_string()
-        } else {
-            style(format!("{:+}", delta)).red().to_string()
+        let offset = (date - event).num_days();
+        if (-days..0).contains(&offset) {
+            before.push(s);
+        } else if (1..=days).contains(&offset) {
+            after.push(s);
         }
-    };
+    }
+    (before, after)
+}
 
-    println!(
-        "{:16} {:>8} → {:>8}    {}",
-        label, baseline, current, arrow
-    );
+/// Per-metric values on each side, for every metric measured in either window.
+pub fn window_comparisons(before: &[&SessionData], after: &[&SessionData]) -> Vec<WindowComparison> {
+    stats::metric_defs()
+        .into_iter()
+        .map(|def| WindowComparison {
+            name: def.name,
+            unit: def.unit,
+            direction: def.direction,
+            before: before.iter().filter_map(|s| (def.extract)(s)).collect(),
+            after: after.iter().filter_map(|s| (def.extract)(s)).collect(),
+        })
+        .filter(|c| !c.before.is_empty() || !c.after.is_empty())
+        .collect()
 }
 
-fn print_missing(exercise: &str, baseline: &SessionData, current: &SessionData) {
-    let b_has = match exercise {
-        "Sustained" => baseline.analysis.sustained.is_some(),
-        "Scale" => baseline.analysis.scale.is_some(),
-        "Reading" => baseline.analysis.reading.is_some(),
-        _ => false,
-    };
-    let c_has = match exercise {
-        "Sustained" => current.analysis.sustained.is_some(),
-        "Scale" => current.analysis.scale.is_some(),
-        "Reading" => current.analysis.reading.is_some(),
-        _ => false,
+/// Compare every session in the window before a clinical event with those
+/// in the window after it: per-metric means, Cohen's d and a Mann–Whitney
+/// U test.
+pub fn compare_around_event(event_id: i64, window: &str) -> Result<()> {
+    let days = parse_window(window)?;
+    let events = store::load_events()?;
+    let Some(event) = events.iter().find(|e| e.id == event_id) else {
+        bail!("No event with id {event_id}. See `voicevo event list`.");
     };
+    let event_date = NaiveDate::parse_from_str(&event.date, "%Y-%m-%d")
+        .with_context(|| format!("Event {event_id} has an invalid date: {}", event.date))?;
 
-    if !b_has && !c_has {
-        println!(
-            "  {} — not recorded in either session",
-            style(exercise).dim()
-        );
-    } else if !b_has {
+    let sessions: Vec<SessionData> = store::list_sessions()?
+        .iter()
+        .filter_map(|d| store::load_session(d).ok())
+        .collect();
+    let (before, after) = split_windows(&sessions, event_date, days);
+
+    println!(
+        "{}",
+        style("=== Before/After Event Comparison ===").bold()
+    );
+    println!();
+    let note = if event.note.is_empty() { String::new() } else { format!(" — {}", event.note) };
+    println!(
+        "  Event: {} {}{note}",
+        style(&event.date).cyan(),
+        style(&event.kind).bold()
+    );
+    println!(
+        "  Window: {days} days each side ({} before, {} after; sessions on the event date are left out)",
+        describe_sessions(&before),
+        describe_sessions(&after)
+    );
+    println!();
+
+    if before.is_empty() || after.is_empty() {
+        let side = if before.is_empty() { "before" } else { "after" };
         println!(
-            "  {} — not recorded in baseline",
-            style(exercise).dim()
+            "  {} No sessions in the {days} days {side} the event — try a wider --window.",
+            style("!").yellow()
         );
-    } else {
+        return Ok(());
+    }
+
+    println!(
+        "  {}",
+        style(format!("  {:14} {:>10}   {:>10}  {:>8}  {:>6}  {:>7}", "Metric", "Before", "After", "Change", "d", "p")).bold()
+    );
+    for c in window_comparisons(&before, &after) {
+        print_window_comparison(&c);
+    }
+
+    println!();
+    println!(
+        "  {}",
+        style("Values are window means. d: Cohen's d (0.2 small, 0.5 medium, 0.8 large).").dim()
+    );
+    println!(
+        "  {}",
+        style(format!(
+            "p: two-sided Mann–Whitney U (exact; ~ normal approximation); * p < {SIGNIFICANCE}. With few sessions per window, a large p means \"not shown\", not \"no effect\"."
+        ))
+        .dim()
+    );
+    Ok(())
+}
+
+/// e.g. "4 sessions, 2026-01-08 to 2026-02-04"
+fn describe_sessions(sessions: &[&SessionData]) -> String {
+    match (sessions.first(), sessions.last()) {
+        (Some(first), Some(last)) if sessions.len() > 1 => {
+            format!("{} sessions, {} to {}", sessions.len(), first.id(), last.id())
+        }
+        (Some(only), _) => format!("1 session, {}", only.id()),
+        _ => "no sessions".into(),
+    }
+}
+
+fn print_window_comparison(c: &WindowComparison) {
+    let (Some(before), Some(after)) = (c.mean_before(), c.mean_after()) else {
+        let side = if c.before.is_empty() { "before" } else { "after" };
         println!(
-            "  {} — not recorded in current session",
-            style(exercise).dim()
+            "    {:14} {}",
+            c.name,
+            style(format!("— not recorded {side} the event")).dim()
         );
+        return;
+    };
+
+    let delta = after - before;
+    // Pad before styling: escape codes would otherwise count towards the width
+    let change = format!("{:>8}", stats::format_signed(delta, c.unit));
+    let change = match (c.direction, delta > 0.0) {
+        _ if delta.abs() < f32::EPSILON => style(change).dim(),
+        (Direction::Neutral, _) => style(change),
+        (Direction::HigherIsBetter, true) | (Direction::LowerIsBetter, false) => style(change).green(),
+        _ => style(change).red(),
+    };
+    let d = c
+        .effect_size()
+        .map(|d| format!("{d:+.2}"))
+        .unwrap_or_else(|| "—".into());
+    let test = c.test();
+    let p = match test {
+        Some(t) => {
+            // "~" marks the normal approximation (ties or larger samples)
+            let approx = if t.exact { "" } else { "~" };
+            let star = if t.p < SIGNIFICANCE { " *" } else { "" };
+            let text = format!("{:>7}", format!("{approx}{:.3}{star}", t.p));
+            if t.p < SIGNIFICANCE { style(text).bold().to_string() } else { text }
+        }
+        None => format!("{:>7}", "—"),
+    };
+    let u = test.map(|t| format!(", U {:.1}", t.u)).unwrap_or_default();
+
+    println!(
+        "    {:14} {:>10} → {:>10}  {}  {:>6}  {}  {}",
+        c.name,
+        stats::format_value(before, c.unit),
+        stats::format_value(after, c.unit),
+        change,
+        d,
+        p,
+        style(format!("(n {}/{}{u})", c.before.len(), c.after.len())).dim()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::session_data::*;
+
+    fn session(date: &str, mpt: f32, range: Option<f32>) -> SessionData {
+        let mut s = SessionData::test_default(date);
+        s.analysis.sustained = Some(SustainedAnalysis { mpt_seconds: mpt, ..SustainedAnalysis::test_default() });
+        s.analysis.scale = range.map(|range_semitones| ScaleAnalysis {
+            pitch_floor_hz: 90.0,
+            pitch_ceiling_hz: 300.0,
+            range_semitones,
+            range_hz: 210.0,
+            recording_quality: None,
+            segment: None,
+        });
+        s
+    }
+
+    #[test]
+    fn parses_windows() {
+        assert_eq!(parse_window("4w").unwrap(), 28);
+        assert_eq!(parse_window("10d").unwrap(), 10);
+        assert_eq!(parse_window("2M").unwrap(), 60);
+        assert_eq!(parse_window("14").unwrap(), 14);
+        assert!(parse_window("0w").is_err());
+        assert!(parse_window("soon").is_err());
+    }
+
+    #[test]
+    fn splits_around_event_and_handles_missing_exercises() {
+        let sessions = vec![
+            session("2026-01-01", 5.0, Some(10.0)), // outside the window
+            session("2026-01-20", 6.0, Some(12.0)),
+            session("2026-01-27", 7.0, None),
+            session("2026-02-03", 6.5, Some(11.0)),
+            session("2026-02-05", 9.0, None), // event day
+            session("2026-02-10", 10.0, None),
+            session("2026-02-17", 11.0, None),
+            session("2026-02-24", 12.0, None),
+        ];
+        let event = NaiveDate::from_ymd_opt(2026, 2, 5).unwrap();
+        let (before, after) = split_windows(&sessions, event, 28);
+        assert_eq!(before.len(), 3);
+        assert_eq!(after.len(), 3);
+
+        let comparisons = window_comparisons(&before, &after);
+        let mpt = comparisons.iter().find(|c| c.name == "MPT").unwrap();
+        assert!((mpt.mean_before().unwrap() - 6.5).abs() < 1e-5);
+        assert!((mpt.mean_after().unwrap() - 11.0).abs() < 1e-5);
+        assert!(mpt.effect_size().unwrap() > 0.8);
+        // Every after value beats every before value: exact p = 2/20
+        assert!((mpt.test().unwrap().p - 0.1).abs() < 1e-6);
+
+        // Scale only recorded before the event
+        let range = comparisons.iter().find(|c| c.name == "Pitch range").unwrap();
+        assert!(range.mean_after().is_none());
+        assert!(range.test().is_none());
+        // Exercises never recorded are left out
+        assert!(!comparisons.iter().any(|c| c.name == "S/Z ratio"));
+    }
+
+    #[test]
+    fn markdown_columns_with_deltas_against_first() {
+        let sessions = vec![
+            session("2026-01-01", 5.0, Some(10.0)),
+            session("2026-02-01", 8.0, None),
+            session("2026-03-01", 11.5, Some(14.0)),
+        ];
+        let md = markdown_table(&sessions, &ProfileConfig::default());
+        assert!(md.starts_with("| Metric | 2026-01-01 | 2026-02-01 | 2026-03-01 | Norm (last) |"));
+        assert!(md.contains("| sustained MPT (s) | 5.0 | 8.0 (+3.0) | 11.5 (+6.5) | z -1.5, 7th pct |"));
+        assert!(md.contains("| scale Range (st) | 10.0 | — | 14.0 (+4.0) |"));
+        // Reading was never recorded
+        assert!(!md.contains("reading"));
+    }
+
+    #[test]
+    fn csv_has_value_and_delta_columns() {
+        let sessions = vec![session("2026-01-01", 5.0, None), session("2026-02-01", 6.5, None)];
+        let csv = csv_table(&sessions);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("exercise,metric,unit,2026-01-01,2026-02-01,delta_2026-02-01"));
+        assert_eq!(lines.next(), Some("sustained,MPT,s,5.000,6.500,1.500"));
+        assert!(!csv.contains("scale,"));
     }
 }
@@ -0,0 +1,131 @@
+use anyhow::Result;
+use console::style;
+
+use crate::config;
+use crate::paths;
+use crate::report::goals;
+use crate::storage::db;
+use crate::storage::integrity::{self, Fix, Issue};
+
+/// `voicevo doctor`: check the config, database and recordings for
+/// inconsistencies, and with `fix` repair what can be repaired safely.
+///
+/// Runs before the config is loaded by `main`, so a broken config file is
+/// reported here instead of stopping the command.
+pub fn run(fix: bool) -> Result<()> {
+    println!("{}", style("=== voicevo doctor ===").bold());
+    println!();
+
+    let config = match check_config() {
+        Ok(config) => {
+            println!("  Config:     {}", style("OK").green());
+            Some(config)
+        }
+        Err(e) => {
+            println!("  Config:     {} {e:#}", style("ERROR").red().bold());
+            None
+        }
+    };
+
+    let conn = db::open_db()?;
+    let (summary, issues) = integrity::check(&conn, &paths::recordings_dir())?;
+    println!(
+        "  Database:   {} sessions, {} analyses, {} recording directories",
+        summary.sessions, summary.analyses, summary.recording_dirs
+    );
+    println!();
+
+    if issues.is_empty() {
+        if config.is_some() {
+            println!("No problems found.");
+        }
+        return Ok(());
+    }
+
+    for issue in &issues {
+        print_issue(issue);
+    }
+    println!();
+
+    let fixable = issues.iter().filter(|i| i.fix.is_some()).count();
+    println!(
+        "{} problem(s), {} fixable{}.",
+        issues.len(),
+        fixable,
+        if fix || fixable == 0 { "" } else { " with `voicevo doctor --fix`" }
+    );
+    if !fix || fixable == 0 {
+        return Ok(());
+    }
+
+    println!();
+    apply_fixes(&conn, &issues, config.as_ref())
+}
+
+/// Parse the config file and validate what can only be checked in use.
+fn check_config() -> Result<config::AppConfig> {
+    let config = config::load_config()?;
+    goals::evaluate(&config.goals, &[], chrono::Local::now().date_naive())?;
+    Ok(config)
+}
+
+fn print_issue(issue: &Issue) {
+    let label = style(format!("{:<12}", issue.kind.label())).yellow();
+    let session = issue
+        .session
+        .as_deref()
+        .map(|s| format!("{} ", style(s).cyan()))
+        .unwrap_or_default();
+    let fix = match &issue.fix {
+        Some(f) => format!(" {}", style(format!("→ {f}")).dim()),
+        None => String::new(),
+    };
+    println!("  {label} {session}{}{fix}", issue.detail);
+}
+
+/// Apply database and filesystem fixes first (a re-link can make a
+/// re-analysis unnecessary), then re-analyze each affected session once.
+fn apply_fixes(conn: &rusqlite::Connection, issues: &[Issue], config: Option<&config::AppConfig>) -> Result<()> {
+    let mut reanalyze: Vec<&str> = Vec::new();
+    let mut fixed = 0;
+
+    for fix in issues.iter().filter_map(|i| i.fix.as_ref()) {
+        match fix {
+            Fix::Reanalyze(session) => {
+                if !reanalyze.contains(&session.as_str()) {
+                    reanalyze.push(session);
+                }
+            }
+            _ => match integrity::apply(conn, fix) {
+                Ok(()) => {
+                    println!("  {} {fix}", style("FIXED").green());
+                    fixed += 1;
+                }
+                Err(e) => println!("  {} {fix}: {e:#}", style("FAILED").red()),
+            },
+        }
+    }
+
+    if !reanalyze.is_empty() {
+        match config {
+            None => println!(
+                "  {} Skipping re-analysis of {} session(s) until the config file is fixed.",
+                style("SKIP").yellow(),
+                reanalyze.len()
+            ),
+            Some(config) => {
+                for session in &reanalyze {
+                    println!();
+                    match crate::analysis::analyzer::analyze_session(session, config) {
+                        Ok(_) => fixed += 1,
+                        Err(e) => println!("  {} re-analyzing {session}: {e:#}", style("FAILED").red()),
+                    }
+                }
+            }
+        }
+    }
+
+    println!();
+    println!("{fixed} fix(es) applied. Run `voicevo doctor` again to confirm.");
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use anyhow::{bail, Context, Result};
+use console::style;
+
+use crate::storage::session_data::{ClinicalEvent, EVENT_KINDS};
+use crate::storage::store;
+use crate::util;
+
+/// Record a clinical event (`voicevo event add`).
+pub fn add_event(date: Option<&str>, kind: &str, note: &str) -> Result<()> {
+    let date = util::resolve_date(date)
+        .with_context(|| format!("Invalid date {date:?} (expected YYYY-MM-DD)"))?
+        .to_string();
+    let kind = kind.to_lowercase();
+    if !EVENT_KINDS.contains(&kind.as_str()) {
+        bail!("Unknown event kind '{kind}'. Use one of: {}.", EVENT_KINDS.join(", "));
+    }
+
+    let id = store::save_event(&date, &kind, note)?;
+    println!(
+        "Recorded {} on {} (event {id}).",
+        style(&kind).bold(),
+        style(&date).cyan()
+    );
+    Ok(())
+}
+
+/// Print the event timeline (`voicevo event list`).
+pub fn list_events() -> Result<()> {
+    let events = store::load_events()?;
+    if events.is_empty() {
+        println!("No events recorded. Add one with `voicevo event add --kind therapy --note \"...\"`.");
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        style("=== Clinical Events ===").bold()
+    );
+    println!();
+    for e in &events {
+        println!(
+            "  {:>4}  {}  {:10}  {}",
+            style(e.id).dim(),
+            style(&e.date).cyan(),
+            e.kind,
+            e.note
+        );
+    }
+    Ok(())
+}
+
+/// Delete an event by id (`voicevo event remove`).
+pub fn remove_event(id: i64) -> Result<()> {
+    if !store::delete_event(id)? {
+        bail!("No event with id {id}. See `voicevo event list`.");
+    }
+    println!("Removed event {id}.");
+    Ok(())
+}
+
+/// Markdown table of the events, for the trend report.
+pub fn markdown_section(events: &[ClinicalEvent]) -> String {
+    let mut md = String::from("| Date | Event | Note |\n");
+    md.push_str("|------|-------|------|\n");
+    for e in events {
+        md.push_str(&format!("| {} | {} | {} |\n", e.date, e.kind, e.note.replace('|', "\\|")));
+    }
+    md
+}
+
+/// Short chart label for the events at one position, e.g. "injection, therapy".
+pub fn label(events: &[&ClinicalEvent]) -> String {
+    events.iter().map(|e| e.kind.as_str()).collect::<Vec<_>>().join(", ")
+}
+
+/// Map events onto session indices for the trend chart: each event sits at
+/// the first session on or after its date. Events outside the recorded span
+/// are left off the chart (the reports still list them).
+pub fn session_positions<'a>(dates: &[&str], events: &'a [ClinicalEvent]) -> Vec<(usize, Vec<&'a ClinicalEvent>)> {
+    let (Some(first), Some(last)) = (dates.first(), dates.last()) else {
+        return Vec::new();
+    };
+    let mut positions: Vec<(usize, Vec<&ClinicalEvent>)> = Vec::new();
+    for e in events {
+        if e.date.as_str() < *first || e.date.as_str() > *last {
+            continue;
+        }
+        let Some(i) = dates.iter().position(|d| *d >= e.date.as_str()) else {
+            continue;
+        };
+        match positions.iter_mut().find(|(p, _)| *p == i) {
+            Some((_, list)) => list.push(e),
+            None => positions.push((i, vec![e])),
+        }
+    }
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(date: &str, kind: &str) -> ClinicalEvent {
+        ClinicalEvent { id: 0, date: date.into(), kind: kind.into(), note: String::new() }
+    }
+
+    #[test]
+    fn events_snap_to_next_session() {
+        let dates = ["2026-02-01", "2026-02-08", "2026-02-15"];
+        let events = vec![
+            event("2026-01-20", "surgery"),
+            event("2026-02-03", "injection"),
+            event("2026-02-08", "therapy"),
+            event("2026-02-15", "illness"),
+            event("2026-03-01", "medication"),
+        ];
+        let positions = session_positions(&dates, &events);
+        assert_eq!(positions.len(), 2);
+        assert_eq!(positions[0].0, 1);
+        assert_eq!(label(&positions[0].1), "injection, therapy");
+        assert_eq!(positions[1].0, 2);
+        assert_eq!(label(&positions[1].1), "illness");
+    }
+
+    #[test]
+    fn markdown_escapes_pipes() {
+        let mut e = event("2026-02-03", "medication");
+        e.note = "PPI | 40 mg".into();
+        let md = markdown_section(&[e]);
+        assert!(md.contains("| 2026-02-03 | medication | PPI \\| 40 mg |"));
+    }
+}
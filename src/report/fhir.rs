@@ -0,0 +1,441 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use console::style;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use super::clinician;
+use crate::storage::session_data::{RecordingQuality, ReliabilityInfo, SessionAnalysis, SessionData};
+
+/// Code system for the metrics and their components. LOINC has no codes
+/// for these acoustic measures, so they use the names `voicevo query` knows.
+const CODE_SYSTEM: &str = "urn:voicevo:metric";
+const UCUM: &str = "http://unitsofmeasure.org";
+const CATEGORY_SYSTEM: &str = "http://terminology.hl7.org/CodeSystem/observation-category";
+
+/// (metric name, display, UCUM code, unit as shown) for every metric that
+/// becomes an Observation.
+const CODES: &[(&str, &str, &str, &str)] = &[
+    ("mpt", "Maximum phonation time", "s", "s"),
+    ("mean_f0", "Mean F0, sustained vowel", "Hz", "Hz"),
+    ("f0_std", "F0 standard deviation, sustained vowel", "Hz", "Hz"),
+    ("jitter", "Jitter (local)", "%", "%"),
+    ("shimmer", "Shimmer (local)", "%", "%"),
+    ("hnr", "Harmonics-to-noise ratio", "dB", "dB"),
+    ("cpps", "Smoothed cepstral peak prominence, sustained vowel", "dB", "dB"),
+    ("pitch_floor", "Pitch floor (5th percentile), pitch glide", "Hz", "Hz"),
+    ("pitch_ceiling", "Pitch ceiling (95th percentile), pitch glide", "Hz", "Hz"),
+    ("range_hz", "Pitch range, pitch glide", "Hz", "Hz"),
+    ("range", "Pitch range in semitones, pitch glide", "{semitone}", "semitones"),
+    ("speaking_f0", "Mean speaking F0, reading", "Hz", "Hz"),
+    ("speaking_f0_std", "Speaking F0 standard deviation, reading", "Hz", "Hz"),
+    ("voice_breaks", "Voice breaks, reading", "{count}", "breaks"),
+    ("voiced_fraction", "Voiced fraction, reading", "1", "ratio"),
+    ("cpps_reading", "Smoothed cepstral peak prominence, reading", "dB", "dB"),
+    ("s_duration", "Mean /s/ duration", "s", "s"),
+    ("z_duration", "Mean /z/ duration", "s", "s"),
+    ("sz_ratio", "S/Z ratio", "1", "ratio"),
+    ("mpt_slope", "MPT slope across fatigue trials", "s/{trial}", "s per trial"),
+    ("cpps_slope", "CPPS slope across fatigue trials", "dB/{trial}", "dB per trial"),
+];
+
+/// The metrics of one exercise with what qualifies them.
+struct ExerciseMetrics<'a> {
+    exercise: &'static str,
+    /// (metric name, value, validity flag where the analysis has one)
+    values: Vec<(&'static str, f32, Option<bool>)>,
+    reliability: Option<&'a ReliabilityInfo>,
+    quality: Option<&'a RecordingQuality>,
+}
+
+/// `voicevo export --fhir`: write the sessions in `range` as a FHIR R4
+/// `Bundle` of `Observation` resources, one per metric per session.
+///
+/// `patient` ("123" or "Patient/123") becomes each Observation's subject.
+/// With `deidentify`, dates are shifted as in the clinician bundle.
+pub fn export(range: Option<&str>, out: &Path, deidentify: bool, patient: Option<&str>) -> Result<()> {
+    let sessions = clinician::sessions_in_range(range)?;
+    let sessions = if deidentify {
+        clinician::deidentified(&sessions, &[])?.0
+    } else {
+        sessions
+    };
+
+    let timestamp = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let bundle = bundle(&sessions, patient, &timestamp);
+    let count = bundle["entry"].as_array().map_or(0, Vec::len);
+
+    if let Some(parent) = out.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(&bundle).context("Failed to serialize FHIR bundle")?;
+    std::fs::write(out, json).with_context(|| format!("Failed to write {}", out.display()))?;
+
+    println!(
+        "FHIR bundle with {count} observation(s) from {} session(s) written to {}",
+        sessions.len(),
+        style(out.display()).green()
+    );
+    Ok(())
+}
+
+/// A `collection` Bundle with an Observation per metric of every session.
+pub fn bundle(sessions: &[SessionData], patient: Option<&str>, timestamp: &str) -> Value {
+    let subject = patient.map(|p| {
+        if p.contains('/') {
+            p.to_string()
+        } else {
+            format!("Patient/{p}")
+        }
+    });
+
+    let entries: Vec<Value> = sessions
+        .iter()
+        .flat_map(|session| {
+            exercise_metrics(&session.analysis)
+                .into_iter()
+                .flat_map(|group| {
+                    group
+                        .values
+                        .iter()
+                        .filter(|(_, value, _)| value.is_finite())
+                        .map(|&metric| observation(session, &group, metric, subject.as_deref()))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        })
+        .map(|resource| {
+            let full_url = format!("urn:uuid:{}", uuid_for(resource["id"].as_str().unwrap_or_default()));
+            json!({ "fullUrl": full_url, "resource": resource })
+        })
+        .collect();
+
+    json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "timestamp": timestamp,
+        "entry": entries,
+    })
+}
+
+fn exercise_metrics(analysis: &SessionAnalysis) -> Vec<ExerciseMetrics<'_>> {
+    let mut groups = Vec::new();
+
+    if let Some(a) = &analysis.sustained {
+        let v = a.reliability.as_ref().map(|r| &r.metrics_validity);
+        let mut values = vec![
+            ("mpt", a.mpt_seconds, None),
+            ("mean_f0", a.mean_f0_hz, None),
+            ("f0_std", a.f0_std_hz, None),
+            ("jitter", a.jitter_local_percent, v.map(|v| v.jitter)),
+            ("shimmer", a.shimmer_local_percent, v.map(|v| v.shimmer)),
+            ("hnr", a.hnr_db, v.map(|v| v.hnr)),
+        ];
+        if let Some(cpps) = a.cpps_db {
+            values.push(("cpps", cpps, v.map(|v| v.cpps)));
+        }
+        groups.push(ExerciseMetrics {
+            exercise: "sustained",
+            values,
+            reliability: a.reliability.as_ref(),
+            quality: a.recording_quality.as_ref(),
+        });
+    }
+
+    if let Some(a) = &analysis.scale {
+        groups.push(ExerciseMetrics {
+            exercise: "scale",
+            values: vec![
+                ("pitch_floor", a.pitch_floor_hz, None),
+                ("pitch_ceiling", a.pitch_ceiling_hz, None),
+                ("range_hz", a.range_hz, None),
+                ("range", a.range_semitones, None),
+            ],
+            reliability: None,
+            quality: a.recording_quality.as_ref(),
+        });
+    }
+
+    if let Some(a) = &analysis.reading {
+        let v = a.reliability.as_ref().map(|r| &r.metrics_validity);
+        let mut values = vec![
+            ("speaking_f0", a.mean_f0_hz, None),
+            ("speaking_f0_std", a.f0_std_hz, None),
+            ("voice_breaks", a.voice_breaks as f32, v.map(|v| v.voice_breaks == "valid")),
+            ("voiced_fraction", a.voiced_fraction, None),
+        ];
+        if let Some(cpps) = a.cpps_db {
+            values.push(("cpps_reading", cpps, v.map(|v| v.cpps)));
+        }
+        groups.push(ExerciseMetrics {
+            exercise: "reading",
+            values,
+            reliability: a.reliability.as_ref(),
+            quality: a.recording_quality.as_ref(),
+        });
+    }
+
+    if let Some(a) = &analysis.sz {
+        groups.push(ExerciseMetrics {
+            exercise: "sz",
+            values: vec![("s_duration", a.mean_s, None), ("z_duration", a.mean_z, None), ("sz_ratio", a.sz_ratio, None)],
+            reliability: None,
+            quality: None,
+        });
+    }
+
+    if let Some(a) = &analysis.fatigue {
+        groups.push(ExerciseMetrics {
+            exercise: "fatigue",
+            values: vec![("mpt_slope", a.mpt_slope, None), ("cpps_slope", a.cpps_slope, None)],
+            reliability: None,
+            quality: None,
+        });
+    }
+
+    groups
+}
+
+/// One Observation. Reliability flags and recording SNR go into components
+/// so a reader can tell a trustworthy value from a trend-only one.
+fn observation(
+    session: &SessionData,
+    group: &ExerciseMetrics,
+    (metric, value, valid): (&str, f32, Option<bool>),
+    subject: Option<&str>,
+) -> Value {
+    let &(code, display, ucum, unit) = CODES
+        .iter()
+        .find(|(name, ..)| *name == metric)
+        .expect("every exported metric has a code");
+
+    let mut components = Vec::new();
+    if let Some(valid) = valid {
+        components.push(component("metric-valid", "Metric valid at this detection quality", json!({ "valueBoolean": valid })));
+    }
+    if let Some(r) = group.reliability {
+        components.push(component("analysis-quality", "Analysis quality", json!({ "valueString": r.analysis_quality })));
+        components.push(component("snr-limited", "Quality limited by recording SNR", json!({ "valueBoolean": r.snr_limited })));
+    }
+    if let Some(q) = group.quality.filter(|q| q.snr_db.is_finite()) {
+        components.push(component("snr", "Recording signal-to-noise ratio", json!({ "valueQuantity": quantity(q.snr_db, "dB", "dB") })));
+    }
+
+    let mut resource = json!({
+        "resourceType": "Observation",
+        "id": resource_id(&session.id(), metric),
+        "status": "final",
+        "category": [{
+            "coding": [{ "system": CATEGORY_SYSTEM, "code": "exam", "display": "Exam" }]
+        }],
+        "code": {
+            "coding": [{ "system": CODE_SYSTEM, "code": code, "display": display }],
+            "text": display,
+        },
+        "effectiveDateTime": session.date,
+        "valueQuantity": quantity(value, unit, ucum),
+        "method": { "text": format!("voicevo {} exercise, session {}", group.exercise, session.id()) },
+    });
+    if let Some(subject) = subject {
+        resource["subject"] = json!({ "reference": subject });
+    }
+    if !components.is_empty() {
+        resource["component"] = Value::Array(components);
+    }
+    if let Some(source) = &session.source {
+        resource["note"] = json!([{ "text": format!("Imported from {source}, measured outside voicevo") }]);
+    }
+    resource
+}
+
+fn component(code: &str, display: &str, value: Value) -> Value {
+    let mut component = json!({
+        "code": { "coding": [{ "system": CODE_SYSTEM, "code": code, "display": display }] }
+    });
+    if let (Some(component), Value::Object(value)) = (component.as_object_mut(), value) {
+        component.extend(value);
+    }
+    component
+}
+
+/// A UCUM Quantity, rounded to drop f32 noise (0.45 rather than 0.449999988).
+fn quantity(value: f32, unit: &str, ucum: &str) -> Value {
+    let value = (value as f64 * 1e4).round() / 1e4;
+    json!({ "value": value, "unit": unit, "system": UCUM, "code": ucum })
+}
+
+/// FHIR ids allow letters, digits, '-' and '.', up to 64 characters.
+fn resource_id(session: &str, metric: &str) -> String {
+    format!("voicevo-{session}-{}", metric.replace('_', "-"))
+}
+
+/// A stable name-based UUID (version 8, from SHA-256), so exporting the same
+/// sessions again gives the same fullUrls.
+fn uuid_for(name: &str) -> String {
+    let hash = Sha256::digest(name.as_bytes());
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&hash[..16]);
+    bytes[6] = (bytes[6] & 0x0f) | 0x80;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::session_data::*;
+
+    fn session(date: &str, slot: u32) -> SessionData {
+        let mut s = SessionData::test_default(date);
+        s.slot = slot;
+        s.analysis.sustained = Some(SustainedAnalysis {
+            mpt_seconds: 8.5,
+            jitter_local_percent: 0.45,
+            cpps_db: Some(4.2),
+            // tier 3 dominated: jitter, shimmer and HNR are not valid
+            reliability: Some(ReliabilityInfo::compute([0, 2, 10], 0.8, 0.2, true)),
+            recording_quality: Some(RecordingQuality {
+                clipped_fraction: 0.0,
+                noise_floor_db: -60.0,
+                noise_from_silence: true,
+                snr_db: 35.0,
+                dc_offset: 0.0,
+                hum_hz: None,
+                hum_prominence_db: 0.0,
+                warnings: Vec::new(),
+                format: None,
+            }),
+            ..SustainedAnalysis::test_default()
+        });
+        s.analysis.reading = Some(ReadingAnalysis {
+            mean_f0_hz: 118.0,
+            f0_std_hz: 14.0,
+            f0_range_hz: (95.0, 160.0),
+            voice_breaks: 2,
+            voiced_fraction: 0.8,
+            cpps_db: None,
+            detection_quality: None,
+            reliability: None,
+            recording_quality: None,
+            segment: None,
+        });
+        s
+    }
+
+    /// Structural checks against the R4 Bundle and Observation definitions
+    /// for the elements the exporter writes. Returns the problems found.
+    fn validate(bundle: &Value) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut check = |ok: bool, what: String| {
+            if !ok {
+                problems.push(what);
+            }
+        };
+        let is_id = |s: &str| !s.is_empty() && s.len() <= 64 && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.');
+        let is_date = |s: &str| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok();
+        let is_coding = |c: &Value| c["system"].as_str().is_some_and(|s| !s.is_empty()) && c["code"].as_str().is_some_and(|s| !s.trim().is_empty());
+        let value_keys = |v: &Value| v.as_object().map_or(0, |o| o.keys().filter(|k| k.starts_with("value")).count());
+
+        check(bundle["resourceType"] == "Bundle", "resourceType is not Bundle".into());
+        check(bundle["type"] == "collection", "Bundle type".into());
+        check(chrono::DateTime::parse_from_rfc3339(bundle["timestamp"].as_str().unwrap_or("")).is_ok(), "timestamp".into());
+        let entries = bundle["entry"].as_array().cloned().unwrap_or_default();
+        let mut urls = std::collections::HashSet::new();
+
+        for (i, entry) in entries.iter().enumerate() {
+            let url = entry["fullUrl"].as_str().unwrap_or("");
+            let uuid = url.strip_prefix("urn:uuid:").unwrap_or("");
+            let groups: Vec<usize> = uuid.split('-').map(str::len).collect();
+            check(groups == [8, 4, 4, 4, 12] && uuid.chars().all(|c| c == '-' || c.is_ascii_hexdigit()), format!("entry {i}: fullUrl {url}"));
+            check(urls.insert(url.to_string()), format!("entry {i}: duplicate fullUrl"));
+
+            let r = &entry["resource"];
+            check(r["resourceType"] == "Observation", format!("entry {i}: resourceType"));
+            check(r["id"].as_str().is_some_and(is_id), format!("entry {i}: id {}", r["id"]));
+            check(
+                ["registered", "preliminary", "final", "amended"].contains(&r["status"].as_str().unwrap_or("")),
+                format!("entry {i}: status"),
+            );
+            check(r["code"]["coding"].as_array().is_some_and(|c| !c.is_empty() && c.iter().all(is_coding)), format!("entry {i}: code"));
+            check(
+                r["category"].as_array().is_some_and(|c| c.iter().all(|cat| cat["coding"].as_array().is_some_and(|c| c.iter().all(is_coding)))),
+                format!("entry {i}: category"),
+            );
+            check(r["effectiveDateTime"].as_str().is_some_and(is_date), format!("entry {i}: effectiveDateTime"));
+            check(value_keys(r) == 1, format!("entry {i}: exactly one value[x]"));
+            let q = &r["valueQuantity"];
+            check(q["value"].is_number() && q["system"] == UCUM && q["code"].is_string(), format!("entry {i}: valueQuantity"));
+            if let Some(subject) = r.get("subject") {
+                check(subject["reference"].as_str().is_some_and(|s| s.contains('/')), format!("entry {i}: subject"));
+            }
+            for (j, c) in r["component"].as_array().into_iter().flatten().enumerate() {
+                check(c["code"]["coding"].as_array().is_some_and(|c| c.iter().all(is_coding)), format!("entry {i} component {j}: code"));
+                check(value_keys(c) == 1, format!("entry {i} component {j}: exactly one value[x]"));
+            }
+        }
+        problems
+    }
+
+    fn observation_for<'a>(bundle: &'a Value, id: &str) -> &'a Value {
+        bundle["entry"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| &e["resource"])
+            .find(|r| r["id"] == id)
+            .unwrap_or_else(|| panic!("no observation {id}"))
+    }
+
+    #[test]
+    fn bundle_is_structurally_valid() {
+        let mut imported = session("2026-01-20", 2);
+        imported.source = Some("praat:clinic.txt".into());
+        let bundle = bundle(&[session("2026-01-15", 1), imported], Some("123"), "2026-02-01T10:00:00Z");
+
+        assert_eq!(validate(&bundle), Vec::<String>::new());
+        // 7 sustained + 4 reading metrics per session
+        assert_eq!(bundle["entry"].as_array().unwrap().len(), 22);
+
+        let mpt = observation_for(&bundle, "voicevo-2026-01-15-mpt");
+        assert_eq!(mpt["effectiveDateTime"], "2026-01-15");
+        assert_eq!(mpt["valueQuantity"]["value"], 8.5);
+        assert_eq!(mpt["valueQuantity"]["code"], "s");
+        assert_eq!(mpt["subject"]["reference"], "Patient/123");
+        assert!(mpt.get("note").is_none());
+
+        let jitter = observation_for(&bundle, "voicevo-2026-01-20.2-jitter");
+        assert_eq!(jitter["valueQuantity"]["value"], 0.45);
+        let components = jitter["component"].as_array().unwrap();
+        let flag = |code: &str| components.iter().find(|c| c["code"]["coding"][0]["code"] == code).unwrap();
+        assert_eq!(flag("metric-valid")["valueBoolean"], false);
+        assert_eq!(flag("analysis-quality")["valueString"], "trend_only");
+        assert_eq!(flag("snr")["valueQuantity"]["value"], 35.0);
+        assert!(jitter["note"][0]["text"].as_str().unwrap().contains("praat:clinic.txt"));
+
+        // reading has no reliability info, so no components
+        let breaks = observation_for(&bundle, "voicevo-2026-01-20.2-voice-breaks");
+        assert_eq!(breaks["valueQuantity"]["value"], 2.0);
+        assert!(breaks.get("component").is_none());
+    }
+
+    #[test]
+    fn full_urls_are_stable() {
+        let a = bundle(&[session("2026-01-15", 1)], None, "2026-02-01T10:00:00Z");
+        let b = bundle(&[session("2026-01-15", 1)], Some("Patient/9"), "2026-03-01T10:00:00Z");
+        assert_eq!(a["entry"][0]["fullUrl"], b["entry"][0]["fullUrl"]);
+        assert!(a["entry"][0]["resource"].get("subject").is_none());
+        assert_eq!(b["entry"][0]["resource"]["subject"]["reference"], "Patient/9");
+    }
+
+    #[test]
+    fn validator_catches_problems() {
+        let mut bundle = bundle(&[session("2026-01-15", 1)], None, "2026-02-01T10:00:00Z");
+        bundle["entry"][0]["resource"]["status"] = json!("done");
+        bundle["entry"][1]["resource"]["valueString"] = json!("x");
+        bundle["entry"][2]["fullUrl"] = bundle["entry"][3]["fullUrl"].clone();
+        let problems = validate(&bundle);
+        assert_eq!(problems.len(), 3, "{problems:?}");
+    }
+}
@@ -0,0 +1,471 @@
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use console::style;
+
+use crate::audio::wav;
+use crate::config::AppConfig;
+use crate::dsp::{activity, cpps, hnr, pitch, segment, voice_breaks};
+use crate::paths;
+use crate::storage::session_data::SessionId;
+use crate::storage::store;
+
+/// Voice break upper bound, same as the reading analysis.
+const MAX_BREAK_MS: f32 = 250.0;
+
+/// Floor for RMS in dB so digital silence doesn't print as -inf.
+const RMS_FLOOR_DB: f32 = -120.0;
+
+/// One analysis frame of a recording.
+pub struct FrameRow {
+    /// Center of the pitch window, in seconds from the start of the WAV.
+    pub time_secs: f32,
+    pub f0_hz: Option<f32>,
+    /// Detection tier: 1 (standard), 2 (relaxed), 3 (energy fallback / unvoiced).
+    pub tier: u8,
+    pub active: bool,
+    pub rms_db: f32,
+    /// Autocorrelation at the pitch period (0.0-1.0), voiced frames only.
+    pub periodicity: Option<f32>,
+    pub hnr_db: Option<f32>,
+    pub cpps_db: Option<f32>,
+}
+
+/// Frame-level contour of one recording, in WAV time.
+pub struct FrameTable {
+    pub duration_secs: f32,
+    pub hop_secs: f32,
+    /// Analyzed region (start, end), or None when the whole take was used.
+    pub segment: Option<(f32, f32)>,
+    pub rows: Vec<FrameRow>,
+    /// Voice breaks as (start, end) in seconds.
+    pub breaks: Vec<(f32, f32)>,
+}
+
+/// Re-run the frame-level analysis of a recording and return every frame.
+///
+/// Follows the same steps as `analyze`: segmentation first, then pitch,
+/// activity, periodicity and CPPS on the trimmed audio. Times are shifted
+/// back by the segment start so they line up with the WAV in Praat.
+pub fn build_frames(
+    samples: &[f32],
+    sample_rate: u32,
+    exercise: &str,
+    pitch_config: &pitch::PitchConfig,
+) -> FrameTable {
+    let sr = sample_rate as f32;
+    let mode = if exercise == "sustained" {
+        segment::SegmentMode::Dominant
+    } else {
+        segment::SegmentMode::Span
+    };
+    let (trimmed, range) = segment::trim(samples, sample_rate, mode);
+    let offset = range.as_ref().map_or(0.0, |r| r.start as f32 / sr);
+
+    // The scale analysis uses standard detection only, without fallback
+    // tiers, so its pitched frames are all tier 1. Either way the tiers come
+    // from the same extraction as the F0 values.
+    let (contour, frame_tiers) = if exercise == "scale" {
+        let contour = pitch::extract_pitch_contour(trimmed, sample_rate, pitch_config);
+        let tiers = contour.iter().map(|f| if f.frequency.is_some() { 1 } else { 3 }).collect();
+        (contour, tiers)
+    } else {
+        let result = pitch::extract_contour_with_fallback(trimmed, sample_rate, pitch_config);
+        (result.contour, result.frame_tiers)
+    };
+
+    let active = activity::detect_activity(trimmed, sample_rate, &activity::ActivityConfig::default())
+        .active_frames;
+    let correlations = hnr::frame_correlations(trimmed, sample_rate, &contour, pitch_config.hop_size_ms);
+    let cpps_config = cpps::CppsConfig::default();
+    let cpps_frames = cpps::cpps_per_frame(trimmed, sample_rate, &cpps_config);
+
+    let half_window = pitch::detector_size(sample_rate, pitch_config) as f32 / 2.0 / sr;
+    let rms_window = (pitch_config.frame_size_ms / 1000.0 * sr) as usize;
+
+    let rows = contour
+        .iter()
+        .enumerate()
+        .map(|(i, frame)| {
+            let center = frame.time + half_window;
+            let r = correlations.get(i).copied().flatten();
+            // Activity and CPPS frames share the analysis hop grid, indexed
+            // the same way the analysis pairs them with pitch frames.
+            let cpps_index = (i as f32 * pitch_config.hop_size_ms / cpps_config.hop_size_ms) as usize;
+
+            FrameRow {
+                time_secs: offset + center,
+                f0_hz: frame.frequency,
+                tier: frame_tiers.get(i).copied().unwrap_or(3),
+                active: active.get(i).copied().unwrap_or(false),
+                rms_db: rms_db(trimmed, (center * sr) as usize, rms_window),
+                periodicity: r.map(|r| r.clamp(0.0, 1.0)),
+                hnr_db: r.map(hnr::hnr_db_from_correlation),
+                cpps_db: cpps_frames.get(cpps_index).copied().flatten(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let hop_secs = pitch_config.hop_size_ms / 1000.0;
+    let breaks = voice_breaks::find_voice_breaks(&contour, pitch_config.hop_size_ms, MAX_BREAK_MS)
+        .into_iter()
+        .map(|(first, last)| {
+            (
+                rows[first].time_secs - hop_secs / 2.0,
+                rows[last].time_secs + hop_secs / 2.0,
+            )
+        })
+        .collect();
+
+    FrameTable {
+        duration_secs: samples.len() as f32 / sr,
+        hop_secs,
+        segment: range.map(|r| (r.start as f32 / sr, r.end as f32 / sr)),
+        rows,
+        breaks,
+    }
+}
+
+/// RMS level in dB of a window centered on `center`.
+fn rms_db(samples: &[f32], center: usize, window: usize) -> f32 {
+    let start = center.saturating_sub(window / 2).min(samples.len());
+    let end = (start + window).min(samples.len());
+    let frame = &samples[start..end];
+    if frame.is_empty() {
+        return RMS_FLOOR_DB;
+    }
+
+    let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+    if rms > 0.0 {
+        (20.0 * rms.log10()).max(RMS_FLOOR_DB)
+    } else {
+        RMS_FLOOR_DB
+    }
+}
+
+/// CSV with one row per frame. Missing values are empty cells.
+pub fn to_csv(table: &FrameTable) -> String {
+    fn opt(value: Option<f32>, decimals: usize) -> String {
+        value.map_or(String::new(), |v| format!("{v:.decimals$}"))
+    }
+
+    let mut out = String::from("time_s,f0_hz,tier,active,rms_db,periodicity,hnr_db,cpps_db\n");
+    for row in &table.rows {
+        let _ = writeln!(
+            out,
+            "{:.4},{},{},{},{:.1},{},{},{}",
+            row.time_secs,
+            opt(row.f0_hz, 2),
+            row.tier,
+            u8::from(row.active),
+            row.rms_db,
+            opt(row.periodicity, 3),
+            opt(row.hnr_db, 2),
+            opt(row.cpps_db, 2),
+        );
+    }
+    out
+}
+
+/// Praat PitchTier (long text format) with one point per voiced frame.
+pub fn to_pitch_tier(table: &FrameTable) -> String {
+    let points: Vec<(f32, f32)> = table
+        .rows
+        .iter()
+        .filter_map(|r| r.f0_hz.map(|f| (r.time_secs, f)))
+        .collect();
+
+    let mut out = String::new();
+    let _ = writeln!(out, "File type = \"ooTextFile\"");
+    let _ = writeln!(out, "Object class = \"PitchTier\"");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "xmin = 0 ");
+    let _ = writeln!(out, "xmax = {:.4} ", table.duration_secs);
+    let _ = writeln!(out, "points: size = {} ", points.len());
+    for (i, (time, f0)) in points.iter().enumerate() {
+        let _ = writeln!(out, "points [{}]:", i + 1);
+        let _ = writeln!(out, "    number = {time:.4} ");
+        let _ = writeln!(out, "    value = {f0:.2} ");
+    }
+    out
+}
+
+/// Praat TextGrid (long text format) with three interval tiers:
+/// the analyzed segment, voiced/unvoiced frames, and voice breaks.
+pub fn to_text_grid(table: &FrameTable) -> String {
+    let xmax = table.duration_secs;
+    let half_hop = table.hop_secs / 2.0;
+
+    let segment: Vec<(f32, f32, &str)> = table
+        .segment
+        .map(|(start, end)| vec![(start, end, "analyzed")])
+        .unwrap_or_default();
+
+    // Merge consecutive frames with the same voicing into one interval.
+    let mut voicing: Vec<(f32, f32, &str)> = Vec::new();
+    for row in &table.rows {
+        let label = if row.f0_hz.is_some() { "V" } else { "U" };
+        let start = (row.time_secs - half_hop).max(0.0);
+        let end = (row.time_secs + half_hop).min(xmax);
+        match voicing.last_mut() {
+            Some(last) if last.2 == label => last.1 = end,
+            _ => voicing.push((start, end, label)),
+        }
+    }
+
+    let breaks: Vec<(f32, f32, &str)> = table
+        .breaks
+        .iter()
+        .map(|&(start, end)| (start, end, "break"))
+        .collect();
+
+    let tiers = [
+        ("segment", segment),
+        ("voicing", voicing),
+        ("breaks", breaks),
+    ];
+
+    let mut out = String::new();
+    let _ = writeln!(out, "File type = \"ooTextFile\"");
+    let _ = writeln!(out, "Object class = \"TextGrid\"");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "xmin = 0 ");
+    let _ = writeln!(out, "xmax = {xmax:.4} ");
+    let _ = writeln!(out, "tiers? <exists> ");
+    let _ = writeln!(out, "size = {} ", tiers.len());
+    let _ = writeln!(out, "item []: ");
+    for (i, (name, spans)) in tiers.iter().enumerate() {
+        let intervals = fill_gaps(spans, xmax);
+        let _ = writeln!(out, "    item [{}]:", i + 1);
+        let _ = writeln!(out, "        class = \"IntervalTier\" ");
+        let _ = writeln!(out, "        name = \"{name}\" ");
+        let _ = writeln!(out, "        xmin = 0 ");
+        let _ = writeln!(out, "        xmax = {xmax:.4} ");
+        let _ = writeln!(out, "        intervals: size = {} ", intervals.len());
+        for (j, (start, end, text)) in intervals.iter().enumerate() {
+            let _ = writeln!(out, "        intervals [{}]:", j + 1);
+            let _ = writeln!(out, "            xmin = {start:.4} ");
+            let _ = writeln!(out, "            xmax = {end:.4} ");
+            let _ = writeln!(out, "            text = \"{text}\" ");
+        }
+    }
+    out
+}
+
+/// Praat interval tiers must cover [0, xmax] without gaps; pad the labelled
+/// spans with empty intervals.
+fn fill_gaps<'a>(spans: &[(f32, f32, &'a str)], xmax: f32) -> Vec<(f32, f32, &'a str)> {
+    let mut out = Vec::new();
+    let mut cursor = 0.0_f32;
+    for &(start, end, text) in spans {
+        let start = start.clamp(cursor, xmax);
+        let end = end.clamp(start, xmax);
+        if end <= start {
+            continue;
+        }
+        if start > cursor {
+            out.push((cursor, start, ""));
+        }
+        out.push((start, end, text));
+        cursor = end;
+    }
+    if cursor < xmax || out.is_empty() {
+        out.push((cursor, xmax, ""));
+    }
+    out
+}
+
+/// Export the frame-level contour of a recording as CSV, PitchTier or TextGrid.
+///
+/// Uses the take behind the session result (or `attempt` when given),
+/// falling back to the newest take when the day hasn't been analyzed.
+pub fn export_frames(
+    date: &str,
+    exercise: &str,
+    format: &str,
+    attempt: Option<u32>,
+    output: Option<&Path>,
+    config: &AppConfig,
+) -> Result<()> {
+    let extension = match format {
+        "csv" => "csv",
+        "pitchtier" => "PitchTier",
+        "textgrid" => "TextGrid",
+        other => anyhow::bail!("Unknown format '{other}'. Use csv, pitchtier, or textgrid."),
+    };
+    if !["sustained", "scale", "reading"].contains(&exercise) {
+        anyhow::bail!("Unknown exercise '{exercise}'. Use sustained, scale, or reading.");
+    }
+
+    let recording = recording_for(date, exercise, attempt)?;
+    let (samples, spec) = wav::load_samples(&recording)
+        .with_context(|| format!("Failed to load {}", recording.display()))?;
+
+    let pitch_config = config.analysis.pitch_config_for(exercise);
+    let table = build_frames(&samples, spec.sample_rate, exercise, &pitch_config);
+
+    let contents = match format {
+        "csv" => to_csv(&table),
+        "pitchtier" => to_pitch_tier(&table),
+        _ => to_text_grid(&table),
+    };
+
+    let path = match output {
+        Some(p) => p.to_path_buf(),
+        None => {
+            let dir = paths::reports_dir();
+            std::fs::create_dir_all(&dir)?;
+            dir.join(format!("frames_{date}_{exercise}.{extension}"))
+        }
+    };
+    std::fs::write(&path, contents)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    println!(
+        "Exported {} frames of {} to {}",
+        table.rows.len(),
+        style(recording.display()).cyan(),
+        style(path.display()).green()
+    );
+    if format != "csv" {
+        println!("  Open it in Praat together with the WAV to inspect the contour.");
+    }
+    Ok(())
+}
+
+/// WAV file to export: a specific take, the session's take, or the newest one.
+pub fn recording_for(date: &str, exercise: &str, attempt: Option<u32>) -> Result<PathBuf> {
+    let session = SessionId::parse(date)?;
+
+    if let Some(n) = attempt {
+        let path = paths::attempt_path(&session, exercise, n);
+        if !path.exists() {
+            anyhow::bail!("Recording not found: {}", path.display());
+        }
+        return Ok(path);
+    }
+
+    let session_path = store::load_session(date).ok().and_then(|s| match exercise {
+        "sustained" => s.recordings.sustained,
+        "scale" => s.recordings.scale,
+        _ => s.recordings.reading,
+    });
+    if let Some(path) = session_path.map(PathBuf::from).filter(|p| p.exists()) {
+        return Ok(path);
+    }
+
+    paths::latest_attempt_path(&session, exercise)
+        .with_context(|| format!("No {exercise} recording found for {date}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    const SR: u32 = 44100;
+
+    fn test_recording() -> Vec<f32> {
+        // 0.5s silence, 0.8s tone, 0.12s gap, 0.8s tone, 0.5s silence
+        let tone = |secs: f32| -> Vec<f32> {
+            (0..(SR as f32 * secs) as usize)
+                .map(|i| 0.3 * (2.0 * PI * 150.0 * i as f32 / SR as f32).sin())
+                .collect()
+        };
+        let silence = |secs: f32| vec![0.0; (SR as f32 * secs) as usize];
+
+        let mut samples = silence(0.5);
+        samples.extend(tone(0.8));
+        samples.extend(silence(0.12));
+        samples.extend(tone(0.8));
+        samples.extend(silence(0.5));
+        samples
+    }
+
+    fn table() -> FrameTable {
+        let config = pitch::PitchConfig::default();
+        build_frames(&test_recording(), SR, "reading", &config)
+    }
+
+    #[test]
+    fn frames_are_in_wav_time() {
+        let t = table();
+        let (start, end) = t.segment.unwrap();
+        assert!((start - 0.45).abs() < 0.05, "segment start {start}");
+        assert!(t.rows[0].time_secs > start, "first frame {}", t.rows[0].time_secs);
+
+        // Voiced frames fall inside the tones, not the leading silence
+        let first_voiced = t.rows.iter().find(|r| r.f0_hz.is_some()).unwrap();
+        assert!(first_voiced.time_secs > 0.45, "first voiced {}", first_voiced.time_secs);
+        assert!(end <= t.duration_secs);
+
+        let mid = t.rows.iter().find(|r| (r.time_secs - 0.9).abs() < 0.01).unwrap();
+        assert!((mid.f0_hz.unwrap() - 150.0).abs() < 3.0);
+        assert!(mid.active);
+        assert!(mid.periodicity.unwrap() > 0.9);
+        assert!(mid.rms_db > -15.0);
+    }
+
+    #[test]
+    fn scale_tiers_match_its_contour() {
+        let config = pitch::PitchConfig::default();
+        let t = build_frames(&test_recording(), SR, "scale", &config);
+        assert!(t.rows.iter().any(|r| r.f0_hz.is_some()));
+        for r in &t.rows {
+            assert_eq!(r.tier == 1, r.f0_hz.is_some(), "frame at {}s", r.time_secs);
+        }
+    }
+
+    #[test]
+    fn gap_between_tones_is_a_break() {
+        let t = table();
+        assert_eq!(t.breaks.len(), 1);
+        let (start, end) = t.breaks[0];
+        assert!(start > 1.2 && end < 1.5, "break {start}-{end}");
+    }
+
+    #[test]
+    fn csv_has_header_and_empty_cells() {
+        let t = table();
+        let csv = to_csv(&t);
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            "time_s,f0_hz,tier,active,rms_db,periodicity,hnr_db,cpps_db"
+        );
+        assert_eq!(csv.lines().count(), t.rows.len() + 1);
+        assert!(lines.all(|l| l.split(',').count() == 8));
+        assert!(csv.lines().any(|l| l.split(',').nth(1) == Some("")));
+    }
+
+    #[test]
+    fn pitch_tier_lists_voiced_points() {
+        let t = table();
+        let tier = to_pitch_tier(&t);
+        let voiced = t.rows.iter().filter(|r| r.f0_hz.is_some()).count();
+        assert!(tier.starts_with("File type = \"ooTextFile\"\nObject class = \"PitchTier\""));
+        assert!(tier.contains(&format!("points: size = {voiced} ")));
+        assert_eq!(tier.matches("    value = ").count(), voiced);
+    }
+
+    #[test]
+    fn text_grid_tiers_cover_recording() {
+        let t = table();
+        let grid = to_text_grid(&t);
+        assert!(grid.contains("size = 3 "));
+        assert!(grid.contains("text = \"analyzed\""));
+        assert!(grid.contains("text = \"V\""));
+        assert_eq!(grid.matches("text = \"break\"").count(), 1);
+    }
+
+    #[test]
+    fn fill_gaps_pads_and_clamps() {
+        let filled = fill_gaps(&[(0.5, 1.0, "a"), (0.9, 1.5, "b")], 2.0);
+        assert_eq!(
+            filled,
+            vec![(0.0, 0.5, ""), (0.5, 1.0, "a"), (1.0, 1.5, "b"), (1.5, 2.0, "")]
+        );
+        assert_eq!(fill_gaps(&[], 1.0), vec![(0.0, 1.0, "")]);
+    }
+}
@@ -0,0 +1,323 @@
+use anyhow::{bail, Context, Result};
+use chrono::{Days, NaiveDate};
+use console::style;
+
+use super::stats::{self, Direction};
+use crate::config::{GoalConfig, GoalDirection};
+use crate::storage::session_data::SessionData;
+
+/// Projections further out than this are treated as "not in sight".
+const MAX_PROJECTION_DAYS: f32 = 730.0;
+
+/// Width of the console progress bar, in characters.
+const BAR_WIDTH: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoalStatus {
+    Achieved,
+    /// Projected to reach the target by the deadline (or no deadline set).
+    OnTrack,
+    /// Projected to reach the target, but after the deadline.
+    Behind,
+    /// Deadline passed without reaching the target.
+    Overdue,
+    /// Too few sessions, or no trend toward the target.
+    Stalled,
+    /// No session has measured this metric.
+    NoData,
+}
+
+/// Where one goal stands against the stored sessions.
+pub struct GoalProgress {
+    pub name: &'static str,
+    pub unit: &'static str,
+    pub direction: GoalDirection,
+    pub target: f32,
+    pub deadline: Option<NaiveDate>,
+    /// Sessions with this metric.
+    pub n: usize,
+    /// First measured value, where progress is counted from.
+    pub baseline: Option<f32>,
+    pub latest: Option<(NaiveDate, f32)>,
+    /// Date the Theil–Sen trend reaches the target.
+    pub projected: Option<NaiveDate>,
+    pub status: GoalStatus,
+}
+
+impl GoalProgress {
+    /// e.g. "MPT ≥ 12.0 s"
+    pub fn label(&self) -> String {
+        let op = match self.direction {
+            GoalDirection::Above => "≥",
+            GoalDirection::Below => "≤",
+        };
+        format!("{} {op} {}", self.name, stats::format_value(self.target, self.unit))
+    }
+
+    /// Share of the way from the baseline to the target, 0.0–1.0.
+    pub fn fraction(&self) -> Option<f32> {
+        let (_, latest) = self.latest?;
+        if self.status == GoalStatus::Achieved {
+            return Some(1.0);
+        }
+        let baseline = self.baseline?;
+        let span = self.target - baseline;
+        let toward = match self.direction {
+            GoalDirection::Above => span > 0.0,
+            GoalDirection::Below => span < 0.0,
+        };
+        if !toward {
+            return Some(0.0);
+        }
+        Some(((latest - baseline) / span).clamp(0.0, 1.0))
+    }
+
+    /// Plain-language status, e.g. "on track, projected 2026-11-20".
+    pub fn status_text(&self) -> String {
+        let projected = |word: &str| match self.projected {
+            Some(date) => format!("{word}, projected {date}"),
+            None => word.to_string(),
+        };
+        match self.status {
+            GoalStatus::Achieved => "achieved".into(),
+            GoalStatus::OnTrack => projected("on track"),
+            GoalStatus::Behind => projected("behind schedule"),
+            GoalStatus::Overdue => "deadline passed".into(),
+            GoalStatus::Stalled if self.n < 3 => "too few sessions to project".into(),
+            GoalStatus::Stalled => "no trend toward target".into(),
+            GoalStatus::NoData => "no data".into(),
+        }
+    }
+}
+
+/// Evaluate every configured goal against the sessions (in date order).
+///
+/// `today` decides whether a deadline has passed.
+pub fn evaluate(goals: &[GoalConfig], sessions: &[SessionData], today: NaiveDate) -> Result<Vec<GoalProgress>> {
+    goals.iter().map(|g| evaluate_goal(g, sessions, today)).collect()
+}
+
+fn evaluate_goal(goal: &GoalConfig, sessions: &[SessionData], today: NaiveDate) -> Result<GoalProgress> {
+    let defs = stats::metric_defs();
+    let Some(def) = defs.iter().find(|d| d.key.eq_ignore_ascii_case(&goal.metric)) else {
+        let keys: Vec<&str> = defs.iter().map(|d| d.key).collect();
+        bail!("Unknown goal metric \"{}\" (expected one of: {})", goal.metric, keys.join(", "));
+    };
+
+    let direction = match (goal.direction, def.direction) {
+        (Some(d), _) => d,
+        (None, Direction::HigherIsBetter) => GoalDirection::Above,
+        (None, Direction::LowerIsBetter) => GoalDirection::Below,
+        (None, Direction::Neutral) => {
+            bail!("Goal for {} needs a direction (\"above\" or \"below\")", def.name)
+        }
+    };
+
+    let deadline = goal
+        .deadline
+        .as_deref()
+        .map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d"))
+        .transpose()
+        .with_context(|| format!("Invalid deadline for {} goal (expected YYYY-MM-DD)", def.name))?;
+
+    let points: Vec<(NaiveDate, f32)> = sessions
+        .iter()
+        .filter_map(|s| {
+            let date = NaiveDate::parse_from_str(&s.date, "%Y-%m-%d").ok()?;
+            Some((date, (def.extract)(s)?))
+        })
+        .collect();
+
+    let reached = |v: f32| match direction {
+        GoalDirection::Above => v >= goal.target,
+        GoalDirection::Below => v <= goal.target,
+    };
+
+    let latest = points.last().copied();
+    let projected = latest.and_then(|(date, value)| {
+        if reached(value) {
+            return None;
+        }
+        let slope = stats::analyze_series(def, &points)?.slope?;
+        let days = (goal.target - value) / slope.per_day;
+        // A negative (or infinite) result means the trend points away from the target
+        (days > 0.0 && days <= MAX_PROJECTION_DAYS).then(|| date + Days::new(days.ceil() as u64))
+    });
+
+    let status = match latest {
+        None => GoalStatus::NoData,
+        Some((_, value)) if reached(value) => GoalStatus::Achieved,
+        _ if deadline.is_some_and(|d| d < today) => GoalStatus::Overdue,
+        _ => match (projected, deadline) {
+            (None, _) => GoalStatus::Stalled,
+            (Some(p), Some(d)) if p > d => GoalStatus::Behind,
+            (Some(_), _) => GoalStatus::OnTrack,
+        },
+    };
+
+    Ok(GoalProgress {
+        name: def.name,
+        unit: def.unit,
+        direction,
+        target: goal.target,
+        deadline,
+        n: points.len(),
+        baseline: points.first().map(|p| p.1),
+        latest,
+        projected,
+        status,
+    })
+}
+
+/// Text progress bar, e.g. "[██████░░░░░░░░░░░░░░]".
+pub fn progress_bar(fraction: f32, width: usize) -> String {
+    let filled = (fraction.clamp(0.0, 1.0) * width as f32).round() as usize;
+    format!("[{}{}]", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+/// Print goal progress to the terminal, one line per goal.
+pub fn print_goals(progress: &[GoalProgress]) {
+    let width = progress.iter().map(|p| p.label().chars().count()).max().unwrap_or(0);
+    for p in progress {
+        let fraction = p.fraction();
+        let bar = progress_bar(fraction.unwrap_or(0.0), BAR_WIDTH);
+        let bar = match p.status {
+            GoalStatus::Achieved => style(bar).green(),
+            GoalStatus::OnTrack => style(bar).cyan(),
+            GoalStatus::NoData | GoalStatus::Stalled => style(bar).dim(),
+            GoalStatus::Behind | GoalStatus::Overdue => style(bar).yellow(),
+        };
+        let percent = fraction.map(|f| format!("{:>3.0}%", f * 100.0)).unwrap_or_else(|| "   —".into());
+        let latest = p
+            .latest
+            .map(|(_, v)| format!("now {}", stats::format_value(v, p.unit)))
+            .unwrap_or_default();
+        let deadline = p.deadline.map(|d| format!(" (deadline {d})")).unwrap_or_default();
+        println!(
+            "  {:width$}  {bar} {percent}  {latest:14} {}{}",
+            p.label(),
+            p.status_text(),
+            style(deadline).dim(),
+        );
+    }
+}
+
+/// Evaluate the goals as of today and print them under a heading.
+/// Prints nothing when no goals are configured.
+pub fn print_status(goals: &[GoalConfig], sessions: &[SessionData]) -> Result<()> {
+    if goals.is_empty() {
+        return Ok(());
+    }
+    let progress = evaluate(goals, sessions, chrono::Local::now().date_naive())?;
+    println!("{}", style("=== Recovery Goals ===").bold());
+    println!();
+    print_goals(&progress);
+    println!();
+    Ok(())
+}
+
+/// Markdown table of goal progress.
+pub fn markdown_section(progress: &[GoalProgress]) -> String {
+    let mut md = String::from("| Goal | Latest | Progress | Deadline | Status |\n");
+    md.push_str("|------|--------|----------|----------|--------|\n");
+    for p in progress {
+        md.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            p.label(),
+            p.latest.map(|(_, v)| stats::format_value(v, p.unit)).unwrap_or_else(|| "—".into()),
+            p.fraction().map(|f| format!("{:.0}%", f * 100.0)).unwrap_or_else(|| "—".into()),
+            p.deadline.map(|d| d.to_string()).unwrap_or_else(|| "—".into()),
+            p.status_text(),
+        ));
+    }
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::session_data::*;
+
+    fn session(day: u32, mpt: f32) -> SessionData {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap() + Days::new(day as u64);
+        let mut s = SessionData::test_default(&date.to_string());
+        s.analysis.sustained = Some(SustainedAnalysis { mpt_seconds: mpt, ..SustainedAnalysis::test_default() });
+        s
+    }
+
+    fn goal(metric: &str, target: f32, deadline: Option<&str>) -> GoalConfig {
+        GoalConfig {
+            metric: metric.into(),
+            target,
+            direction: None,
+            deadline: deadline.map(Into::into),
+        }
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, 10).unwrap()
+    }
+
+    #[test]
+    fn projects_achievement_date_from_trend() {
+        // MPT rises 0.5 s/day from 6 s: 10 s on day 8, 12 s on day 12
+        let sessions: Vec<SessionData> = (0..=8).map(|d| session(d, 6.0 + 0.5 * d as f32)).collect();
+        let p = &evaluate(&[goal("mpt", 12.0, Some("2026-04-01"))], &sessions, today()).unwrap()[0];
+
+        assert_eq!(p.label(), "MPT ≥ 12.0 s");
+        assert_eq!(p.direction, GoalDirection::Above);
+        assert_eq!(p.projected, NaiveDate::from_ymd_opt(2026, 3, 13));
+        assert_eq!(p.status, GoalStatus::OnTrack);
+        assert!((p.fraction().unwrap() - 4.0 / 6.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn behind_when_projection_passes_deadline() {
+        let sessions: Vec<SessionData> = (0..=8).map(|d| session(d, 6.0 + 0.5 * d as f32)).collect();
+        let p = &evaluate(&[goal("mpt", 12.0, Some("2026-03-12"))], &sessions, today()).unwrap()[0];
+        assert_eq!(p.status, GoalStatus::Behind);
+    }
+
+    #[test]
+    fn achieved_and_direction_from_metric() {
+        let sessions: Vec<SessionData> = (0..3).map(|d| session(d, 10.0)).collect();
+        let goals = [goal("MPT", 8.0, None), goal("jitter", 1.5, None)];
+        let progress = evaluate(&goals, &sessions, today()).unwrap();
+        assert_eq!(progress[0].status, GoalStatus::Achieved);
+        assert_eq!(progress[0].fraction(), Some(1.0));
+        // Jitter defaults to "below" and 1.0% already meets 1.5%
+        assert_eq!(progress[1].direction, GoalDirection::Below);
+        assert_eq!(progress[1].status, GoalStatus::Achieved);
+    }
+
+    #[test]
+    fn stalled_overdue_and_missing() {
+        let flat: Vec<SessionData> = (0..5).map(|d| session(d, 8.0)).collect();
+        let goals = [
+            goal("mpt", 12.0, None),
+            goal("mpt", 12.0, Some("2026-03-05")),
+            goal("sz_ratio", 1.2, None),
+        ];
+        let progress = evaluate(&goals, &flat, today()).unwrap();
+        assert_eq!(progress[0].status, GoalStatus::Stalled);
+        assert_eq!(progress[0].status_text(), "no trend toward target");
+        assert_eq!(progress[1].status, GoalStatus::Overdue);
+        assert_eq!(progress[2].status, GoalStatus::NoData);
+        assert_eq!(progress[2].fraction(), None);
+    }
+
+    #[test]
+    fn rejects_unknown_metric_and_bad_deadline() {
+        let sessions = vec![session(0, 8.0)];
+        assert!(evaluate(&[goal("loudness", 1.0, None)], &sessions, today()).is_err());
+        assert!(evaluate(&[goal("mpt", 12.0, Some("next month"))], &sessions, today()).is_err());
+        // Speaking F0 has no better direction
+        assert!(evaluate(&[goal("speaking_f0", 120.0, None)], &sessions, today()).is_err());
+    }
+
+    #[test]
+    fn progress_bar_fills() {
+        assert_eq!(progress_bar(0.5, 4), "[██░░]");
+        assert_eq!(progress_bar(1.5, 2), "[██]");
+    }
+}
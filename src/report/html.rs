@@ -0,0 +1,888 @@
+use std::path::{Component, Path};
+
+use anyhow::Result;
+use plotters::prelude::*;
+
+use super::charts;
+use super::adjust::{self, AdjustedTrend};
+use super::events;
+use super::goals::{GoalProgress, GoalStatus};
+use super::stats::{self, Verdict};
+use crate::config::AppConfig;
+use crate::norms::{self, Norm, NormMetric};
+use crate::report::stats::Direction;
+use crate::storage::session_data::{ClinicalEvent, Interpretation, SessionData};
+
+/// Chart dimensions (SVG pixels; the page scales them to its width)
+const WIDTH: u32 = 900;
+const HEIGHT: u32 = 300;
+
+const COLOR_PRIMARY: RGBColor = RGBColor(41, 128, 185); // blue
+const COLOR_SECONDARY: RGBColor = RGBColor(231, 76, 60); // red
+const COLOR_TERTIARY: RGBColor = RGBColor(46, 204, 113); // green
+const COLOR_EVENT: RGBColor = RGBColor(230, 126, 34); // orange
+
+/// Threshold band fills
+const BAND_BAD: RGBColor = RGBColor(255, 200, 200);
+const BAND_WARN: RGBColor = RGBColor(255, 240, 200);
+const BAND_GOOD: RGBColor = RGBColor(200, 255, 200);
+const BAND_NEUTRAL: RGBColor = RGBColor(225, 225, 235);
+
+/// One line on a metric chart.
+struct Series {
+    label: &'static str,
+    color: RGBColor,
+    values: Vec<Option<f32>>,
+    /// Sex- and age-matched norm, for z-scores in the table.
+    norm: Option<Norm>,
+}
+
+/// A shaded y-range with its meaning. Open ends use infinities.
+struct Band {
+    low: f32,
+    high: f32,
+    color: RGBColor,
+    label: String,
+}
+
+/// A report section: chart, threshold bands and a table of values.
+struct Metric {
+    title: &'static str,
+    unit: &'static str,
+    series: Vec<Series>,
+    bands: Vec<Band>,
+}
+
+impl Metric {
+    fn has_data(&self) -> bool {
+        self.series.iter().any(|s| s.values.iter().any(Option::is_some))
+    }
+}
+
+fn band(low: f32, high: f32, color: RGBColor, label: impl Into<String>) -> Band {
+    Band { low, high, color, label: label.into() }
+}
+
+/// Bands from a norm: typical (within 1 SD, or beyond on the better side),
+/// borderline (1–2 SD worse) and atypical (more than 2 SD worse).
+fn norm_bands(norm: Option<Norm>, unit: &str) -> Vec<Band> {
+    let Some(n) = norm else {
+        return Vec::new();
+    };
+    let inf = f32::INFINITY;
+    let v = |x: f32| stats::format_value(x, unit);
+    let (m, sd) = (n.mean, n.sd);
+    match n.direction {
+        Direction::HigherIsBetter => vec![
+            band(-inf, m - 2.0 * sd, BAND_BAD, format!("<{} atypical", v(m - 2.0 * sd))),
+            band(m - 2.0 * sd, m - sd, BAND_WARN, "borderline (1–2 SD below)"),
+            band(m - sd, inf, BAND_GOOD, format!("≥{} typical", v(m - sd))),
+        ],
+        Direction::LowerIsBetter => vec![
+            band(-inf, m + sd, BAND_GOOD, format!("≤{} typical", v(m + sd))),
+            band(m + sd, m + 2.0 * sd, BAND_WARN, "borderline (1–2 SD above)"),
+            band(m + 2.0 * sd, inf, BAND_BAD, format!(">{} atypical", v(m + 2.0 * sd))),
+        ],
+        Direction::Neutral => vec![
+            band(m - 2.0 * sd, m - sd, BAND_WARN, "1–2 SD below"),
+            band(m - sd, m + sd, BAND_NEUTRAL, format!("typical {}–{}", v(m - sd), v(m + sd))),
+            band(m + sd, m + 2.0 * sd, BAND_WARN, "1–2 SD above"),
+        ],
+    }
+}
+
+/// Per-metric sections, in the same order as the PNG trend chart.
+fn metrics(sessions: &[SessionData], config: &AppConfig) -> Vec<Metric> {
+    let norm = |m: NormMetric| norms::lookup(m, &config.profile);
+    let values = |f: &dyn Fn(&SessionData) -> Option<f32>| -> Vec<Option<f32>> {
+        sessions.iter().map(f).collect()
+    };
+    let inf = f32::INFINITY;
+
+    vec![
+        Metric {
+            title: "Voice Quality Index",
+            unit: "",
+            series: vec![Series {
+                label: "VQI",
+                color: COLOR_PRIMARY,
+                values: values(&charts::compute_voice_quality_index),
+                norm: None,
+            }],
+            bands: vec![
+                band(0.0, 33.0, BAND_BAD, "Poor"),
+                band(33.0, 66.0, BAND_WARN, "Moderate"),
+                band(66.0, 100.0, BAND_GOOD, "Healthy"),
+            ],
+        },
+        Metric {
+            title: "Maximum Phonation Time",
+            unit: "s",
+            series: vec![Series {
+                label: "MPT",
+                color: COLOR_TERTIARY,
+                values: values(&|s| s.analysis.sustained.as_ref().map(|a| a.mpt_seconds)),
+                norm: norm(NormMetric::Mpt),
+            }],
+            bands: norm_bands(norm(NormMetric::Mpt), "s"),
+        },
+        Metric {
+            title: "Harmonics-to-Noise Ratio",
+            unit: "dB",
+            series: vec![Series {
+                label: "HNR",
+                color: COLOR_PRIMARY,
+                values: values(&|s| s.analysis.sustained.as_ref().map(|a| a.hnr_db)),
+                norm: norm(NormMetric::Hnr),
+            }],
+            bands: norm_bands(norm(NormMetric::Hnr), "dB"),
+        },
+        Metric {
+            title: "Jitter",
+            unit: "%",
+            series: vec![Series {
+                label: "Jitter",
+                color: COLOR_PRIMARY,
+                values: values(&|s| s.analysis.sustained.as_ref().map(|a| a.jitter_local_percent)),
+                norm: norm(NormMetric::Jitter),
+            }],
+            bands: norm_bands(norm(NormMetric::Jitter), "%"),
+        },
+        Metric {
+            title: "Shimmer",
+            unit: "%",
+            series: vec![Series {
+                label: "Shimmer",
+                color: COLOR_SECONDARY,
+                values: values(&|s| s.analysis.sustained.as_ref().map(|a| a.shimmer_local_percent)),
+                norm: norm(NormMetric::Shimmer),
+            }],
+            bands: norm_bands(norm(NormMetric::Shimmer), "%"),
+        },
+        Metric {
+            title: "Cepstral Peak Prominence (CPPS)",
+            unit: "dB",
+            series: vec![
+                Series {
+                    label: "Sustained",
+                    color: COLOR_PRIMARY,
+                    values: values(&|s| s.analysis.sustained.as_ref().and_then(|a| a.cpps_db)),
+                    norm: None,
+                },
+                Series {
+                    label: "Reading",
+                    color: COLOR_TERTIARY,
+                    values: values(&|s| s.analysis.reading.as_ref().and_then(|a| a.cpps_db)),
+                    norm: None,
+                },
+            ],
+            bands: vec![
+                band(-inf, 3.0, BAND_BAD, "<3 dB significant dysphonia"),
+                band(3.0, 5.0, BAND_WARN, "3–5 dB mild"),
+                band(5.0, inf, BAND_GOOD, ">5 dB normal voice"),
+            ],
+        },
+        Metric {
+            title: "Pitch Range",
+            unit: "Hz",
+            series: vec![
+                Series {
+                    label: "Floor",
+                    color: COLOR_PRIMARY,
+                    values: values(&|s| s.analysis.scale.as_ref().map(|a| a.pitch_floor_hz)),
+                    norm: None,
+                },
+                Series {
+                    label: "Ceiling",
+                    color: COLOR_SECONDARY,
+                    values: values(&|s| s.analysis.scale.as_ref().map(|a| a.pitch_ceiling_hz)),
+                    norm: None,
+                },
+            ],
+            bands: Vec::new(),
+        },
+        Metric {
+            title: "Voice Breaks (reading)",
+            unit: "",
+            series: vec![Series {
+                label: "Breaks",
+                color: COLOR_SECONDARY,
+                values: values(&|s| s.analysis.reading.as_ref().map(|a| a.voice_breaks as f32)),
+                norm: None,
+            }],
+            bands: vec![
+                band(-inf, 5.0, BAND_GOOD, "0–5 breaks"),
+                band(5.0, inf, BAND_BAD, ">5 concerning"),
+            ],
+        },
+        Metric {
+            title: "Mean Speaking F0 (reading)",
+            unit: "Hz",
+            series: vec![Series {
+                label: "Mean F0",
+                color: COLOR_PRIMARY,
+                values: values(&|s| s.analysis.reading.as_ref().map(|a| a.mean_f0_hz)),
+                norm: norm(NormMetric::SpeakingF0),
+            }],
+            bands: norm_bands(norm(NormMetric::SpeakingF0), "Hz"),
+        },
+        Metric {
+            title: "S/Z Ratio",
+            unit: "",
+            series: vec![Series {
+                label: "S/Z",
+                color: COLOR_PRIMARY,
+                values: values(&|s| s.analysis.sz.as_ref().map(|a| a.sz_ratio)),
+                norm: norm(NormMetric::SzRatio),
+            }],
+            bands: norm_bands(norm(NormMetric::SzRatio), ""),
+        },
+    ]
+}
+
+/// Render a metric as an inline SVG chart.
+///
+/// plotters has no notion of tooltips, so each point gets a transparent
+/// circle with a `<title>` appended after rendering, positioned with the
+/// chart's own coordinate mapping. Browsers show the title on hover.
+fn metric_svg(metric: &Metric, dates: &[&str], markers: &[(usize, String)]) -> Result<String> {
+    let n = dates.len().max(1);
+    let all: Vec<f32> = metric
+        .series
+        .iter()
+        .flat_map(|s| s.values.iter().flatten().copied())
+        .collect();
+    let (mut y_min, mut y_max) = min_max_with_margin(&all);
+    // Keep finite band edges close to the data in view
+    for b in &metric.bands {
+        for edge in [b.low, b.high] {
+            if edge.is_finite() && edge >= y_min - (y_max - y_min) && edge <= y_max + (y_max - y_min) {
+                y_min = y_min.min(edge);
+                y_max = y_max.max(edge);
+            }
+        }
+    }
+
+    let mut svg = String::new();
+    let mut tooltips = Vec::new();
+    {
+        let root = SVGBackend::with_string(&mut svg, (WIDTH, HEIGHT)).into_drawing_area();
+        root.fill(&WHITE)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .margin(10)
+            .x_label_area_size(30)
+            .y_label_area_size(50)
+            .build_cartesian_2d(0..n, y_min..y_max)?;
+
+        let step = (dates.len() / 8).max(1);
+        chart
+            .configure_mesh()
+            .x_labels(8)
+            .x_label_formatter(&|x| {
+                if x % step == 0 {
+                    dates.get(*x).map(|d| d.to_string()).unwrap_or_default()
+                } else {
+                    String::new()
+                }
+            })
+            .y_desc(metric.unit)
+            .draw()?;
+
+        for b in &metric.bands {
+            let (low, high) = (b.low.max(y_min), b.high.min(y_max));
+            if low < high {
+                chart.draw_series(std::iter::once(Rectangle::new(
+                    [(0, low), (n, high)],
+                    b.color.mix(0.6).filled(),
+                )))?;
+            }
+        }
+
+        // Clinical events as dashed vertical lines, named on hover
+        for (x, label) in markers {
+            chart.draw_series(DashedLineSeries::new(
+                vec![(*x, y_min), (*x, y_max)],
+                4,
+                3,
+                COLOR_EVENT.stroke_width(2),
+            ))?;
+            let (px, py) = chart.backend_coord(&(*x, y_max));
+            tooltips.push((px, py, label.clone()));
+        }
+
+        for series in &metric.series {
+            let points: Vec<(usize, f32)> = series
+                .values
+                .iter()
+                .enumerate()
+                .filter_map(|(i, v)| v.map(|v| (i, v)))
+                .collect();
+            if points.is_empty() {
+                continue;
+            }
+
+            let color = series.color;
+            chart
+                .draw_series(LineSeries::new(points.iter().copied(), color.stroke_width(2)))?
+                .label(series.label)
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2)));
+            chart.draw_series(points.iter().map(|&p| Circle::new(p, 4, color.filled())))?;
+
+            for &(i, v) in &points {
+                let (px, py) = chart.backend_coord(&(i, v));
+                tooltips.push((px, py, format!("{}: {} {}", dates[i], format_value(v), series.label)));
+            }
+        }
+
+        if metric.series.len() > 1 {
+            chart
+                .configure_series_labels()
+                .position(SeriesLabelPosition::UpperLeft)
+                .background_style(WHITE.mix(0.8))
+                .border_style(BLACK)
+                .draw()?;
+        }
+
+        root.present()?;
+    }
+
+    let overlay: String = tooltips
+        .iter()
+        .map(|(x, y, text)| {
+            format!(
+                "<circle class=\"pt\" cx=\"{x}\" cy=\"{y}\" r=\"8\"><title>{}</title></circle>",
+                escape(text)
+            )
+        })
+        .collect();
+    if let Some(end) = svg.rfind("</svg>") {
+        svg.insert_str(end, &overlay);
+    }
+    Ok(svg)
+}
+
+fn min_max_with_margin(values: &[f32]) -> (f32, f32) {
+    if values.is_empty() {
+        return (0.0, 1.0);
+    }
+    let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let margin = (max - min).max(1.0) * 0.1;
+    (min - margin, max + margin)
+}
+
+fn format_value(v: f32) -> String {
+    if v.fract() == 0.0 {
+        format!("{v:.0}")
+    } else {
+        format!("{v:.2}")
+    }
+}
+
+/// Generate a self-contained HTML report.
+///
+/// Charts are inline SVG with hover tooltips; audio players point at the
+/// session WAVs relative to `report_dir`, or carry the audio inline as
+/// base64 when `embed_audio` is set (larger file, but fully portable).
+pub fn generate_html_report(
+    sessions: &[SessionData],
+    events: &[ClinicalEvent],
+    config: &AppConfig,
+    interpretation: Option<&Interpretation>,
+    report_dir: &Path,
+    embed_audio: bool,
+) -> Result<String> {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>Voice Recovery — Trend Report</title>\n");
+    html.push_str(STYLE);
+    html.push_str("</head>\n<body>\n");
+    html.push_str("<h1>Voice Recovery — Trend Report</h1>\n");
+    html.push_str(&format!(
+        "<p class=\"meta\">Generated {} · {} session(s) · norms for {} (≈ marks approximate z-scores)</p>\n",
+        chrono::Local::now().format("%Y-%m-%d %H:%M"),
+        sessions.len(),
+        escape(&config.profile.describe())
+    ));
+
+    if sessions.is_empty() {
+        html.push_str("<p>No sessions to report.</p>\n</body>\n</html>\n");
+        return Ok(html);
+    }
+
+    let dates: Vec<&str> = sessions.iter().map(|s| s.date.as_str()).collect();
+
+    // Table of contents
+    let metrics: Vec<Metric> = metrics(sessions, config)
+        .into_iter()
+        .filter(Metric::has_data)
+        .collect();
+    let markers: Vec<(usize, String)> = events::session_positions(&dates, events)
+        .into_iter()
+        .map(|(i, list)| {
+            let text = list.iter().map(|e| format!("{} {}: {}", e.date, e.kind, e.note)).collect::<Vec<_>>();
+            (i, text.join("\n"))
+        })
+        .collect();
+    let goals = super::goals::evaluate(&config.goals, sessions, chrono::Local::now().date_naive())?;
+    let trends = stats::compute_trends(sessions);
+    let show_trends = trends.iter().any(|t| t.n >= 3);
+    html.push_str("<nav><ul>\n");
+    if !goals.is_empty() {
+        html.push_str("<li><a href=\"#goals\">Goals</a></li>\n");
+    }
+    if show_trends {
+        html.push_str("<li><a href=\"#trend-statistics\">Trend statistics</a></li>\n");
+    }
+    let adjusted = adjust::compute_adjusted_trends(sessions);
+    if !adjusted.is_empty() {
+        html.push_str("<li><a href=\"#condition-effects\">Condition effects</a></li>\n");
+    }
+    if !events.is_empty() {
+        html.push_str("<li><a href=\"#events\">Clinical events</a></li>\n");
+    }
+    for (i, m) in metrics.iter().enumerate() {
+        html.push_str(&format!("<li><a href=\"#metric-{i}\">{}</a></li>\n", escape(m.title)));
+    }
+    html.push_str("<li><a href=\"#conditions\">Conditions</a></li>\n");
+    html.push_str("<li><a href=\"#recordings\">Recordings</a></li>\n");
+    if interpretation.is_some() {
+        html.push_str("<li><a href=\"#interpretation\">Interpretation</a></li>\n");
+    }
+    html.push_str("</ul></nav>\n");
+
+    if !goals.is_empty() {
+        html.push_str(&goals_section(&goals));
+    }
+    if show_trends {
+        html.push_str(&trend_statistics_section(&trends));
+    }
+    if !adjusted.is_empty() {
+        html.push_str(&condition_effects_section(&adjusted));
+    }
+    if !events.is_empty() {
+        html.push_str(&events_section(events));
+    }
+
+    for (i, metric) in metrics.iter().enumerate() {
+        html.push_str(&format!("<section id=\"metric-{i}\">\n<h2>{}</h2>\n", escape(metric.title)));
+        html.push_str(&format!("<div class=\"chart\">{}</div>\n", metric_svg(metric, &dates, &markers)?));
+        if !metric.bands.is_empty() {
+            html.push_str("<p class=\"bands\">");
+            for b in &metric.bands {
+                let RGBColor(r, g, bl) = b.color;
+                html.push_str(&format!(
+                    "<span class=\"swatch\" style=\"background:rgb({r},{g},{bl})\"></span>{} ",
+                    escape(&b.label)
+                ));
+            }
+            html.push_str("</p>\n");
+        }
+        html.push_str(&metric_table(metric, &dates));
+        html.push_str("</section>\n");
+    }
+
+    html.push_str(&conditions_section(sessions));
+    html.push_str(&recordings_section(sessions, report_dir, embed_audio));
+    if let Some(interp) = interpretation {
+        html.push_str(&interpretation_section(interp));
+    }
+
+    html.push_str("</body>\n</html>\n");
+    Ok(html)
+}
+
+fn metric_table(metric: &Metric, dates: &[&str]) -> String {
+    let mut t = String::from("<details><summary>Values</summary>\n<table>\n<tr><th>Date</th>");
+    for s in &metric.series {
+        let unit = if metric.unit.is_empty() { String::new() } else { format!(" ({})", metric.unit) };
+        t.push_str(&format!("<th>{}{}</th>", escape(s.label), escape(&unit)));
+    }
+    t.push_str("</tr>\n");
+    for (i, date) in dates.iter().enumerate() {
+        if metric.series.iter().all(|s| s.values[i].is_none()) {
+            continue;
+        }
+        t.push_str(&format!("<tr><td>{date}</td>"));
+        for s in &metric.series {
+            let cell = match (s.values[i], s.norm) {
+                (Some(v), Some(n)) => format!("{} <span class=\"z\">(z {})</span>", format_value(v), n.z_text(v)),
+                (Some(v), None) => format_value(v),
+                (None, _) => "—".into(),
+            };
+            t.push_str(&format!("<td>{cell}</td>"));
+        }
+        t.push_str("</tr>\n");
+    }
+    t.push_str("</table>\n</details>\n");
+    t
+}
+
+fn goals_section(goals: &[GoalProgress]) -> String {
+    let mut html = String::from("<section id=\"goals\">\n<h2>Recovery goals</h2>\n");
+    html.push_str(
+        "<p class=\"meta\">Progress is measured from the first session; projected dates extend \
+         the Theil–Sen trend.</p>\n",
+    );
+    html.push_str(
+        "<table>\n<tr><th>Goal</th><th>Latest</th><th>Progress</th><th>Deadline</th><th>Status</th></tr>\n",
+    );
+    let dash = || "—".to_string();
+    for g in goals {
+        let class = match g.status {
+            GoalStatus::Achieved => " class=\"improved\"",
+            GoalStatus::Behind | GoalStatus::Overdue => " class=\"worsened\"",
+            _ => "",
+        };
+        let progress = g
+            .fraction()
+            .map(|f| format!("<progress max=\"100\" value=\"{0:.0}\"></progress> {0:.0}%", f * 100.0))
+            .unwrap_or_else(dash);
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{progress}</td><td>{}</td><td{class}>{}</td></tr>\n",
+            escape(&g.label()),
+            g.latest.map(|(_, v)| stats::format_value(v, g.unit)).unwrap_or_else(dash),
+            g.deadline.map(|d| d.to_string()).unwrap_or_else(dash),
+            escape(&g.status_text()),
+        ));
+    }
+    html.push_str("</table>\n</section>\n");
+    html
+}
+
+fn trend_statistics_section(trends: &[stats::MetricTrend]) -> String {
+    let mut html = String::from("<section id=\"trend-statistics\">\n<h2>Trend statistics</h2>\n");
+    html.push_str(
+        "<p class=\"meta\">Theil–Sen slopes per week with 95% CI (* marks a CI excluding zero). MDC is the minimal detectable \
+         change (95%) from day-to-day variability; smaller changes are measurement noise.</p>\n",
+    );
+    html.push_str(
+        "<table>\n<tr><th>Metric</th><th>Sessions</th><th>Slope (95% CI)</th><th>Rolling median</th>\
+         <th>MDC</th><th>Latest vs baseline</th><th>Change point</th></tr>\n",
+    );
+    let dash = || "—".to_string();
+    for t in trends {
+        let class = match t.verdict() {
+            Verdict::Improved => " class=\"improved\"",
+            Verdict::Worsened => " class=\"worsened\"",
+            _ => "",
+        };
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td{class}>{}</td><td>{}</td></tr>\n",
+            escape(t.name),
+            t.n,
+            t.slope.as_ref().map(|s| stats::format_slope(s, t.unit)).unwrap_or_else(dash),
+            t.rolling_median.last().map(|&(_, v)| stats::format_value(v, t.unit)).unwrap_or_else(dash),
+            t.mdc.map(|m| stats::format_value(m, t.unit)).unwrap_or_else(dash),
+            stats::format_change(t).map(|c| escape(&c)).unwrap_or_else(dash),
+            t.change_point
+                .as_ref()
+                .map(|c| escape(&format!(
+                    "{} ({} → {})",
+                    c.date,
+                    stats::format_value(c.before, t.unit),
+                    stats::format_value(c.after, t.unit)
+                )))
+                .unwrap_or_else(dash),
+        ));
+    }
+    html.push_str("</table>\n<ul>\n");
+    for t in trends {
+        html.push_str(&format!("<li>{}</li>\n", escape(&t.summary())));
+    }
+    html.push_str("</ul>\n</section>\n");
+    html
+}
+
+fn condition_effects_section(trends: &[AdjustedTrend]) -> String {
+    let mut html = String::from("<section id=\"condition-effects\">\n<h2>Condition effects</h2>\n");
+    html.push_str(
+        "<p class=\"meta\">Least-squares fit of each metric on time plus recorded conditions \
+         (sessions with conditions only). Effects hold time and the other conditions fixed.</p>\n",
+    );
+    html.push_str(
+        "<table>\n<tr><th>Metric</th><th>Sessions</th><th>Raw slope</th>\
+         <th>Adjusted slope (95% CI)</th><th>Condition effects</th></tr>\n",
+    );
+    for t in trends {
+        let unit = if t.unit.is_empty() { String::new() } else { format!(" {}", t.unit) };
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{:+.2}{}/wk</td><td>{}</td><td>{}</td></tr>\n",
+            escape(t.name),
+            t.n,
+            t.raw_slope * 7.0,
+            escape(&unit),
+            escape(&stats::format_slope(&t.adjusted_slope, t.unit)),
+            escape(&t.effects_text()),
+        ));
+    }
+    html.push_str("</table>\n</section>\n");
+    html
+}
+
+fn events_section(events: &[ClinicalEvent]) -> String {
+    let mut html = String::from("<section id=\"events\">\n<h2>Clinical events</h2>\n");
+    html.push_str(
+        "<p class=\"meta\">Shown on the charts as orange dashed lines at the first session on or after each event.</p>\n",
+    );
+    html.push_str("<table>\n<tr><th>Date</th><th>Event</th><th>Note</th></tr>\n");
+    for e in events {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td class=\"note\">{}</td></tr>\n",
+            escape(&e.date),
+            escape(&e.kind),
+            escape(&e.note)
+        ));
+    }
+    html.push_str("</table>\n</section>\n");
+    html
+}
+
+fn conditions_section(sessions: &[SessionData]) -> String {
+    let mut html = String::from("<section id=\"conditions\">\n<h2>Conditions</h2>\n");
+    let with_conditions: Vec<_> = sessions
+        .iter()
+        .filter_map(|s| s.conditions.as_ref().map(|c| (s.date.as_str(), c)))
+        .collect();
+
+    if with_conditions.is_empty() {
+        html.push_str("<p>No recording conditions were logged.</p>\n</section>\n");
+        return html;
+    }
+
+    html.push_str("<table>\n<tr><th>Date</th><th>Time of day</th><th>Fatigue</th><th>Throat cleared</th><th>Mucus</th><th>Hydration</th><th>Notes</th></tr>\n");
+    for (date, c) in with_conditions {
+        html.push_str(&format!(
+            "<tr><td>{date}</td><td>{}</td><td>{}/10</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape(&c.time_of_day),
+            c.fatigue_level,
+            if c.throat_cleared { "yes" } else { "no" },
+            escape(&c.mucus_level),
+            escape(&c.hydration),
+            escape(c.notes.as_deref().unwrap_or("")),
+        ));
+    }
+    html.push_str("</table>\n</section>\n");
+    html
+}
+
+fn recordings_section(sessions: &[SessionData], report_dir: &Path, embed_audio: bool) -> String {
+    let mut html = String::from("<section id=\"recordings\">\n<h2>Recordings</h2>\n");
+    let mut any = false;
+
+    for session in sessions.iter().rev() {
+        let recordings = [
+            ("Sustained", session.recordings.sustained.as_deref()),
+            ("Scale", session.recordings.scale.as_deref()),
+            ("Reading", session.recordings.reading.as_deref()),
+        ];
+        let players: Vec<String> = recordings
+            .iter()
+            .filter_map(|(name, path)| {
+                let path = Path::new((*path)?);
+                let src = audio_source(path, report_dir, embed_audio)?;
+                Some(format!(
+                    "<figure><figcaption>{name}</figcaption><audio controls preload=\"none\" src=\"{}\"></audio></figure>",
+                    escape(&src)
+                ))
+            })
+            .collect();
+
+        if players.is_empty() {
+            continue;
+        }
+        any = true;
+        html.push_str(&format!(
+            "<div class=\"session\"><h3>{}</h3>{}</div>\n",
+            session.date,
+            players.join("")
+        ));
+    }
+
+    if !any {
+        html.push_str("<p>No recordings found.</p>\n");
+    }
+    html.push_str("</section>\n");
+    html
+}
+
+/// `src` for an audio player: inline base64, or a link relative to the
+/// report. None when embedding was requested but the file can't be read.
+fn audio_source(path: &Path, report_dir: &Path, embed_audio: bool) -> Option<String> {
+    if embed_audio {
+        let bytes = std::fs::read(path).ok()?;
+        let mime = if crate::audio::flac::is_flac(path) { "audio/flac" } else { "audio/wav" };
+        return Some(format!("data:{mime};base64,{}", base64_encode(&bytes)));
+    }
+    Some(relative_link(report_dir, path))
+}
+
+/// URL of `target` relative to `from_dir`, falling back to a file:// URL
+/// when the two don't share a root.
+fn relative_link(from_dir: &Path, target: &Path) -> String {
+    let from: Vec<Component> = from_dir.components().collect();
+    let to: Vec<Component> = target.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    if common == 0 {
+        return format!("file://{}", url_encode(&target.to_string_lossy()));
+    }
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    parts.extend(to[common..].iter().map(|c| c.as_os_str().to_string_lossy().into_owned()));
+    url_encode(&parts.join("/"))
+}
+
+/// Percent-encode the characters that break a URL path.
+fn url_encode(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for c in path.chars() {
+        match c {
+            ' ' => out.push_str("%20"),
+            '#' => out.push_str("%23"),
+            '?' => out.push_str("%3F"),
+            '%' => out.push_str("%25"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn interpretation_section(interp: &Interpretation) -> String {
+    format!(
+        "<section id=\"interpretation\">\n<h2>Latest Interpretation</h2>\n\
+         <p class=\"meta\">Session {} · {} ({}) · generated {} UTC</p>\n\
+         <div class=\"interp\">{}</div>\n</section>\n",
+        escape(&interp.date),
+        escape(&interp.provider),
+        escape(&interp.model),
+        escape(&interp.created_at),
+        escape(&interp.content),
+    )
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Standard base64 (RFC 4648) with padding.
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 { ALPHABET[(n >> 6) as usize & 63] as char } else { '=' });
+        out.push(if chunk.len() > 2 { ALPHABET[n as usize & 63] as char } else { '=' });
+    }
+    out
+}
+
+const STYLE: &str = "<style>
+body { font-family: -apple-system, 'Segoe UI', sans-serif; max-width: 960px; margin: 2em auto; padding: 0 1em; color: #222; }
+h1 { margin-bottom: 0.2em; }
+h2 { border-bottom: 1px solid #ddd; padding-bottom: 0.2em; margin-top: 2em; }
+.meta { color: #777; }
+nav ul { list-style: none; padding: 0; display: flex; flex-wrap: wrap; gap: 0.5em 1.2em; }
+.chart svg { width: 100%; height: auto; }
+circle.pt { fill: transparent; cursor: pointer; }
+circle.pt:hover { fill: rgba(0, 0, 0, 0.15); }
+.bands { font-size: 0.9em; color: #555; }
+.swatch { display: inline-block; width: 0.9em; height: 0.9em; margin: 0 0.3em 0 0.8em; vertical-align: middle; border: 1px solid #bbb; }
+table { border-collapse: collapse; margin: 0.5em 0; }
+th, td { border: 1px solid #ddd; padding: 0.25em 0.6em; text-align: right; }
+th:first-child, td:first-child { text-align: left; }
+span.z { color: #777; font-size: 0.85em; }
+td.improved { color: #1a7f37; font-weight: 600; }
+td.worsened { color: #c62828; font-weight: 600; }
+td.note { text-align: left; }
+.session figure { display: inline-block; margin: 0 1em 0.5em 0; }
+.interp { white-space: pre-wrap; background: #f7f7f9; padding: 1em; border-radius: 4px; }
+</style>
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::session_data::*;
+
+    fn session(date: &str, mpt: Option<f32>) -> SessionData {
+        let mut s = SessionData::test_default(date);
+        s.recordings.sustained = Some(format!("/data/voicevo/recordings/{date}/sustained_001.wav"));
+        s.analysis.sustained = mpt.map(|mpt_seconds| SustainedAnalysis { mpt_seconds, ..SustainedAnalysis::test_default() });
+        s
+    }
+
+    #[test]
+    fn report_has_charts_tooltips_and_players() {
+        let sessions = vec![session("2026-02-01", Some(8.0)), session("2026-02-08", Some(11.5))];
+        let interp = Interpretation {
+            date: "2026-02-08".into(),
+            provider: "claude".into(),
+            model: "test".into(),
+            content: "MPT <improved>".into(),
+            created_at: "2026-02-08 10:00:00".into(),
+        };
+        let events = vec![ClinicalEvent {
+            id: 1,
+            date: "2026-02-05".into(),
+            kind: "injection".into(),
+            note: "Injection <laryngoplasty>".into(),
+        }];
+        let html = generate_html_report(
+            &sessions,
+            &events,
+            &AppConfig::default(),
+            Some(&interp),
+            Path::new("/data/voicevo/reports"),
+            false,
+        )
+        .unwrap();
+
+        assert!(html.contains("<h2>Maximum Phonation Time</h2>"));
+        assert!(html.contains("<title>2026-02-08: 11.50 MPT</title>"));
+        assert!(html.contains("&lt;8.0 s atypical"));
+        // Metrics without data are left out
+        assert!(!html.contains("<h2>S/Z Ratio</h2>"));
+        assert!(html.contains("src=\"../recordings/2026-02-08/sustained_001.wav\""));
+        assert!(html.contains("MPT &lt;improved&gt;"));
+        // Events are listed and marked on the charts at the next session
+        assert!(html.contains("<td>injection</td><td class=\"note\">Injection &lt;laryngoplasty&gt;</td>"));
+        assert!(html.contains("<title>2026-02-05 injection: Injection &lt;laryngoplasty&gt;</title>"));
+        assert_eq!(html.matches("<svg").count(), html.matches("</svg>").count());
+    }
+
+    #[test]
+    fn relative_links() {
+        assert_eq!(
+            relative_link(Path::new("/a/b/reports"), Path::new("/a/b/rec dir/x.wav")),
+            "../rec%20dir/x.wav"
+        );
+        assert_eq!(relative_link(Path::new("reports"), Path::new("/abs/x.wav")), "file:///abs/x.wav");
+    }
+
+    #[test]
+    fn base64_matches_rfc_vectors() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+}
@@ -1,12 +1,13 @@
 use anyhow::Result;
 
 use crate::config::AppConfig;
-use crate::storage::session_data::SessionData;
+use crate::norms::{self, Norm, NormMetric, Standing};
+use crate::storage::session_data::{ClinicalEvent, SessionData};
 
 /// Generate a markdown trend report from a list of sessions.
 ///
 /// Returns the markdown content as a string. The caller decides where to save it.
-pub fn generate_report(sessions: &[SessionData], config: &AppConfig) -> Result<String> {
+pub fn generate_report(sessions: &[SessionData], events: &[ClinicalEvent], config: &AppConfig) -> Result<String> {
     let mut md = String::new();
 
     md.push_str("# Voice Recovery — Trend Report\n\n");
@@ -23,12 +24,33 @@ pub fn generate_report(sessions: &[SessionData], config: &AppConfig) -> Result<S
 
     md.push_str("---\n\n");
 
+    // Latest session against sex- and age-matched norms
+    if let Some(latest) = sessions.last() {
+        let table = norms::markdown_table(latest, &config.profile);
+        if !table.is_empty() {
+            md.push_str(&format!("## Normative Comparison ({})\n\n", latest.date));
+            md.push_str(&format!(
+                "Healthy adult norms for: {}. \u{26a0} marks values more than 1 SD from the \
+                 mean on the worse side (also in the tables below).\n\n",
+                config.profile.describe()
+            ));
+            md.push_str(&table);
+            md.push('\n');
+        }
+    }
+
+    if !events.is_empty() {
+        md.push_str("## Clinical Events\n\n");
+        md.push_str(&super::events::markdown_section(events));
+        md.push('\n');
+    }
+
     // Sustained vowel metrics table
     md.push_str("## Sustained Vowel Metrics\n\n");
     md.push_str("| Date | MPT (s) | Mean F0 (Hz) | Jitter (%) | Shimmer (%) | HNR (dB) | CPPS (dB) | Periodicity | Quality |\n");
     md.push_str("|------|---------|-------------|-----------|------------|----------|----------|------------|----------|\n");
 
-    let thresholds = &config.analysis.thresholds;
+    let norm = |m: NormMetric| norms::lookup(m, &config.profile);
 
     for session in sessions {
         if let Some(ref s) = session.analysis.sustained {
@@ -40,16 +62,17 @@ pub fn generate_report(sessions: &[SessionData], config: &AppConfig) -> Result<S
             let cpps_str = s.cpps_db.map(|c| format!("{c:.1}")).unwrap_or_else(|| "—".into());
             let period_str = s.periodicity_mean.map(|p| format!("{p:.2}")).unwrap_or_else(|| "—".into());
             md.push_str(&format!(
-                "| {} | {:.1} | {:.1} | {:.2}{} | {:.2}{} | {:.1}{} | {} | {} | {} |\n",
+                "| {} | {:.1}{} | {:.1} | {:.2}{} | {:.2}{} | {:.1}{} | {} | {} | {} |\n",
                 session.date,
                 s.mpt_seconds,
+                flag(norm(NormMetric::Mpt), s.mpt_seconds),
                 s.mean_f0_hz,
                 s.jitter_local_percent,
-                flag_high(s.jitter_local_percent, thresholds.jitter_pathological),
+                flag(norm(NormMetric::Jitter), s.jitter_local_percent),
                 s.shimmer_local_percent,
-                flag_high(s.shimmer_local_percent, thresholds.shimmer_pathological),
+                flag(norm(NormMetric::Shimmer), s.shimmer_local_percent),
                 s.hnr_db,
-                flag_low(s.hnr_db, thresholds.hnr_low),
+                flag(norm(NormMetric::Hnr), s.hnr_db),
                 cpps_str,
                 period_str,
                 quality,
@@ -91,9 +114,10 @@ pub fn generate_report(sessions: &[SessionData], config: &AppConfig) -> Result<S
                 .unwrap_or_else(|| r.detection_quality.as_deref().unwrap_or("pitch"));
             let cpps_str = r.cpps_db.map(|c| format!("{c:.1}")).unwrap_or_else(|| "—".into());
             md.push_str(&format!(
-                "| {} | {:.1} | {:.1} | {} | {:.0} | {} | {} |\n",
+                "| {} | {:.1}{} | {:.1} | {} | {:.0} | {} | {} |\n",
                 session.date,
                 r.mean_f0_hz,
+                flag(norm(NormMetric::SpeakingF0), r.mean_f0_hz),
                 r.f0_std_hz,
                 r.voice_breaks,
                 r.voiced_fraction * 100.0,
@@ -104,6 +128,31 @@ pub fn generate_report(sessions: &[SessionData], config: &AppConfig) -> Result<S
     }
     md.push('\n');
 
+    // MPT trials table (best-of-N protocol)
+    let has_trials = sessions
+        .iter()
+        .any(|s| s.analysis.sustained.as_ref().is_some_and(|a| a.trials.is_some()));
+    if has_trials {
+        md.push_str("## MPT Trials\n\n");
+        md.push_str("| Date | Trials | Max MPT (s) | Mean MPT (s) | Best take |\n");
+        md.push_str("|------|--------|-------------|--------------|-----------|\n");
+
+        for session in sessions {
+            let Some(t) = session.analysis.sustained.as_ref().and_then(|a| a.trials.as_ref()) else {
+                continue;
+            };
+            md.push_str(&format!(
+                "| {} | {} | {:.1} | {:.1} | {} |\n",
+                session.date,
+                t.attempts.len(),
+                t.mpt_max_seconds,
+                t.mpt_mean_seconds,
+                t.source,
+            ));
+        }
+        md.push('\n');
+    }
+
     // S/Z ratio table
     let has_sz = sessions.iter().any(|s| s.analysis.sz.is_some());
     if has_sz {
@@ -208,24 +257,73 @@ pub fn generate_report(sessions: &[SessionData], config: &AppConfig) -> Result<S
         md.push('\n');
     }
 
+    if !config.goals.is_empty() {
+        let progress = super::goals::evaluate(&config.goals, sessions, chrono::Local::now().date_naive())?;
+        md.push_str("## Recovery Goals\n\n");
+        md.push_str(
+            "Progress is measured from the first session; projected dates extend the Theil–Sen trend.\n\n",
+        );
+        md.push_str(&super::goals::markdown_section(&progress));
+        md.push('\n');
+    }
+
+    let trends = super::stats::compute_trends(sessions);
+    if trends.iter().any(|t| t.n >= 3) {
+        md.push_str("## Trend Statistics\n\n");
+        md.push_str(
+            "Slopes are Theil–Sen estimates per week; MDC is the minimal detectable change \
+             (95%) from day-to-day variability. Changes smaller than the MDC are measurement noise.\n\n",
+        );
+        md.push_str(&super::stats::markdown_section(&trends));
+        md.push('\n');
+    }
+
+    let adjusted = super::adjust::compute_adjusted_trends(sessions);
+    if !adjusted.is_empty() {
+        md.push_str("## Condition-Adjusted Trends\n\n");
+        md.push_str(
+            "Least-squares fit of each metric on time plus recorded conditions (sessions with \
+             conditions only). Effects hold time and the other conditions fixed.\n\n",
+        );
+        md.push_str(&super::adjust::markdown_section(&adjusted));
+        md.push('\n');
+    }
+
     Ok(md)
 }
 
-/// Append a flag marker if value exceeds threshold (higher is worse).
-fn flag_high(value: f32, threshold: f32) -> &'static str {
-    if value > threshold {
-        " \u{26a0}" // ⚠
-    } else {
-        ""
+/// Append a flag marker if the value falls outside the typical range on
+/// the worse side of its norm.
+fn flag(norm: Option<Norm>, value: f32) -> &'static str {
+    match norm.map(|n| n.standing(value)) {
+        Some(Standing::Borderline | Standing::Atypical) => " \u{26a0}", // ⚠
+        _ => "",
+    }
+}
+
+/// Markdown section embedding the per-recording spectrogram images.
+///
+/// `images` holds (exercise, file name) pairs; the files are expected next
+/// to the report so the links are relative.
+pub fn recording_plots_section(date: &str, images: &[(String, String)]) -> String {
+    if images.is_empty() {
+        return String::new();
+    }
+
+    let mut md = format!("## Recordings ({date})\n\n");
+    md.push_str("Spectrogram with the pitch contour (blue = standard detection, orange = relaxed, red = energy fallback). ");
+    md.push_str("Red bands are voice breaks; the green strip shows detected activity and the box the analyzed segment.\n\n");
+    for (exercise, file) in images {
+        md.push_str(&format!("### {}\n\n![{exercise} {date}]({file})\n\n", capitalize(exercise)));
     }
+    md
 }
 
-/// Append a flag marker if value is below threshold (lower is worse).
-fn flag_low(value: f32, threshold: f32) -> &'static str {
-    if value < threshold {
-        " \u{26a0}" // ⚠
-    } else {
-        ""
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
     }
 }
 
@@ -237,6 +335,7 @@ mod tests {
     fn sample_session(date: &str, hnr: f32, mpt: f32) -> SessionData {
         SessionData {
             date: date.into(),
+            slot: 1,
             recordings: SessionRecordings {
                 sustained: Some(format!("data/recordings/{date}/sustained.wav")),
                 scale: None,
@@ -254,6 +353,9 @@ mod tests {
                     periodicity_mean: None,
                     detection_quality: None,
                     reliability: None,
+                    trials: None,
+                    recording_quality: None,
+                    segment: None,
                 }),
                 scale: None,
                 reading: None,
@@ -261,6 +363,7 @@ mod tests {
                 fatigue: None,
             },
             conditions: None,
+            source: None,
         }
     }
 
@@ -272,7 +375,7 @@ mod tests {
         ];
 
         let config = AppConfig::default();
-        let md = generate_report(&sessions, &config).unwrap();
+        let md = generate_report(&sessions, &[], &config).unwrap();
 
         assert!(md.contains("Voice Recovery"));
         assert!(md.contains("2026-02-01"));
@@ -288,16 +391,120 @@ mod tests {
         ];
 
         let config = AppConfig::default();
-        let md = generate_report(&sessions, &config).unwrap();
+        let md = generate_report(&sessions, &[], &config).unwrap();
 
         assert!(md.contains("improved"));
         assert!(md.contains("breathiness is decreasing"));
     }
 
+    #[test]
+    fn trend_statistics_need_three_sessions() {
+        let config = AppConfig::default();
+        let mut sessions = vec![
+            sample_session("2026-02-01", 8.0, 5.0),
+            sample_session("2026-02-08", 12.0, 7.0),
+        ];
+        assert!(!generate_report(&sessions, &[], &config).unwrap().contains("## Trend Statistics"));
+
+        sessions.push(sample_session("2026-02-15", 13.0, 8.0));
+        let md = generate_report(&sessions, &[], &config).unwrap();
+        assert!(md.contains("## Trend Statistics"));
+        assert!(md.contains("| HNR | 3 |"));
+        assert!(md.contains("too few to separate change"));
+    }
+
+    #[test]
+    fn normative_comparison_uses_profile() {
+        let mut config = AppConfig::default();
+        config.profile.sex = Some(crate::config::Sex::Male);
+        config.profile.age = Some(55);
+        let sessions = vec![sample_session("2026-02-01", 8.0, 5.0)];
+
+        let md = generate_report(&sessions, &[], &config).unwrap();
+        assert!(md.contains("## Normative Comparison (2026-02-01)"));
+        assert!(md.contains("Healthy adult norms for: male, 55."));
+        assert!(md.contains("| HNR | 8.0 dB | 19.0 dB ± 3.5 dB | ≈-3.1 |"));
+        // Low MPT and HNR are flagged in the table
+        assert!(md.contains("| 2026-02-01 | 5.0 \u{26a0} |"));
+
+        config.profile.age = Some(12);
+        assert!(!generate_report(&sessions, &[], &config).unwrap().contains("Normative Comparison"));
+    }
+
+    #[test]
+    fn goals_section_only_when_configured() {
+        let mut config = AppConfig::default();
+        let sessions = vec![sample_session("2026-02-01", 8.0, 5.0)];
+        assert!(!generate_report(&sessions, &[], &config).unwrap().contains("Recovery Goals"));
+
+        config.goals.push(crate::config::GoalConfig {
+            metric: "mpt".into(),
+            target: 12.0,
+            direction: None,
+            deadline: None,
+        });
+        let md = generate_report(&sessions, &[], &config).unwrap();
+        assert!(md.contains("## Recovery Goals"));
+        assert!(md.contains("| MPT ≥ 12.0 s | 5.0 s | 0% | — | too few sessions to project |"));
+    }
+
+    #[test]
+    fn lists_clinical_events() {
+        let config = AppConfig::default();
+        let sessions = vec![sample_session("2026-02-01", 8.0, 5.0)];
+        assert!(!generate_report(&sessions, &[], &config).unwrap().contains("Clinical Events"));
+
+        let events = vec![ClinicalEvent {
+            id: 1,
+            date: "2026-01-28".into(),
+            kind: "therapy".into(),
+            note: "Weekly voice therapy starts".into(),
+        }];
+        let md = generate_report(&sessions, &events, &config).unwrap();
+        assert!(md.contains("## Clinical Events"));
+        assert!(md.contains("| 2026-01-28 | therapy | Weekly voice therapy starts |"));
+    }
+
+    #[test]
+    fn trials_table_only_when_present() {
+        let config = AppConfig::default();
+        let mut sessions = vec![sample_session("2026-02-01", 8.0, 5.0)];
+
+        let md = generate_report(&sessions, &[], &config).unwrap();
+        assert!(!md.contains("MPT Trials"));
+
+        if let Some(ref mut s) = sessions[0].analysis.sustained {
+            s.trials = Some(SustainedTrials {
+                attempts: vec![
+                    TrialMpt { attempt: "sustained_001.wav".into(), mpt_seconds: 4.0 },
+                    TrialMpt { attempt: "sustained_002.wav".into(), mpt_seconds: 5.0 },
+                ],
+                mpt_max_seconds: 5.0,
+                mpt_mean_seconds: 4.5,
+                source: "sustained_002.wav".into(),
+            });
+        }
+
+        let md = generate_report(&sessions, &[], &config).unwrap();
+        assert!(md.contains("## MPT Trials"));
+        assert!(md.contains("| 2026-02-01 | 2 | 5.0 | 4.5 | sustained_002.wav |"));
+    }
+
     #[test]
     fn empty_sessions() {
         let config = AppConfig::default();
-        let md = generate_report(&[], &config).unwrap();
+        let md = generate_report(&[], &[], &config).unwrap();
         assert!(md.contains("No sessions"));
     }
+
+    #[test]
+    fn recording_plots_section_embeds_images() {
+        assert!(recording_plots_section("2026-02-01", &[]).is_empty());
+
+        let images = vec![("reading".to_string(), "plot_2026-02-01_reading.png".to_string())];
+        let md = recording_plots_section("2026-02-01", &images);
+        assert!(md.contains("## Recordings (2026-02-01)"));
+        assert!(md.contains("### Reading"));
+        assert!(md.contains("![reading 2026-02-01](plot_2026-02-01_reading.png)"));
+    }
 }
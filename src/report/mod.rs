@@ -1,19 +1,36 @@
+pub mod adjust;
+pub mod attempts;
 pub mod charts;
+pub mod clinician;
 pub mod compare;
+pub mod doctor;
+pub mod events;
+pub mod fhir;
+pub mod frames;
+pub mod goals;
+pub mod html;
 pub mod markdown;
+pub mod query;
+pub mod spectrogram;
+pub mod stats;
+pub mod summary;
 
 use anyhow::Result;
 use console::style;
 
 use crate::config::AppConfig;
 use crate::paths;
-use crate::storage::{session_data::SessionData, store};
+use crate::storage::{session_data::{ClinicalEvent, SessionData}, store};
 
-/// Generate the full trend report (chart PNG + markdown) from all stored sessions.
+/// Generate the full trend report (chart PNG, markdown and HTML) from all stored sessions.
 ///
 /// This is the shared logic used by both `voicevo report` and the guided session flow.
 /// Returns the loaded sessions for further use (e.g., passing to explain).
-pub fn generate_full_report(config: &AppConfig) -> Result<Vec<SessionData>> {
+pub fn generate_full_report(
+    config: &AppConfig,
+    embed_audio: bool,
+    adjust_conditions: bool,
+) -> Result<Vec<SessionData>> {
     let dates = store::list_sessions()?;
     if dates.is_empty() {
         println!("No analyzed sessions found.");
@@ -30,20 +47,23 @@ pub fn generate_full_report(config: &AppConfig) -> Result<Vec<SessionData>> {
         return Ok(sessions);
     }
 
+    let events = store::load_events()?;
+
     let reports = paths::reports_dir();
     std::fs::create_dir_all(&reports)?;
 
     // Generate chart PNG
     let chart_path =
         reports.join(format!("report_{}.png", chrono::Local::now().format("%Y-%m-%d")));
-    charts::generate_trend_chart(&sessions, &chart_path)?;
+    charts::generate_trend_chart(&sessions, &events, &chart_path, &config.profile, adjust_conditions)?;
     println!(
         "Chart saved to {}",
         style(chart_path.display()).green()
     );
 
     // Generate markdown report
-    let md = markdown::generate_report(&sessions, config)?;
+    let mut md = markdown::generate_report(&sessions, &events, config)?;
+    md.push_str(&recording_plots_markdown(&sessions, config, &reports));
     let md_path =
         reports.join(format!("report_{}.md", chrono::Local::now().format("%Y-%m-%d")));
     std::fs::write(&md_path, &md)?;
@@ -52,5 +72,36 @@ pub fn generate_full_report(config: &AppConfig) -> Result<Vec<SessionData>> {
         style(md_path.display()).green()
     );
 
+    let html_path = write_html_report(&sessions, &events, config, &reports, embed_audio)?;
+    println!(
+        "HTML report saved to {}",
+        style(html_path.display()).green()
+    );
+
     Ok(sessions)
 }
+
+/// Write the self-contained HTML report into `dir` and return its path.
+pub fn write_html_report(
+    sessions: &[SessionData],
+    events: &[ClinicalEvent],
+    config: &AppConfig,
+    dir: &std::path::Path,
+    embed_audio: bool,
+) -> Result<std::path::PathBuf> {
+    let interpretation = store::load_latest_interpretation().ok().flatten();
+    let html = html::generate_html_report(sessions, events, config, interpretation.as_ref(), dir, embed_audio)?;
+    let path = dir.join(format!("report_{}.html", chrono::Local::now().format("%Y-%m-%d")));
+    std::fs::write(&path, html)?;
+    Ok(path)
+}
+
+/// Render spectrograms of the latest session's recordings into `dir` and
+/// return the markdown section that embeds them.
+pub fn recording_plots_markdown(sessions: &[SessionData], config: &AppConfig, dir: &std::path::Path) -> String {
+    let Some(latest) = sessions.last() else {
+        return String::new();
+    };
+    let images = spectrogram::render_session_plots(latest, config, dir);
+    markdown::recording_plots_section(&latest.date, &images)
+}
@@ -0,0 +1,57 @@
+use anyhow::Result;
+use console::style;
+
+use crate::storage::db::Comparison;
+use crate::storage::store;
+
+/// `voicevo query`: list the sessions whose metric satisfies `expr` and every
+/// `--where` filter.
+pub fn run(expr: &str, filters: &[String], valid_only: bool) -> Result<()> {
+    let metric = Comparison::parse(expr)?;
+    let filters = filters
+        .iter()
+        .map(|f| Comparison::parse(f))
+        .collect::<Result<Vec<_>>>()?;
+
+    let matches = store::query_metrics(&metric, &filters, valid_only)?;
+    let total = store::list_sessions()?.len();
+
+    let mut heading = metric.to_string();
+    for f in &filters {
+        heading.push_str(&format!(" and {f}"));
+    }
+    println!("{}", style(format!("=== Sessions where {heading} ===")).bold());
+    println!();
+
+    if matches.is_empty() {
+        println!("No matching sessions.");
+        return Ok(());
+    }
+    for m in &matches {
+        let value = format!("{:10.2}", m.value);
+        let value = if m.valid {
+            value
+        } else {
+            format!("{}", style(format!("{value} (unreliable)")).yellow())
+        };
+        let conditions = m
+            .conditions
+            .as_ref()
+            .map(|c| {
+                format!(
+                    "{}, fatigue {}/10, mucus {}, hydration {}",
+                    c.time_of_day, c.fatigue_level, c.mucus_level, c.hydration
+                )
+            })
+            .unwrap_or_else(|| "no conditions logged".into());
+        println!(
+            "  {}  {} {value}  {}",
+            style(&m.session).cyan(),
+            metric.field,
+            style(conditions).dim()
+        );
+    }
+    println!();
+    println!("{} of {total} sessions match.", matches.len());
+    Ok(())
+}
@@ -0,0 +1,387 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use console::style;
+use plotters::prelude::*;
+use rustfft::{num_complex::Complex, FftPlanner};
+
+use super::frames::{self, FrameTable};
+use crate::audio::wav;
+use crate::config::AppConfig;
+use crate::dsp::{pitch, windowing};
+use crate::paths;
+use crate::storage::session_data::SessionData;
+
+/// Image dimensions
+const WIDTH: u32 = 1200;
+const HEIGHT: u32 = 560;
+const ACTIVITY_HEIGHT: u32 = 70;
+
+/// Narrowband analysis window — long enough to resolve individual harmonics.
+const WINDOW_MS: f32 = 50.0;
+/// Smallest hop between spectrogram columns.
+const MIN_HOP_MS: f32 = 5.0;
+/// Cap on columns so long readings don't explode the image size.
+const MAX_COLUMNS: usize = 1200;
+/// Levels this far below the loudest cell are drawn white.
+const DYNAMIC_RANGE_DB: f32 = 70.0;
+
+/// Pitch point colors by detection tier
+const COLOR_TIER1: RGBColor = RGBColor(41, 128, 185); // blue
+const COLOR_TIER2: RGBColor = RGBColor(243, 156, 18); // orange
+const COLOR_TIER3: RGBColor = RGBColor(231, 76, 60); // red
+const COLOR_ACTIVE: RGBColor = RGBColor(46, 204, 113); // green
+const COLOR_BREAK: RGBColor = RGBColor(192, 57, 43); // dark red
+
+/// Magnitude spectrogram in dB, one column per hop.
+pub struct Spectrogram {
+    /// Column start times in seconds.
+    pub times: Vec<f32>,
+    pub column_secs: f32,
+    pub bin_hz: f32,
+    /// `columns[t][bin]` in dB, bins from 0 Hz up to the requested maximum.
+    pub columns: Vec<Vec<f32>>,
+}
+
+/// Compute a narrowband spectrogram up to `max_hz`.
+pub fn spectrogram(samples: &[f32], sample_rate: u32, max_hz: f32) -> Spectrogram {
+    let sr = sample_rate as f32;
+    let window = (WINDOW_MS / 1000.0 * sr) as usize;
+    let fft_size = window.next_power_of_two();
+    let bin_hz = sr / fft_size as f32;
+    let bins = ((max_hz / bin_hz).ceil() as usize).min(fft_size / 2);
+
+    let min_hop = (MIN_HOP_MS / 1000.0 * sr) as usize;
+    let hop = (samples.len() / MAX_COLUMNS).max(min_hop).max(1);
+
+    let fft = FftPlanner::new().plan_fft_forward(fft_size);
+
+    let mut times = Vec::new();
+    let mut columns = Vec::new();
+    let mut pos = 0;
+
+    while pos + window <= samples.len() {
+        let windowed = windowing::hanning(&samples[pos..pos + window]);
+        let mut buf: Vec<Complex<f32>> = windowed.iter().map(|&s| Complex::new(s, 0.0)).collect();
+        buf.resize(fft_size, Complex::new(0.0, 0.0));
+        fft.process(&mut buf);
+
+        columns.push(
+            buf[..bins]
+                .iter()
+                .map(|c| 10.0 * c.norm_sqr().max(1e-12).log10())
+                .collect(),
+        );
+        times.push(pos as f32 / sr);
+        pos += hop;
+    }
+
+    Spectrogram {
+        times,
+        column_secs: hop as f32 / sr,
+        bin_hz,
+        columns,
+    }
+}
+
+/// Grayscale level for a cell: loud is dark, as in Praat.
+fn shade(db: f32, max_db: f32) -> RGBColor {
+    let norm = ((db - (max_db - DYNAMIC_RANGE_DB)) / DYNAMIC_RANGE_DB).clamp(0.0, 1.0);
+    let level = (255.0 * (1.0 - norm)) as u8;
+    RGBColor(level, level, level)
+}
+
+/// Merge consecutive active frames into (start, end) spans in seconds.
+fn activity_spans(table: &FrameTable) -> Vec<(f32, f32)> {
+    let half_hop = table.hop_secs / 2.0;
+    let mut spans: Vec<(f32, f32)> = Vec::new();
+    let mut previous_active = false;
+
+    for row in &table.rows {
+        let (start, end) = (row.time_secs - half_hop, row.time_secs + half_hop);
+        match spans.last_mut() {
+            Some(last) if row.active && previous_active => last.1 = end,
+            _ if row.active => spans.push((start.max(0.0), end)),
+            _ => {}
+        }
+        previous_active = row.active;
+    }
+    spans
+}
+
+/// Render a spectrogram of one recording with the pitch contour on top.
+///
+/// Pitch points are coloured by detection tier, voice breaks are shaded
+/// red, and a strip underneath shows where activity detection found sound.
+pub fn render_recording(
+    samples: &[f32],
+    sample_rate: u32,
+    exercise: &str,
+    pitch_config: &pitch::PitchConfig,
+    title: &str,
+    output_path: &Path,
+) -> Result<()> {
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let table = frames::build_frames(samples, sample_rate, exercise, pitch_config);
+    let max_hz = (pitch_config.pitch_ceiling_hz * 2.0).min(sample_rate as f32 / 2.0);
+    let spec = spectrogram(samples, sample_rate, max_hz);
+    let duration = table.duration_secs.max(0.01);
+
+    let root = BitMapBackend::new(output_path, (WIDTH, HEIGHT)).into_drawing_area();
+    root.fill(&WHITE).context("Failed to fill background")?;
+    let (upper, lower) = root.split_vertically(HEIGHT - ACTIVITY_HEIGHT);
+
+    let mut chart = ChartBuilder::on(&upper)
+        .caption(title, ("sans-serif", 20))
+        .margin(10)
+        .x_label_area_size(0)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0f32..duration, 0f32..max_hz)?;
+
+    chart
+        .configure_mesh()
+        .disable_mesh()
+        .y_desc("Frequency (Hz)")
+        .y_label_style(("sans-serif", 12))
+        .axis_desc_style(("sans-serif", 13))
+        .draw()?;
+
+    // Spectrogram cells
+    let max_db = spec
+        .columns
+        .iter()
+        .flatten()
+        .copied()
+        .fold(f32::NEG_INFINITY, f32::max);
+    chart.draw_series(spec.times.iter().zip(&spec.columns).flat_map(|(&t, column)| {
+        let t_end = (t + spec.column_secs).min(duration);
+        column.iter().enumerate().map(move |(bin, &db)| {
+            let f = bin as f32 * spec.bin_hz;
+            Rectangle::new(
+                [(t, f), (t_end, (f + spec.bin_hz).min(max_hz))],
+                shade(db, max_db).filled(),
+            )
+        })
+    }))?;
+
+    // Voice breaks
+    chart.draw_series(table.breaks.iter().map(|&(start, end)| {
+        Rectangle::new([(start, 0.0), (end, max_hz)], COLOR_BREAK.mix(0.25).filled())
+    }))?;
+
+    // Pitch contour, one series per tier so each gets a legend entry
+    for (tier, color, label) in [
+        (1, COLOR_TIER1, "pitch (standard)"),
+        (2, COLOR_TIER2, "pitch (relaxed)"),
+        (3, COLOR_TIER3, "pitch (energy fallback)"),
+    ] {
+        let points: Vec<(f32, f32)> = table
+            .rows
+            .iter()
+            .filter(|r| r.tier == tier)
+            .filter_map(|r| r.f0_hz.map(|f| (r.time_secs, f)))
+            .collect();
+        if points.is_empty() {
+            continue;
+        }
+        chart
+            .draw_series(points.iter().map(|&p| Circle::new(p, 2, color.filled())))?
+            .label(label)
+            .legend(move |(x, y)| Circle::new((x + 10, y), 4, color.filled()));
+    }
+
+    if !table.breaks.is_empty() {
+        chart
+            .draw_series(std::iter::empty::<Rectangle<(f32, f32)>>())?
+            .label(format!("voice break ({})", table.breaks.len()))
+            .legend(|(x, y)| Rectangle::new([(x, y - 5), (x + 20, y + 5)], COLOR_BREAK.mix(0.4).filled()));
+    }
+
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperRight)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+
+    // Activity strip
+    let mut strip = ChartBuilder::on(&lower)
+        .margin_left(10)
+        .margin_right(10)
+        .margin_bottom(5)
+        .x_label_area_size(35)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0f32..duration, 0f32..1f32)?;
+
+    strip
+        .configure_mesh()
+        .disable_mesh()
+        .disable_y_axis()
+        .x_desc("Time (s)")
+        .x_label_style(("sans-serif", 12))
+        .axis_desc_style(("sans-serif", 13))
+        .draw()?;
+
+    strip.draw_series(activity_spans(&table).into_iter().map(|(start, end)| {
+        Rectangle::new([(start, 0.0), (end.min(duration), 1.0)], COLOR_ACTIVE.mix(0.6).filled())
+    }))?;
+    if let Some((start, end)) = table.segment {
+        strip.draw_series(std::iter::once(Rectangle::new(
+            [(start, 0.0), (end, 1.0)],
+            BLACK.stroke_width(1),
+        )))?;
+    }
+
+    root.present().context("Failed to write spectrogram PNG")?;
+    Ok(())
+}
+
+/// Render the spectrogram of a recording to a PNG (`voicevo plot`).
+pub fn plot_recording(
+    date: &str,
+    exercise: &str,
+    attempt: Option<u32>,
+    output: Option<&Path>,
+    config: &AppConfig,
+) -> Result<()> {
+    if !["sustained", "scale", "reading"].contains(&exercise) {
+        anyhow::bail!("Unknown exercise '{exercise}'. Use sustained, scale, or reading.");
+    }
+
+    let recording = frames::recording_for(date, exercise, attempt)?;
+    let path = match output {
+        Some(p) => p.to_path_buf(),
+        None => paths::reports_dir().join(plot_file_name(date, exercise)),
+    };
+
+    render_file(&recording, date, exercise, config, &path)?;
+    println!(
+        "Spectrogram of {} saved to {}",
+        style(recording.display()).cyan(),
+        style(path.display()).green()
+    );
+    Ok(())
+}
+
+/// Render every analyzed recording of a session into `dir`.
+///
+/// Returns (exercise, file name) for each image written. Failures are
+/// reported and skipped so a bad WAV doesn't block the whole report.
+pub fn render_session_plots(
+    session: &SessionData,
+    config: &AppConfig,
+    dir: &Path,
+) -> Vec<(String, String)> {
+    let recordings = [
+        ("sustained", session.recordings.sustained.as_deref()),
+        ("scale", session.recordings.scale.as_deref()),
+        ("reading", session.recordings.reading.as_deref()),
+    ];
+
+    let mut images = Vec::new();
+    for (exercise, recording) in recordings {
+        let Some(recording) = recording.map(PathBuf::from).filter(|p| p.exists()) else {
+            continue;
+        };
+        let name = plot_file_name(&session.date, exercise);
+        match render_file(&recording, &session.date, exercise, config, &dir.join(&name)) {
+            Ok(()) => images.push((exercise.to_string(), name)),
+            Err(e) => eprintln!(
+                "  {} could not plot {}: {e:#}",
+                style("Warning:").yellow(),
+                recording.display()
+            ),
+        }
+    }
+    images
+}
+
+fn plot_file_name(date: &str, exercise: &str) -> String {
+    format!("plot_{date}_{exercise}.png")
+}
+
+fn render_file(
+    recording: &Path,
+    date: &str,
+    exercise: &str,
+    config: &AppConfig,
+    output: &Path,
+) -> Result<()> {
+    let (samples, spec) = wav::load_samples(recording)
+        .with_context(|| format!("Failed to load {}", recording.display()))?;
+    let pitch_config = config.analysis.pitch_config_for(exercise);
+    let title = format!("{date} — {exercise}");
+    render_recording(&samples, spec.sample_rate, exercise, &pitch_config, &title, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::frames::FrameRow;
+    use std::f32::consts::PI;
+
+    fn row(time_secs: f32, active: bool) -> FrameRow {
+        FrameRow {
+            time_secs,
+            f0_hz: None,
+            tier: 3,
+            active,
+            rms_db: -60.0,
+            periodicity: None,
+            hnr_db: None,
+            cpps_db: None,
+        }
+    }
+
+    #[test]
+    fn spectrogram_peak_at_tone_frequency() {
+        let sr = 44100;
+        let samples: Vec<f32> = (0..sr)
+            .map(|i| (2.0 * PI * 300.0 * i as f32 / sr as f32).sin())
+            .collect();
+
+        let spec = spectrogram(&samples, sr, 1000.0);
+        assert!(!spec.columns.is_empty());
+        assert!(spec.columns.len() <= MAX_COLUMNS);
+        assert!((spec.bin_hz * spec.columns[0].len() as f32) >= 1000.0);
+
+        let column = &spec.columns[spec.columns.len() / 2];
+        let peak = column
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .unwrap()
+            .0;
+        assert!((peak as f32 * spec.bin_hz - 300.0).abs() < spec.bin_hz * 1.5);
+    }
+
+    #[test]
+    fn shade_maps_loud_to_dark() {
+        assert_eq!(shade(0.0, 0.0), RGBColor(0, 0, 0));
+        assert_eq!(shade(-DYNAMIC_RANGE_DB - 10.0, 0.0), RGBColor(255, 255, 255));
+    }
+
+    #[test]
+    fn activity_spans_merge_runs() {
+        let table = FrameTable {
+            duration_secs: 1.0,
+            hop_secs: 0.1,
+            segment: None,
+            rows: vec![
+                row(0.05, false),
+                row(0.15, true),
+                row(0.25, true),
+                row(0.35, false),
+                row(0.45, true),
+            ],
+            breaks: Vec::new(),
+        };
+        let spans = activity_spans(&table);
+        assert_eq!(spans.len(), 2);
+        assert!((spans[0].0 - 0.1).abs() < 1e-5 && (spans[0].1 - 0.3).abs() < 1e-5);
+        assert!((spans[1].0 - 0.4).abs() < 1e-5 && (spans[1].1 - 0.5).abs() < 1e-5);
+    }
+}
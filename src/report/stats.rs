@@ -0,0 +1,763 @@
+use chrono::NaiveDate;
+
+use crate::storage::session_data::SessionData;
+
+/// Largest n1·n2 for which the Mann–Whitney p-value is computed exactly
+/// (when there are no ties); larger samples use the normal approximation.
+const MANN_WHITNEY_EXACT_MAX: usize = 400;
+
+/// Two-sided 95% normal quantile.
+const Z95: f32 = 1.96;
+
+/// Sessions in the trailing rolling-median window.
+const ROLLING_WINDOW: usize = 5;
+
+/// Sessions averaged (median) for the baseline the latest value is compared to.
+const BASELINE_SESSIONS: usize = 3;
+
+/// Fewest sessions for a slope and for an MDC estimate (3 day-to-day differences).
+const MIN_SESSIONS_SLOPE: usize = 3;
+const MIN_SESSIONS_MDC: usize = 4;
+
+/// Each side of a change point needs at least this many sessions.
+const MIN_SEGMENT: usize = 3;
+
+/// Welch t statistic a level shift needs to count as a change point.
+const CHANGE_POINT_T: f32 = 2.5;
+
+/// Which direction counts as improvement for a metric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    HigherIsBetter,
+    LowerIsBetter,
+    /// No clinical "better" direction (e.g. speaking F0).
+    Neutral,
+}
+
+/// A metric tracked across sessions.
+pub struct MetricDef {
+    /// Short identifier used in config, e.g. `metric = "sz_ratio"` for goals.
+    pub key: &'static str,
+    pub name: &'static str,
+    pub unit: &'static str,
+    pub direction: Direction,
+    pub extract: fn(&SessionData) -> Option<f32>,
+}
+
+/// The metrics trend statistics are computed for.
+pub fn metric_defs() -> Vec<MetricDef> {
+    use Direction::*;
+    vec![
+        MetricDef {
+            key: "mpt",
+            name: "MPT",
+            unit: "s",
+            direction: HigherIsBetter,
+            extract: |s| s.analysis.sustained.as_ref().map(|a| a.mpt_seconds),
+        },
+        MetricDef {
+            key: "hnr",
+            name: "HNR",
+            unit: "dB",
+            direction: HigherIsBetter,
+            extract: |s| s.analysis.sustained.as_ref().map(|a| a.hnr_db),
+        },
+        MetricDef {
+            key: "jitter",
+            name: "Jitter",
+            unit: "%",
+            direction: LowerIsBetter,
+            extract: |s| s.analysis.sustained.as_ref().map(|a| a.jitter_local_percent),
+        },
+        MetricDef {
+            key: "shimmer",
+            name: "Shimmer",
+            unit: "%",
+            direction: LowerIsBetter,
+            extract: |s| s.analysis.sustained.as_ref().map(|a| a.shimmer_local_percent),
+        },
+        MetricDef {
+            key: "cpps",
+            name: "CPPS (sustained)",
+            unit: "dB",
+            direction: HigherIsBetter,
+            extract: |s| s.analysis.sustained.as_ref().and_then(|a| a.cpps_db),
+        },
+        MetricDef {
+            key: "cpps_reading",
+            name: "CPPS (reading)",
+            unit: "dB",
+            direction: HigherIsBetter,
+            extract: |s| s.analysis.reading.as_ref().and_then(|a| a.cpps_db),
+        },
+        MetricDef {
+            key: "range",
+            name: "Pitch range",
+            unit: "st",
+            direction: HigherIsBetter,
+            extract: |s| s.analysis.scale.as_ref().map(|a| a.range_semitones),
+        },
+        MetricDef {
+            key: "voice_breaks",
+            name: "Voice breaks",
+            unit: "",
+            direction: LowerIsBetter,
+            extract: |s| s.analysis.reading.as_ref().map(|a| a.voice_breaks as f32),
+        },
+        MetricDef {
+            key: "speaking_f0",
+            name: "Speaking F0",
+            unit: "Hz",
+            direction: Neutral,
+            extract: |s| s.analysis.reading.as_ref().map(|a| a.mean_f0_hz),
+        },
+        MetricDef {
+            key: "sz_ratio",
+            name: "S/Z ratio",
+            unit: "",
+            direction: LowerIsBetter,
+            extract: |s| s.analysis.sz.as_ref().map(|a| a.sz_ratio),
+        },
+    ]
+}
+
+/// Theil–Sen slope with its 95% confidence interval, per day.
+#[derive(Debug, Clone, Copy)]
+pub struct Slope {
+    pub per_day: f32,
+    pub ci_low: f32,
+    pub ci_high: f32,
+}
+
+impl Slope {
+    /// Whether the interval excludes zero (a trend, not just scatter).
+    pub fn is_significant(&self) -> bool {
+        self.ci_low > 0.0 || self.ci_high < 0.0
+    }
+}
+
+/// A level shift between two runs of sessions.
+#[derive(Debug, Clone)]
+pub struct ChangePoint {
+    /// First session after the shift.
+    pub date: NaiveDate,
+    /// Median before and after.
+    pub before: f32,
+    pub after: f32,
+}
+
+/// Latest value against the baseline.
+#[derive(Debug, Clone)]
+pub struct Change {
+    /// Date of the first baseline session.
+    pub since: NaiveDate,
+    pub baseline: f32,
+    pub delta: f32,
+}
+
+/// How the latest value compares to the baseline, given measurement noise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Improved,
+    Worsened,
+    /// Beyond noise for a metric without a better direction.
+    Rose,
+    Fell,
+    WithinNoise,
+    /// Too few sessions to estimate the noise.
+    Insufficient,
+}
+
+/// Trend statistics for one metric.
+pub struct MetricTrend {
+    pub name: &'static str,
+    pub unit: &'static str,
+    pub direction: Direction,
+    pub n: usize,
+    pub latest: f32,
+    pub slope: Option<Slope>,
+    /// Trailing median of the last `ROLLING_WINDOW` sessions, per session.
+    pub rolling_median: Vec<(NaiveDate, f32)>,
+    pub change_point: Option<ChangePoint>,
+    /// Minimal detectable change (95%) from day-to-day variability.
+    pub mdc: Option<f32>,
+    pub change: Option<Change>,
+}
+
+impl MetricTrend {
+    pub fn verdict(&self) -> Verdict {
+        let (Some(mdc), Some(change)) = (self.mdc, &self.change) else {
+            return Verdict::Insufficient;
+        };
+        if change.delta.abs() <= mdc {
+            return Verdict::WithinNoise;
+        }
+        match (self.direction, change.delta > 0.0) {
+            (Direction::HigherIsBetter, true) | (Direction::LowerIsBetter, false) => Verdict::Improved,
+            (Direction::HigherIsBetter, false) | (Direction::LowerIsBetter, true) => Verdict::Worsened,
+            (Direction::Neutral, true) => Verdict::Rose,
+            (Direction::Neutral, false) => Verdict::Fell,
+        }
+    }
+
+    /// One plain-language sentence, e.g. "HNR improved beyond measurement
+    /// noise since 2026-03-01 (+3.2 dB; MDC 1.8 dB)."
+    pub fn summary(&self) -> String {
+        let Some(change) = &self.change else {
+            return format!("{}: only {} session(s), no trend yet.", self.name, self.n);
+        };
+        let delta = format_signed(change.delta, self.unit);
+
+        let Some(mdc) = self.mdc else {
+            return format!(
+                "{} changed {delta} since {}, but {} sessions are too few to separate change from day-to-day noise (need {MIN_SESSIONS_MDC}).",
+                self.name, change.since, self.n
+            );
+        };
+        let mdc = format_value(mdc, self.unit);
+
+        match self.verdict() {
+            Verdict::WithinNoise => format!(
+                "{} change since {} is within measurement noise ({delta}; MDC {mdc}).",
+                self.name, change.since
+            ),
+            verdict => {
+                let word = match verdict {
+                    Verdict::Improved => "improved",
+                    Verdict::Worsened => "worsened",
+                    Verdict::Rose => "rose",
+                    _ => "fell",
+                };
+                format!(
+                    "{} {word} beyond measurement noise since {} ({delta}; MDC {mdc}).",
+                    self.name, change.since
+                )
+            }
+        }
+    }
+}
+
+/// Trend statistics for every metric with at least two sessions.
+pub fn compute_trends(sessions: &[SessionData]) -> Vec<MetricTrend> {
+    metric_defs()
+        .iter()
+        .filter_map(|def| {
+            let points: Vec<(NaiveDate, f32)> = sessions
+                .iter()
+                .filter_map(|s| {
+                    let date = NaiveDate::parse_from_str(&s.date, "%Y-%m-%d").ok()?;
+                    Some((date, (def.extract)(s)?))
+                })
+                .collect();
+            analyze_series(def, &points)
+        })
+        .collect()
+}
+
+/// Trend statistics for one metric's (date, value) series, in date order.
+/// Non-finite values (a hand-edited or imported row, a degenerate metric)
+/// are skipped.
+pub fn analyze_series(def: &MetricDef, points: &[(NaiveDate, f32)]) -> Option<MetricTrend> {
+    let points: Vec<(NaiveDate, f32)> = points.iter().copied().filter(|p| p.1.is_finite()).collect();
+    let &(_, latest) = points.last()?;
+    let first_date = points[0].0;
+    let values: Vec<f32> = points.iter().map(|p| p.1).collect();
+    let n = values.len();
+
+    let slope = (n >= MIN_SESSIONS_SLOPE)
+        .then(|| {
+            let xy: Vec<(f32, f32)> = points
+                .iter()
+                .map(|&(d, v)| ((d - first_date).num_days() as f32, v))
+                .collect();
+            theil_sen(&xy)
+        })
+        .flatten();
+
+    let mdc = minimal_detectable_change(&values);
+
+    let rolling = rolling_median(&values, ROLLING_WINDOW);
+    let rolling_median = points.iter().map(|p| p.0).zip(rolling).collect();
+
+    let change_point = change_point(&values).and_then(|k| {
+        let before = median(&values[..k])?;
+        let after = median(&values[k..])?;
+        // A shift smaller than the noise isn't worth reporting
+        let real = mdc.is_none_or(|m| (after - before).abs() > m);
+        real.then_some(ChangePoint { date: points[k].0, before, after })
+    });
+
+    let change = (n >= 2).then(|| {
+        let baseline_n = BASELINE_SESSIONS.min(n - 1);
+        let baseline = median(&values[..baseline_n]).unwrap_or(values[0]);
+        Change { since: first_date, baseline, delta: latest - baseline }
+    });
+
+    Some(MetricTrend {
+        name: def.name,
+        unit: def.unit,
+        direction: def.direction,
+        n,
+        latest,
+        slope,
+        rolling_median,
+        change_point,
+        mdc,
+        change,
+    })
+}
+
+/// Theil–Sen estimator: the median of all pairwise slopes, which a single
+/// bad session can't drag around the way it drags a least-squares fit.
+///
+/// The confidence interval is Sen's (1968) rank-based interval from the
+/// variance of Kendall's S. Pairs with equal x, and pairs involving a
+/// non-finite value, are skipped.
+pub fn theil_sen(points: &[(f32, f32)]) -> Option<Slope> {
+    let mut slopes = Vec::new();
+    for (i, &(xi, yi)) in points.iter().enumerate() {
+        for &(xj, yj) in &points[i + 1..] {
+            let dx = xj - xi;
+            let slope = (yj - yi) / dx;
+            if dx.abs() > f32::EPSILON && slope.is_finite() {
+                slopes.push(slope);
+            }
+        }
+    }
+    if slopes.is_empty() {
+        return None;
+    }
+    slopes.sort_by(f32::total_cmp);
+    let per_day = median_sorted(&slopes);
+
+    let n = points.len() as f32;
+    let var_s = n * (n - 1.0) * (2.0 * n + 5.0) / 18.0;
+    let c = Z95 * var_s.sqrt();
+    let pairs = slopes.len() as f32;
+    let last = slopes.len() - 1;
+    let low = (((pairs - c) / 2.0).floor().max(0.0) as usize).min(last);
+    let high = (((pairs + c) / 2.0).ceil().max(0.0) as usize).min(last);
+
+    Some(Slope {
+        per_day,
+        ci_low: slopes[low],
+        ci_high: slopes[high],
+    })
+}
+
+/// Minimal detectable change at 95% confidence.
+///
+/// Day-to-day variability is the spread of differences between consecutive
+/// sessions, which cancels slow recovery trends. With typical error
+/// TE = SD(diff) / √2, MDC95 = 1.96 · √2 · TE = 1.96 · SD(diff).
+pub fn minimal_detectable_change(values: &[f32]) -> Option<f32> {
+    if values.len() < MIN_SESSIONS_MDC {
+        return None;
+    }
+    let diffs: Vec<f32> = values.windows(2).map(|w| w[1] - w[0]).collect();
+    let mean = diffs.iter().sum::<f32>() / diffs.len() as f32;
+    let var = diffs.iter().map(|d| (d - mean).powi(2)).sum::<f32>() / (diffs.len() - 1) as f32;
+    Some(Z95 * var.sqrt())
+}
+
+/// Trailing median over up to `window` sessions.
+pub fn rolling_median(values: &[f32], window: usize) -> Vec<f32> {
+    (0..values.len())
+        .map(|i| {
+            let start = (i + 1).saturating_sub(window);
+            median(&values[start..=i]).unwrap_or(values[i])
+        })
+        .collect()
+}
+
+/// Single change point: the split that most reduces the squared error of
+/// a two-level fit, kept only when the shift is clear (Welch t ≥ 2.5).
+/// Returns the index of the first session after the shift.
+pub fn change_point(values: &[f32]) -> Option<usize> {
+    let n = values.len();
+    if n < 2 * MIN_SEGMENT {
+        return None;
+    }
+
+    let sse = |v: &[f32]| {
+        let m = v.iter().sum::<f32>() / v.len() as f32;
+        v.iter().map(|x| (x - m).powi(2)).sum::<f32>()
+    };
+
+    let k = (MIN_SEGMENT..=n - MIN_SEGMENT).min_by(|&a, &b| {
+        let ca = sse(&values[..a]) + sse(&values[a..]);
+        let cb = sse(&values[..b]) + sse(&values[b..]);
+        ca.total_cmp(&cb)
+    })?;
+
+    (welch_t(&values[..k], &values[k..]) >= CHANGE_POINT_T).then_some(k)
+}
+
+fn welch_t(a: &[f32], b: &[f32]) -> f32 {
+    let stats = |v: &[f32]| {
+        let m = v.iter().sum::<f32>() / v.len() as f32;
+        let var = v.iter().map(|x| (x - m).powi(2)).sum::<f32>() / (v.len() - 1) as f32;
+        (m, var / v.len() as f32)
+    };
+    let (ma, sa) = stats(a);
+    let (mb, sb) = stats(b);
+    let se = (sa + sb).sqrt();
+    if se <= f32::EPSILON {
+        return if (ma - mb).abs() > f32::EPSILON { f32::INFINITY } else { 0.0 };
+    }
+    (ma - mb).abs() / se
+}
+
+/// Mann–Whitney U test result for two independent samples.
+#[derive(Debug, Clone, Copy)]
+pub struct MannWhitney {
+    /// U statistic of the second sample (how often it beats the first).
+    pub u: f32,
+    /// Two-sided p-value.
+    pub p: f32,
+    /// Whether `p` comes from the exact distribution rather than the normal approximation.
+    pub exact: bool,
+}
+
+/// Mann–Whitney U (Wilcoxon rank-sum) test of `a` against `b`.
+///
+/// Ties get mid-ranks. Without ties and for small samples the p-value is
+/// exact; otherwise it uses the tie-corrected normal approximation with a
+/// continuity correction. Non-finite values can't be ranked and are skipped.
+pub fn mann_whitney(a: &[f32], b: &[f32]) -> Option<MannWhitney> {
+    let finite = |v: &[f32]| v.iter().copied().filter(|x| x.is_finite()).collect::<Vec<f32>>();
+    let (a, b) = (finite(a), finite(b));
+    let (n1, n2) = (a.len(), b.len());
+    if n1 == 0 || n2 == 0 {
+        return None;
+    }
+
+    // Rank the pooled sample; tied runs share their mean rank
+    let mut pooled: Vec<(f32, bool)> = a.iter().map(|&v| (v, false)).chain(b.iter().map(|&v| (v, true))).collect();
+    pooled.sort_by(|x, y| x.0.total_cmp(&y.0));
+    let n = pooled.len();
+    let mut rank_sum_b = 0.0_f64;
+    let mut tie_term = 0.0_f64;
+    let mut i = 0;
+    while i < n {
+        let mut j = i + 1;
+        while j < n && pooled[j].0 == pooled[i].0 {
+            j += 1;
+        }
+        let t = (j - i) as f64;
+        let mid_rank = (i + j + 1) as f64 / 2.0;
+        rank_sum_b += pooled[i..j].iter().filter(|p| p.1).count() as f64 * mid_rank;
+        tie_term += t * t * t - t;
+        i = j;
+    }
+
+    let u = rank_sum_b - (n2 * (n2 + 1)) as f64 / 2.0;
+    let mean = (n1 * n2) as f64 / 2.0;
+
+    if tie_term == 0.0 && n1 * n2 <= MANN_WHITNEY_EXACT_MAX {
+        let counts = mann_whitney_counts(n1, n2);
+        let total: f64 = counts.iter().sum();
+        let u_low = u.min((n1 * n2) as f64 - u).round() as usize;
+        let tail: f64 = counts[..=u_low].iter().sum::<f64>() / total;
+        return Some(MannWhitney { u: u as f32, p: (2.0 * tail).min(1.0) as f32, exact: true });
+    }
+
+    let nf = n as f64;
+    let var = (n1 * n2) as f64 / 12.0 * ((nf + 1.0) - tie_term / (nf * (nf - 1.0)));
+    if var <= 0.0 {
+        // Every value identical
+        return Some(MannWhitney { u: u as f32, p: 1.0, exact: false });
+    }
+    let z = ((u - mean).abs() - 0.5).max(0.0) / var.sqrt();
+    let p = 2.0 * (1.0 - crate::norms::normal_cdf(z as f32));
+    Some(MannWhitney { u: u as f32, p: p.clamp(0.0, 1.0), exact: false })
+}
+
+/// Number of orderings giving each U value for sample sizes m and n
+/// (index = U), via c(m, n, u) = c(m-1, n, u-n) + c(m, n-1, u): the largest
+/// value either comes from the first sample (beating all n of the second)
+/// or not. U's null distribution is symmetric, so which sample's U is
+/// counted doesn't matter.
+fn mann_whitney_counts(m: usize, n: usize) -> Vec<f64> {
+    // table[j][u] holds the counts for sizes (i, j) as i grows
+    let mut table: Vec<Vec<f64>> = (0..=n).map(|_| vec![1.0]).collect();
+    for i in 1..=m {
+        let mut next: Vec<Vec<f64>> = Vec::with_capacity(n + 1);
+        next.push(vec![1.0]);
+        for j in 1..=n {
+            let mut counts = vec![0.0; i * j + 1];
+            for (u, c) in table[j].iter().enumerate() {
+                counts[u + j] += c;
+            }
+            for (u, c) in next[j - 1].iter().enumerate() {
+                counts[u] += c;
+            }
+            next.push(counts);
+        }
+        table = next;
+    }
+    table.pop().unwrap_or_else(|| vec![1.0])
+}
+
+/// Cohen's d of `b` relative to `a`, using the pooled standard deviation.
+/// Needs at least two values on each side and some spread.
+pub fn cohens_d(a: &[f32], b: &[f32]) -> Option<f32> {
+    if a.len() < 2 || b.len() < 2 {
+        return None;
+    }
+    let stats = |v: &[f32]| {
+        let m = v.iter().sum::<f32>() / v.len() as f32;
+        let ss = v.iter().map(|x| (x - m).powi(2)).sum::<f32>();
+        (m, ss)
+    };
+    let (ma, ssa) = stats(a);
+    let (mb, ssb) = stats(b);
+    let pooled = ((ssa + ssb) / (a.len() + b.len() - 2) as f32).sqrt();
+    (pooled > f32::EPSILON).then(|| (mb - ma) / pooled)
+}
+
+/// Median of the finite values, or None if there are none.
+pub fn median(values: &[f32]) -> Option<f32> {
+    let mut sorted: Vec<f32> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    if sorted.is_empty() {
+        return None;
+    }
+    sorted.sort_by(f32::total_cmp);
+    Some(median_sorted(&sorted))
+}
+
+fn median_sorted(sorted: &[f32]) -> f32 {
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+/// A metric value with its unit, e.g. "1.8 dB", "0.40%", "3".
+pub fn format_value(value: f32, unit: &str) -> String {
+    match unit {
+        "%" => format!("{value:.2}%"),
+        "" => format!("{value:.2}"),
+        _ => format!("{value:.1} {unit}"),
+    }
+}
+
+/// Like `format_value` with an explicit sign.
+pub fn format_signed(value: f32, unit: &str) -> String {
+    let s = format_value(value, unit);
+    if value >= 0.0 {
+        format!("+{s}")
+    } else {
+        s
+    }
+}
+
+/// Slope per week with its CI, e.g. "+0.30 dB/wk (+0.10 to +0.50) *".
+/// The trailing star marks an interval that excludes zero.
+pub fn format_slope(slope: &Slope, unit: &str) -> String {
+    let week = |v: f32| v * 7.0;
+    let unit = if unit.is_empty() { String::new() } else { format!(" {unit}") };
+    format!(
+        "{:+.2}{unit}/wk ({:+.2} to {:+.2}){}",
+        week(slope.per_day),
+        week(slope.ci_low),
+        week(slope.ci_high),
+        if slope.is_significant() { " *" } else { "" }
+    )
+}
+
+/// Latest value against the baseline, e.g. "8.2 dB → 11.5 dB (+3.3 dB)".
+pub fn format_change(trend: &MetricTrend) -> Option<String> {
+    let c = trend.change.as_ref()?;
+    Some(format!(
+        "{} → {} ({})",
+        format_value(c.baseline, trend.unit),
+        format_value(trend.latest, trend.unit),
+        format_signed(c.delta, trend.unit)
+    ))
+}
+
+/// Markdown table plus one summary sentence per metric, shared by the
+/// markdown report and the LLM prompt.
+pub fn markdown_section(trends: &[MetricTrend]) -> String {
+    let mut md = String::from(
+        "| Metric | Sessions | Slope (95% CI) | Rolling median | MDC | Latest vs baseline | Change point |\n\
+         |--------|----------|----------------|----------------|-----|--------------------|--------------|\n",
+    );
+    for t in trends {
+        let slope = t.slope.as_ref().map(|s| format_slope(s, t.unit)).unwrap_or_else(|| "—".into());
+        let rolling = t
+            .rolling_median
+            .last()
+            .map(|&(_, v)| format_value(v, t.unit))
+            .unwrap_or_else(|| "—".into());
+        let mdc = t.mdc.map(|m| format_value(m, t.unit)).unwrap_or_else(|| "—".into());
+        let change = format_change(t).unwrap_or_else(|| "—".into());
+        let cp = t
+            .change_point
+            .as_ref()
+            .map(|c| format!("{} ({} → {})", c.date, format_value(c.before, t.unit), format_value(c.after, t.unit)))
+            .unwrap_or_else(|| "—".into());
+        md.push_str(&format!(
+            "| {} | {} | {slope} | {rolling} | {mdc} | {change} | {cp} |\n",
+            t.name, t.n
+        ));
+    }
+    md.push_str("\n\\* 95% CI excludes zero.\n\n");
+    for t in trends {
+        md.push_str(&format!("- {}\n", t.summary()));
+    }
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, 1).unwrap() + chrono::Days::new(day as u64)
+    }
+
+    fn hnr_def() -> MetricDef {
+        metric_defs().into_iter().find(|d| d.name == "HNR").unwrap()
+    }
+
+    #[test]
+    fn theil_sen_exact_line_and_outlier() {
+        let line: Vec<(f32, f32)> = (0..10).map(|i| (i as f32, 2.0 + 0.5 * i as f32)).collect();
+        let s = theil_sen(&line).unwrap();
+        assert!((s.per_day - 0.5).abs() < 1e-5);
+        assert!(s.ci_low <= 0.5 && s.ci_high >= 0.5);
+
+        // One wild session barely moves the estimate
+        let mut noisy = line.clone();
+        noisy[4].1 = 40.0;
+        let s = theil_sen(&noisy).unwrap();
+        assert!((s.per_day - 0.5).abs() < 0.1, "slope {}", s.per_day);
+        assert!(s.is_significant());
+    }
+
+    #[test]
+    fn flat_scatter_is_not_significant() {
+        let values = [10.0, 12.0, 9.0, 11.0, 10.5, 9.5, 11.5, 10.0];
+        let points: Vec<(f32, f32)> = values.iter().enumerate().map(|(i, &v)| (i as f32, v)).collect();
+        assert!(!theil_sen(&points).unwrap().is_significant());
+    }
+
+    #[test]
+    fn mdc_from_successive_differences() {
+        // Alternating around a flat level: diffs are ±1, SD(diff) = √(4/3)
+        let values = [10.0, 11.0, 10.0, 11.0, 10.0];
+        let mdc = minimal_detectable_change(&values).unwrap();
+        assert!((mdc - 1.96 * 1.1547).abs() < 0.01, "mdc {mdc}");
+        assert!(minimal_detectable_change(&values[..3]).is_none());
+    }
+
+    #[test]
+    fn rolling_median_trails() {
+        let r = rolling_median(&[1.0, 5.0, 2.0, 8.0, 3.0, 9.0], 3);
+        assert_eq!(r, vec![1.0, 3.0, 2.0, 5.0, 3.0, 8.0]);
+    }
+
+    #[test]
+    fn change_point_finds_step() {
+        let values = [5.0, 5.5, 4.8, 5.2, 9.0, 9.4, 8.8, 9.1];
+        assert_eq!(change_point(&values), Some(4));
+
+        let flat = [5.0, 5.5, 4.8, 5.2, 5.1, 4.9, 5.3, 5.0];
+        assert_eq!(change_point(&flat), None);
+    }
+
+    #[test]
+    fn non_finite_values_are_skipped() {
+        let values = [8.0, f32::NAN, 8.5, 7.8, 8.2, 11.0, f32::INFINITY, 11.4, 10.8, 11.5];
+        let points: Vec<_> = values.iter().enumerate().map(|(i, &v)| (date(i as u32 * 3), v)).collect();
+        let trend = analyze_series(&hnr_def(), &points).unwrap();
+        assert_eq!(trend.n, 8);
+        assert!(trend.slope.unwrap().per_day.is_finite());
+        assert!(trend.change_point.is_some());
+
+        let line = [(0.0, 1.0), (1.0, f32::NAN), (2.0, 3.0), (3.0, 4.0)];
+        assert!((theil_sen(&line).unwrap().per_day - 1.0).abs() < 1e-5);
+        assert_eq!(change_point(&[5.0, f32::NAN, 4.8, 5.2, 9.0, 9.4, f32::NAN, 9.1]), None);
+        assert_eq!(median(&[f32::NAN, 3.0, 1.0, f32::NEG_INFINITY]), Some(2.0));
+        assert_eq!(median(&[f32::NAN]), None);
+        let mw = mann_whitney(&[1.0, 2.0, f32::NAN], &[3.0, 4.0, f32::INFINITY]).unwrap();
+        assert!(mw.p.is_finite());
+        assert_eq!(mw.u, 4.0);
+    }
+
+    #[test]
+    fn improvement_beyond_noise() {
+        let values = [8.0, 8.5, 7.8, 8.2, 11.0, 11.4, 10.8, 11.5];
+        let points: Vec<_> = values.iter().enumerate().map(|(i, &v)| (date(i as u32 * 3), v)).collect();
+        let trend = analyze_series(&hnr_def(), &points).unwrap();
+
+        assert_eq!(trend.verdict(), Verdict::Improved);
+        assert_eq!(trend.change_point.as_ref().unwrap().date, date(12));
+        assert!(trend.summary().starts_with("HNR improved beyond measurement noise since 2026-03-01 (+3.5 dB; MDC"));
+    }
+
+    #[test]
+    fn small_change_is_noise() {
+        let values = [8.0, 9.0, 8.0, 9.0, 8.2];
+        let points: Vec<_> = values.iter().enumerate().map(|(i, &v)| (date(i as u32), v)).collect();
+        let trend = analyze_series(&hnr_def(), &points).unwrap();
+        assert_eq!(trend.verdict(), Verdict::WithinNoise);
+        assert!(trend.change_point.is_none());
+    }
+
+    #[test]
+    fn too_few_sessions() {
+        let points = vec![(date(0), 8.0), (date(1), 12.0)];
+        let trend = analyze_series(&hnr_def(), &points).unwrap();
+        assert_eq!(trend.verdict(), Verdict::Insufficient);
+        assert!(trend.slope.is_none());
+        assert!(trend.summary().contains("too few"));
+    }
+
+    #[test]
+    fn lower_is_better_direction() {
+        let jitter = metric_defs().into_iter().find(|d| d.name == "Jitter").unwrap();
+        let values = [3.0, 3.1, 2.9, 3.0, 1.0];
+        let points: Vec<_> = values.iter().enumerate().map(|(i, &v)| (date(i as u32), v)).collect();
+        assert_eq!(analyze_series(&jitter, &points).unwrap().verdict(), Verdict::Improved);
+    }
+
+    #[test]
+    fn mann_whitney_exact_p_values() {
+        let r = mann_whitney(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]).unwrap();
+        assert_eq!(r.u, 9.0);
+        assert!(r.exact);
+        assert!((r.p - 0.1).abs() < 1e-6);
+
+        let r = mann_whitney(&[1.0, 2.0, 3.0, 4.0], &[5.0, 6.0, 7.0, 8.0]).unwrap();
+        assert!((r.p - 2.0 / 70.0).abs() < 1e-6);
+
+        // Interleaved: U = 6 of 9, P(U ≤ 3) = 7/20
+        let r = mann_whitney(&[1.0, 3.0, 5.0], &[2.0, 4.0, 6.0]).unwrap();
+        assert_eq!(r.u, 6.0);
+        assert!((r.p - 0.7).abs() < 1e-6);
+    }
+
+    #[test]
+    fn mann_whitney_ties_use_normal_approximation() {
+        let r = mann_whitney(&[1.0, 1.0, 2.0, 2.0], &[2.0, 3.0, 3.0, 4.0]).unwrap();
+        assert!(!r.exact);
+        assert!(r.p > 0.0 && r.p < 0.1, "p {}", r.p);
+
+        let r = mann_whitney(&[5.0, 5.0], &[5.0, 5.0]).unwrap();
+        assert_eq!(r.p, 1.0);
+        assert!(mann_whitney(&[], &[1.0]).is_none());
+    }
+
+    #[test]
+    fn cohens_d_pooled() {
+        let d = cohens_d(&[1.0, 2.0, 3.0], &[3.0, 4.0, 5.0]).unwrap();
+        assert!((d - 2.0).abs() < 1e-6);
+        assert!(cohens_d(&[1.0], &[2.0, 3.0]).is_none());
+        assert!(cohens_d(&[1.0, 1.0], &[1.0, 1.0]).is_none());
+    }
+}
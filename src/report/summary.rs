@@ -0,0 +1,414 @@
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Result};
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use console::style;
+
+use super::charts;
+use super::stats::{self, Direction};
+use crate::paths;
+use crate::storage::session_data::SessionData;
+use crate::storage::store;
+
+/// How sessions are grouped for a summary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    /// ISO week (Monday to Sunday), labelled e.g. "2026-W07".
+    Week,
+    /// Calendar month, labelled e.g. "2026-03".
+    Month,
+}
+
+impl Period {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "week" | "weekly" => Ok(Period::Week),
+            "month" | "monthly" => Ok(Period::Month),
+            _ => bail!("Unknown period '{s}'. Use 'week' or 'month'."),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Period::Week => "week",
+            Period::Month => "month",
+        }
+    }
+
+    /// Label and first/last calendar day of the period containing `date`.
+    fn bounds(self, date: NaiveDate) -> (String, NaiveDate, NaiveDate) {
+        match self {
+            Period::Week => {
+                let week = date.iso_week();
+                let start = NaiveDate::from_isoywd_opt(week.year(), week.week(), Weekday::Mon)
+                    .unwrap_or(date);
+                let label = format!("{}-W{:02}", week.year(), week.week());
+                (label, start, start + Days::new(6))
+            }
+            Period::Month => {
+                let start = date.with_day(1).unwrap_or(date);
+                let end = start + Months::new(1) - Days::new(1);
+                (format!("{}-{:02}", date.year(), date.month()), start, end)
+            }
+        }
+    }
+}
+
+/// One metric aggregated over a period. `min`/`max` are drawn as error bars.
+#[derive(Debug, Clone, Copy)]
+pub struct MetricSummary {
+    pub median: f32,
+    pub min: f32,
+    pub max: f32,
+}
+
+/// Value counts of the logged recording conditions in a period.
+#[derive(Debug, Default)]
+pub struct ConditionSummary {
+    /// Sessions with conditions logged.
+    pub logged: usize,
+    pub time_of_day: BTreeMap<String, usize>,
+    pub mucus: BTreeMap<String, usize>,
+    pub hydration: BTreeMap<String, usize>,
+    pub fatigue_median: Option<f32>,
+}
+
+impl ConditionSummary {
+    /// e.g. "time morning 3, evening 1 · mucus low 4 · hydration normal 4 · fatigue 3".
+    pub fn describe(&self) -> Option<String> {
+        if self.logged == 0 {
+            return None;
+        }
+        let counts = |name: &str, map: &BTreeMap<String, usize>| {
+            let parts: Vec<String> = map.iter().map(|(k, n)| format!("{k} {n}")).collect();
+            format!("{name} {}", parts.join(", "))
+        };
+        let mut parts = vec![
+            counts("time", &self.time_of_day),
+            counts("mucus", &self.mucus),
+            counts("hydration", &self.hydration),
+        ];
+        if let Some(f) = self.fatigue_median {
+            parts.push(format!("fatigue {f:.0}/10"));
+        }
+        Some(parts.join(" · "))
+    }
+}
+
+/// Sessions of one week or month, aggregated.
+#[derive(Debug)]
+pub struct PeriodSummary {
+    pub label: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub sessions: usize,
+    /// One entry per `stats::metric_defs()`, `None` when never recorded.
+    pub metrics: Vec<Option<MetricSummary>>,
+    pub best_mpt: Option<f32>,
+    pub conditions: ConditionSummary,
+}
+
+/// Group sessions (in date order) by period. Sessions with an unparseable
+/// date are skipped; periods without sessions are not listed.
+pub fn summarize(sessions: &[SessionData], period: Period) -> Vec<PeriodSummary> {
+    let mut groups: Vec<(String, NaiveDate, NaiveDate, Vec<&SessionData>)> = Vec::new();
+    for s in sessions {
+        let Ok(date) = NaiveDate::parse_from_str(&s.date, "%Y-%m-%d") else {
+            continue;
+        };
+        let (label, start, end) = period.bounds(date);
+        match groups.last_mut() {
+            Some((last, _, _, list)) if *last == label => list.push(s),
+            _ => groups.push((label, start, end, vec![s])),
+        }
+    }
+
+    let defs = stats::metric_defs();
+    groups
+        .into_iter()
+        .map(|(label, start, end, list)| {
+            let metrics = defs
+                .iter()
+                .map(|def| {
+                    let values: Vec<f32> = list.iter().filter_map(|s| (def.extract)(s)).collect();
+                    Some(MetricSummary {
+                        median: stats::median(&values)?,
+                        min: values.iter().copied().fold(f32::INFINITY, f32::min),
+                        max: values.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+                    })
+                })
+                .collect();
+            let best_mpt = list
+                .iter()
+                .filter_map(|s| s.analysis.sustained.as_ref().map(|a| a.mpt_seconds))
+                .reduce(f32::max);
+            PeriodSummary {
+                label,
+                start,
+                end,
+                sessions: list.len(),
+                metrics,
+                best_mpt,
+                conditions: condition_summary(&list),
+            }
+        })
+        .collect()
+}
+
+fn condition_summary(sessions: &[&SessionData]) -> ConditionSummary {
+    let mut summary = ConditionSummary::default();
+    let mut fatigue = Vec::new();
+    for c in sessions.iter().filter_map(|s| s.conditions.as_ref()) {
+        summary.logged += 1;
+        *summary.time_of_day.entry(c.time_of_day.clone()).or_default() += 1;
+        *summary.mucus.entry(c.mucus_level.clone()).or_default() += 1;
+        *summary.hydration.entry(c.hydration.clone()).or_default() += 1;
+        fatigue.push(c.fatigue_level as f32);
+    }
+    summary.fatigue_median = stats::median(&fatigue);
+    summary
+}
+
+/// Change in a metric's median from the previous summarised period.
+fn change(summaries: &[PeriodSummary], i: usize, metric: usize) -> Option<f32> {
+    let current = summaries[i].metrics[metric]?;
+    let previous = summaries[..i].last()?.metrics[metric]?;
+    Some(current.median - previous.median)
+}
+
+/// `voicevo summary`: print the per-period summary and save markdown and a
+/// chart of the period medians to the reports directory.
+pub fn run(period: &str) -> Result<()> {
+    let period = Period::parse(period)?;
+    let sessions: Vec<SessionData> = store::list_sessions()?
+        .iter()
+        .filter_map(|d| store::load_session(d).ok())
+        .collect();
+    let summaries = summarize(&sessions, period);
+    if summaries.is_empty() {
+        println!("No analyzed sessions found.");
+        return Ok(());
+    }
+
+    print_summary(&summaries, period);
+
+    let reports = paths::reports_dir();
+    std::fs::create_dir_all(&reports)?;
+    let today = chrono::Local::now().format("%Y-%m-%d");
+
+    let md_path = reports.join(format!("summary_{}_{today}.md", period.name()));
+    std::fs::write(&md_path, markdown(&summaries, period))?;
+    println!("Summary saved to {}", style(md_path.display()).green());
+
+    let chart_path = reports.join(format!("summary_{}_{today}.png", period.name()));
+    charts::generate_summary_chart(&summaries, &chart_path)?;
+    println!("Chart saved to {}", style(chart_path.display()).green());
+    Ok(())
+}
+
+fn print_summary(summaries: &[PeriodSummary], period: Period) {
+    let defs = stats::metric_defs();
+    let width = defs.iter().map(|d| d.name.len()).max().unwrap_or(0);
+    println!(
+        "{}",
+        style(format!("=== {}ly Summary ===", capitalize(period.name()))).bold()
+    );
+    println!("{}", style("Medians per period; changes are against the previous period.").dim());
+
+    for (i, p) in summaries.iter().enumerate() {
+        println!();
+        println!(
+            "{}  {}  {}",
+            style(&p.label).bold().cyan(),
+            style(format!("{} – {}", p.start, p.end)).dim(),
+            plural(p.sessions, "session"),
+        );
+        for (m, def) in defs.iter().enumerate() {
+            let Some(summary) = p.metrics[m] else {
+                continue;
+            };
+            let value = format!("{:10}", stats::format_value(summary.median, def.unit));
+            let delta = change(summaries, i, m)
+                .map(|d| delta_text(d, def.unit, def.direction))
+                .unwrap_or_default();
+            let best = match (def.key, p.best_mpt) {
+                ("mpt", Some(best)) => style(format!("  best {}", stats::format_value(best, "s"))).dim().to_string(),
+                _ => String::new(),
+            };
+            println!("  {:width$}  {value} {delta}{best}", def.name);
+        }
+        if let Some(conditions) = p.conditions.describe() {
+            println!("  {}", style(conditions).dim());
+        }
+    }
+    println!();
+}
+
+/// Signed change, green when it is an improvement and red when it is not.
+fn delta_text(delta: f32, unit: &str, direction: Direction) -> String {
+    let text = format!("{:12}", format!("({})", stats::format_signed(delta, unit)));
+    if delta.abs() < 0.005 {
+        return style(text).dim().to_string();
+    }
+    match direction {
+        Direction::HigherIsBetter if delta > 0.0 => style(text).green().to_string(),
+        Direction::LowerIsBetter if delta < 0.0 => style(text).green().to_string(),
+        Direction::Neutral => text,
+        _ => style(text).red().to_string(),
+    }
+}
+
+/// Markdown report: one row per period with the metric medians (and their
+/// change from the previous period), then the conditions logged.
+pub fn markdown(summaries: &[PeriodSummary], period: Period) -> String {
+    let defs = stats::metric_defs();
+    let shown: Vec<usize> = (0..defs.len())
+        .filter(|&m| summaries.iter().any(|p| p.metrics[m].is_some()))
+        .collect();
+
+    let mut md = format!("# {}ly Summary\n\n", capitalize(period.name()));
+    md.push_str("Medians per period; changes in parentheses are against the previous period.\n\n");
+    md.push_str("| Period | Sessions |");
+    for &m in &shown {
+        md.push_str(&format!(" {} |", header(&defs[m])));
+    }
+    md.push_str(" Best MPT |\n|--------|----------|");
+    md.push_str(&"------|".repeat(shown.len()));
+    md.push_str("----------|\n");
+
+    for (i, p) in summaries.iter().enumerate() {
+        md.push_str(&format!("| {} | {} |", p.label, p.sessions));
+        for &m in &shown {
+            let cell = match (p.metrics[m], change(summaries, i, m)) {
+                (Some(s), Some(d)) => format!("{:.2} ({:+.2})", s.median, d),
+                (Some(s), None) => format!("{:.2}", s.median),
+                (None, _) => "—".into(),
+            };
+            md.push_str(&format!(" {cell} |"));
+        }
+        let best = p.best_mpt.map(|b| format!("{b:.1} s")).unwrap_or_else(|| "—".into());
+        md.push_str(&format!(" {best} |\n"));
+    }
+
+    if summaries.iter().any(|p| p.conditions.logged > 0) {
+        md.push_str("\n## Conditions\n\n| Period | Logged | Conditions |\n|--------|--------|------------|\n");
+        for p in summaries {
+            let text = p.conditions.describe().unwrap_or_else(|| "—".into());
+            md.push_str(&format!("| {} | {} | {text} |\n", p.label, p.conditions.logged));
+        }
+    }
+    md
+}
+
+fn header(def: &stats::MetricDef) -> String {
+    if def.unit.is_empty() {
+        def.name.to_string()
+    } else {
+        format!("{} ({})", def.name, def.unit)
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .map(|c| c.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+fn plural(n: usize, word: &str) -> String {
+    if n == 1 {
+        format!("1 {word}")
+    } else {
+        format!("{n} {word}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::session_data::*;
+
+    fn session(date: &str, mpt: f32, time_of_day: Option<&str>) -> SessionData {
+        let mut s = SessionData::test_default(date);
+        s.analysis.sustained = Some(SustainedAnalysis { mpt_seconds: mpt, ..SustainedAnalysis::test_default() });
+        s.conditions = time_of_day.map(RecordingConditions::test_default);
+        s
+    }
+
+    fn mpt_index() -> usize {
+        stats::metric_defs().iter().position(|d| d.key == "mpt").unwrap()
+    }
+
+    #[test]
+    fn groups_by_iso_week_across_year_end() {
+        let sessions = vec![
+            session("2025-12-29", 6.0, None), // Monday of 2026-W01
+            session("2026-01-02", 8.0, None),
+            session("2026-01-04", 7.0, None), // Sunday, still W01
+            session("2026-01-05", 9.0, None),
+        ];
+        let summaries = summarize(&sessions, Period::Week);
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].label, "2026-W01");
+        assert_eq!(summaries[0].start.to_string(), "2025-12-29");
+        assert_eq!(summaries[0].end.to_string(), "2026-01-04");
+        assert_eq!(summaries[0].sessions, 3);
+
+        let mpt = summaries[0].metrics[mpt_index()].unwrap();
+        assert_eq!((mpt.median, mpt.min, mpt.max), (7.0, 6.0, 8.0));
+        assert_eq!(summaries[0].best_mpt, Some(8.0));
+        assert_eq!(summaries[1].label, "2026-W02");
+        assert_eq!(change(&summaries, 1, mpt_index()), Some(2.0));
+        assert_eq!(change(&summaries, 0, mpt_index()), None);
+    }
+
+    #[test]
+    fn groups_by_month_and_counts_conditions() {
+        let sessions = vec![
+            session("2026-02-03", 6.0, Some("morning")),
+            session("2026-02-10", 7.0, Some("evening")),
+            session("2026-02-17", 8.0, Some("morning")),
+            session("2026-02-24", 9.0, None),
+            session("2026-03-02", 10.0, None),
+        ];
+        let summaries = summarize(&sessions, Period::Month);
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].label, "2026-02");
+        assert_eq!(summaries[0].end.to_string(), "2026-02-28");
+
+        let conditions = &summaries[0].conditions;
+        assert_eq!(conditions.logged, 3);
+        assert_eq!(conditions.time_of_day["morning"], 2);
+        assert_eq!(
+            conditions.describe().unwrap(),
+            "time evening 1, morning 2 · mucus low 3 · hydration normal 3 · fatigue 3/10"
+        );
+        assert!(summaries[1].conditions.describe().is_none());
+    }
+
+    #[test]
+    fn markdown_rows_with_changes() {
+        let sessions = vec![
+            session("2026-02-03", 6.0, Some("morning")),
+            session("2026-02-05", 8.0, None),
+            session("2026-02-10", 9.5, None),
+        ];
+        let md = markdown(&summarize(&sessions, Period::Week), Period::Week);
+        assert!(md.starts_with("# Weekly Summary"));
+        assert!(md.contains("| Period | Sessions | MPT (s) | HNR (dB) |"));
+        assert!(md.contains("| 2026-W06 | 2 | 7.00 | 12.00 |"));
+        assert!(md.contains("| 2026-W07 | 1 | 9.50 (+2.50) | 12.00 (+0.00) |"));
+        assert!(md.contains("| 8.0 s |\n"));
+        // Exercises never recorded get no column
+        assert!(!md.contains("Pitch range"));
+        assert!(md.contains("| 2026-W06 | 1 | time morning 1"));
+    }
+
+    #[test]
+    fn parses_periods() {
+        assert_eq!(Period::parse("week").unwrap(), Period::Week);
+        assert_eq!(Period::parse("Monthly").unwrap(), Period::Month);
+        assert!(Period::parse("year").is_err());
+    }
+}
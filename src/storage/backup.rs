@@ -0,0 +1,612 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use rusqlite::{Connection, DatabaseName, OpenFlags};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::schema;
+
+/// Archive layout version written to the manifest. Bump when the layout
+/// changes incompatibly; restore refuses archives newer than this.
+const FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_NAME: &str = "manifest.json";
+const DB_NAME: &str = "voicevo.db";
+
+/// Describes an archive: what wrote it and a checksum for every file.
+///
+/// Archive paths are `data/...` (relative to `data_dir()`) and `config/...`
+/// (relative to `config_dir()`); the manifest itself is stored last.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format: u32,
+    pub created: String,
+    pub voicevo_version: String,
+    /// Schema version of the database snapshot, if the archive has one.
+    pub schema_version: Option<u32>,
+    pub files: Vec<ManifestFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestFile {
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+impl Manifest {
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|f| f.size).sum()
+    }
+}
+
+/// Write a backup of `data_dir` and `config_dir` to `output` (tar + zstd).
+///
+/// The database goes in as a snapshot taken with SQLite's online backup API,
+/// so the archive is consistent even if another voicevo process is writing;
+/// the live database and its WAL files are not copied directly.
+pub fn create(data_dir: &Path, config_dir: &Path, output: &Path) -> Result<Manifest> {
+    let mut files = Vec::new();
+    for (prefix, root) in [("data", data_dir), ("config", config_dir)] {
+        collect_files(root, root, prefix, &mut files)?;
+    }
+    // The archive may be written inside the data dir; compare resolved paths
+    // so a relative or symlinked `output` is still left out
+    let output_resolved = resolve_output(output)?;
+    files.retain(|(name, path)| {
+        !is_live_db_file(name) && path.canonicalize().map_or(true, |p| p != output_resolved)
+    });
+    files.sort();
+
+    let file = File::create(output)
+        .with_context(|| format!("Failed to create {}", output.display()))?;
+    let encoder = zstd::Encoder::new(file, 3).context("Failed to start compression")?;
+    let mut builder = tar::Builder::new(encoder);
+    let mut manifest = Manifest {
+        format: FORMAT_VERSION,
+        created: chrono::Local::now().to_rfc3339(),
+        voicevo_version: env!("CARGO_PKG_VERSION").to_string(),
+        schema_version: None,
+        files: Vec::new(),
+    };
+
+    let db_path = data_dir.join(DB_NAME);
+    if db_path.exists() {
+        let (snapshot, version) = snapshot_db(&db_path)?;
+        manifest.schema_version = Some(version);
+        let size = snapshot.len() as u64;
+        append(&mut builder, &mut manifest, &format!("data/{DB_NAME}"), size, snapshot.as_slice())?;
+    }
+    for (name, path) in &files {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let size = file.metadata()?.len();
+        append(&mut builder, &mut manifest, name, size, file)?;
+    }
+
+    let json = serde_json::to_vec_pretty(&manifest)?;
+    append_entry(&mut builder, MANIFEST_NAME, json.len() as u64, json.as_slice())?;
+    builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .and_then(|mut file| file.flush())
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+
+    Ok(manifest)
+}
+
+/// Copy the live database to a temporary file with the online backup API and
+/// return the file's bytes and schema version.
+fn snapshot_db(db_path: &Path) -> Result<(Vec<u8>, u32)> {
+    let source = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .with_context(|| format!("Failed to open database: {}", db_path.display()))?;
+    let snapshot_path = std::env::temp_dir()
+        .join(format!("voicevo-backup-{}.db", std::process::id()));
+    source
+        .backup(DatabaseName::Main, &snapshot_path, None)
+        .context("Failed to snapshot database")?;
+
+    let result = (|| {
+        let snapshot = Connection::open(&snapshot_path)?;
+        // A single file, so the archive never needs a WAL alongside it
+        snapshot.execute_batch("PRAGMA journal_mode=DELETE;")?;
+        let version = schema::current_version(&snapshot)?;
+        drop(snapshot);
+        let data = std::fs::read(&snapshot_path)?;
+        Ok((data, version))
+    })();
+    let _ = std::fs::remove_file(&snapshot_path);
+    result
+}
+
+/// `output` with its directory resolved, so it compares equal to the
+/// canonical path of the archive file once it exists.
+fn resolve_output(output: &Path) -> Result<PathBuf> {
+    let name = output
+        .file_name()
+        .with_context(|| format!("Not a file path: {}", output.display()))?;
+    let dir = match output.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let dir = dir
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", dir.display()))?;
+    Ok(dir.join(name))
+}
+
+/// Stream `size` bytes from `data` into the archive, hashing as they go.
+fn append<W: Write>(
+    builder: &mut tar::Builder<W>,
+    manifest: &mut Manifest,
+    name: &str,
+    size: u64,
+    data: impl Read,
+) -> Result<()> {
+    let mut reader = HashingReader::new(data.take(size));
+    append_entry(builder, name, size, &mut reader)?;
+    // A file that shrank while being read would leave the entry short
+    if reader.len != size {
+        bail!("{name} changed size while being backed up");
+    }
+    manifest.files.push(ManifestFile {
+        path: name.to_string(),
+        size,
+        sha256: reader.hex_digest(),
+    });
+    Ok(())
+}
+
+fn append_entry<W: Write>(builder: &mut tar::Builder<W>, name: &str, size: u64, data: impl Read) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(size);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp() as u64);
+    builder
+        .append_data(&mut header, name, data)
+        .with_context(|| format!("Failed to add {name} to the archive"))
+}
+
+/// Reader that hashes and counts everything read through it.
+struct HashingReader<R> {
+    inner: R,
+    hasher: Sha256,
+    len: u64,
+}
+
+impl<R: Read> HashingReader<R> {
+    fn new(inner: R) -> Self {
+        HashingReader { inner, hasher: Sha256::new(), len: 0 }
+    }
+
+    fn hex_digest(self) -> String {
+        format!("{:x}", self.hasher.finalize())
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        self.len += n as u64;
+        Ok(n)
+    }
+}
+
+/// Every regular file under `dir`, as (archive name, path on disk).
+fn collect_files(root: &Path, dir: &Path, prefix: &str, out: &mut Vec<(String, PathBuf)>) -> Result<()> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Ok(());
+    };
+    for entry in entries {
+        let entry = entry.with_context(|| format!("Failed to list {}", dir.display()))?;
+        let path = entry.path();
+        let kind = entry.file_type()?;
+        if kind.is_dir() {
+            collect_files(root, &path, prefix, out)?;
+        } else if kind.is_file() {
+            let relative = path.strip_prefix(root)?;
+            let name = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            out.push((format!("{prefix}/{name}"), path));
+        }
+    }
+    Ok(())
+}
+
+/// The live database and its WAL/SHM files, replaced by the snapshot.
+fn is_live_db_file(name: &str) -> bool {
+    let db = format!("data/{DB_NAME}");
+    name == db || name == format!("{db}-wal") || name == format!("{db}-shm")
+}
+
+#[cfg(test)]
+fn sha256_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// One file a restore would write.
+pub struct PlannedFile {
+    pub target: PathBuf,
+    pub size: u64,
+    /// A file already exists at `target`.
+    pub overwrites: bool,
+}
+
+/// What restoring an archive does (or, for a dry run, would do).
+pub struct RestorePlan {
+    pub manifest: Manifest,
+    pub files: Vec<PlannedFile>,
+}
+
+impl RestorePlan {
+    pub fn overwrites(&self) -> usize {
+        self.files.iter().filter(|f| f.overwrites).count()
+    }
+}
+
+/// Verify `archive` against its manifest and restore it into `data_dir` and
+/// `config_dir`.
+///
+/// The archive is read once: files are extracted into a staging directory
+/// under each root while their checksums are computed, and only moved into
+/// place once every file matches the manifest. Existing files are only
+/// replaced with `force`; with `dry_run` the archive is verified and the plan
+/// returned without touching the disk.
+pub fn restore(
+    archive: &Path,
+    data_dir: &Path,
+    config_dir: &Path,
+    dry_run: bool,
+    force: bool,
+) -> Result<RestorePlan> {
+    let mut staging = Staging::default();
+    let mut extract = !dry_run;
+    let manifest = read_checked(archive, |name, entry| {
+        let (root, rest) = archive_location(name, data_dir, config_dir)?;
+        // Without `force` an existing file fails the restore, so there is no
+        // point extracting the rest; keep reading to report on the archive
+        if !force && root.join(&rest).exists() {
+            extract = false;
+        }
+        if !extract {
+            return Ok(());
+        }
+        let staged = staging.dir(root)?.join(&rest);
+        if let Some(parent) = staged.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let mut file = File::create(&staged)
+            .with_context(|| format!("Failed to write {}", staged.display()))?;
+        io::copy(entry, &mut file).with_context(|| format!("Failed to extract {name}"))?;
+        Ok(())
+    })?;
+
+    let files = manifest
+        .files
+        .iter()
+        .map(|f| {
+            let target = target_path(&f.path, data_dir, config_dir)?;
+            let overwrites = target.exists();
+            Ok(PlannedFile { target, size: f.size, overwrites })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let plan = RestorePlan { manifest, files };
+
+    if dry_run {
+        return Ok(plan);
+    }
+    if plan.overwrites() > 0 && !force {
+        bail!(
+            "{} file(s) already exist and would be overwritten. Use --dry-run to list them, or --force to replace them.",
+            plan.overwrites()
+        );
+    }
+
+    // Everything checked out: move the staged files into place
+    for file in &plan.manifest.files {
+        let (root, rest) = archive_location(&file.path, data_dir, config_dir)?;
+        let staged = staging.dir(root)?.join(&rest);
+        let target = root.join(&rest);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::rename(&staged, &target)
+            .with_context(|| format!("Failed to write {}", target.display()))?;
+    }
+    drop(staging);
+
+    // A WAL left by the replaced database would be replayed over the snapshot
+    if plan.manifest.schema_version.is_some() {
+        for suffix in ["-wal", "-shm"] {
+            let stale = data_dir.join(format!("{DB_NAME}{suffix}"));
+            if stale.exists() {
+                std::fs::remove_file(&stale)
+                    .with_context(|| format!("Failed to remove {}", stale.display()))?;
+            }
+        }
+    }
+
+    Ok(plan)
+}
+
+/// Directories a restore extracts into before moving files into place, one
+/// per root so the final rename stays on the same filesystem. Removed on
+/// drop, so a failed restore leaves nothing behind.
+#[derive(Default)]
+struct Staging {
+    /// (root, staging directory inside it)
+    dirs: Vec<(PathBuf, PathBuf)>,
+}
+
+impl Staging {
+    /// The staging directory for `root`, created on first use.
+    fn dir(&mut self, root: &Path) -> Result<PathBuf> {
+        if let Some((_, dir)) = self.dirs.iter().find(|(r, _)| r == root) {
+            return Ok(dir.clone());
+        }
+        let dir = root.join(format!(".voicevo-restore-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        self.dirs.push((root.to_path_buf(), dir.clone()));
+        Ok(dir)
+    }
+}
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        for (_, dir) in &self.dirs {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+    }
+}
+
+/// Read the archive once, handing each file's contents to `on_file` as they
+/// stream past, and check every file against the manifest. Whatever
+/// `on_file` leaves unread is still hashed. Returns the manifest if the
+/// archive is complete and intact.
+fn read_checked(
+    archive: &Path,
+    mut on_file: impl FnMut(&str, &mut dyn Read) -> Result<()>,
+) -> Result<Manifest> {
+    let mut found: BTreeMap<String, (u64, String)> = BTreeMap::new();
+    let mut manifest_json = None;
+    for_each_entry(archive, |name, entry| {
+        if name == MANIFEST_NAME {
+            let mut json = Vec::new();
+            entry
+                .read_to_end(&mut json)
+                .context("Failed to read the archive manifest")?;
+            manifest_json = Some(json);
+        } else {
+            let mut reader = HashingReader::new(entry);
+            on_file(name, &mut reader)?;
+            io::copy(&mut reader, &mut io::sink())
+                .with_context(|| format!("Failed to read {name} from the archive"))?;
+            found.insert(name.to_string(), (reader.len, reader.hex_digest()));
+        }
+        Ok(())
+    })?;
+
+    let json = manifest_json.context("Archive has no manifest; not a voicevo backup")?;
+    let manifest: Manifest = serde_json::from_slice(&json).context("Archive manifest is corrupt")?;
+    if manifest.format > FORMAT_VERSION {
+        bail!(
+            "Backup format {} is newer than this build supports ({FORMAT_VERSION}). Update voicevo.",
+            manifest.format
+        );
+    }
+
+    let mut problems = Vec::new();
+    for file in &manifest.files {
+        match found.remove(&file.path) {
+            None => problems.push(format!("{}: missing", file.path)),
+            Some((size, _)) if size != file.size => {
+                problems.push(format!("{}: {size} bytes, expected {}", file.path, file.size))
+            }
+            Some((_, hash)) if hash != file.sha256 => problems.push(format!("{}: checksum mismatch", file.path)),
+            Some(_) => {}
+        }
+    }
+    for extra in found.keys() {
+        problems.push(format!("{extra}: not in the manifest"));
+    }
+    if !problems.is_empty() {
+        bail!("Backup failed verification:\n  {}", problems.join("\n  "));
+    }
+
+    Ok(manifest)
+}
+
+/// Call `f` with the name and a reader over the contents of each regular
+/// file in the archive.
+fn for_each_entry(archive: &Path, mut f: impl FnMut(&str, &mut dyn Read) -> Result<()>) -> Result<()> {
+    let file = File::open(archive)
+        .with_context(|| format!("Failed to open {}", archive.display()))?;
+    let decoder = zstd::Decoder::new(file).context("Failed to start decompression")?;
+    let mut tar = tar::Archive::new(decoder);
+    let entries = tar
+        .entries()
+        .with_context(|| format!("Failed to read {}", archive.display()))?;
+
+    for entry in entries {
+        let mut entry = entry.with_context(|| format!("{} is not a valid backup", archive.display()))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry.path()?.to_string_lossy().into_owned();
+        f(&name, &mut entry)?;
+    }
+    Ok(())
+}
+
+/// Where an archive path is restored to.
+fn target_path(name: &str, data_dir: &Path, config_dir: &Path) -> Result<PathBuf> {
+    let (root, rest) = archive_location(name, data_dir, config_dir)?;
+    Ok(root.join(rest))
+}
+
+/// The root an archive path belongs to and its path under that root.
+/// Rejects anything outside the `data/` and `config/` trees (absolute
+/// paths, `..`).
+fn archive_location<'a>(name: &str, data_dir: &'a Path, config_dir: &'a Path) -> Result<(&'a Path, PathBuf)> {
+    let path = Path::new(name);
+    let mut components = path.components();
+    let root = match components.next() {
+        Some(Component::Normal(c)) if c == "data" => data_dir,
+        Some(Component::Normal(c)) if c == "config" => config_dir,
+        _ => bail!("Unexpected path in archive: {name}"),
+    };
+    let rest = components.as_path();
+    if rest.as_os_str().is_empty() || !rest.components().all(|c| matches!(c, Component::Normal(_))) {
+        bail!("Unexpected path in archive: {name}");
+    }
+    Ok((root, rest.to_path_buf()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::db;
+    use tempfile::TempDir;
+
+    /// A data dir with a database (one event), a recording and a legacy JSON
+    /// session, plus a config dir with a config file.
+    fn populated() -> (TempDir, PathBuf, PathBuf) {
+        let tmp = TempDir::new().unwrap();
+        let data = tmp.path().join("data");
+        let config = tmp.path().join("config");
+        std::fs::create_dir_all(data.join("recordings/2026-01-15")).unwrap();
+        std::fs::create_dir_all(data.join("sessions")).unwrap();
+        std::fs::create_dir_all(&config).unwrap();
+
+        let conn = Connection::open(data.join(DB_NAME)).unwrap();
+        conn.execute_batch("PRAGMA journal_mode=WAL;").unwrap();
+        db::init_schema(&conn).unwrap();
+        db::save_event(&conn, "2026-01-10", "injection", "left fold").unwrap();
+        drop(conn);
+
+        std::fs::write(data.join("recordings/2026-01-15/sustained_001.wav"), b"RIFF fake").unwrap();
+        std::fs::write(data.join("sessions/2026-01-15.json"), b"{}").unwrap();
+        std::fs::write(config.join("config.toml"), b"[profile]\n").unwrap();
+        (tmp, data, config)
+    }
+
+    #[test]
+    fn backup_and_restore_round_trip() {
+        let (tmp, data, config) = populated();
+        let archive = tmp.path().join("backup.tar.zst");
+        let manifest = create(&data, &config, &archive).unwrap();
+
+        let names: Vec<&str> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "data/voicevo.db",
+                "config/config.toml",
+                "data/recordings/2026-01-15/sustained_001.wav",
+                "data/sessions/2026-01-15.json",
+            ]
+        );
+        assert_eq!(manifest.schema_version, Some(schema::latest_version()));
+
+        let restored = TempDir::new().unwrap();
+        let (new_data, new_config) = (restored.path().join("data"), restored.path().join("config"));
+        let plan = restore(&archive, &new_data, &new_config, false, false).unwrap();
+        assert_eq!(plan.files.len(), 4);
+        assert_eq!(plan.overwrites(), 0);
+
+        assert_eq!(
+            std::fs::read(new_data.join("recordings/2026-01-15/sustained_001.wav")).unwrap(),
+            b"RIFF fake"
+        );
+        assert_eq!(std::fs::read(new_config.join("config.toml")).unwrap(), b"[profile]\n");
+        let conn = Connection::open(new_data.join(DB_NAME)).unwrap();
+        let events = db::load_events(&conn).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].note, "left fold");
+    }
+
+    #[test]
+    fn archive_inside_data_dir_is_left_out() {
+        let (_tmp, data, config) = populated();
+        // An earlier backup at the same place, named through a detour
+        std::fs::write(data.join("backup.tar.zst"), b"old").unwrap();
+        let output = data.join("recordings/../backup.tar.zst");
+        let manifest = create(&data, &config, &output).unwrap();
+        assert!(manifest.files.iter().all(|f| f.path != "data/backup.tar.zst"));
+        assert_eq!(manifest.files.len(), 4);
+    }
+
+    #[test]
+    fn dry_run_and_existing_files() {
+        let (tmp, data, config) = populated();
+        let archive = tmp.path().join("backup.tar.zst");
+        create(&data, &config, &archive).unwrap();
+
+        // Restoring over the original data: everything already exists
+        let plan = restore(&archive, &data, &config, true, false).unwrap();
+        assert_eq!(plan.overwrites(), 4);
+        assert!(restore(&archive, &data, &config, false, false).is_err());
+        assert!(restore(&archive, &data, &config, false, true).is_ok());
+        let leftovers = std::fs::read_dir(&data)
+            .unwrap()
+            .filter(|e| e.as_ref().unwrap().file_name().to_string_lossy().starts_with(".voicevo-restore"))
+            .count();
+        assert_eq!(leftovers, 0, "staging directories are cleaned up");
+
+        // A dry run into an empty location writes nothing
+        let empty = TempDir::new().unwrap();
+        let plan = restore(&archive, &empty.path().join("data"), &empty.path().join("config"), true, false).unwrap();
+        assert_eq!(plan.overwrites(), 0);
+        assert_eq!(std::fs::read_dir(empty.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn tampered_archive_fails_verification() {
+        let tmp = TempDir::new().unwrap();
+        let archive = tmp.path().join("bad.tar.zst");
+        let manifest = Manifest {
+            format: FORMAT_VERSION,
+            created: String::new(),
+            voicevo_version: String::new(),
+            schema_version: None,
+            files: vec![ManifestFile {
+                path: "config/config.toml".into(),
+                size: 5,
+                sha256: sha256_hex(b"hello"),
+            }],
+        };
+        let mut builder = tar::Builder::new(zstd::Encoder::new(File::create(&archive).unwrap(), 3).unwrap());
+        append_entry(&mut builder, "config/config.toml", 5, &b"HELLO"[..]).unwrap();
+        let json = serde_json::to_vec(&manifest).unwrap();
+        append_entry(&mut builder, MANIFEST_NAME, json.len() as u64, json.as_slice()).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let err = read_checked(&archive, |_, _| Ok(())).unwrap_err().to_string();
+        assert!(err.contains("config/config.toml: checksum mismatch"), "{err}");
+        let out = TempDir::new().unwrap();
+        assert!(restore(&archive, out.path(), out.path(), false, true).is_err());
+        assert_eq!(std::fs::read_dir(out.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn archive_paths_stay_inside_their_roots() {
+        let (data, config) = (Path::new("/d"), Path::new("/c"));
+        assert_eq!(target_path("data/recordings/a.wav", data, config).unwrap(), Path::new("/d/recordings/a.wav"));
+        assert_eq!(target_path("config/config.toml", data, config).unwrap(), Path::new("/c/config.toml"));
+        assert!(target_path("data/../../etc/passwd", data, config).is_err());
+        assert!(target_path("/etc/passwd", data, config).is_err());
+        assert!(target_path("other/file", data, config).is_err());
+        assert!(target_path("data", data, config).is_err());
+    }
+}
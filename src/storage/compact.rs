@@ -0,0 +1,204 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use hound::{SampleFormat, WavReader};
+use rusqlite::Connection;
+
+use super::db;
+use super::session_data::SessionId;
+use crate::audio::flac::{self, FlacWriter};
+
+/// What `voicevo compact` converted, or would convert on a dry run.
+#[derive(Debug, Default)]
+pub struct CompactSummary {
+    /// (WAV path, FLAC path) of each converted recording.
+    pub converted: Vec<(PathBuf, PathBuf)>,
+    /// Recordings left as WAV, with the reason.
+    pub skipped: Vec<(PathBuf, String)>,
+    pub bytes_before: u64,
+    /// Size of the FLAC files written; 0 on a dry run.
+    pub bytes_after: u64,
+}
+
+/// Convert every WAV recording under `recordings_dir` to FLAC.
+///
+/// Each file is decoded again after encoding and compared sample for sample
+/// before the WAV is removed, so a failure at any point leaves the original
+/// in place. The FLAC keeps the WAV's modification time (the doctor compares
+/// it with the analysis time) and session paths in the database are updated
+/// to match.
+pub fn compact(conn: &Connection, recordings_dir: &Path, dry_run: bool) -> Result<CompactSummary> {
+    let mut summary = CompactSummary::default();
+
+    for wav in wav_recordings(recordings_dir)? {
+        let size = std::fs::metadata(&wav)
+            .with_context(|| format!("Failed to read {}", wav.display()))?
+            .len();
+        let reader = WavReader::open(&wav).with_context(|| format!("Failed to open WAV file: {}", wav.display()))?;
+        let spec = reader.spec();
+        if spec.sample_format == SampleFormat::Float {
+            summary.skipped.push((wav, "floating-point samples can't be stored as FLAC".into()));
+            continue;
+        }
+        if spec.bits_per_sample > 24 {
+            summary.skipped.push((wav, format!("{}-bit samples can't be stored as FLAC", spec.bits_per_sample)));
+            continue;
+        }
+
+        let target = wav.with_extension("flac");
+        summary.bytes_before += size;
+        if !dry_run {
+            convert(reader, &wav, &target)?;
+            db::rename_recording(conn, &wav.to_string_lossy(), &target.to_string_lossy())?;
+            std::fs::remove_file(&wav).with_context(|| format!("Failed to remove {}", wav.display()))?;
+            summary.bytes_after += std::fs::metadata(&target)?.len();
+        }
+        summary.converted.push((wav, target));
+    }
+
+    Ok(summary)
+}
+
+/// WAV files in the session directories, in session then file name order.
+fn wav_recordings(recordings_dir: &Path) -> Result<Vec<PathBuf>> {
+    let Ok(entries) = std::fs::read_dir(recordings_dir) else {
+        return Ok(Vec::new());
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .filter(|p| p.file_name().and_then(|n| n.to_str()).is_some_and(|n| SessionId::parse(n).is_ok()))
+        .collect();
+    dirs.sort();
+
+    let mut wavs = Vec::new();
+    for dir in dirs {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(&dir)
+            .with_context(|| format!("Failed to list {}", dir.display()))?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("wav")))
+            .collect();
+        files.sort();
+        wavs.extend(files);
+    }
+    Ok(wavs)
+}
+
+/// Encode one WAV as FLAC next to it, verified and with the WAV's mtime.
+fn convert(mut reader: WavReader<std::io::BufReader<std::fs::File>>, wav: &Path, target: &Path) -> Result<()> {
+    let spec = reader.spec();
+    let samples = reader
+        .samples::<i32>()
+        .collect::<hound::Result<Vec<_>>>()
+        .with_context(|| format!("Failed to read WAV samples: {}", wav.display()))?;
+    let modified = std::fs::metadata(wav)?.modified()?;
+
+    // Written under a temporary name so an interrupted run never leaves a
+    // truncated .flac that would shadow the WAV in `paths::list_attempts`
+    let tmp = target.with_extension("flac.tmp");
+    let result = (|| -> Result<()> {
+        let mut writer = FlacWriter::create(&tmp, spec)?;
+        for &s in &samples {
+            writer.write_sample(s)?;
+        }
+        writer.finalize()?;
+
+        let (decoded, decoded_spec) = flac::read_samples(&tmp)?;
+        if decoded != samples || decoded_spec.channels != spec.channels || decoded_spec.sample_rate != spec.sample_rate {
+            anyhow::bail!("FLAC of {} did not decode to the original samples", wav.display());
+        }
+        Ok(())
+    })();
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e);
+    }
+
+    std::fs::rename(&tmp, target).with_context(|| format!("Failed to write {}", target.display()))?;
+    std::fs::File::options()
+        .write(true)
+        .open(target)
+        .and_then(|f| f.set_modified(modified))
+        .with_context(|| format!("Failed to set the modification time of {}", target.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::wav;
+    use crate::storage::session_data::*;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    fn write_wav(path: &Path, spec: hound::WavSpec) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let mut writer = hound::WavWriter::create(path, spec).unwrap();
+        for i in 0..20_000i32 {
+            let t = i as f32 / 44100.0;
+            let s = (t * 2.0 * std::f32::consts::PI * 150.0).sin() * 0.4;
+            match spec.sample_format {
+                SampleFormat::Int => writer.write_sample((s * i16::MAX as f32) as i16).unwrap(),
+                SampleFormat::Float => writer.write_sample(s).unwrap(),
+            }
+        }
+        writer.finalize().unwrap();
+    }
+
+    fn session_with(sustained: &Path) -> SessionData {
+        SessionData {
+            date: "2026-01-15".into(),
+            slot: 1,
+            recordings: SessionRecordings {
+                sustained: Some(sustained.to_string_lossy().into_owned()),
+                scale: None,
+                reading: None,
+            },
+            analysis: SessionAnalysis { sustained: None, scale: None, reading: None, sz: None, fatigue: None },
+            conditions: None,
+            source: None,
+        }
+    }
+
+    #[test]
+    fn compact_converts_and_relinks() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join("2026-01-15");
+        let take = dir.join("sustained_001.wav");
+        write_wav(&take, wav::recording_spec(44100, crate::config::BitDepth::I16));
+        let recorded = SystemTime::now() - Duration::from_secs(3600);
+        std::fs::File::options().write(true).open(&take).unwrap().set_modified(recorded).unwrap();
+        let (original, _) = wav::load_samples(&take).unwrap();
+
+        let float_take = dir.join("reading_001.wav");
+        write_wav(&float_take, hound::WavSpec { bits_per_sample: 32, sample_format: SampleFormat::Float, ..wav::recording_spec(44100, crate::config::BitDepth::I16) });
+
+        let conn = Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        db::save_session(&conn, &session_with(&take)).unwrap();
+
+        let dry = compact(&conn, tmp.path(), true).unwrap();
+        assert_eq!(dry.converted.len(), 1);
+        assert!(take.exists());
+
+        let summary = compact(&conn, tmp.path(), false).unwrap();
+        let flac = dir.join("sustained_001.flac");
+        assert_eq!(summary.converted, vec![(take.clone(), flac.clone())]);
+        assert_eq!(summary.skipped.len(), 1);
+        assert!(summary.bytes_after < summary.bytes_before);
+        assert!(!take.exists());
+        assert!(float_take.exists());
+
+        let (decoded, _) = wav::load_samples(&flac).unwrap();
+        assert_eq!(decoded, original);
+        assert_eq!(std::fs::metadata(&flac).unwrap().modified().unwrap(), recorded);
+
+        let session = db::load_session(&conn, "2026-01-15").unwrap();
+        assert_eq!(session.recordings.sustained.as_deref(), Some(flac.to_string_lossy().as_ref()));
+
+        // Nothing left to do on a second run
+        assert!(compact(&conn, tmp.path(), false).unwrap().converted.is_empty());
+    }
+}
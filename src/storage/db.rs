@@ -1,10 +1,21 @@
+use std::collections::BTreeMap;
+
 use anyhow::{Context, Result};
-use rusqlite::Connection;
+use rusqlite::{Connection, OptionalExtension};
 
+use super::schema;
 use super::session_data::*;
 
-/// Open (or create) the SQLite database at the configured path.
+/// Open (or create) the SQLite database at the configured path and bring
+/// its schema up to date.
 pub fn open_db() -> Result<Connection> {
+    let conn = connect()?;
+    init_schema(&conn)?;
+    Ok(conn)
+}
+
+/// Open the database without running migrations (for `voicevo db status`).
+pub fn connect() -> Result<Connection> {
     let path = crate::paths::db_path();
 
     if let Some(parent) = path.parent() {
@@ -18,44 +29,104 @@ pub fn open_db() -> Result<Connection> {
     conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA foreign_keys=ON;")
         .context("Failed to set database pragmas")?;
 
-    init_schema(&conn)?;
     Ok(conn)
 }
 
-/// Create tables if they don't exist. Idempotent.
+/// Bring the schema up to date (see `schema::MIGRATIONS`). Idempotent.
 pub fn init_schema(conn: &Connection) -> Result<()> {
-    conn.execute_batch(
-        "CREATE TABLE IF NOT EXISTS sessions (
-            id INTEGER PRIMARY KEY,
-            date TEXT NOT NULL UNIQUE,
-            sustained_path TEXT,
-            scale_path TEXT,
-            reading_path TEXT
-        );
+    schema::migrate(conn)?;
+    Ok(())
+}
 
-        CREATE TABLE IF NOT EXISTS analyses (
-            id INTEGER PRIMARY KEY,
-            session_id INTEGER NOT NULL REFERENCES sessions(id),
-            version INTEGER NOT NULL DEFAULT 1,
-            exercise TEXT NOT NULL,
-            data TEXT NOT NULL,
-            created_at TEXT NOT NULL DEFAULT (datetime('now')),
-            UNIQUE(session_id, version, exercise)
-        );",
-    )
-    .context("Failed to initialize database schema")?;
+/// Scalar metrics mirrored from the analysis blobs into the `metrics` table:
+/// (exercise, JSON field, metric name, validity flag in
+/// `reliability.metrics_validity`). Names are unique across exercises.
+const METRIC_FIELDS: &[(&str, &str, &str, Option<&str>)] = &[
+    ("sustained", "mpt_seconds", "mpt", None),
+    ("sustained", "mean_f0_hz", "mean_f0", None),
+    ("sustained", "f0_std_hz", "f0_std", None),
+    ("sustained", "jitter_local_percent", "jitter", Some("jitter")),
+    ("sustained", "shimmer_local_percent", "shimmer", Some("shimmer")),
+    ("sustained", "hnr_db", "hnr", Some("hnr")),
+    ("sustained", "cpps_db", "cpps", Some("cpps")),
+    ("scale", "pitch_floor_hz", "pitch_floor", None),
+    ("scale", "pitch_ceiling_hz", "pitch_ceiling", None),
+    ("scale", "range_hz", "range_hz", None),
+    ("scale", "range_semitones", "range", None),
+    ("reading", "mean_f0_hz", "speaking_f0", None),
+    ("reading", "f0_std_hz", "speaking_f0_std", None),
+    ("reading", "voice_breaks", "voice_breaks", Some("voice_breaks")),
+    ("reading", "voiced_fraction", "voiced_fraction", None),
+    ("reading", "cpps_db", "cpps_reading", Some("cpps")),
+    ("sz", "mean_s", "s_duration", None),
+    ("sz", "mean_z", "z_duration", None),
+    ("sz", "sz_ratio", "sz_ratio", None),
+    ("fatigue", "mpt_slope", "mpt_slope", None),
+    ("fatigue", "cpps_slope", "cpps_slope", None),
+];
+
+/// Metric names that can be queried, in table order.
+pub fn metric_names() -> Vec<&'static str> {
+    METRIC_FIELDS.iter().map(|&(_, _, name, _)| name).collect()
+}
+
+/// Exercise and JSON field a metric is stored in, e.g. "jitter" ->
+/// ("sustained", "jitter_local_percent").
+pub fn metric_field(name: &str) -> Option<(&'static str, &'static str)> {
+    METRIC_FIELDS
+        .iter()
+        .find(|&&(_, _, metric, _)| metric == name)
+        .map(|&(exercise, field, _, _)| (exercise, field))
+}
+
+/// Replace the `metrics` rows of one session-level analysis.
+///
+/// A metric counts as valid unless the analysis' reliability info flags it
+/// (voice breaks only when "valid"); older analyses without it are valid.
+fn save_metrics(conn: &Connection, session_id: i64, version: u32, exercise: &str, data: &str) -> Result<()> {
+    let value: serde_json::Value = serde_json::from_str(data)
+        .with_context(|| format!("Failed to parse {exercise} analysis"))?;
+    let validity = value.pointer("/reliability/metrics_validity");
 
-    // Migration: add conditions column if it doesn't exist
-    let has_conditions: bool = conn
-        .prepare("SELECT 1 FROM pragma_table_info('sessions') WHERE name = 'conditions'")
-        .and_then(|mut stmt| stmt.exists([]))
-        .unwrap_or(false);
+    conn.execute(
+        "DELETE FROM metrics WHERE session_id = ?1 AND version = ?2 AND exercise = ?3",
+        rusqlite::params![session_id, version, exercise],
+    )
+    .context("Failed to clear metrics")?;
 
-    if !has_conditions {
-        conn.execute_batch("ALTER TABLE sessions ADD COLUMN conditions TEXT;")
-            .context("Failed to add conditions column")?;
+    for &(_, field, name, flag) in METRIC_FIELDS.iter().filter(|f| f.0 == exercise) {
+        let Some(metric) = value.get(field).and_then(|v| v.as_f64()) else {
+            continue;
+        };
+        let valid = match flag.and_then(|f| validity?.get(f)) {
+            Some(serde_json::Value::Bool(b)) => *b,
+            Some(serde_json::Value::String(s)) => s == "valid",
+            _ => true,
+        };
+        conn.execute(
+            "INSERT INTO metrics (session_id, version, exercise, name, value, valid)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![session_id, version, exercise, name, metric, valid],
+        )
+        .with_context(|| format!("Failed to save metric {name}"))?;
     }
+    Ok(())
+}
+
+/// Populate `metrics` from every stored session-level analysis.
+pub(super) fn backfill_metrics(conn: &Connection) -> Result<()> {
+    let mut stmt = conn
+        .prepare("SELECT session_id, version, exercise, data FROM analyses WHERE attempt = 0")
+        .context("Failed to prepare metrics backfill")?;
+    let rows: Vec<(i64, u32, String, String)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+        .context("Failed to read analyses for backfill")?
+        .filter_map(|r| r.ok())
+        .collect();
 
+    for (session_id, version, exercise, data) in rows {
+        save_metrics(conn, session_id, version, &exercise, &data)?;
+    }
     Ok(())
 }
 
@@ -79,27 +150,30 @@ pub fn save_session_version(
 
     // Upsert the session row
     conn.execute(
-        "INSERT INTO sessions (date, sustained_path, scale_path, reading_path, conditions)
-         VALUES (?1, ?2, ?3, ?4, ?5)
-         ON CONFLICT(date) DO UPDATE SET
+        "INSERT INTO sessions (date, slot, sustained_path, scale_path, reading_path, conditions, source)
+         VALUES (?1, ?6, ?2, ?3, ?4, ?5, ?7)
+         ON CONFLICT(date, slot) DO UPDATE SET
             sustained_path = COALESCE(?2, sustained_path),
             scale_path = COALESCE(?3, scale_path),
             reading_path = COALESCE(?4, reading_path),
-            conditions = COALESCE(?5, conditions)",
+            conditions = COALESCE(?5, conditions),
+            source = COALESCE(?7, source)",
         rusqlite::params![
             session.date,
             session.recordings.sustained,
             session.recordings.scale,
             session.recordings.reading,
             conditions_json,
+            session.slot,
+            session.source,
         ],
     )
     .context("Failed to upsert session")?;
 
     let session_id: i64 = conn
         .query_row(
-            "SELECT id FROM sessions WHERE date = ?1",
-            [&session.date],
+            "SELECT id FROM sessions WHERE date = ?1 AND slot = ?2",
+            rusqlite::params![session.date, session.slot],
             |row| row.get(0),
         )
         .context("Failed to get session id")?;
@@ -107,78 +181,86 @@ pub fn save_session_version(
     // Save each analysis as a JSON blob
     if let Some(ref sustained) = session.analysis.sustained {
         let json = serde_json::to_string(sustained).context("Failed to serialize sustained")?;
-        upsert_analysis(conn, session_id, version, "sustained", &json)?;
+        upsert_analysis(conn, session_id, version, "sustained", 0, &json)?;
     }
 
     if let Some(ref scale) = session.analysis.scale {
         let json = serde_json::to_string(scale).context("Failed to serialize scale")?;
-        upsert_analysis(conn, session_id, version, "scale", &json)?;
+        upsert_analysis(conn, session_id, version, "scale", 0, &json)?;
     }
 
     if let Some(ref reading) = session.analysis.reading {
         let json = serde_json::to_string(reading).context("Failed to serialize reading")?;
-        upsert_analysis(conn, session_id, version, "reading", &json)?;
+        upsert_analysis(conn, session_id, version, "reading", 0, &json)?;
     }
 
     if let Some(ref sz) = session.analysis.sz {
         let json = serde_json::to_string(sz).context("Failed to serialize sz")?;
-        upsert_analysis(conn, session_id, version, "sz", &json)?;
+        upsert_analysis(conn, session_id, version, "sz", 0, &json)?;
     }
 
     if let Some(ref fatigue) = session.analysis.fatigue {
         let json = serde_json::to_string(fatigue).context("Failed to serialize fatigue")?;
-        upsert_analysis(conn, session_id, version, "fatigue", &json)?;
+        upsert_analysis(conn, session_id, version, "fatigue", 0, &json)?;
     }
 
     Ok(())
 }
 
+/// Insert or replace one analysis row. Attempt 0 is the session-level
+/// result; numbered attempts are the individual takes.
 fn upsert_analysis(
     conn: &Connection,
     session_id: i64,
     version: u32,
     exercise: &str,
+    attempt: u32,
     data: &str,
 ) -> Result<()> {
     conn.execute(
-        "INSERT INTO analyses (session_id, version, exercise, data)
-         VALUES (?1, ?2, ?3, ?4)
-         ON CONFLICT(session_id, version, exercise) DO UPDATE SET
-            data = ?4,
+        "INSERT INTO analyses (session_id, version, exercise, attempt, data)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(session_id, version, exercise, attempt) DO UPDATE SET
+            data = ?5,
             created_at = datetime('now')",
-        rusqlite::params![session_id, version, exercise, data],
+        rusqlite::params![session_id, version, exercise, attempt, data],
     )
     .with_context(|| format!("Failed to upsert analysis for {exercise}"))?;
 
+    if attempt == 0 {
+        save_metrics(conn, session_id, version, exercise, data)?;
+    }
     Ok(())
 }
 
-/// Load a session at the latest available version.
-pub fn load_session(conn: &Connection, date: &str) -> Result<SessionData> {
-    let (session_id, recordings) = load_session_row(conn, date)?;
+/// Load a session (by id, see `SessionId`) at the latest available version.
+pub fn load_session(conn: &Connection, id: &str) -> Result<SessionData> {
+    let key = SessionId::parse(id)?;
+    let (session_id, recordings) = load_session_row(conn, &key)?;
 
     // Find latest version
     let version: u32 = conn
         .query_row(
-            "SELECT COALESCE(MAX(version), 1) FROM analyses WHERE session_id = ?1",
+            "SELECT COALESCE(MAX(version), 1) FROM analyses WHERE session_id = ?1 AND attempt = 0",
             [session_id],
             |row| row.get(0),
         )
         .context("Failed to query latest version")?;
 
-    load_analysis(conn, date, session_id, version, recordings)
+    load_analysis(conn, &key, session_id, version, recordings)
 }
 
 /// Load a session at a specific analysis version.
-pub fn load_session_version(conn: &Connection, date: &str, version: u32) -> Result<SessionData> {
-    let (session_id, recordings) = load_session_row(conn, date)?;
-    load_analysis(conn, date, session_id, version, recordings)
+pub fn load_session_version(conn: &Connection, id: &str, version: u32) -> Result<SessionData> {
+    let key = SessionId::parse(id)?;
+    let (session_id, recordings) = load_session_row(conn, &key)?;
+    load_analysis(conn, &key, session_id, version, recordings)
 }
 
-fn load_session_row(conn: &Connection, date: &str) -> Result<(i64, SessionRecordings)> {
+fn load_session_row(conn: &Connection, key: &SessionId) -> Result<(i64, SessionRecordings)> {
     conn.query_row(
-        "SELECT id, sustained_path, scale_path, reading_path FROM sessions WHERE date = ?1",
-        [date],
+        "SELECT id, sustained_path, scale_path, reading_path FROM sessions WHERE date = ?1 AND slot = ?2",
+        rusqlite::params![key.date.to_string(), key.slot],
         |row| {
             Ok((
                 row.get::<_, i64>(0)?,
@@ -190,7 +272,7 @@ fn load_session_row(conn: &Connection, date: &str) -> Result<(i64, SessionRecord
             ))
         },
     )
-    .with_context(|| format!("No session found for date: {date}"))
+    .with_context(|| format!("No session found for {key}"))
 }
 
 fn load_conditions(conn: &Connection, session_id: i64) -> Result<Option<RecordingConditions>> {
@@ -214,7 +296,7 @@ fn load_conditions(conn: &Connection, session_id: i64) -> Result<Option<Recordin
 
 fn load_analysis(
     conn: &Connection,
-    date: &str,
+    key: &SessionId,
     session_id: i64,
     version: u32,
     recordings: SessionRecordings,
@@ -226,9 +308,13 @@ fn load_analysis(
     let fatigue = load_analysis_json::<FatigueAnalysis>(conn, session_id, version, "fatigue")?;
 
     let conditions = load_conditions(conn, session_id)?;
+    let source: Option<String> = conn
+        .query_row("SELECT source FROM sessions WHERE id = ?1", [session_id], |row| row.get(0))
+        .context("Failed to load session source")?;
 
     Ok(SessionData {
-        date: date.to_string(),
+        date: key.date.to_string(),
+        slot: key.slot,
         recordings,
         analysis: SessionAnalysis {
             sustained,
@@ -238,6 +324,7 @@ fn load_analysis(
             fatigue,
         },
         conditions,
+        source,
     })
 }
 
@@ -249,7 +336,8 @@ fn load_analysis_json<T: serde::de::DeserializeOwned>(
 ) -> Result<Option<T>> {
     let json: Option<String> = conn
         .query_row(
-            "SELECT data FROM analyses WHERE session_id = ?1 AND version = ?2 AND exercise = ?3",
+            "SELECT data FROM analyses
+             WHERE session_id = ?1 AND version = ?2 AND exercise = ?3 AND attempt = 0",
             rusqlite::params![session_id, version, exercise],
             |row| row.get(0),
         )
@@ -265,30 +353,232 @@ fn load_analysis_json<T: serde::de::DeserializeOwned>(
     }
 }
 
-/// List all session dates, sorted chronologically.
-pub fn list_sessions(conn: &Connection) -> Result<Vec<String>> {
+/// Save per-attempt analyses for a session that already exists.
+pub fn save_attempts(
+    conn: &Connection,
+    id: &str,
+    version: u32,
+    attempts: &[AttemptAnalysis],
+) -> Result<()> {
+    let session_id = session_id(conn, id)?;
+
+    for a in attempts {
+        let exercise = a.result.exercise();
+        let json = a
+            .result
+            .to_json()
+            .with_context(|| format!("Failed to serialize {exercise} attempt {}", a.attempt))?;
+        upsert_analysis(conn, session_id, version, exercise, a.attempt, &json)?;
+    }
+
+    Ok(())
+}
+
+/// Load every per-attempt analysis for a session at the latest version that
+/// has any, ordered by exercise then attempt number.
+pub fn load_attempts(conn: &Connection, id: &str) -> Result<Vec<AttemptAnalysis>> {
+    let session_id = session_id(conn, id)?;
+
     let mut stmt = conn
-        .prepare("SELECT date FROM sessions ORDER BY date")
-        .context("Failed to prepare list query")?;
+        .prepare(
+            "SELECT exercise, attempt, data FROM analyses
+             WHERE session_id = ?1 AND attempt > 0 AND version = (
+                SELECT MAX(version) FROM analyses WHERE session_id = ?1 AND attempt > 0
+             )
+             ORDER BY exercise, attempt",
+        )
+        .context("Failed to prepare attempts query")?;
 
-    let dates = stmt
-        .query_map([], |row| row.get(0))
-        .context("Failed to list sessions")?
+    let rows: Vec<(String, u32, String)> = stmt
+        .query_map([session_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .context("Failed to load attempts")?
         .filter_map(|r| r.ok())
         .collect();
 
-    Ok(dates)
+    let mut attempts = Vec::new();
+    for (exercise, attempt, json) in rows {
+        let result = ExerciseResult::from_json(&exercise, &json)
+            .with_context(|| format!("Failed to parse {exercise} attempt {attempt}"))?;
+        if let Some(result) = result {
+            attempts.push(AttemptAnalysis { attempt, result });
+        }
+    }
+
+    Ok(attempts)
 }
 
-/// List all analysis versions available for a given date.
-pub fn list_versions(conn: &Connection, date: &str) -> Result<Vec<u32>> {
-    let session_id: i64 = conn
+/// Canonical take chosen per exercise for a session. Empty when none were
+/// chosen or the session doesn't exist yet.
+pub fn load_canonical_attempts(conn: &Connection, id: &str) -> Result<BTreeMap<String, u32>> {
+    let key = SessionId::parse(id)?;
+    let json: Option<String> = conn
+        .query_row(
+            "SELECT canonical_attempts FROM sessions WHERE date = ?1 AND slot = ?2",
+            rusqlite::params![key.date.to_string(), key.slot],
+            |row| row.get(0),
+        )
+        .ok()
+        .flatten();
+
+    match json {
+        Some(j) => serde_json::from_str(&j).context("Failed to parse canonical attempts"),
+        None => Ok(BTreeMap::new()),
+    }
+}
+
+/// Mark one take as canonical for an exercise.
+///
+/// The take's stored analysis becomes the session-level result and its file
+/// becomes the session's recording, so reports pick it up without a
+/// re-analysis. The choice is remembered for later re-analyses.
+pub fn set_canonical_attempt(
+    conn: &Connection,
+    id: &str,
+    exercise: &str,
+    attempt: u32,
+    path: &str,
+) -> Result<()> {
+    let path_column = match exercise {
+        "sustained" => "sustained_path",
+        "scale" => "scale_path",
+        "reading" => "reading_path",
+        other => anyhow::bail!("Exercise '{other}' has no recorded takes"),
+    };
+
+    let session_id = session_id(conn, id)?;
+
+    let (version, data): (u32, String) = conn
         .query_row(
-            "SELECT id FROM sessions WHERE date = ?1",
-            [date],
+            "SELECT version, data FROM analyses
+             WHERE session_id = ?1 AND exercise = ?2 AND attempt = ?3
+             ORDER BY version DESC LIMIT 1",
+            rusqlite::params![session_id, exercise, attempt],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .with_context(|| {
+            format!("No analysis for {exercise} take {attempt} in session {id}. Run `voicevo analyze --session {id}` first.")
+        })?;
+
+    let mut canonical = load_canonical_attempts(conn, id)?;
+    canonical.insert(exercise.to_string(), attempt);
+    let canonical_json =
+        serde_json::to_string(&canonical).context("Failed to serialize canonical attempts")?;
+
+    conn.execute(
+        &format!(
+            "UPDATE sessions SET canonical_attempts = ?1, {path_column} = ?2 WHERE id = ?3"
+        ),
+        rusqlite::params![canonical_json, path, session_id],
+    )
+    .context("Failed to update canonical attempt")?;
+
+    upsert_analysis(conn, session_id, version, exercise, 0, &data)
+}
+
+/// Forget the canonical take of an exercise, e.g. because it was discarded.
+/// The session result stays until the next analysis picks a new take.
+pub fn clear_canonical_attempt(conn: &Connection, id: &str, exercise: &str) -> Result<()> {
+    let mut canonical = load_canonical_attempts(conn, id)?;
+    if canonical.remove(exercise).is_none() {
+        return Ok(());
+    }
+    let key = SessionId::parse(id)?;
+    let canonical_json =
+        serde_json::to_string(&canonical).context("Failed to serialize canonical attempts")?;
+    conn.execute(
+        "UPDATE sessions SET canonical_attempts = ?1 WHERE date = ?2 AND slot = ?3",
+        rusqlite::params![canonical_json, key.date.to_string(), key.slot],
+    )
+    .context("Failed to update canonical attempt")?;
+    Ok(())
+}
+
+/// Remember the takes recorded as one multi-trial run (guided session
+/// best-of-N), replacing any earlier run for the same session and exercise.
+pub fn save_trial_run(conn: &Connection, id: &str, exercise: &str, attempts: &[u32]) -> Result<()> {
+    let key = SessionId::parse(id)?;
+    let json = serde_json::to_string(attempts).context("Failed to serialize trial run")?;
+    conn.execute(
+        "INSERT OR REPLACE INTO trial_runs (date, slot, exercise, attempts) VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![key.date.to_string(), key.slot, exercise, json],
+    )
+    .context("Failed to save trial run")?;
+    Ok(())
+}
+
+/// Attempt numbers of the last multi-trial run of an exercise, if any.
+pub fn load_trial_run(conn: &Connection, id: &str, exercise: &str) -> Result<Option<Vec<u32>>> {
+    let key = SessionId::parse(id)?;
+    let json: Option<String> = conn
+        .query_row(
+            "SELECT attempts FROM trial_runs WHERE date = ?1 AND slot = ?2 AND exercise = ?3",
+            rusqlite::params![key.date.to_string(), key.slot, exercise],
             |row| row.get(0),
         )
-        .with_context(|| format!("No session found for date: {date}"))?;
+        .optional()
+        .context("Failed to load trial run")?;
+
+    json.map(|j| serde_json::from_str(&j).context("Failed to parse trial run"))
+        .transpose()
+}
+
+fn session_id(conn: &Connection, id: &str) -> Result<i64> {
+    let key = SessionId::parse(id)?;
+    conn.query_row(
+        "SELECT id FROM sessions WHERE date = ?1 AND slot = ?2",
+        rusqlite::params![key.date.to_string(), key.slot],
+        |row| row.get(0),
+    )
+    .with_context(|| format!("No session found for {key}"))
+}
+
+/// List all session ids (see `SessionId`), sorted chronologically.
+pub fn list_sessions(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn
+        .prepare("SELECT date, slot FROM sessions ORDER BY date, slot")
+        .context("Failed to prepare list query")?;
+
+    let ids = stmt
+        .query_map([], |row| Ok(format_session_id(&row.get::<_, String>(0)?, row.get(1)?)))
+        .context("Failed to list sessions")?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(ids)
+}
+
+/// (date, slot, source) of every session, sorted chronologically.
+pub fn list_session_sources(conn: &Connection) -> Result<Vec<(String, u32, Option<String>)>> {
+    let mut stmt = conn
+        .prepare("SELECT date, slot, source FROM sessions ORDER BY date, slot")
+        .context("Failed to prepare list query")?;
+
+    let sessions = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .context("Failed to list sessions")?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(sessions)
+}
+
+/// Point every session recording path equal to `old` at `new`, e.g. after a
+/// WAV is compacted to FLAC. Returns the number of sessions changed.
+pub fn rename_recording(conn: &Connection, old: &str, new: &str) -> Result<usize> {
+    conn.execute(
+        "UPDATE sessions SET
+            sustained_path = CASE WHEN sustained_path = ?1 THEN ?2 ELSE sustained_path END,
+            scale_path = CASE WHEN scale_path = ?1 THEN ?2 ELSE scale_path END,
+            reading_path = CASE WHEN reading_path = ?1 THEN ?2 ELSE reading_path END
+         WHERE ?1 IN (sustained_path, scale_path, reading_path)",
+        rusqlite::params![old, new],
+    )
+    .context("Failed to update recording path")
+}
+
+/// List all analysis versions available for a session.
+pub fn list_versions(conn: &Connection, id: &str) -> Result<Vec<u32>> {
+    let session_id = session_id(conn, id)?;
 
     let mut stmt = conn
         .prepare("SELECT DISTINCT version FROM analyses WHERE session_id = ?1 ORDER BY version")
@@ -303,6 +593,237 @@ pub fn list_versions(conn: &Connection, date: &str) -> Result<Vec<u32>> {
     Ok(versions)
 }
 
+/// Store an LLM interpretation of a session.
+pub fn save_interpretation(
+    conn: &Connection,
+    date: &str,
+    provider: &str,
+    model: &str,
+    content: &str,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO interpretations (date, provider, model, content) VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![date, provider, model, content],
+    )
+    .context("Failed to save interpretation")?;
+    Ok(())
+}
+
+/// The most recently stored LLM interpretation, if any.
+pub fn load_latest_interpretation(conn: &Connection) -> Result<Option<Interpretation>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT date, provider, model, content, created_at FROM interpretations
+             ORDER BY created_at DESC, id DESC LIMIT 1",
+        )
+        .context("Failed to prepare interpretation query")?;
+
+    let mut rows = stmt
+        .query_map([], |row| {
+            Ok(Interpretation {
+                date: row.get(0)?,
+                provider: row.get(1)?,
+                model: row.get(2)?,
+                content: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })
+        .context("Failed to load interpretation")?;
+
+    rows.next().transpose().context("Failed to read interpretation")
+}
+
+/// Record a clinical event. Returns its id.
+pub fn save_event(conn: &Connection, date: &str, kind: &str, note: &str) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO events (date, kind, note) VALUES (?1, ?2, ?3)",
+        rusqlite::params![date, kind, note],
+    )
+    .context("Failed to save event")?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// All clinical events, in date order.
+pub fn load_events(conn: &Connection) -> Result<Vec<ClinicalEvent>> {
+    let mut stmt = conn
+        .prepare("SELECT id, date, kind, note FROM events ORDER BY date, id")
+        .context("Failed to prepare events query")?;
+
+    let events = stmt
+        .query_map([], |row| {
+            Ok(ClinicalEvent {
+                id: row.get(0)?,
+                date: row.get(1)?,
+                kind: row.get(2)?,
+                note: row.get(3)?,
+            })
+        })
+        .context("Failed to load events")?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .context("Failed to read events")?;
+
+    Ok(events)
+}
+
+/// Delete a clinical event. Returns whether it existed.
+pub fn delete_event(conn: &Connection, id: i64) -> Result<bool> {
+    let n = conn
+        .execute("DELETE FROM events WHERE id = ?1", [id])
+        .context("Failed to delete event")?;
+    Ok(n > 0)
+}
+
+/// Recording conditions that can be filtered on: (name, JSON path in
+/// `sessions.conditions`).
+const CONDITION_FIELDS: &[(&str, &str)] = &[
+    ("fatigue", "$.fatigue_level"),
+    ("time_of_day", "$.time_of_day"),
+    ("mucus", "$.mucus_level"),
+    ("hydration", "$.hydration"),
+    ("throat_cleared", "$.throat_cleared"),
+];
+
+/// Condition names that can be queried.
+pub fn condition_names() -> Vec<&'static str> {
+    CONDITION_FIELDS.iter().map(|&(name, _)| name).collect()
+}
+
+/// One `field op value` filter, e.g. `hnr > 12` or `time_of_day=morning`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub field: String,
+    pub op: &'static str,
+    pub value: rusqlite::types::Value,
+}
+
+impl Comparison {
+    /// Parse `field op value` with op one of `< <= > >= = !=`. Numbers and
+    /// true/false compare numerically; anything else as text.
+    pub fn parse(expr: &str) -> Result<Self> {
+        // Two-character operators first so "<=" isn't read as "<"
+        const OPS: [&str; 7] = ["<=", ">=", "!=", "==", "<", ">", "="];
+        let (at, op) = OPS
+            .iter()
+            .filter_map(|op| expr.find(op).map(|at| (at, *op)))
+            .min_by_key(|&(at, op)| (at, std::cmp::Reverse(op.len())))
+            .with_context(|| format!("No comparison in '{expr}' (expected e.g. \"hnr > 12\")"))?;
+        let field = expr[..at].trim().to_lowercase();
+        let raw = expr[at + op.len()..].trim();
+        if field.is_empty() || raw.is_empty() {
+            anyhow::bail!("Incomplete comparison '{expr}' (expected e.g. \"hnr > 12\")");
+        }
+        let value = match raw.to_lowercase().as_str() {
+            "true" => rusqlite::types::Value::Integer(1),
+            "false" => rusqlite::types::Value::Integer(0),
+            lower => match raw.parse::<f64>() {
+                Ok(n) => rusqlite::types::Value::Real(n),
+                Err(_) => rusqlite::types::Value::Text(lower.to_string()),
+            },
+        };
+        let op = if op == "==" { "=" } else { op };
+        Ok(Self { field, op, value })
+    }
+}
+
+impl std::fmt::Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use rusqlite::types::Value;
+        let value = match &self.value {
+            Value::Real(n) => n.to_string(),
+            Value::Integer(n) => n.to_string(),
+            Value::Text(t) => t.clone(),
+            _ => String::new(),
+        };
+        write!(f, "{} {} {value}", self.field, self.op)
+    }
+}
+
+/// A session matched by `query_metrics`.
+#[derive(Debug)]
+pub struct MetricMatch {
+    /// Session id, see `SessionId`.
+    pub session: String,
+    /// Value of the queried metric.
+    pub value: f64,
+    /// Whether the analysis considered the value reliable.
+    pub valid: bool,
+    pub conditions: Option<RecordingConditions>,
+}
+
+/// Sessions (latest analysis version) whose `metric` comparison holds, along
+/// with every filter in `filters` — each either another metric or a
+/// recording condition (`condition_names`). With `valid_only`, values the
+/// analysis flagged as unreliable don't match.
+pub fn query_metrics(
+    conn: &Connection,
+    metric: &Comparison,
+    filters: &[Comparison],
+    valid_only: bool,
+) -> Result<Vec<MetricMatch>> {
+    let unknown = |field: &str| {
+        anyhow::anyhow!(
+            "Unknown field '{field}'. Metrics: {}. Conditions: {}.",
+            metric_names().join(", "),
+            condition_names().join(", ")
+        )
+    };
+    if !metric_names().contains(&metric.field.as_str()) {
+        return Err(unknown(&metric.field));
+    }
+
+    let valid = if valid_only { " AND m.valid = 1" } else { "" };
+    let mut sql = format!(
+        "SELECT s.date, s.slot, m.value, m.valid, s.conditions FROM metrics m
+         JOIN sessions s ON s.id = m.session_id
+         WHERE m.version = (SELECT MAX(version) FROM analyses a WHERE a.session_id = m.session_id AND a.attempt = 0)
+           AND m.name = ?1 AND m.value {} ?2{valid}",
+        metric.op
+    );
+    let mut params = vec![rusqlite::types::Value::Text(metric.field.clone()), metric.value.clone()];
+
+    for f in filters {
+        let n = params.len();
+        if metric_names().contains(&f.field.as_str()) {
+            let valid = if valid_only { " AND f.valid = 1" } else { "" };
+            sql.push_str(&format!(
+                " AND EXISTS (SELECT 1 FROM metrics f WHERE f.session_id = m.session_id
+                   AND f.version = m.version AND f.name = ?{} AND f.value {} ?{}{valid})",
+                n + 1,
+                f.op,
+                n + 2
+            ));
+            params.push(rusqlite::types::Value::Text(f.field.clone()));
+        } else {
+            let (_, path) = CONDITION_FIELDS
+                .iter()
+                .find(|(name, _)| *name == f.field)
+                .ok_or_else(|| unknown(&f.field))?;
+            sql.push_str(&format!(" AND json_extract(s.conditions, '{path}') {} ?{}", f.op, n + 1));
+        }
+        params.push(f.value.clone());
+    }
+    sql.push_str(" ORDER BY s.date, s.slot");
+
+    let mut stmt = conn.prepare(&sql).context("Failed to prepare metrics query")?;
+    let rows: Vec<(String, f64, bool, Option<String>)> = stmt
+        .query_map(rusqlite::params_from_iter(params), |row| {
+            Ok((format_session_id(&row.get::<_, String>(0)?, row.get(1)?), row.get(2)?, row.get(3)?, row.get(4)?))
+        })
+        .context("Failed to query metrics")?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .context("Failed to read metrics")?;
+
+    rows.into_iter()
+        .map(|(session, value, valid, conditions)| {
+            let conditions = conditions
+                .map(|j| serde_json::from_str(&j))
+                .transpose()
+                .context("Failed to parse conditions")?;
+            Ok(MetricMatch { session, value, valid, conditions })
+        })
+        .collect()
+}
+
 /// Returns the current analysis pipeline version.
 pub fn current_analysis_version() -> u32 {
     ANALYSIS_VERSION
@@ -342,8 +863,8 @@ pub fn migrate_json_sessions(conn: &Connection) -> Result<usize> {
         // Check if already migrated
         let exists: bool = conn
             .query_row(
-                "SELECT EXISTS(SELECT 1 FROM sessions WHERE date = ?1)",
-                [&session.date],
+                "SELECT EXISTS(SELECT 1 FROM sessions WHERE date = ?1 AND slot = ?2)",
+                rusqlite::params![session.date, session.slot],
                 |row| row.get(0),
             )
             .unwrap_or(false);
@@ -373,6 +894,7 @@ mod tests {
     fn sample_session() -> SessionData {
         SessionData {
             date: "2026-01-15".into(),
+            slot: 1,
             recordings: SessionRecordings {
                 sustained: Some("/data/sustained.wav".into()),
                 scale: None,
@@ -390,6 +912,9 @@ mod tests {
                     periodicity_mean: None,
                     detection_quality: Some("relaxed_pitch".into()),
                     reliability: None,
+                    trials: None,
+                    recording_quality: None,
+                    segment: None,
                 }),
                 scale: None,
                 reading: Some(ReadingAnalysis {
@@ -401,11 +926,14 @@ mod tests {
                     cpps_db: None,
                     detection_quality: None,
                     reliability: None,
+                    recording_quality: None,
+                    segment: None,
                 }),
                 sz: None,
                 fatigue: None,
             },
             conditions: None,
+            source: None,
         }
     }
 
@@ -503,6 +1031,27 @@ mod tests {
         assert_eq!(listed, vec!["2026-01-15", "2026-02-20", "2026-03-01"]);
     }
 
+    #[test]
+    fn several_sessions_per_day() {
+        let conn = test_db();
+        let first = sample_session();
+        let mut second = sample_session();
+        second.slot = 2;
+        second.analysis.sustained.as_mut().unwrap().mpt_seconds = 9.0;
+        save_session(&conn, &second).unwrap();
+        save_session(&conn, &first).unwrap();
+
+        let listed = list_sessions(&conn).unwrap();
+        assert_eq!(listed, vec!["2026-01-15", "2026-01-15.2"]);
+
+        let loaded = load_session(&conn, "2026-01-15.2").unwrap();
+        assert_eq!(loaded.slot, 2);
+        assert_eq!(loaded.analysis.sustained.unwrap().mpt_seconds, 9.0);
+        let loaded = load_session(&conn, "2026-01-15").unwrap();
+        assert_eq!(loaded.slot, 1);
+        assert_eq!(loaded.analysis.sustained.unwrap().mpt_seconds, 6.5);
+    }
+
     #[test]
     fn list_versions_for_date() {
         let conn = test_db();
@@ -600,4 +1149,242 @@ mod tests {
         let loaded = load_session(&conn, "2026-01-15").unwrap();
         assert!(loaded.conditions.is_none());
     }
+
+    fn sustained_take(mpt: f32) -> SustainedAnalysis {
+        let mut s = sample_session().analysis.sustained.unwrap();
+        s.mpt_seconds = mpt;
+        s
+    }
+
+    #[test]
+    fn migrate_legacy_analyses_table() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE sessions (
+                id INTEGER PRIMARY KEY,
+                date TEXT NOT NULL UNIQUE,
+                sustained_path TEXT,
+                scale_path TEXT,
+                reading_path TEXT
+            );
+            CREATE TABLE analyses (
+                id INTEGER PRIMARY KEY,
+                session_id INTEGER NOT NULL REFERENCES sessions(id),
+                version INTEGER NOT NULL DEFAULT 1,
+                exercise TEXT NOT NULL,
+                data TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                UNIQUE(session_id, version, exercise)
+            );
+            INSERT INTO sessions (id, date) VALUES (1, '2026-01-15');",
+        )
+        .unwrap();
+        let json = serde_json::to_string(&sustained_take(6.5)).unwrap();
+        conn.execute(
+            "INSERT INTO analyses (session_id, version, exercise, data) VALUES (1, 2, 'sustained', ?1)",
+            [&json],
+        )
+        .unwrap();
+
+        init_schema(&conn).unwrap();
+
+        let loaded = load_session(&conn, "2026-01-15").unwrap();
+        assert!((loaded.analysis.sustained.unwrap().mpt_seconds - 6.5).abs() < 0.01);
+        assert!(load_attempts(&conn, "2026-01-15").unwrap().is_empty());
+    }
+
+    #[test]
+    fn attempts_roundtrip_separate_from_session() {
+        let conn = test_db();
+        save_session(&conn, &sample_session()).unwrap();
+
+        let attempts = vec![
+            AttemptAnalysis { attempt: 1, result: ExerciseResult::Sustained(sustained_take(4.0)) },
+            AttemptAnalysis { attempt: 2, result: ExerciseResult::Sustained(sustained_take(7.0)) },
+        ];
+        save_attempts(&conn, "2026-01-15", ANALYSIS_VERSION, &attempts).unwrap();
+
+        let loaded = load_attempts(&conn, "2026-01-15").unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].attempt, 1);
+        assert_eq!(loaded[1].result.exercise(), "sustained");
+
+        // Session-level result is untouched by per-attempt rows
+        let session = load_session(&conn, "2026-01-15").unwrap();
+        assert!((session.analysis.sustained.unwrap().mpt_seconds - 6.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn canonical_attempt_replaces_session_result() {
+        let conn = test_db();
+        save_session(&conn, &sample_session()).unwrap();
+        assert!(load_canonical_attempts(&conn, "2026-01-15").unwrap().is_empty());
+
+        let attempts = vec![
+            AttemptAnalysis { attempt: 1, result: ExerciseResult::Sustained(sustained_take(4.0)) },
+            AttemptAnalysis { attempt: 2, result: ExerciseResult::Sustained(sustained_take(7.0)) },
+        ];
+        save_attempts(&conn, "2026-01-15", ANALYSIS_VERSION, &attempts).unwrap();
+
+        set_canonical_attempt(&conn, "2026-01-15", "sustained", 1, "/data/sustained_001.wav").unwrap();
+
+        let session = load_session(&conn, "2026-01-15").unwrap();
+        assert!((session.analysis.sustained.unwrap().mpt_seconds - 4.0).abs() < 0.01);
+        assert_eq!(
+            session.recordings.sustained.as_deref(),
+            Some("/data/sustained_001.wav")
+        );
+        // Other exercises keep their session-level result
+        assert!(session.analysis.reading.is_some());
+
+        let canonical = load_canonical_attempts(&conn, "2026-01-15").unwrap();
+        assert_eq!(canonical.get("sustained"), Some(&1));
+
+        clear_canonical_attempt(&conn, "2026-01-15", "sustained").unwrap();
+        assert!(load_canonical_attempts(&conn, "2026-01-15").unwrap().is_empty());
+    }
+
+    #[test]
+    fn trial_run_roundtrip_before_session_exists() {
+        let conn = test_db();
+        assert!(load_trial_run(&conn, "2026-01-15", "sustained").unwrap().is_none());
+
+        save_trial_run(&conn, "2026-01-15", "sustained", &[1, 2, 4]).unwrap();
+        save_trial_run(&conn, "2026-01-15.2", "sustained", &[1]).unwrap();
+        assert_eq!(load_trial_run(&conn, "2026-01-15", "sustained").unwrap(), Some(vec![1, 2, 4]));
+
+        // A later run replaces the earlier one
+        save_trial_run(&conn, "2026-01-15", "sustained", &[5, 6, 7]).unwrap();
+        assert_eq!(load_trial_run(&conn, "2026-01-15", "sustained").unwrap(), Some(vec![5, 6, 7]));
+        assert_eq!(load_trial_run(&conn, "2026-01-15.2", "sustained").unwrap(), Some(vec![1]));
+    }
+
+    #[test]
+    fn canonical_attempt_requires_analysis() {
+        let conn = test_db();
+        save_session(&conn, &sample_session()).unwrap();
+        assert!(set_canonical_attempt(&conn, "2026-01-15", "scale", 3, "x.wav").is_err());
+        assert!(set_canonical_attempt(&conn, "2026-01-15", "sz", 1, "x.wav").is_err());
+    }
+
+    #[test]
+    fn latest_interpretation_wins() {
+        let conn = test_db();
+        assert!(load_latest_interpretation(&conn).unwrap().is_none());
+
+        save_interpretation(&conn, "2026-01-14", "claude", "model-a", "first").unwrap();
+        save_interpretation(&conn, "2026-01-15", "gpt", "model-b", "second").unwrap();
+
+        let latest = load_latest_interpretation(&conn).unwrap().unwrap();
+        assert_eq!(latest.date, "2026-01-15");
+        assert_eq!(latest.provider, "gpt");
+        assert_eq!(latest.content, "second");
+    }
+
+    #[test]
+    fn events_in_date_order() {
+        let conn = test_db();
+        let later = save_event(&conn, "2026-03-01", "therapy", "Voice therapy starts").unwrap();
+        save_event(&conn, "2026-02-10", "injection", "").unwrap();
+
+        let events = load_events(&conn).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind, "injection");
+        assert_eq!(events[1].note, "Voice therapy starts");
+
+        assert!(delete_event(&conn, later).unwrap());
+        assert!(!delete_event(&conn, later).unwrap());
+        assert_eq!(load_events(&conn).unwrap().len(), 1);
+    }
+
+    fn metric_count(conn: &Connection) -> i64 {
+        conn.query_row("SELECT COUNT(*) FROM metrics", [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn metrics_saved_with_session() {
+        let conn = test_db();
+        save_session(&conn, &sample_session()).unwrap();
+        // 7 sustained fields (cpps present) + 4 reading fields (no cpps)
+        assert_eq!(metric_count(&conn), 11);
+
+        let hnr: f64 = conn
+            .query_row("SELECT value FROM metrics WHERE name = 'hnr'", [], |row| row.get(0))
+            .unwrap();
+        assert!((hnr - 9.0).abs() < 1e-6);
+
+        // Re-saving replaces rather than duplicates
+        save_session(&conn, &sample_session()).unwrap();
+        assert_eq!(metric_count(&conn), 11);
+    }
+
+    #[test]
+    fn metric_validity_from_reliability() {
+        let conn = test_db();
+        let mut session = sample_session();
+        let mut reliability = ReliabilityInfo::compute([0, 10, 0], 0.9, 0.6, true);
+        reliability.apply_snr(15.0);
+        session.analysis.sustained.as_mut().unwrap().reliability = Some(reliability);
+        save_session(&conn, &session).unwrap();
+
+        let valid = |name: &str| -> bool {
+            conn.query_row("SELECT valid FROM metrics WHERE name = ?1", [name], |row| row.get(0))
+                .unwrap()
+        };
+        assert!(!valid("hnr"));
+        assert!(valid("cpps"));
+        assert!(valid("mpt"));
+    }
+
+    #[test]
+    fn query_by_metric_and_conditions() {
+        let conn = test_db();
+        for (date, hnr, fatigue) in [("2026-01-10", 13.0, 2), ("2026-01-11", 14.0, 6), ("2026-01-12", 10.0, 1)] {
+            let mut session = sample_session();
+            session.date = date.into();
+            let sustained = session.analysis.sustained.as_mut().unwrap();
+            sustained.hnr_db = hnr;
+            sustained.mpt_seconds = hnr / 2.0;
+            session.conditions = Some(RecordingConditions {
+                time_of_day: "morning".into(),
+                fatigue_level: fatigue,
+                throat_cleared: false,
+                mucus_level: "low".into(),
+                hydration: "normal".into(),
+                notes: None,
+            });
+            save_session(&conn, &session).unwrap();
+        }
+        // A newer analysis version supersedes the old value
+        let mut reanalyzed = sample_session();
+        reanalyzed.date = "2026-01-12".into();
+        reanalyzed.analysis.sustained.as_mut().unwrap().hnr_db = 12.5;
+        save_session_version(&conn, &reanalyzed, ANALYSIS_VERSION + 1).unwrap();
+
+        let hnr = Comparison::parse("hnr > 12").unwrap();
+        let dates = |filters: &[&str]| -> Vec<String> {
+            let filters: Vec<Comparison> = filters.iter().map(|f| Comparison::parse(f).unwrap()).collect();
+            query_metrics(&conn, &hnr, &filters, false).unwrap().into_iter().map(|m| m.session).collect()
+        };
+        assert_eq!(dates(&[]), ["2026-01-10", "2026-01-11", "2026-01-12"]);
+        // Conditions are kept from the earlier save
+        assert_eq!(dates(&["fatigue<3"]), ["2026-01-10", "2026-01-12"]);
+        assert_eq!(dates(&["time_of_day = Morning", "mpt > 6.6"]), ["2026-01-11"]);
+        assert!(dates(&["throat_cleared=true"]).is_empty());
+
+        let unknown = Comparison::parse("colour = blue").unwrap();
+        assert!(query_metrics(&conn, &hnr, &[unknown], false).is_err());
+    }
+
+    #[test]
+    fn parse_comparisons() {
+        let c = Comparison::parse("HNR>=12.5").unwrap();
+        assert_eq!((c.field.as_str(), c.op), ("hnr", ">="));
+        assert_eq!(c.value, rusqlite::types::Value::Real(12.5));
+        assert_eq!(c.to_string(), "hnr >= 12.5");
+        assert_eq!(Comparison::parse("mucus != high").unwrap().op, "!=");
+        assert_eq!(Comparison::parse("fatigue == 3").unwrap().op, "=");
+        assert!(Comparison::parse("hnr 12").is_err());
+        assert!(Comparison::parse("hnr >").is_err());
+    }
 }
@@ -0,0 +1,587 @@
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Context, Result};
+use chrono::{NaiveDate, NaiveDateTime};
+use rusqlite::Connection;
+use serde_json::{Map, Value};
+
+use super::db;
+use super::session_data::*;
+
+/// Exercises whose analyses can be imported; sz and fatigue need per-trial
+/// data no other tool reports.
+const IMPORTABLE_EXERCISES: [&str; 3] = ["sustained", "scale", "reading"];
+
+/// Column spellings for metrics that other tools commonly use, after
+/// `normalize_column`: (alias, metric name).
+const ALIASES: &[(&str, &str)] = &[
+    ("f0", "mean_f0"),
+    ("fo", "mean_f0"),
+    ("mean_pitch", "mean_f0"),
+    ("std", "f0_std"),
+    ("f0_sd", "f0_std"),
+    ("jitt", "jitter"),
+    ("jitter_local", "jitter"),
+    ("shim", "shimmer"),
+    ("shimmer_local", "shimmer"),
+    ("range_semitones", "range"),
+];
+
+/// Unit suffixes dropped from a column name when it doesn't match as is,
+/// so "mpt_s" and "hnr_db" find "mpt" and "hnr".
+const UNIT_SUFFIXES: &[&str] = &["_seconds", "_percent", "_pct", "_hz", "_db", "_s"];
+
+/// Cell values that mean "not measured".
+const MISSING: &[&str] = &["", "na", "n/a", "nan", "--undefined--"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    PraatVoiceReport,
+}
+
+impl Format {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "csv" => Ok(Format::Csv),
+            "praat-voice-report" | "praat" => Ok(Format::PraatVoiceReport),
+            other => bail!("Unknown import format '{other}'. Use csv or praat-voice-report"),
+        }
+    }
+
+    /// Prefix of the `source` recorded on imported sessions.
+    fn source_prefix(&self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::PraatVoiceReport => "praat",
+        }
+    }
+}
+
+/// Sessions read from an import file, not yet saved. Slots are assigned by
+/// `save`.
+#[derive(Debug, Default)]
+pub struct Imported {
+    pub sessions: Vec<SessionData>,
+    /// Rows, reports or exercises that were skipped, and why.
+    pub warnings: Vec<String>,
+}
+
+/// Parse an import file. `exercise` and `date` only apply to Praat voice
+/// reports, which don't say what was recorded and may lack a date.
+pub fn parse(format: Format, text: &str, file_name: &str, exercise: &str, date: Option<NaiveDate>) -> Result<Imported> {
+    let source = format!("{}:{file_name}", format.source_prefix());
+    match format {
+        Format::Csv => parse_csv(text, &source),
+        Format::PraatVoiceReport => parse_praat_voice_report(text, &source, exercise, date),
+    }
+}
+
+/// Save imported sessions, assigning each a slot on its date.
+///
+/// Re-importing the same file updates the sessions it created before: the
+/// n-th session of a date reuses the n-th existing slot with the same
+/// source, and only takes a new slot when there is none.
+pub fn save(conn: &Connection, sessions: &mut [SessionData]) -> Result<()> {
+    let existing = db::list_session_sources(conn)?;
+    let mut taken: Vec<(String, u32)> = existing.iter().map(|(date, slot, _)| (date.clone(), *slot)).collect();
+    let mut imported_per_date: BTreeMap<String, usize> = BTreeMap::new();
+
+    let tx = conn.unchecked_transaction().context("Failed to start import")?;
+    for session in sessions.iter_mut() {
+        let nth = imported_per_date.entry(session.date.clone()).or_default();
+        let reusable = existing
+            .iter()
+            .filter(|(date, _, source)| *date == session.date && *source == session.source)
+            .map(|(_, slot, _)| *slot)
+            .nth(*nth);
+        *nth += 1;
+
+        session.slot = match reusable {
+            Some(slot) => slot,
+            None => {
+                let last = taken.iter().filter(|(date, _)| *date == session.date).map(|(_, slot)| *slot).max();
+                let slot = last.unwrap_or(0) + 1;
+                taken.push((session.date.clone(), slot));
+                slot
+            }
+        };
+        db::save_session(&tx, session).with_context(|| format!("Failed to save imported session {}", session.id()))?;
+    }
+    tx.commit().context("Failed to commit import")
+}
+
+/// Parse a CSV with a header row and one session per row.
+///
+/// Needs a `date` column (YYYY-MM-DD). Metric columns are named as in
+/// `voicevo query` (mpt, jitter, speaking_f0, range, ...), optionally with a
+/// unit ("MPT (s)", "hnr_db"); f0_p5 and f0_p95 give the reading F0 range.
+/// Exercises missing a required field are skipped with a warning, optional
+/// fields (CPPS, reliability, ...) are left empty. Quoted fields may not
+/// contain line breaks.
+fn parse_csv(text: &str, source: &str) -> Result<Imported> {
+    let mut lines = text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty());
+    let (_, header) = lines.next().context("CSV file is empty")?;
+    let columns: Vec<String> = split_csv_line(header).iter().map(|c| normalize_column(c)).collect();
+
+    let date_column = columns
+        .iter()
+        .position(|c| c == "date")
+        .context("CSV has no date column")?;
+
+    let mut imported = Imported::default();
+    let mut fields: Vec<Option<(&'static str, &'static str)>> = Vec::new();
+    let mut ignored = Vec::new();
+    for (i, column) in columns.iter().enumerate() {
+        let field = import_field(column);
+        if field.is_none() && i != date_column && !matches!(column.as_str(), "session" | "slot" | "day") {
+            ignored.push(column.clone());
+        }
+        fields.push(field);
+    }
+    if fields.iter().all(|f| f.is_none()) {
+        bail!("CSV has no metric columns (expected names like mpt, jitter, hnr or speaking_f0)");
+    }
+    if !ignored.is_empty() {
+        imported.warnings.push(format!("Ignored columns: {}", ignored.join(", ")));
+    }
+
+    for (index, line) in lines {
+        let row = index + 1;
+        let cells = split_csv_line(line);
+        let date_cell = cells.get(date_column).map(|c| c.trim()).unwrap_or_default();
+        let Ok(date) = NaiveDate::parse_from_str(date_cell, "%Y-%m-%d") else {
+            imported.warnings.push(format!("Line {row}: skipped, invalid date '{date_cell}'"));
+            continue;
+        };
+
+        let mut values: BTreeMap<&str, Map<String, Value>> = BTreeMap::new();
+        for (cell, field) in cells.iter().zip(&fields) {
+            let Some((exercise, field)) = *field else { continue };
+            let cell = cell.trim();
+            if MISSING.contains(&cell.to_lowercase().as_str()) {
+                continue;
+            }
+            match cell.parse::<f64>() {
+                Ok(v) => {
+                    values.entry(exercise).or_default().insert(field.to_string(), number(v));
+                }
+                Err(_) => imported.warnings.push(format!("Line {row}: ignored non-numeric {field} '{cell}'")),
+            }
+        }
+
+        let label = format!("Line {row}");
+        if let Some(session) = build_session(date, values, source, &label, &mut imported.warnings) {
+            imported.sessions.push(session);
+        }
+    }
+    Ok(imported)
+}
+
+/// Exercise and JSON field a CSV column imports into.
+fn import_field(column: &str) -> Option<(&'static str, &'static str)> {
+    let lookup = |name: &str| -> Option<(&'static str, &'static str)> {
+        match name {
+            "f0_p5" => Some(("reading", "f0_p5")),
+            "f0_p95" => Some(("reading", "f0_p95")),
+            _ => {
+                let metric = ALIASES.iter().find(|(alias, _)| *alias == name).map_or(name, |&(_, m)| m);
+                db::metric_field(metric).filter(|(exercise, _)| IMPORTABLE_EXERCISES.contains(exercise))
+            }
+        }
+    };
+    lookup(column).or_else(|| {
+        UNIT_SUFFIXES
+            .iter()
+            .find_map(|suffix| column.strip_suffix(suffix))
+            .and_then(lookup)
+    })
+}
+
+/// Lowercase a header, drop units in parentheses or brackets and turn
+/// everything else that isn't alphanumeric into single underscores:
+/// "Jitter (%)" -> "jitter", "Mean F0 [Hz]" -> "mean_f0".
+fn normalize_column(header: &str) -> String {
+    let mut out = String::new();
+    let mut depth = 0;
+    for c in header.trim().trim_start_matches('\u{feff}').chars() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            _ if depth > 0 => {}
+            c if c.is_ascii_alphanumeric() => out.push(c.to_ascii_lowercase()),
+            _ => {
+                if !out.is_empty() && !out.ends_with('_') {
+                    out.push('_');
+                }
+            }
+        }
+    }
+    out.trim_end_matches('_').to_string()
+}
+
+/// Split one CSV line on commas, honouring double-quoted fields.
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => cells.push(std::mem::take(&mut cell)),
+            c => cell.push(c),
+        }
+    }
+    cells.push(cell);
+    cells
+}
+
+/// Parse the output of Praat's "Voice report" (Sound + Pitch + PointProcess
+/// selected, Voice report...), pasted or saved from the Info window. Several
+/// reports in one file become several sessions.
+///
+/// Praat doesn't know which exercise was recorded, so `exercise` says how
+/// to read the numbers:
+/// - sustained: MPT is the duration of the selected time range, so select
+///   just the vowel; F0, jitter (local), shimmer (local) and HNR as reported.
+/// - scale: floor and ceiling are Praat's minimum and maximum pitch, not the
+///   5th/95th percentiles voicevo uses, so ranges come out a little wider.
+/// - reading: mean and SD of pitch, min..max as the F0 range, voice breaks,
+///   and the voiced fraction from the fraction of unvoiced frames.
+///
+/// `date` overrides the report's Date line, and is required if it has none.
+fn parse_praat_voice_report(text: &str, source: &str, exercise: &str, date: Option<NaiveDate>) -> Result<Imported> {
+    if !IMPORTABLE_EXERCISES.contains(&exercise) {
+        bail!("Cannot import {exercise} from a voice report. Use sustained, scale or reading");
+    }
+
+    let mut reports: Vec<Vec<&str>> = Vec::new();
+    for line in text.lines() {
+        if line.trim_start().starts_with("-- Voice report for") || reports.is_empty() {
+            reports.push(Vec::new());
+        }
+        reports.last_mut().unwrap().push(line);
+    }
+    reports.retain(|r| r.iter().any(|l| l.contains("Jitter (local):") || l.contains("Mean pitch:")));
+    if reports.is_empty() {
+        bail!("No Praat voice report found");
+    }
+
+    let mut imported = Imported::default();
+    for (i, lines) in reports.iter().enumerate() {
+        let label = format!("Report {}", i + 1);
+        let report = PraatReport::parse(lines);
+        let Some(date) = date.or(report.date) else {
+            imported.warnings.push(format!("{label}: skipped, no Date line (pass --date)"));
+            continue;
+        };
+
+        let get = |key: &str| report.values.get(key).copied();
+        let mut fields = Map::new();
+        let mut put = |field: &str, value: Option<f64>| {
+            if let Some(v) = value {
+                fields.insert(field.to_string(), number(v));
+            }
+        };
+        match exercise {
+            "sustained" => {
+                put("mpt_seconds", report.duration);
+                put("mean_f0_hz", get("mean pitch"));
+                put("f0_std_hz", get("standard deviation"));
+                put("jitter_local_percent", get("jitter (local)"));
+                put("shimmer_local_percent", get("shimmer (local)"));
+                put("hnr_db", get("mean harmonics-to-noise ratio"));
+            }
+            "scale" => {
+                put("pitch_floor_hz", get("minimum pitch"));
+                put("pitch_ceiling_hz", get("maximum pitch"));
+            }
+            _ => {
+                put("mean_f0_hz", get("mean pitch"));
+                put("f0_std_hz", get("standard deviation"));
+                put("f0_p5", get("minimum pitch"));
+                put("f0_p95", get("maximum pitch"));
+                put("voice_breaks", get("number of voice breaks"));
+                put("voiced_fraction", get("fraction of locally unvoiced frames").map(|pct| 1.0 - pct / 100.0));
+            }
+        }
+
+        let values = BTreeMap::from([(exercise, fields)]);
+        if let Some(session) = build_session(date, values, source, &label, &mut imported.warnings) {
+            imported.sessions.push(session);
+        }
+    }
+    Ok(imported)
+}
+
+/// The numbers of one Praat voice report, keyed by lowercased label.
+struct PraatReport {
+    date: Option<NaiveDate>,
+    /// Duration of the analysed time range in seconds.
+    duration: Option<f64>,
+    values: BTreeMap<String, f64>,
+}
+
+impl PraatReport {
+    fn parse(lines: &[&str]) -> Self {
+        let mut report = PraatReport { date: None, duration: None, values: BTreeMap::new() };
+        for line in lines.iter().map(|l| l.trim()) {
+            if let Some((_, rest)) = line.split_once("(duration:") {
+                report.duration = leading_number(rest);
+            } else if let Some(date) = line.strip_prefix("Date:") {
+                // e.g. "Mon Jan 12 10:30:00 2026", day padded with a space
+                let date = date.split_whitespace().collect::<Vec<_>>().join(" ");
+                report.date = NaiveDateTime::parse_from_str(&date, "%a %b %d %H:%M:%S %Y")
+                    .ok()
+                    .map(|dt| dt.date());
+            } else if let Some((key, value)) = line.split_once(": ") {
+                if let Some(v) = leading_number(value) {
+                    report.values.insert(key.to_lowercase(), v);
+                }
+            }
+        }
+        report
+    }
+}
+
+/// The number a Praat value starts with: "0.452%" -> 0.452,
+/// "38.1E-6 seconds" -> 3.81e-5, "--undefined--" -> None.
+fn leading_number(value: &str) -> Option<f64> {
+    value.split_whitespace().next()?.trim_end_matches('%').parse().ok()
+}
+
+/// Turn per-exercise JSON fields into a session, relying on the analysis
+/// structs' `Option` and `#[serde(default)]` fields for what wasn't given.
+/// Exercises missing a required field are dropped with a warning; None if
+/// nothing is left.
+fn build_session(
+    date: NaiveDate,
+    values: BTreeMap<&str, Map<String, Value>>,
+    source: &str,
+    label: &str,
+    warnings: &mut Vec<String>,
+) -> Option<SessionData> {
+    let mut analysis = SessionAnalysis { sustained: None, scale: None, reading: None, sz: None, fatigue: None };
+    for (exercise, mut fields) in values {
+        fill_derived(exercise, &mut fields);
+        let value = Value::Object(fields);
+        let result = match exercise {
+            "sustained" => serde_json::from_value(value).map(|a| analysis.sustained = Some(a)),
+            "scale" => serde_json::from_value(value).map(|a| analysis.scale = Some(a)),
+            _ => serde_json::from_value(value).map(|a| analysis.reading = Some(a)),
+        };
+        if let Err(e) = result {
+            warnings.push(format!("{label}: {exercise} skipped, {e}"));
+        }
+    }
+
+    if analysis.sustained.is_none() && analysis.scale.is_none() && analysis.reading.is_none() {
+        warnings.push(format!("{label}: skipped, no complete exercise"));
+        return None;
+    }
+    Some(SessionData {
+        date: date.to_string(),
+        slot: 1,
+        recordings: SessionRecordings { sustained: None, scale: None, reading: None },
+        analysis,
+        conditions: None,
+        source: Some(source.to_string()),
+    })
+}
+
+/// Fill fields that follow from others: the scale range from floor and
+/// ceiling, and the reading F0 range from its two percentiles.
+fn fill_derived(exercise: &str, fields: &mut Map<String, Value>) {
+    let get = |fields: &Map<String, Value>, key: &str| fields.get(key).and_then(Value::as_f64);
+    match exercise {
+        "scale" => {
+            if let (Some(floor), Some(ceiling)) = (get(fields, "pitch_floor_hz"), get(fields, "pitch_ceiling_hz")) {
+                if !fields.contains_key("range_hz") {
+                    fields.insert("range_hz".into(), number(ceiling - floor));
+                }
+                if !fields.contains_key("range_semitones") && floor > 0.0 {
+                    fields.insert("range_semitones".into(), number(12.0 * (ceiling / floor).log2()));
+                }
+            }
+        }
+        "reading" => {
+            let low = fields.remove("f0_p5").and_then(|v| v.as_f64());
+            let high = fields.remove("f0_p95").and_then(|v| v.as_f64());
+            if let (Some(low), Some(high)) = (low, high) {
+                fields.insert("f0_range_hz".into(), Value::from(vec![low, high]));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// A JSON number that also deserializes into integer fields when whole
+/// (voice breaks).
+fn number(v: f64) -> Value {
+    if v.fract() == 0.0 && v.abs() < 1e15 {
+        Value::from(v as i64)
+    } else {
+        Value::from(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VOICE_REPORT: &str = "\
+-- Voice report for 1. Sound aah --
+Date: Mon Jan  5 10:30:00 2026
+
+Time range of SELECTION
+   From 0.523 to 9.023 seconds (duration: 8.5 seconds)
+Pitch:
+   Median pitch: 118.42 Hz
+   Mean pitch: 119.034 Hz
+   Standard deviation: 2.84 Hz
+   Minimum pitch: 110.2 Hz
+   Maximum pitch: 127.9 Hz
+Pulses:
+   Standard deviation of period: 0.2E-3 seconds
+Voicing:
+   Fraction of locally unvoiced frames: 1.2%   (4 / 340)
+   Number of voice breaks: 2
+Jitter:
+   Jitter (local): 0.452%
+   Jitter (local, absolute): 38.1E-6 seconds
+Shimmer:
+   Shimmer (local): 3.120%
+   Shimmer (local, dB): 0.28 dB
+Harmonicity of the voiced parts only:
+   Mean harmonics-to-noise ratio: 20.1 dB
+-- Voice report for 2. Sound aah --
+Date: Tue Jan 13 09:00:00 2026
+   From 0 to 6 seconds (duration: 6 seconds)
+   Mean pitch: 121 Hz
+   Standard deviation: 3 Hz
+   Jitter (local): --undefined--
+   Shimmer (local): 4.2%
+   Mean harmonics-to-noise ratio: 18 dB
+";
+
+    fn test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn csv_maps_columns_and_skips_incomplete_exercises() {
+        let csv = "\
+Date,MPT (s),mean_f0_hz,F0 SD (Hz),Jitt,shimmer_pct,HNR (dB),CPPS,speaking_f0,Notes
+2026-01-05,8.5,118,2.8,0.45,3.1,20.1,,121,\"after lunch, tired\"
+2026-01-12,9.1,117,2.5,0.40,2.9,21.0,6.2,,
+bad-date,1,2,3,4,5,6,7,8,
+";
+        let imported = parse(Format::Csv, csv, "clinic.csv", "sustained", None).unwrap();
+        assert_eq!(imported.sessions.len(), 2);
+
+        let first = &imported.sessions[0];
+        assert_eq!(first.date, "2026-01-05");
+        assert_eq!(first.source.as_deref(), Some("csv:clinic.csv"));
+        let s = first.analysis.sustained.as_ref().unwrap();
+        assert_eq!(s.mpt_seconds, 8.5);
+        assert_eq!(s.f0_std_hz, 2.8);
+        assert_eq!(s.jitter_local_percent, 0.45);
+        assert!(s.cpps_db.is_none());
+        assert!(s.reliability.is_none());
+        assert_eq!(imported.sessions[1].analysis.sustained.as_ref().unwrap().cpps_db, Some(6.2));
+
+        // speaking_f0 alone can't make a reading analysis
+        assert!(first.analysis.reading.is_none());
+        let warnings = imported.warnings.join("\n");
+        assert!(warnings.contains("Ignored columns: notes"), "{warnings}");
+        assert!(warnings.contains("Line 2: reading skipped"), "{warnings}");
+        assert!(warnings.contains("Line 4: skipped, invalid date"), "{warnings}");
+    }
+
+    #[test]
+    fn csv_scale_and_reading_fill_derived_fields() {
+        let csv = "date,pitch_floor,pitch_ceiling,speaking_f0,speaking_f0_std,f0_p5,f0_p95,voice_breaks,voiced_fraction\n\
+                   2026-02-01,100,200,120,15,95,160,3,0.8\n";
+        let imported = parse(Format::Csv, csv, "x.csv", "sustained", None).unwrap();
+        let analysis = &imported.sessions[0].analysis;
+        let scale = analysis.scale.as_ref().unwrap();
+        assert_eq!(scale.range_hz, 100.0);
+        assert!((scale.range_semitones - 12.0).abs() < 1e-4);
+        let reading = analysis.reading.as_ref().unwrap();
+        assert_eq!(reading.f0_range_hz, (95.0, 160.0));
+        assert_eq!(reading.voice_breaks, 3);
+        assert!(analysis.sustained.is_none());
+    }
+
+    #[test]
+    fn praat_voice_reports() {
+        let imported = parse(Format::PraatVoiceReport, VOICE_REPORT, "praat.txt", "sustained", None).unwrap();
+        // the second report has an undefined jitter, so it has no sustained analysis
+        assert_eq!(imported.sessions.len(), 1);
+        assert!(imported.warnings[0].starts_with("Report 2: sustained skipped"), "{:?}", imported.warnings);
+
+        let session = &imported.sessions[0];
+        assert_eq!(session.date, "2026-01-05");
+        assert_eq!(session.source.as_deref(), Some("praat:praat.txt"));
+        let s = session.analysis.sustained.as_ref().unwrap();
+        assert_eq!(s.mpt_seconds, 8.5);
+        assert_eq!(s.mean_f0_hz, 119.034);
+        assert_eq!(s.f0_std_hz, 2.84);
+        assert_eq!(s.jitter_local_percent, 0.452);
+        assert_eq!(s.shimmer_local_percent, 3.12);
+        assert_eq!(s.hnr_db, 20.1);
+
+        let reading = parse(Format::PraatVoiceReport, VOICE_REPORT, "praat.txt", "reading", NaiveDate::from_ymd_opt(2026, 3, 1))
+            .unwrap();
+        let first = &reading.sessions[0];
+        assert_eq!(first.date, "2026-03-01");
+        let r = first.analysis.reading.as_ref().unwrap();
+        assert_eq!(r.f0_range_hz, (110.2, 127.9));
+        assert_eq!(r.voice_breaks, 2);
+        assert!((r.voiced_fraction - 0.988).abs() < 1e-6);
+    }
+
+    #[test]
+    fn save_assigns_slots_and_reimport_updates() {
+        let conn = test_db();
+        let mut recorded = parse(Format::Csv, "date,mpt,mean_f0,f0_std,jitter,shimmer,hnr\n2026-01-05,8,118,2,0.4,3,20\n", "a", "", None)
+            .unwrap()
+            .sessions;
+        recorded[0].source = None;
+        save(&conn, &mut recorded).unwrap();
+
+        let csv = "date,mpt,mean_f0,f0_std,jitter,shimmer,hnr\n\
+                   2026-01-05,9,118,2,0.4,3,20\n\
+                   2026-01-05,10,118,2,0.4,3,20\n";
+        let mut imported = parse(Format::Csv, csv, "clinic.csv", "", None).unwrap().sessions;
+        save(&conn, &mut imported).unwrap();
+        assert_eq!(imported.iter().map(|s| s.slot).collect::<Vec<_>>(), [2, 3]);
+
+        // importing again overwrites the same two sessions
+        let mut again = parse(Format::Csv, &csv.replace(",10,", ",11,"), "clinic.csv", "", None).unwrap().sessions;
+        save(&conn, &mut again).unwrap();
+        assert_eq!(db::list_sessions(&conn).unwrap(), ["2026-01-05", "2026-01-05.2", "2026-01-05.3"]);
+        let third = db::load_session(&conn, "2026-01-05.3").unwrap();
+        assert_eq!(third.analysis.sustained.unwrap().mpt_seconds, 11.0);
+        assert_eq!(third.source.as_deref(), Some("csv:clinic.csv"));
+        assert!(db::load_session(&conn, "2026-01-05").unwrap().source.is_none());
+    }
+
+    #[test]
+    fn normalizes_headers() {
+        assert_eq!(normalize_column("Jitter (%)"), "jitter");
+        assert_eq!(normalize_column("Mean F0 [Hz]"), "mean_f0");
+        assert_eq!(normalize_column("\u{feff}Date"), "date");
+        assert_eq!(import_field("mpt_s"), Some(("sustained", "mpt_seconds")));
+        assert_eq!(import_field("range_hz"), Some(("scale", "range_hz")));
+        assert_eq!(import_field("sz_ratio"), None);
+        assert_eq!(split_csv_line("a,\"b, \"\"c\"\"\",d"), ["a", "b, \"c\"", "d"]);
+    }
+}
@@ -0,0 +1,617 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use rusqlite::Connection;
+
+use super::db;
+use super::session_data::*;
+use crate::paths;
+
+/// Exercises with WAV recordings (sz and fatigue are stored results only).
+const RECORDED_EXERCISES: [&str; 3] = ["sustained", "scale", "reading"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueKind {
+    /// A session, stored take analysis or canonical choice points at a
+    /// recording that no longer exists.
+    MissingRecording,
+    /// A recordings directory or file no session accounts for.
+    OrphanedRecording,
+    /// A stored JSON blob no longer parses under the current structs.
+    Unparsable,
+    /// A session row with no analyses at all.
+    NoAnalyses,
+    /// A take on disk is newer than the analysis, or was never analyzed.
+    Stale,
+}
+
+impl IssueKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            IssueKind::MissingRecording => "missing",
+            IssueKind::OrphanedRecording => "orphaned",
+            IssueKind::Unparsable => "unparsable",
+            IssueKind::NoAnalyses => "no analyses",
+            IssueKind::Stale => "stale",
+        }
+    }
+}
+
+/// What `voicevo doctor --fix` does about an issue. Only changes that lose
+/// nothing the recordings could still back up are offered; everything else
+/// is left for the user.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fix {
+    /// Point the session's recording path at a file that does exist.
+    Relink { row: i64, exercise: &'static str, path: PathBuf },
+    /// Run the analysis again from the recordings on disk.
+    Reanalyze(String),
+    /// Delete a session row that has neither analyses nor recordings.
+    PruneSession(i64),
+    /// Delete the per-attempt analyses of a take whose recording is gone
+    /// (not even kept as discarded); they can't be checked or redone.
+    PruneAttempt { row: i64, exercise: String, attempt: u32 },
+    /// Forget a canonical choice of a take that is no longer an attempt.
+    ClearCanonical { session: String, exercise: String },
+    /// Delete an empty recordings directory.
+    RemoveDir(PathBuf),
+}
+
+impl std::fmt::Display for Fix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Fix::Relink { path, .. } => write!(f, "re-link to {}", path.display()),
+            Fix::Reanalyze(_) => write!(f, "re-analyze"),
+            Fix::PruneSession(_) => write!(f, "remove the empty session"),
+            Fix::PruneAttempt { .. } => write!(f, "remove the take's analyses"),
+            Fix::ClearCanonical { .. } => write!(f, "clear the canonical choice"),
+            Fix::RemoveDir(_) => write!(f, "remove the empty directory"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub kind: IssueKind,
+    /// Session id, or None for problems outside any session.
+    pub session: Option<String>,
+    pub detail: String,
+    pub fix: Option<Fix>,
+}
+
+/// Counts of what was examined, for the summary line.
+#[derive(Debug, Default)]
+pub struct CheckSummary {
+    pub sessions: usize,
+    pub analyses: usize,
+    pub recording_dirs: usize,
+}
+
+struct SessionRow {
+    row: i64,
+    id: SessionId,
+    paths: [Option<String>; 3],
+    conditions: Option<String>,
+}
+
+/// Check the database against itself and against `recordings_dir`.
+pub fn check(conn: &Connection, recordings_dir: &Path) -> Result<(CheckSummary, Vec<Issue>)> {
+    let mut summary = CheckSummary::default();
+    let mut issues = Vec::new();
+
+    let sessions = load_session_rows(conn)?;
+    summary.sessions = sessions.len();
+    for session in &sessions {
+        summary.analyses += check_session(conn, recordings_dir, session, &mut issues)?;
+    }
+    summary.recording_dirs = check_recording_dirs(recordings_dir, &sessions, &mut issues)?;
+
+    Ok((summary, issues))
+}
+
+fn load_session_rows(conn: &Connection) -> Result<Vec<SessionRow>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, date, slot, sustained_path, scale_path, reading_path, conditions
+             FROM sessions ORDER BY date, slot",
+        )
+        .context("Failed to prepare session check")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, u32>(2)?,
+                [row.get(3)?, row.get(4)?, row.get(5)?],
+                row.get(6)?,
+            ))
+        })
+        .context("Failed to read sessions")?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .context("Failed to read sessions")?;
+
+    rows.into_iter()
+        .map(|(row, date, slot, paths, conditions)| {
+            let id = SessionId::parse(&format_session_id(&date, slot))
+                .with_context(|| format!("Session row {row} has an invalid date '{date}'"))?;
+            Ok(SessionRow { row, id, paths, conditions })
+        })
+        .collect()
+}
+
+/// Check one session's recordings and analyses; returns how many analysis
+/// rows were examined.
+fn check_session(
+    conn: &Connection,
+    recordings_dir: &Path,
+    session: &SessionRow,
+    issues: &mut Vec<Issue>,
+) -> Result<usize> {
+    let id = session.id.to_string();
+    let dir = recordings_dir.join(&id);
+    let has_takes = RECORDED_EXERCISES
+        .iter()
+        .any(|ex| !paths::list_attempts_in(&dir, ex).is_empty());
+    let reanalyze = || has_takes.then(|| Fix::Reanalyze(id.clone()));
+    let mut issue = |kind, detail: String, fix| {
+        issues.push(Issue { kind, session: Some(id.clone()), detail, fix });
+    };
+
+    for (exercise, path) in RECORDED_EXERCISES.iter().zip(&session.paths) {
+        let Some(path) = path else { continue };
+        if Path::new(path).exists() {
+            continue;
+        }
+        // After a restore or a moved data directory the file is usually
+        // where it belongs under the current recordings directory, possibly
+        // converted to the other storage format
+        let moved = Path::new(path)
+            .file_name()
+            .map(|name| dir.join(name))
+            .filter(|p| p.exists())
+            .or_else(|| {
+                let stem = Path::new(path).file_stem()?;
+                paths::RECORDING_EXTENSIONS
+                    .iter()
+                    .map(|ext| dir.join(stem).with_extension(ext))
+                    .find(|p| p.exists())
+            });
+        let fix = match moved {
+            Some(found) => Some(Fix::Relink { row: session.row, exercise, path: found }),
+            None => reanalyze(),
+        };
+        issue(IssueKind::MissingRecording, format!("{exercise} recording {path} no longer exists"), fix);
+    }
+
+    if let Some(ref json) = session.conditions {
+        if let Err(e) = serde_json::from_str::<RecordingConditions>(json) {
+            issue(IssueKind::Unparsable, format!("recording conditions: {e}"), None);
+        }
+    }
+
+    let mut stmt = conn
+        .prepare("SELECT exercise, version, attempt, data, created_at FROM analyses WHERE session_id = ?1")
+        .context("Failed to prepare analysis check")?;
+    let analyses = stmt
+        .query_map([session.row], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, u32>(1)?,
+                row.get::<_, u32>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+            ))
+        })
+        .context("Failed to read analyses")?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .context("Failed to read analyses")?;
+
+    if analyses.is_empty() {
+        let fix = reanalyze().or(Some(Fix::PruneSession(session.row)));
+        issue(IssueKind::NoAnalyses, "session has no analyses".to_string(), fix);
+        return Ok(0);
+    }
+
+    for (exercise, version, attempt, data, _) in &analyses {
+        if let Err(e) = parse_analysis(exercise, data) {
+            let which = if *attempt == 0 { "session result".to_string() } else { format!("attempt {attempt}") };
+            let fix = if RECORDED_EXERCISES.contains(&exercise.as_str()) { reanalyze() } else { None };
+            issue(IssueKind::Unparsable, format!("{exercise} {which} (v{version}): {e}"), fix);
+        }
+    }
+
+    // Per-attempt results and canonical choices must refer to takes that
+    // still exist. A discarded take keeps its analyses but can't be canonical.
+    let canonical = db::load_canonical_attempts(conn, &id).unwrap_or_else(|e| {
+        issue(IssueKind::Unparsable, format!("canonical attempts: {e:#}"), None);
+        Default::default()
+    });
+    for exercise in RECORDED_EXERCISES {
+        let kept: Vec<u32> = paths::list_attempts_in(&dir, exercise)
+            .iter()
+            .filter_map(|p| paths::attempt_number(p, exercise))
+            .collect();
+        let discarded: Vec<u32> = paths::list_discarded_in(&dir, exercise)
+            .iter()
+            .filter_map(|p| paths::discarded_attempt_number(p, exercise))
+            .collect();
+        let mut numbers: Vec<u32> = analyses
+            .iter()
+            .filter(|a| a.0 == exercise && a.2 > 0)
+            .map(|a| a.2)
+            .collect();
+        numbers.sort_unstable();
+        numbers.dedup();
+        for n in numbers.into_iter().filter(|n| !kept.contains(n) && !discarded.contains(n)) {
+            issue(
+                IssueKind::MissingRecording,
+                format!("{exercise} take {n:03} was analyzed but its recording no longer exists"),
+                Some(Fix::PruneAttempt { row: session.row, exercise: exercise.to_string(), attempt: n }),
+            );
+        }
+
+        if let Some(n) = canonical.get(exercise).filter(|n| !kept.contains(n)) {
+            issue(
+                IssueKind::MissingRecording,
+                format!("canonical {exercise} take {n:03} was discarded or deleted"),
+                Some(Fix::ClearCanonical { session: id.clone(), exercise: exercise.to_string() }),
+            );
+        }
+    }
+
+    // A take newer than the session-level analysis (or never analyzed at
+    // all) means the stored result doesn't reflect what is on disk
+    for exercise in RECORDED_EXERCISES {
+        let takes = paths::list_attempts_in(&dir, exercise);
+        if takes.is_empty() {
+            continue;
+        }
+        let analyzed = analyses
+            .iter()
+            .filter(|a| a.0 == exercise && a.2 == 0)
+            .filter_map(|a| parse_timestamp(&a.4))
+            .max();
+        let Some(analyzed) = analyzed else {
+            issue(IssueKind::Stale, format!("{exercise} has {} take(s) that were never analyzed", takes.len()), reanalyze());
+            continue;
+        };
+        let newer: Vec<&PathBuf> = takes
+            .iter()
+            .filter(|p| modified(p).is_some_and(|m| m.timestamp() > analyzed.timestamp()))
+            .collect();
+        if let Some(newest) = newer.last() {
+            issue(
+                IssueKind::Stale,
+                format!(
+                    "{} changed after the {exercise} analysis ({} UTC)",
+                    newest.file_name().unwrap_or_default().to_string_lossy(),
+                    analyzed.format("%Y-%m-%d %H:%M")
+                ),
+                reanalyze(),
+            );
+        }
+    }
+
+    Ok(analyses.len())
+}
+
+/// Parse a stored analysis blob with the struct for its exercise.
+fn parse_analysis(exercise: &str, data: &str) -> Result<()> {
+    match exercise {
+        "sustained" => serde_json::from_str::<SustainedAnalysis>(data).map(drop)?,
+        "scale" => serde_json::from_str::<ScaleAnalysis>(data).map(drop)?,
+        "reading" => serde_json::from_str::<ReadingAnalysis>(data).map(drop)?,
+        "sz" => serde_json::from_str::<SzAnalysis>(data).map(drop)?,
+        "fatigue" => serde_json::from_str::<FatigueAnalysis>(data).map(drop)?,
+        other => anyhow::bail!("unknown exercise '{other}'"),
+    }
+    Ok(())
+}
+
+/// SQLite `datetime('now')` values are UTC.
+fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").ok().map(|t| t.and_utc())
+}
+
+fn modified(path: &Path) -> Option<DateTime<Utc>> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok().map(DateTime::from)
+}
+
+/// Directories and files under `recordings_dir` that no session accounts
+/// for. Returns how many session directories were found.
+fn check_recording_dirs(recordings_dir: &Path, sessions: &[SessionRow], issues: &mut Vec<Issue>) -> Result<usize> {
+    let Ok(entries) = std::fs::read_dir(recordings_dir) else {
+        return Ok(0);
+    };
+    let mut entries: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+    entries.sort();
+
+    let mut count = 0;
+    for path in entries {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        let id = SessionId::parse(&name).ok().filter(|_| path.is_dir());
+        let Some(id) = id else {
+            issues.push(Issue {
+                kind: IssueKind::OrphanedRecording,
+                session: None,
+                detail: format!("{} is not a session directory", path.display()),
+                fix: None,
+            });
+            continue;
+        };
+        count += 1;
+        if sessions.iter().any(|s| s.id == id) {
+            continue;
+        }
+
+        let empty = std::fs::read_dir(&path)
+            .with_context(|| format!("Failed to list {}", path.display()))?
+            .next()
+            .is_none();
+        let has_takes = RECORDED_EXERCISES
+            .iter()
+            .any(|ex| !paths::list_attempts_in(&path, ex).is_empty());
+        let (detail, fix) = if empty {
+            ("empty recordings directory".to_string(), Some(Fix::RemoveDir(path.clone())))
+        } else if has_takes {
+            ("recorded but never analyzed".to_string(), Some(Fix::Reanalyze(id.to_string())))
+        } else {
+            (format!("{} has no recognised takes", path.display()), None)
+        };
+        issues.push(Issue { kind: IssueKind::OrphanedRecording, session: Some(id.to_string()), detail, fix });
+    }
+    Ok(count)
+}
+
+/// Apply a fix that only touches the database or the filesystem.
+/// `Fix::Reanalyze` needs the analyzer and is left to the caller.
+pub fn apply(conn: &Connection, fix: &Fix) -> Result<()> {
+    match fix {
+        Fix::Relink { row, exercise, path } => {
+            let column = match *exercise {
+                "sustained" => "sustained_path",
+                "scale" => "scale_path",
+                "reading" => "reading_path",
+                other => anyhow::bail!("No recording path for {other}"),
+            };
+            conn.execute(
+                &format!("UPDATE sessions SET {column} = ?1 WHERE id = ?2"),
+                rusqlite::params![path.to_string_lossy(), row],
+            )
+            .with_context(|| format!("Failed to re-link {exercise} recording"))?;
+        }
+        Fix::PruneSession(row) => {
+            // Guarded so a session that gained analyses since the check survives
+            conn.execute(
+                "DELETE FROM sessions WHERE id = ?1
+                 AND NOT EXISTS (SELECT 1 FROM analyses WHERE session_id = ?1)",
+                [row],
+            )
+            .context("Failed to remove session")?;
+        }
+        Fix::PruneAttempt { row, exercise, attempt } => {
+            conn.execute(
+                "DELETE FROM analyses WHERE session_id = ?1 AND exercise = ?2 AND attempt = ?3",
+                rusqlite::params![row, exercise, attempt],
+            )
+            .with_context(|| format!("Failed to remove {exercise} take {attempt}"))?;
+        }
+        Fix::ClearCanonical { session, exercise } => {
+            db::clear_canonical_attempt(conn, session, exercise)?;
+        }
+        Fix::RemoveDir(path) => {
+            // remove_dir (not remove_dir_all) refuses if anything appeared
+            std::fs::remove_dir(path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        Fix::Reanalyze(_) => anyhow::bail!("Re-analysis is not a database fix"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::db;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    fn test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        conn
+    }
+
+    fn session(date: &str, sustained: Option<&Path>) -> SessionData {
+        let mut s = SessionData::test_default(date);
+        s.recordings.sustained = sustained.map(|p| p.to_string_lossy().into_owned());
+        s
+    }
+
+    fn kinds(issues: &[Issue]) -> Vec<IssueKind> {
+        issues.iter().map(|i| i.kind).collect()
+    }
+
+    #[test]
+    fn healthy_database_has_no_issues() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join("2026-01-15");
+        std::fs::create_dir_all(&dir).unwrap();
+        let wav = dir.join("sustained_001.wav");
+        std::fs::write(&wav, b"RIFF").unwrap();
+        // Recorded well before the analysis
+        std::fs::File::options()
+            .write(true)
+            .open(&wav)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(3600))
+            .unwrap();
+
+        let conn = test_db();
+        db::save_session(&conn, &session("2026-01-15", Some(&wav))).unwrap();
+
+        let (summary, issues) = check(&conn, tmp.path()).unwrap();
+        assert!(issues.is_empty(), "{issues:?}");
+        assert_eq!(summary.sessions, 1);
+        assert_eq!(summary.analyses, 1);
+        assert_eq!(summary.recording_dirs, 1);
+    }
+
+    #[test]
+    fn attempts_and_canonical_without_a_take_are_flagged() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join("2026-01-15");
+        std::fs::create_dir_all(&dir).unwrap();
+        let old = SystemTime::now() - Duration::from_secs(3600);
+        for name in ["sustained_001.wav", "sustained_002.discarded.wav", "sustained_003.wav"] {
+            std::fs::write(dir.join(name), b"RIFF").unwrap();
+            std::fs::File::options().write(true).open(dir.join(name)).unwrap().set_modified(old).unwrap();
+        }
+
+        let conn = test_db();
+        let take = |n| AttemptAnalysis {
+            attempt: n,
+            result: ExerciseResult::Sustained(session("2026-01-15", None).analysis.sustained.unwrap()),
+        };
+        db::save_session(&conn, &session("2026-01-15", Some(&dir.join("sustained_001.wav")))).unwrap();
+        db::save_attempts(&conn, "2026-01-15", 2, &[take(1), take(2), take(3), take(4)]).unwrap();
+        db::set_canonical_attempt(&conn, "2026-01-15", "sustained", 3, &dir.join("sustained_003.wav").to_string_lossy())
+            .unwrap();
+        // Take 3 deleted by hand after being chosen; take 4 never on disk
+        std::fs::remove_file(dir.join("sustained_003.wav")).unwrap();
+
+        let (_, issues) = check(&conn, tmp.path()).unwrap();
+        // (The session path to take 3 is missing too, which re-analysis fixes)
+        let fixes: Vec<Fix> = issues
+            .iter()
+            .filter_map(|i| i.fix.clone())
+            .filter(|f| !matches!(f, Fix::Reanalyze(_)))
+            .collect();
+        let row = 1;
+        assert_eq!(
+            fixes,
+            vec![
+                Fix::PruneAttempt { row, exercise: "sustained".into(), attempt: 3 },
+                Fix::PruneAttempt { row, exercise: "sustained".into(), attempt: 4 },
+                Fix::ClearCanonical { session: "2026-01-15".into(), exercise: "sustained".into() },
+            ]
+        );
+
+        for fix in &fixes {
+            apply(&conn, fix).unwrap();
+        }
+        let (_, issues) = check(&conn, tmp.path()).unwrap();
+        // The discarded take keeps its analysis
+        assert!(
+            !issues.iter().any(|i| matches!(i.fix, Some(Fix::PruneAttempt { .. } | Fix::ClearCanonical { .. }))),
+            "{issues:?}"
+        );
+        let kept: Vec<u32> = db::load_attempts(&conn, "2026-01-15").unwrap().iter().map(|a| a.attempt).collect();
+        assert_eq!(kept, [1, 2]);
+    }
+
+    #[test]
+    fn missing_recording_is_relinked_to_compacted_take() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join("2026-01-15");
+        std::fs::create_dir_all(&dir).unwrap();
+        let flac = dir.join("sustained_001.flac");
+        std::fs::write(&flac, b"fLaC").unwrap();
+
+        let conn = test_db();
+        db::save_session(&conn, &session("2026-01-15", Some(&dir.join("sustained_001.wav")))).unwrap();
+
+        let (_, issues) = check(&conn, tmp.path()).unwrap();
+        let fix = issues[0].fix.clone().unwrap();
+        assert!(matches!(fix, Fix::Relink { ref path, .. } if *path == flac));
+    }
+
+    #[test]
+    fn missing_recording_is_relinked_when_found() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join("2026-01-15");
+        std::fs::create_dir_all(&dir).unwrap();
+        let moved = dir.join("sustained_001.wav");
+        std::fs::write(&moved, b"RIFF").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&moved)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(3600))
+            .unwrap();
+
+        let conn = test_db();
+        let old = Path::new("/old/home/recordings/2026-01-15/sustained_001.wav");
+        db::save_session(&conn, &session("2026-01-15", Some(old))).unwrap();
+
+        let (_, issues) = check(&conn, tmp.path()).unwrap();
+        assert_eq!(kinds(&issues), vec![IssueKind::MissingRecording]);
+        let fix = issues[0].fix.clone().unwrap();
+        assert!(matches!(fix, Fix::Relink { ref path, .. } if *path == moved));
+
+        apply(&conn, &fix).unwrap();
+        let (_, issues) = check(&conn, tmp.path()).unwrap();
+        assert!(issues.is_empty(), "{issues:?}");
+        let loaded = db::load_session(&conn, "2026-01-15").unwrap();
+        assert_eq!(loaded.recordings.sustained.as_deref(), Some(moved.to_str().unwrap()));
+    }
+
+    #[test]
+    fn detects_unparsable_stale_and_empty() {
+        let tmp = TempDir::new().unwrap();
+        let conn = test_db();
+
+        // Unparsable blob, and a take newer than its analysis
+        db::save_session(&conn, &session("2026-01-15", None)).unwrap();
+        conn.execute("UPDATE analyses SET data = '{\"mpt_seconds\": \"long\"}'", []).unwrap();
+        let dir = tmp.path().join("2026-01-15");
+        std::fs::create_dir_all(&dir).unwrap();
+        let wav = dir.join("sustained_002.wav");
+        std::fs::write(&wav, b"RIFF").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&wav)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(3600))
+            .unwrap();
+
+        // A session row with nothing behind it
+        conn.execute("INSERT INTO sessions (date, slot) VALUES ('2026-01-20', 1)", []).unwrap();
+
+        let (_, issues) = check(&conn, tmp.path()).unwrap();
+        assert_eq!(kinds(&issues), vec![IssueKind::Unparsable, IssueKind::Stale, IssueKind::NoAnalyses]);
+        assert_eq!(issues[0].fix, Some(Fix::Reanalyze("2026-01-15".into())));
+        assert_eq!(issues[1].fix, Some(Fix::Reanalyze("2026-01-15".into())));
+
+        let prune = issues[2].fix.clone().unwrap();
+        assert!(matches!(prune, Fix::PruneSession(_)));
+        apply(&conn, &prune).unwrap();
+        assert!(db::list_sessions(&conn).unwrap() == vec!["2026-01-15"]);
+    }
+
+    #[test]
+    fn orphaned_recordings() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir_all(tmp.path().join("2026-01-15.2")).unwrap();
+        std::fs::write(tmp.path().join("2026-01-15.2/reading_001.wav"), b"RIFF").unwrap();
+        std::fs::create_dir_all(tmp.path().join("2026-01-16")).unwrap();
+        std::fs::create_dir_all(tmp.path().join("scratch")).unwrap();
+
+        let conn = test_db();
+        let (summary, issues) = check(&conn, tmp.path()).unwrap();
+        assert_eq!(summary.recording_dirs, 2);
+        let fixes: Vec<Option<Fix>> = issues.iter().map(|i| i.fix.clone()).collect();
+        assert_eq!(
+            fixes,
+            vec![
+                Some(Fix::Reanalyze("2026-01-15.2".into())),
+                Some(Fix::RemoveDir(tmp.path().join("2026-01-16"))),
+                None,
+            ]
+        );
+
+        apply(&conn, fixes[1].as_ref().unwrap()).unwrap();
+        assert!(!tmp.path().join("2026-01-16").exists());
+    }
+}
@@ -1,3 +1,8 @@
+pub mod backup;
+pub mod compact;
 pub mod db;
+pub mod import;
+pub mod integrity;
+pub mod schema;
 pub mod session_data;
 pub mod store;
@@ -0,0 +1,402 @@
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+
+use super::db;
+
+/// One schema change. Migrations run in order, each in its own transaction;
+/// `PRAGMA user_version` records how many have been applied.
+pub struct Migration {
+    pub description: &'static str,
+    apply: fn(&Connection) -> Result<()>,
+}
+
+/// Every schema change since the first release, oldest first. Append only:
+/// a database's version is its index into this list.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        description: "Create sessions and analyses tables",
+        apply: |conn| {
+            exec(
+                conn,
+                "CREATE TABLE sessions (
+                    id INTEGER PRIMARY KEY,
+                    date TEXT NOT NULL UNIQUE,
+                    sustained_path TEXT,
+                    scale_path TEXT,
+                    reading_path TEXT
+                );
+                CREATE TABLE analyses (
+                    id INTEGER PRIMARY KEY,
+                    session_id INTEGER NOT NULL REFERENCES sessions(id),
+                    version INTEGER NOT NULL DEFAULT 1,
+                    exercise TEXT NOT NULL,
+                    data TEXT NOT NULL,
+                    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                    UNIQUE(session_id, version, exercise)
+                );",
+            )
+        },
+    },
+    Migration {
+        description: "Add recording conditions to sessions",
+        apply: |conn| exec(conn, "ALTER TABLE sessions ADD COLUMN conditions TEXT;"),
+    },
+    Migration {
+        description: "Add canonical attempt choices to sessions",
+        // JSON map exercise -> attempt
+        apply: |conn| exec(conn, "ALTER TABLE sessions ADD COLUMN canonical_attempts TEXT;"),
+    },
+    Migration {
+        description: "Store analyses per attempt",
+        // The unique key changes, which SQLite can't ALTER, so the table is
+        // rebuilt. Existing rows become attempt 0 (the session-level result).
+        apply: |conn| {
+            exec(
+                conn,
+                "ALTER TABLE analyses RENAME TO analyses_old;
+                CREATE TABLE analyses (
+                    id INTEGER PRIMARY KEY,
+                    session_id INTEGER NOT NULL REFERENCES sessions(id),
+                    version INTEGER NOT NULL DEFAULT 1,
+                    exercise TEXT NOT NULL,
+                    attempt INTEGER NOT NULL DEFAULT 0,
+                    data TEXT NOT NULL,
+                    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                    UNIQUE(session_id, version, exercise, attempt)
+                );
+                INSERT INTO analyses (id, session_id, version, exercise, attempt, data, created_at)
+                    SELECT id, session_id, version, exercise, 0, data, created_at FROM analyses_old;
+                DROP TABLE analyses_old;",
+            )
+        },
+    },
+    Migration {
+        description: "Add interpretations table",
+        apply: |conn| {
+            exec(
+                conn,
+                "CREATE TABLE interpretations (
+                    id INTEGER PRIMARY KEY,
+                    date TEXT NOT NULL,
+                    provider TEXT NOT NULL,
+                    model TEXT NOT NULL,
+                    content TEXT NOT NULL,
+                    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+                );",
+            )
+        },
+    },
+    Migration {
+        description: "Add clinical events table",
+        apply: |conn| {
+            exec(
+                conn,
+                "CREATE TABLE events (
+                    id INTEGER PRIMARY KEY,
+                    date TEXT NOT NULL,
+                    kind TEXT NOT NULL,
+                    note TEXT NOT NULL DEFAULT '',
+                    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+                );",
+            )
+        },
+    },
+    Migration {
+        description: "Add normalized metrics table",
+        apply: |conn| {
+            exec(
+                conn,
+                "CREATE TABLE metrics (
+                    session_id INTEGER NOT NULL REFERENCES sessions(id),
+                    version INTEGER NOT NULL,
+                    exercise TEXT NOT NULL,
+                    name TEXT NOT NULL,
+                    value REAL NOT NULL,
+                    valid INTEGER NOT NULL DEFAULT 1,
+                    PRIMARY KEY(session_id, version, exercise, name)
+                );",
+            )?;
+            db::backfill_metrics(conn)
+        },
+    },
+    Migration {
+        description: "Allow several sessions per day (date, slot)",
+        // Rebuilt to replace UNIQUE(date); existing sessions become slot 1.
+        // Dropping and renaming relies on foreign keys being off (see `migrate`).
+        apply: |conn| {
+            exec(
+                conn,
+                "CREATE TABLE sessions_new (
+                    id INTEGER PRIMARY KEY,
+                    date TEXT NOT NULL,
+                    slot INTEGER NOT NULL DEFAULT 1,
+                    sustained_path TEXT,
+                    scale_path TEXT,
+                    reading_path TEXT,
+                    conditions TEXT,
+                    canonical_attempts TEXT,
+                    UNIQUE(date, slot)
+                );
+                INSERT INTO sessions_new (id, date, slot, sustained_path, scale_path, reading_path, conditions, canonical_attempts)
+                    SELECT id, date, 1, sustained_path, scale_path, reading_path, conditions, canonical_attempts FROM sessions;
+                DROP TABLE sessions;
+                ALTER TABLE sessions_new RENAME TO sessions;",
+            )
+        },
+    },
+    Migration {
+        description: "Record the source of imported sessions",
+        apply: |conn| exec(conn, "ALTER TABLE sessions ADD COLUMN source TEXT;"),
+    },
+    Migration {
+        description: "Add trial runs table",
+        // Keyed by date and slot rather than session id: a guided run is
+        // recorded before its session is first analyzed and saved.
+        apply: |conn| {
+            exec(
+                conn,
+                "CREATE TABLE trial_runs (
+                    date TEXT NOT NULL,
+                    slot INTEGER NOT NULL,
+                    exercise TEXT NOT NULL,
+                    attempts TEXT NOT NULL,
+                    PRIMARY KEY(date, slot, exercise)
+                );",
+            )
+        },
+    },
+];
+
+fn exec(conn: &Connection, sql: &str) -> Result<()> {
+    conn.execute_batch(sql).map_err(Into::into)
+}
+
+/// Schema version the code expects.
+pub fn latest_version() -> u32 {
+    MIGRATIONS.len() as u32
+}
+
+/// Version recorded in the database, or inferred from its tables for
+/// databases created before versions were recorded.
+pub fn current_version(conn: &Connection) -> Result<u32> {
+    match recorded_version(conn)? {
+        0 => legacy_version(conn),
+        version => Ok(version),
+    }
+}
+
+fn recorded_version(conn: &Connection) -> Result<u32> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+        .context("Failed to read schema version")
+}
+
+/// Infer the version of an unversioned database. Older releases applied every
+/// change they knew about on open, so the schema is always a prefix of
+/// `MIGRATIONS`: count how many of their effects are present.
+fn legacy_version(conn: &Connection) -> Result<u32> {
+    let has_table = |table: &str| -> Result<bool> {
+        conn.prepare("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1")
+            .and_then(|mut stmt| stmt.exists([table]))
+            .context("Failed to inspect schema")
+    };
+    let has_column = |table: &str, column: &str| -> Result<bool> {
+        conn.prepare("SELECT 1 FROM pragma_table_info(?1) WHERE name = ?2")
+            .and_then(|mut stmt| stmt.exists([table, column]))
+            .context("Failed to inspect schema")
+    };
+
+    let applied = [
+        has_table("sessions")?,
+        has_column("sessions", "conditions")?,
+        has_column("sessions", "canonical_attempts")?,
+        has_column("analyses", "attempt")?,
+        has_table("interpretations")?,
+        has_table("events")?,
+        has_table("metrics")?,
+        has_column("sessions", "slot")?,
+        has_column("sessions", "source")?,
+        has_table("trial_runs")?,
+    ];
+    debug_assert_eq!(applied.len(), MIGRATIONS.len());
+    Ok(applied.iter().take_while(|&&a| a).count() as u32)
+}
+
+/// Where a database stands relative to `MIGRATIONS`.
+pub struct SchemaStatus {
+    pub current: u32,
+    pub latest: u32,
+    /// (version, description) of each migration still to run.
+    pub pending: Vec<(u32, &'static str)>,
+}
+
+pub fn status(conn: &Connection) -> Result<SchemaStatus> {
+    let current = current_version(conn)?;
+    let pending = MIGRATIONS
+        .iter()
+        .enumerate()
+        .skip(current as usize)
+        .map(|(i, m)| (i as u32 + 1, m.description))
+        .collect();
+    Ok(SchemaStatus { current, latest: latest_version(), pending })
+}
+
+/// Apply pending migrations in order. Each runs in a transaction together
+/// with its version bump, so a failure leaves the database at the last
+/// completed version. Returns how many were applied.
+///
+/// Foreign keys are switched off while migrating so tables can be rebuilt
+/// (SQLite's documented procedure) and checked before each commit.
+pub fn migrate(conn: &Connection) -> Result<usize> {
+    let current = current_version(conn)?;
+    if current > latest_version() {
+        anyhow::bail!(
+            "Database schema version {current} is newer than this build supports ({}). Update voicevo.",
+            latest_version()
+        );
+    }
+    // An up-to-date legacy database only needs its version recorded
+    if current > 0 && recorded_version(conn)? == 0 {
+        conn.pragma_update(None, "user_version", current)
+            .context("Failed to record schema version")?;
+    }
+
+    if current == latest_version() {
+        return Ok(0);
+    }
+
+    let foreign_keys: bool = conn
+        .pragma_query_value(None, "foreign_keys", |row| row.get(0))
+        .context("Failed to read foreign_keys")?;
+    conn.pragma_update(None, "foreign_keys", false)
+        .context("Failed to disable foreign keys")?;
+    let result = MIGRATIONS
+        .iter()
+        .enumerate()
+        .skip(current as usize)
+        .try_for_each(|(i, migration)| apply(conn, i as u32 + 1, migration));
+    conn.pragma_update(None, "foreign_keys", foreign_keys)
+        .context("Failed to restore foreign keys")?;
+    result?;
+
+    Ok((latest_version() - current) as usize)
+}
+
+fn apply(conn: &Connection, version: u32, migration: &Migration) -> Result<()> {
+    let tx = conn
+        .unchecked_transaction()
+        .context("Failed to start migration")?;
+    (migration.apply)(&tx)
+        .with_context(|| format!("Migration {version} failed: {}", migration.description))?;
+
+    let violation = tx
+        .prepare("PRAGMA foreign_key_check")
+        .and_then(|mut stmt| stmt.exists([]))
+        .context("Failed to check foreign keys")?;
+    if violation {
+        anyhow::bail!("Migration {version} ({}) broke foreign key references", migration.description);
+    }
+
+    tx.pragma_update(None, "user_version", version)
+        .context("Failed to record schema version")?;
+    tx.commit()
+        .with_context(|| format!("Failed to commit migration {version}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A database as an older release left it: the first `version`
+    /// migrations applied, with no version recorded, and one session saved
+    /// once the tables existed.
+    fn legacy_db(version: u32) -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        // Older releases rebuilt tables with foreign keys off, as `migrate` does
+        conn.execute_batch("PRAGMA foreign_keys=OFF;").unwrap();
+        for (i, m) in MIGRATIONS[..version as usize].iter().enumerate() {
+            (m.apply)(&conn).unwrap();
+            if i == 0 {
+                conn.execute("INSERT INTO sessions (id, date) VALUES (1, '2026-01-15')", [])
+                    .unwrap();
+                let data = r#"{"mpt_seconds":6.5,"mean_f0_hz":110.0,"f0_std_hz":3.0,
+                    "jitter_local_percent":1.8,"shimmer_local_percent":4.5,"hnr_db":9.0}"#;
+                conn.execute(
+                    "INSERT INTO analyses (session_id, version, exercise, data) VALUES (1, 2, 'sustained', ?1)",
+                    [data],
+                )
+                .unwrap();
+            }
+        }
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        conn
+    }
+
+    #[test]
+    fn fresh_database_has_everything_pending() {
+        let conn = Connection::open_in_memory().unwrap();
+        let status = status(&conn).unwrap();
+        assert_eq!(status.current, 0);
+        assert_eq!(status.latest, MIGRATIONS.len() as u32);
+        assert_eq!(status.pending.len(), MIGRATIONS.len());
+        assert_eq!(status.pending[0], (1, "Create sessions and analyses tables"));
+        assert_eq!(status.pending.last().unwrap().1, "Add trial runs table");
+    }
+
+    #[test]
+    fn upgrades_every_historical_version() {
+        for version in 0..=latest_version() {
+            let conn = legacy_db(version);
+            assert_eq!(current_version(&conn).unwrap(), version, "inferred version");
+
+            migrate(&conn).unwrap();
+            assert_eq!(recorded_version(&conn).unwrap(), latest_version());
+            assert!(status(&conn).unwrap().pending.is_empty());
+            // Running again is a no-op
+            assert_eq!(migrate(&conn).unwrap(), 0);
+
+            let foreign_keys: bool = conn
+                .pragma_query_value(None, "foreign_keys", |row| row.get(0))
+                .unwrap();
+            assert!(foreign_keys, "foreign keys restored");
+
+            if version > 0 {
+                let session = db::load_session(&conn, "2026-01-15").unwrap();
+                assert_eq!(session.slot, 1);
+                assert!((session.analysis.sustained.unwrap().mpt_seconds - 6.5).abs() < 0.01);
+                let metrics: i64 = conn
+                    .query_row("SELECT COUNT(*) FROM metrics WHERE session_id = 1", [], |row| row.get(0))
+                    .unwrap();
+                assert_eq!(metrics, 6, "metrics backfilled from version {version}");
+            }
+        }
+    }
+
+    #[test]
+    fn recorded_version_wins_over_inference() {
+        let conn = legacy_db(latest_version());
+        conn.pragma_update(None, "user_version", 3).unwrap();
+        assert_eq!(current_version(&conn).unwrap(), 3);
+    }
+
+    #[test]
+    fn newer_database_is_refused() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", latest_version() + 1).unwrap();
+        assert!(migrate(&conn).is_err());
+    }
+
+    #[test]
+    fn failed_migration_rolls_back() {
+        let conn = legacy_db(5);
+        // Block the events migration
+        conn.execute_batch("CREATE VIEW events AS SELECT 1;").unwrap();
+        conn.pragma_update(None, "user_version", 5).unwrap();
+        assert!(migrate(&conn).is_err());
+        assert_eq!(current_version(&conn).unwrap(), 5);
+
+        let sessions: i64 = conn
+            .query_row("SELECT COUNT(*) FROM sessions", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(sessions, 1);
+    }
+}
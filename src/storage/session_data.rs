@@ -1,9 +1,13 @@
+use anyhow::Context;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
 /// Analysis pipeline version. Bump when the DSP pipeline changes fundamentally.
 /// v2: tighter bridge thresholds, gated jitter/shimmer, periodicity score,
 ///     CPPS, per-exercise pitch ceilings, reliability metadata.
-pub const ANALYSIS_VERSION: u32 = 2;
+/// v3: silence trimming and phonation segmentation before analysis,
+///     recording quality checks with SNR-based reliability downgrade.
+pub const ANALYSIS_VERSION: u32 = 3;
 
 /// Self-reported conditions at the time of recording.
 /// These help the LLM distinguish genuine recovery progress from day-to-day
@@ -24,7 +28,70 @@ pub struct RecordingConditions {
     pub notes: Option<String>,
 }
 
-/// Complete session data for one recording date.
+/// Identifies a session: its date plus a slot for further sessions that
+/// day. Written as the bare date for slot 1 ("2026-03-01") and with the slot
+/// appended otherwise ("2026-03-01.2"), which is also the name of the
+/// session's recordings directory. The written forms sort chronologically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SessionId {
+    pub date: NaiveDate,
+    /// 1 for the first session of the day.
+    pub slot: u32,
+}
+
+impl SessionId {
+    pub fn new(date: NaiveDate, slot: u32) -> Self {
+        Self { date, slot }
+    }
+
+    /// Parse "YYYY-MM-DD" (slot 1) or "YYYY-MM-DD.N".
+    pub fn parse(s: &str) -> anyhow::Result<Self> {
+        let (date, slot) = match s.split_once('.') {
+            Some((date, slot)) => {
+                let slot: u32 = slot
+                    .parse()
+                    .ok()
+                    .filter(|&n| n >= 1)
+                    .with_context(|| format!("Invalid session slot in '{s}' (expected e.g. 2026-03-01.2)"))?;
+                (date, slot)
+            }
+            None => (s, 1),
+        };
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .with_context(|| format!("Invalid session '{s}' (expected YYYY-MM-DD or YYYY-MM-DD.N)"))?;
+        Ok(Self { date, slot })
+    }
+
+    /// The session picked by `--session` or else `--date` (slot 1),
+    /// defaulting to today's first session.
+    pub fn resolve(session: Option<&str>, date: Option<&str>) -> anyhow::Result<Self> {
+        match (session, date) {
+            (Some(id), _) => Self::parse(id),
+            (None, Some(date)) => Ok(Self::new(
+                NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                    .with_context(|| format!("Invalid date '{date}' (expected YYYY-MM-DD)"))?,
+                1,
+            )),
+            (None, None) => Ok(Self::new(chrono::Local::now().date_naive(), 1)),
+        }
+    }
+}
+
+impl std::fmt::Display for SessionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.slot == 1 {
+            write!(f, "{}", self.date)
+        } else {
+            write!(f, "{}.{}", self.date, self.slot)
+        }
+    }
+}
+
+fn first_slot() -> u32 {
+    1
+}
+
+/// Complete session data for one recording session.
 ///
 /// The `#[derive(Serialize, Deserialize)]` macro auto-generates code
 /// to convert this struct to/from JSON. serde inspects each field's type
@@ -33,11 +100,34 @@ pub struct RecordingConditions {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionData {
     pub date: String,
+    /// Session of the day, see `SessionId`. Older data has slot 1.
+    #[serde(default = "first_slot")]
+    pub slot: u32,
     pub recordings: SessionRecordings,
     pub analysis: SessionAnalysis,
     /// Self-reported recording conditions. None for older sessions or CLI analyze.
     #[serde(default)]
     pub conditions: Option<RecordingConditions>,
+    /// Where an imported session came from, e.g. "praat:clinic.txt".
+    /// None for sessions recorded and analyzed by voicevo.
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+impl SessionData {
+    /// The session's id as written on the command line, e.g. "2026-03-01.2".
+    pub fn id(&self) -> String {
+        format_session_id(&self.date, self.slot)
+    }
+}
+
+/// Write a (date, slot) pair the way `SessionId` displays it.
+pub fn format_session_id(date: &str, slot: u32) -> String {
+    if slot == 1 {
+        date.to_string()
+    } else {
+        format!("{date}.{slot}")
+    }
 }
 
 /// Paths to the WAV files for each exercise.
@@ -87,8 +177,18 @@ pub struct ReliabilityInfo {
     pub analysis_quality: String,
     /// Per-metric validity flags.
     pub metrics_validity: MetricsValidity,
+    /// True when quality was downgraded because the recording's SNR was too
+    /// low for perturbation measures.
+    #[serde(default)]
+    pub snr_limited: bool,
 }
 
+/// Below this SNR (dB), "good" analyses are capped at "ok".
+pub const SNR_OK_DB: f32 = 30.0;
+/// Below this SNR (dB), jitter, shimmer and HNR are dominated by background
+/// noise and the take is only usable for trends.
+pub const SNR_TREND_ONLY_DB: f32 = 20.0;
+
 impl ReliabilityInfo {
     /// Compute reliability from tier counts and activity data.
     pub fn compute(
@@ -134,10 +234,112 @@ impl ReliabilityInfo {
             dominant_tier,
             analysis_quality,
             metrics_validity,
+            snr_limited: false,
+        }
+    }
+
+    /// Downgrade quality when the recording is too noisy to trust.
+    ///
+    /// Perturbation measures need a clean signal (Deliyski et al., 2005):
+    /// below SNR_OK_DB a "good" analysis becomes "ok", and below
+    /// SNR_TREND_ONLY_DB jitter, shimmer and HNR are marked invalid.
+    pub fn apply_snr(&mut self, snr_db: f32) {
+        if snr_db < SNR_TREND_ONLY_DB {
+            self.analysis_quality = "trend_only".to_string();
+            self.metrics_validity.jitter = false;
+            self.metrics_validity.shimmer = false;
+            self.metrics_validity.hnr = false;
+            self.snr_limited = true;
+        } else if snr_db < SNR_OK_DB && self.analysis_quality == "good" {
+            self.analysis_quality = "ok".to_string();
+            self.snr_limited = true;
+        }
+    }
+}
+
+/// Region of a recording that was analyzed, in seconds from its start.
+/// Everything outside it (keypress, breaths, trailing silence) was trimmed.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AnalyzedSegment {
+    pub start_secs: f32,
+    pub end_secs: f32,
+}
+
+impl AnalyzedSegment {
+    pub fn from_range(range: std::ops::Range<usize>, sample_rate: u32) -> Self {
+        Self {
+            start_secs: range.start as f32 / sample_rate as f32,
+            end_secs: range.end as f32 / sample_rate as f32,
+        }
+    }
+
+    pub fn duration_secs(&self) -> f32 {
+        self.end_secs - self.start_secs
+    }
+}
+
+/// Technical quality of a recording, independent of the voice in it.
+///
+/// Clipping, background noise, DC offset and mains hum all distort the
+/// acoustic measures, so a bad take should be re-recorded rather than
+/// flow into the trend data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingQuality {
+    /// Fraction of samples at full scale (0.0 to 1.0).
+    pub clipped_fraction: f32,
+    /// Background noise level in dBFS.
+    pub noise_floor_db: f32,
+    /// Whether the noise floor came from leading/trailing silence. When
+    /// false there was too little silence and the quietest frames were used.
+    pub noise_from_silence: bool,
+    /// Signal-to-noise ratio in dB: active-frame level minus noise floor.
+    pub snr_db: f32,
+    /// Mean sample value in full-scale units.
+    pub dc_offset: f32,
+    /// Mains hum frequency (50 or 60 Hz) when a peak stands out; None otherwise.
+    pub hum_hz: Option<f32>,
+    /// Prominence of the stronger of the 50/60 Hz peaks over the surrounding
+    /// spectrum (dB).
+    pub hum_prominence_db: f32,
+    /// Problems found, in plain language. Empty for a clean recording.
+    pub warnings: Vec<String>,
+    /// Sample rate and format of the file, or of the device stream for a
+    /// live exercise. None for older analyses.
+    #[serde(default)]
+    pub format: Option<RecordingFormat>,
+}
+
+/// Sample rate and sample format of a recording, as stored in its header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordingFormat {
+    pub sample_rate: u32,
+    pub bits_per_sample: u16,
+    /// IEEE float samples rather than integer PCM.
+    #[serde(default)]
+    pub float: bool,
+}
+
+impl RecordingFormat {
+    /// E.g. "48 kHz / 24-bit" or "44.1 kHz / 32-bit float".
+    pub fn describe(&self) -> String {
+        let rate = format!("{:.1}", self.sample_rate as f32 / 1000.0);
+        let rate = rate.trim_end_matches(".0");
+        let float = if self.float { " float" } else { "" };
+        format!("{rate} kHz / {}-bit{float}", self.bits_per_sample)
+    }
+}
+
+impl From<hound::WavSpec> for RecordingFormat {
+    fn from(spec: hound::WavSpec) -> Self {
+        Self {
+            sample_rate: spec.sample_rate,
+            bits_per_sample: spec.bits_per_sample,
+            float: spec.sample_format == hound::SampleFormat::Float,
         }
     }
 }
 
+
 /// Analysis of the sustained vowel ("AAAH") recording.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SustainedAnalysis {
@@ -169,6 +371,45 @@ pub struct SustainedAnalysis {
     /// Rich reliability metadata. Replaces detection_quality for new analyses.
     #[serde(default)]
     pub reliability: Option<ReliabilityInfo>,
+    /// Best-of-N summary when the day has several sustained attempts.
+    /// None for single-take sessions.
+    #[serde(default)]
+    pub trials: Option<SustainedTrials>,
+    /// Clipping, noise and hum checks of the recording.
+    #[serde(default)]
+    pub recording_quality: Option<RecordingQuality>,
+    /// Region of the recording the metrics were computed on.
+    /// None for older analyses (whole recording).
+    #[serde(default)]
+    pub segment: Option<AnalyzedSegment>,
+}
+
+/// MPT of one sustained vowel attempt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrialMpt {
+    /// Recording file name, e.g. "sustained_002.wav".
+    pub attempt: String,
+    /// Maximum phonation time of this attempt in seconds.
+    pub mpt_seconds: f32,
+}
+
+/// Multi-trial sustained vowel summary.
+///
+/// Clinical MPT is the best of several trials. Every reported number comes
+/// from that one best attempt; the other attempts only contribute their MPT
+/// to the mean.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SustainedTrials {
+    /// MPT of each analyzed attempt, in recording order.
+    pub attempts: Vec<TrialMpt>,
+    /// Longest MPT across attempts (the value stored in `mpt_seconds`).
+    pub mpt_max_seconds: f32,
+    /// Mean MPT across attempts.
+    pub mpt_mean_seconds: f32,
+    /// Attempt that supplied the reported MPT and every other metric.
+    /// Older analyses also stored a separate `quality_source`, now ignored.
+    #[serde(alias = "mpt_source")]
+    pub source: String,
 }
 
 /// Analysis of the chromatic scale recording.
@@ -182,6 +423,13 @@ pub struct ScaleAnalysis {
     pub range_hz: f32,
     /// Range in semitones: 12 * log2(ceiling / floor)
     pub range_semitones: f32,
+    /// Clipping, noise and hum checks of the recording.
+    #[serde(default)]
+    pub recording_quality: Option<RecordingQuality>,
+    /// Region of the recording the metrics were computed on.
+    /// None for older analyses (whole recording).
+    #[serde(default)]
+    pub segment: Option<AnalyzedSegment>,
 }
 
 /// Analysis of the reading passage recording.
@@ -207,6 +455,13 @@ pub struct ReadingAnalysis {
     /// Rich reliability metadata.
     #[serde(default)]
     pub reliability: Option<ReliabilityInfo>,
+    /// Clipping, noise and hum checks of the recording.
+    #[serde(default)]
+    pub recording_quality: Option<RecordingQuality>,
+    /// Region of the recording the metrics were computed on.
+    /// None for older analyses (whole recording).
+    #[serde(default)]
+    pub segment: Option<AnalyzedSegment>,
 }
 
 /// S/Z ratio analysis — glottal efficiency test.
@@ -226,6 +481,9 @@ pub struct SzAnalysis {
     pub mean_z: f32,
     /// S/Z ratio. Normal ~1.0. Above 1.4 is concerning.
     pub sz_ratio: f32,
+    /// Format of the live capture. None for older results.
+    #[serde(default)]
+    pub format: Option<RecordingFormat>,
 }
 
 /// Fatigue slope analysis — vocal endurance test.
@@ -245,6 +503,162 @@ pub struct FatigueAnalysis {
     pub mpt_slope: f32,
     /// Slope of CPPS across trials (negative = fatiguing).
     pub cpps_slope: f32,
+    /// Format of the live capture. None for older results.
+    #[serde(default)]
+    pub format: Option<RecordingFormat>,
+}
+
+/// Analysis of one numbered recording take (`{exercise}_NNN.wav`).
+///
+/// Every take of the day is analyzed and stored per attempt so takes can be
+/// compared; the session-level result in SessionAnalysis comes from the
+/// canonical take (or the newest one when none was chosen).
+#[derive(Debug, Clone)]
+pub struct AttemptAnalysis {
+    pub attempt: u32,
+    pub result: ExerciseResult,
+}
+
+/// Analysis result of a single recorded exercise.
+#[derive(Debug, Clone)]
+pub enum ExerciseResult {
+    Sustained(SustainedAnalysis),
+    Scale(ScaleAnalysis),
+    Reading(ReadingAnalysis),
+}
+
+impl ExerciseResult {
+    /// Exercise name as used in file names and the analyses table.
+    pub fn exercise(&self) -> &'static str {
+        match self {
+            ExerciseResult::Sustained(_) => "sustained",
+            ExerciseResult::Scale(_) => "scale",
+            ExerciseResult::Reading(_) => "reading",
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        match self {
+            ExerciseResult::Sustained(a) => serde_json::to_string(a),
+            ExerciseResult::Scale(a) => serde_json::to_string(a),
+            ExerciseResult::Reading(a) => serde_json::to_string(a),
+        }
+    }
+
+    /// Parse a stored analysis blob for the given exercise.
+    /// Returns None for exercises that have no per-attempt recordings.
+    pub fn from_json(exercise: &str, json: &str) -> serde_json::Result<Option<Self>> {
+        Ok(match exercise {
+            "sustained" => Some(ExerciseResult::Sustained(serde_json::from_str(json)?)),
+            "scale" => Some(ExerciseResult::Scale(serde_json::from_str(json)?)),
+            "reading" => Some(ExerciseResult::Reading(serde_json::from_str(json)?)),
+            _ => None,
+        })
+    }
+
+    pub fn reliability(&self) -> Option<&ReliabilityInfo> {
+        match self {
+            ExerciseResult::Sustained(a) => a.reliability.as_ref(),
+            ExerciseResult::Scale(_) => None,
+            ExerciseResult::Reading(a) => a.reliability.as_ref(),
+        }
+    }
+
+    pub fn recording_quality(&self) -> Option<&RecordingQuality> {
+        match self {
+            ExerciseResult::Sustained(a) => a.recording_quality.as_ref(),
+            ExerciseResult::Scale(a) => a.recording_quality.as_ref(),
+            ExerciseResult::Reading(a) => a.recording_quality.as_ref(),
+        }
+    }
+}
+
+/// A stored LLM interpretation of a session (`voicevo explain`).
+#[derive(Debug, Clone)]
+pub struct Interpretation {
+    /// Session date the interpretation is about.
+    pub date: String,
+    pub provider: String,
+    pub model: String,
+    /// The response text (markdown).
+    pub content: String,
+    /// When it was generated (UTC, SQLite datetime format).
+    pub created_at: String,
+}
+
+/// Kinds of clinical event, in the order `voicevo event add --help` lists them.
+pub const EVENT_KINDS: &[&str] = &["injection", "surgery", "therapy", "illness", "medication", "other"];
+
+/// Something that happened during recovery (an intervention, illness,
+/// medication change), shown alongside the measurements.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClinicalEvent {
+    pub id: i64,
+    /// YYYY-MM-DD
+    pub date: String,
+    /// One of `EVENT_KINDS`.
+    pub kind: String,
+    pub note: String,
+}
+
+/// Fixtures shared by tests across the crate: plausible values that a test
+/// overrides only where it cares.
+#[cfg(test)]
+impl SessionData {
+    /// Slot 1 on `date` with no recordings and a `SustainedAnalysis::test_default`.
+    pub fn test_default(date: &str) -> Self {
+        SessionData {
+            date: date.into(),
+            slot: 1,
+            recordings: SessionRecordings { sustained: None, scale: None, reading: None },
+            analysis: SessionAnalysis {
+                sustained: Some(SustainedAnalysis::test_default()),
+                scale: None,
+                reading: None,
+                sz: None,
+                fatigue: None,
+            },
+            conditions: None,
+            source: None,
+        }
+    }
+}
+
+#[cfg(test)]
+impl SustainedAnalysis {
+    /// A 10 s phonation at 120 Hz with unremarkable quality metrics.
+    pub fn test_default() -> Self {
+        SustainedAnalysis {
+            mpt_seconds: 10.0,
+            mean_f0_hz: 120.0,
+            f0_std_hz: 5.0,
+            jitter_local_percent: 1.0,
+            shimmer_local_percent: 4.0,
+            hnr_db: 12.0,
+            cpps_db: None,
+            periodicity_mean: None,
+            detection_quality: None,
+            reliability: None,
+            trials: None,
+            recording_quality: None,
+            segment: None,
+        }
+    }
+}
+
+#[cfg(test)]
+impl RecordingConditions {
+    /// Conditions at `time_of_day` with moderate fatigue and nothing unusual.
+    pub fn test_default(time_of_day: &str) -> Self {
+        RecordingConditions {
+            time_of_day: time_of_day.into(),
+            fatigue_level: 3,
+            throat_cleared: false,
+            mucus_level: "low".into(),
+            hydration: "normal".into(),
+            notes: None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -255,6 +669,7 @@ mod tests {
     fn session_data_roundtrip() {
         let session = SessionData {
             date: "2026-02-08".into(),
+            slot: 1,
             recordings: SessionRecordings {
                 sustained: Some("data/recordings/2026-02-08/sustained.wav".into()),
                 scale: Some("data/recordings/2026-02-08/scale.wav".into()),
@@ -272,18 +687,24 @@ mod tests {
                     periodicity_mean: None,
                     detection_quality: None,
                     reliability: None,
+                    trials: None,
+                    recording_quality: None,
+                    segment: None,
                 }),
                 scale: Some(ScaleAnalysis {
                     pitch_floor_hz: 42.0,
                     pitch_ceiling_hz: 185.0,
                     range_hz: 143.0,
                     range_semitones: 25.5,
+                    recording_quality: None,
+                    segment: None,
                 }),
                 reading: None,
                 sz: None,
                 fatigue: None,
             },
             conditions: None,
+            source: None,
         };
 
         // Serialize to JSON
@@ -341,6 +762,45 @@ mod tests {
         assert!(s.detection_quality.is_none());
     }
 
+    #[test]
+    fn recording_format_from_spec() {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 48000,
+            bits_per_sample: 24,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let format = RecordingFormat::from(spec);
+        assert_eq!(format.describe(), "48 kHz / 24-bit");
+        let float = RecordingFormat { sample_rate: 44100, bits_per_sample: 32, float: true };
+        assert_eq!(float.describe(), "44.1 kHz / 32-bit float");
+
+        // Quality stored before the format was recorded still loads
+        let json = r#"{"clipped_fraction":0.0,"noise_floor_db":-60.0,"noise_from_silence":true,
+            "snr_db":30.0,"dc_offset":0.0,"hum_hz":null,"hum_prominence_db":0.0,"warnings":[]}"#;
+        let q: RecordingQuality = serde_json::from_str(json).unwrap();
+        assert!(q.format.is_none());
+    }
+
+    #[test]
+    fn low_snr_downgrades_reliability() {
+        let mut r = ReliabilityInfo::compute([80, 10, 10], 0.9, 0.7, true);
+        r.apply_snr(40.0);
+        assert_eq!(r.analysis_quality, "good");
+        assert!(!r.snr_limited);
+
+        r.apply_snr(25.0);
+        assert_eq!(r.analysis_quality, "ok");
+        assert!(r.metrics_validity.jitter);
+        assert!(r.snr_limited);
+
+        r.apply_snr(12.0);
+        assert_eq!(r.analysis_quality, "trend_only");
+        assert!(!r.metrics_validity.jitter);
+        assert!(!r.metrics_validity.shimmer);
+        assert!(!r.metrics_validity.hnr);
+    }
+
     #[test]
     fn conditions_roundtrip() {
         let conditions = RecordingConditions {
@@ -370,4 +830,32 @@ mod tests {
         let s: SessionData = serde_json::from_str(json).unwrap();
         assert!(s.conditions.is_none());
     }
+
+    #[test]
+    fn session_ids() {
+        let first = SessionId::parse("2026-03-01").unwrap();
+        assert_eq!(first.slot, 1);
+        assert_eq!(first.to_string(), "2026-03-01");
+        let second = SessionId::parse("2026-03-01.2").unwrap();
+        assert_eq!(second.slot, 2);
+        assert_eq!(second.to_string(), "2026-03-01.2");
+        assert_eq!(SessionId::parse("2026-03-01.1").unwrap(), first);
+        assert!(first < second && second < SessionId::parse("2026-03-02").unwrap());
+        assert!(SessionId::parse("2026-03-01.0").is_err());
+        assert!(SessionId::parse("2026-03-01.b").is_err());
+        assert!(SessionId::parse("yesterday").is_err());
+
+        assert_eq!(SessionId::resolve(Some("2026-03-01.3"), None).unwrap().slot, 3);
+        assert_eq!(SessionId::resolve(None, Some("2026-03-01")).unwrap(), first);
+        assert!(SessionId::resolve(None, Some("2026-03-01.2")).is_err());
+    }
+
+    #[test]
+    fn older_json_is_slot_one() {
+        let json = r#"{"date":"2026-02-08","recordings":{"sustained":null,"scale":null,"reading":null},
+            "analysis":{"sustained":null,"scale":null,"reading":null}}"#;
+        let session: SessionData = serde_json::from_str(json).unwrap();
+        assert_eq!(session.slot, 1);
+        assert_eq!(session.id(), "2026-02-08");
+    }
 }
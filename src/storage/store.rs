@@ -1,7 +1,14 @@
-use anyhow::Result;
+use std::collections::BTreeMap;
 
-use super::db;
-use super::session_data::SessionData;
+use anyhow::{Context, Result};
+
+use super::backup::{self, Manifest, RestorePlan};
+use super::compact::{self, CompactSummary};
+use super::db::{self, Comparison, MetricMatch};
+use super::import;
+use super::schema;
+use crate::paths;
+use super::session_data::{AttemptAnalysis, ClinicalEvent, Interpretation, SessionData, SessionId, ANALYSIS_VERSION};
 
 /// Save session data to the SQLite database at the current analysis version.
 pub fn save_session(session: &SessionData) -> Result<()> {
@@ -33,6 +40,126 @@ pub fn list_versions(date: &str) -> Result<Vec<u32>> {
     db::list_versions(&conn, date)
 }
 
+/// Save per-attempt analyses for an already-saved session.
+pub fn save_attempts(date: &str, attempts: &[AttemptAnalysis]) -> Result<()> {
+    let conn = db::open_db()?;
+    db::save_attempts(&conn, date, ANALYSIS_VERSION, attempts)
+}
+
+/// Load every per-attempt analysis for a date.
+pub fn load_attempts(date: &str) -> Result<Vec<AttemptAnalysis>> {
+    let conn = db::open_db()?;
+    db::load_attempts(&conn, date)
+}
+
+/// Canonical take per exercise for a date (empty if none chosen).
+pub fn load_canonical_attempts(date: &str) -> Result<BTreeMap<String, u32>> {
+    let conn = db::open_db()?;
+    db::load_canonical_attempts(&conn, date)
+}
+
+/// Discard a take. The file is renamed out of the attempt list (see
+/// `paths::discarded_path`) rather than deleted, the session's recording
+/// path follows it, and a canonical choice of it is dropped. Its stored
+/// per-attempt analysis is kept and listed as discarded. Returns the new path.
+pub fn discard_take(session: &SessionId, exercise: &str, path: &std::path::Path) -> Result<std::path::PathBuf> {
+    let target = paths::discarded_path(path);
+    std::fs::rename(path, &target)
+        .with_context(|| format!("Failed to discard {}", path.display()))?;
+
+    let conn = db::open_db()?;
+    let id = session.to_string();
+    db::rename_recording(&conn, &path.to_string_lossy(), &target.to_string_lossy())?;
+    let number = paths::attempt_number(path, exercise);
+    if number.is_some() && db::load_canonical_attempts(&conn, &id)?.get(exercise) == number.as_ref() {
+        db::clear_canonical_attempt(&conn, &id, exercise)?;
+    }
+    Ok(target)
+}
+
+/// Remember which takes one multi-trial run recorded.
+pub fn save_trial_run(date: &str, exercise: &str, attempts: &[u32]) -> Result<()> {
+    let conn = db::open_db()?;
+    db::save_trial_run(&conn, date, exercise, attempts)
+}
+
+/// Takes of the last multi-trial run of an exercise, if any.
+pub fn load_trial_run(date: &str, exercise: &str) -> Result<Option<Vec<u32>>> {
+    let conn = db::open_db()?;
+    db::load_trial_run(&conn, date, exercise)
+}
+
+/// Mark a take as canonical; reports use it instead of the newest take.
+pub fn set_canonical_attempt(date: &str, exercise: &str, attempt: u32, path: &str) -> Result<()> {
+    let conn = db::open_db()?;
+    db::set_canonical_attempt(&conn, date, exercise, attempt, path)
+}
+
+/// Store an LLM interpretation so reports can show it later.
+pub fn save_interpretation(date: &str, provider: &str, model: &str, content: &str) -> Result<()> {
+    let conn = db::open_db()?;
+    db::save_interpretation(&conn, date, provider, model, content)
+}
+
+/// The most recently stored LLM interpretation, if any.
+pub fn load_latest_interpretation() -> Result<Option<Interpretation>> {
+    let conn = db::open_db()?;
+    db::load_latest_interpretation(&conn)
+}
+
+/// Record a clinical event. Returns its id.
+pub fn save_event(date: &str, kind: &str, note: &str) -> Result<i64> {
+    let conn = db::open_db()?;
+    db::save_event(&conn, date, kind, note)
+}
+
+/// All clinical events, in date order.
+pub fn load_events() -> Result<Vec<ClinicalEvent>> {
+    let conn = db::open_db()?;
+    db::load_events(&conn)
+}
+
+/// Delete a clinical event. Returns whether it existed.
+pub fn delete_event(id: i64) -> Result<bool> {
+    let conn = db::open_db()?;
+    db::delete_event(&conn, id)
+}
+
+/// Schema version of the database and the migrations not yet applied,
+/// without applying them.
+pub fn schema_status() -> Result<schema::SchemaStatus> {
+    let conn = db::connect()?;
+    schema::status(&conn)
+}
+
+/// Back up the data and config directories to a `.tar.zst` archive.
+pub fn backup(output: &std::path::Path) -> Result<Manifest> {
+    backup::create(paths::data_dir(), paths::config_dir(), output)
+}
+
+/// Verify a backup archive and restore it into the data and config directories.
+pub fn restore(archive: &std::path::Path, dry_run: bool, force: bool) -> Result<RestorePlan> {
+    backup::restore(archive, paths::data_dir(), paths::config_dir(), dry_run, force)
+}
+
+/// Convert WAV recordings to FLAC and update the session paths.
+pub fn compact(dry_run: bool) -> Result<CompactSummary> {
+    let conn = db::open_db()?;
+    compact::compact(&conn, &paths::recordings_dir(), dry_run)
+}
+
+/// Save imported sessions, assigning each a slot on its date.
+pub fn import(sessions: &mut [SessionData]) -> Result<()> {
+    let conn = db::open_db()?;
+    import::save(&conn, sessions)
+}
+
+/// Sessions matching a metric comparison and extra metric/condition filters.
+pub fn query_metrics(metric: &Comparison, filters: &[Comparison], valid_only: bool) -> Result<Vec<MetricMatch>> {
+    let conn = db::open_db()?;
+    db::query_metrics(&conn, metric, filters, valid_only)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -41,6 +168,7 @@ mod tests {
     fn temp_session() -> SessionData {
         SessionData {
             date: "2099-01-01".into(),
+            slot: 1,
             recordings: SessionRecordings {
                 sustained: Some("test.wav".into()),
                 scale: None,
@@ -58,6 +186,9 @@ mod tests {
                     periodicity_mean: None,
                     detection_quality: None,
                     reliability: None,
+                    trials: None,
+                    recording_quality: None,
+                    segment: None,
                 }),
                 scale: None,
                 reading: None,
@@ -65,6 +196,7 @@ mod tests {
                 fatigue: None,
             },
             conditions: None,
+            source: None,
         }
     }
 